@@ -9,11 +9,19 @@ impl EnvVars {
     pub const HOME: &'static str = "HOME";
     pub const CI: &'static str = "CI";
     pub const LC_ALL: &'static str = "LC_ALL";
+    pub const DISPLAY: &'static str = "DISPLAY";
+    pub const WAYLAND_DISPLAY: &'static str = "WAYLAND_DISPLAY";
+
+    // Color related
+    pub const NO_COLOR: &'static str = "NO_COLOR";
+    pub const FORCE_COLOR: &'static str = "FORCE_COLOR";
+    pub const CLICOLOR_FORCE: &'static str = "CLICOLOR_FORCE";
 
     // Git related
     pub const GIT_DIR: &'static str = "GIT_DIR";
     pub const GIT_WORK_TREE: &'static str = "GIT_WORK_TREE";
     pub const GIT_TERMINAL_PROMPT: &'static str = "GIT_TERMINAL_PROMPT";
+    pub const GIT_CONFIG_COUNT: &'static str = "GIT_CONFIG_COUNT";
 
     pub const SKIP: &'static str = "SKIP";
 
@@ -25,8 +33,14 @@ impl EnvVars {
     pub const PREK_NO_CONCURRENCY: &'static str = "PREK_NO_CONCURRENCY";
     pub const PREK_MAX_CONCURRENCY: &'static str = "PREK_MAX_CONCURRENCY";
     pub const PREK_NO_FAST_PATH: &'static str = "PREK_NO_FAST_PATH";
+    pub const PREK_NO_RESULT_CACHE: &'static str = "PREK_NO_RESULT_CACHE";
     pub const PREK_UV_SOURCE: &'static str = "PREK_UV_SOURCE";
     pub const PREK_NATIVE_TLS: &'static str = "PREK_NATIVE_TLS";
+    pub const PREK_DOWNLOAD_RETRIES: &'static str = "PREK_DOWNLOAD_RETRIES";
+    pub const PREK_OFFLINE: &'static str = "PREK_OFFLINE";
+    pub const PREK_GIT_TOKEN: &'static str = "PREK_GIT_TOKEN";
+    pub const PREK_GIT_TOKEN_HOSTS: &'static str = "PREK_GIT_TOKEN_HOSTS";
+    pub const PREK_CLONE_DEPTH: &'static str = "PREK_CLONE_DEPTH";
     pub const SSL_CERT_FILE: &'static str = "SSL_CERT_FILE";
     pub const SSL_CERT_DIR: &'static str = "SSL_CERT_DIR";
     pub const PREK_CONTAINER_RUNTIME: &'static str = "PREK_CONTAINER_RUNTIME";
@@ -59,9 +73,13 @@ impl EnvVars {
     pub const UV_PYTHON_INSTALL_DIR: &'static str = "UV_PYTHON_INSTALL_DIR";
     pub const UV_MANAGED_PYTHON: &'static str = "UV_MANAGED_PYTHON";
     pub const UV_NO_MANAGED_PYTHON: &'static str = "UV_NO_MANAGED_PYTHON";
+    pub const UV_OFFLINE: &'static str = "UV_OFFLINE";
 
     // Node/Npm related
     pub const NODE_PATH: &'static str = "NODE_PATH";
+    pub const COREPACK_HOME: &'static str = "COREPACK_HOME";
+    pub const COREPACK_ENABLE_DOWNLOAD_PROMPT: &'static str = "COREPACK_ENABLE_DOWNLOAD_PROMPT";
+    pub const PNPM_HOME: &'static str = "PNPM_HOME";
 
     // Bun related
     pub const BUN_INSTALL: &'static str = "BUN_INSTALL";
@@ -72,6 +90,7 @@ impl EnvVars {
 
     // GitHub API authentication (to avoid rate limits)
     pub const GITHUB_TOKEN: &'static str = "GITHUB_TOKEN";
+    pub const GITHUB_ACTIONS: &'static str = "GITHUB_ACTIONS";
 
     // Go related
     pub const GOTOOLCHAIN: &'static str = "GOTOOLCHAIN";
@@ -79,6 +98,11 @@ impl EnvVars {
     pub const GOPATH: &'static str = "GOPATH";
     pub const GOBIN: &'static str = "GOBIN";
     pub const GOFLAGS: &'static str = "GOFLAGS";
+    pub const GOPROXY: &'static str = "GOPROXY";
+    pub const GOPRIVATE: &'static str = "GOPRIVATE";
+    pub const GONOPROXY: &'static str = "GONOPROXY";
+    pub const GONOSUMDB: &'static str = "GONOSUMDB";
+    pub const GOINSECURE: &'static str = "GOINSECURE";
 
     // Lua related
     pub const LUA_PATH: &'static str = "LUA_PATH";
@@ -9,6 +9,8 @@ pub const PRE_COMMIT_CONFIG_YAML: &str = ".pre-commit-config.yaml";
 pub const PRE_COMMIT_CONFIG_YML: &str = ".pre-commit-config.yml";
 pub const PREK_TOML: &str = "prek.toml";
 pub const PRE_COMMIT_HOOKS_YAML: &str = ".pre-commit-hooks.yaml";
+pub const PREKIGNORE: &str = ".prekignore";
+pub const PREK_SKIP_FILE: &str = ".prek-skip";
 
 pub const CONFIG_FILENAMES: &[&str] = &[PREK_TOML, PRE_COMMIT_CONFIG_YAML, PRE_COMMIT_CONFIG_YML];
 
@@ -32,6 +32,7 @@ TAG_SET_CONSTS = [
     ("TAG_SET_EXECUTABLE_TEXT", ["executable", "text"]),
     ("TAG_SET_JSON", ["json"]),
     ("TAG_SET_JSON5", ["json5"]),
+    ("TAG_SET_PYTHON", ["python"]),
     ("TAG_SET_TOML", ["toml"]),
     ("TAG_SET_XML", ["xml"]),
     ("TAG_SET_YAML", ["yaml"]),
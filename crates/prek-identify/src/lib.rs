@@ -18,14 +18,13 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 // THE SOFTWARE.
 
+use std::borrow::Cow;
 use std::io::{BufRead, Read};
 use std::ops::BitOrAssign;
 use std::path::Path;
 
 #[cfg(feature = "serde")]
 use serde::de::{Error as DeError, SeqAccess, Visitor};
-#[cfg(any(feature = "serde", feature = "schemars"))]
-use std::borrow::Cow;
 
 pub mod tags;
 
@@ -240,9 +239,69 @@ pub enum Error {
     Shebang(#[from] ShebangError),
 }
 
+/// Return `path` in the extended-length `\\?\` form when it is too long for the Win32 APIs.
+///
+/// Relative paths are made absolute first, as the extended-length form is not normalized.
+/// UNC paths become `\\?\UNC\server\share\...`, and verbatim or device paths are returned
+/// unchanged, so they are never prefixed twice. Use the result only to open the file: the
+/// original path should still be used for display, `file_name()`, and as a process argument.
+///
+/// On other platforms, `path` is always returned unchanged.
+pub fn extended_length_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(windows)]
+    if let Some(extended) = windows_extended_length_path(path) {
+        return Cow::Owned(extended);
+    }
+
+    Cow::Borrowed(path)
+}
+
+#[cfg(windows)]
+fn windows_extended_length_path(path: &Path) -> Option<std::path::PathBuf> {
+    use std::ffi::OsString;
+    use std::path::{Component, Prefix};
+
+    // `MAX_PATH` counts UTF-16 units, which is at most the number of WTF-8 bytes.
+    const MAX_PATH: usize = 260;
+
+    if path.as_os_str().len() < MAX_PATH && path.is_absolute() {
+        return None;
+    }
+    let absolute = std::path::absolute(path).ok()?;
+    if absolute.as_os_str().len() < MAX_PATH {
+        return None;
+    }
+
+    let mut components = absolute.components();
+    let Some(Component::Prefix(prefix)) = components.next() else {
+        return None;
+    };
+    let mut extended = match prefix.kind() {
+        Prefix::Disk(_) => {
+            let mut extended = OsString::from(r"\\?\");
+            extended.push(prefix.as_os_str());
+            extended
+        }
+        Prefix::UNC(server, share) => {
+            let mut extended = OsString::from(r"\\?\UNC\");
+            extended.push(server);
+            extended.push(r"\");
+            extended.push(share);
+            extended
+        }
+        Prefix::Verbatim(_)
+        | Prefix::VerbatimUNC(..)
+        | Prefix::VerbatimDisk(_)
+        | Prefix::DeviceNS(_) => return None,
+    };
+    extended.push(components.as_path());
+
+    Some(extended.into())
+}
+
 /// Identify tags for a file at the given path.
 pub fn tags_from_path(path: &Path) -> Result<TagSet, Error> {
-    let metadata = fs_err::symlink_metadata(path)?;
+    let metadata = fs_err::symlink_metadata(extended_length_path(path))?;
     if metadata.is_dir() {
         return Ok(tags::TAG_SET_DIRECTORY);
     } else if metadata.is_symlink() {
@@ -428,7 +487,7 @@ fn parse_nix_shebang<R: BufRead>(reader: &mut R, mut cmd: Vec<String>) -> Vec<St
 }
 
 pub fn parse_shebang(path: &Path) -> Result<Vec<String>, ShebangError> {
-    let file = fs_err::File::open(path)?;
+    let file = fs_err::File::open(extended_length_path(path))?;
     let mut reader = std::io::BufReader::new(file);
     let mut line = String::new();
     reader.read_line(&mut line)?;
@@ -497,7 +556,7 @@ fn is_text_char(b: u8) -> bool {
 /// <https://github.com/file/file/blob/df74b09b9027676088c797528edcaae5a9ce9ad0/src/encoding.c#L203-L228>
 fn is_text_file(path: &Path) -> bool {
     let mut buffer = [0; 1024];
-    let Ok(mut file) = fs_err::File::open(path) else {
+    let Ok(mut file) = fs_err::File::open(extended_length_path(path)) else {
         return false;
     };
 
@@ -559,6 +618,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn tags_from_long_path() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        // Nest directories until the path exceeds `MAX_PATH` (260 characters).
+        let mut nested = dir.path().to_path_buf();
+        while nested.as_os_str().len() <= 300 {
+            nested.push("a".repeat(50));
+        }
+        fs_err::create_dir_all(super::extended_length_path(&nested))?;
+        let script = nested.join("script.py");
+        fs_err::write(
+            super::extended_length_path(&script),
+            "#!/usr/bin/env python3\nprint('hello')\n",
+        )?;
+
+        assert_eq!(super::parse_shebang(&script)?, vec!["python3"]);
+        let tags = super::tags_from_path(&script)?;
+        assert_tagset(&tags, &["executable", "file", "python", "python3", "text"]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn extended_length_path() {
+        use std::path::PathBuf;
+
+        let long = "a".repeat(300);
+
+        let short = Path::new(r"C:\short\path.txt");
+        assert_eq!(super::extended_length_path(short), short);
+
+        let disk = PathBuf::from(format!(r"C:\{long}\file.txt"));
+        assert_eq!(
+            super::extended_length_path(&disk),
+            PathBuf::from(format!(r"\\?\C:\{long}\file.txt"))
+        );
+
+        let unc = PathBuf::from(format!(r"\\server\share\{long}\file.txt"));
+        assert_eq!(
+            super::extended_length_path(&unc),
+            PathBuf::from(format!(r"\\?\UNC\server\share\{long}\file.txt"))
+        );
+
+        // Already extended paths are not prefixed again.
+        for verbatim in [
+            format!(r"\\?\C:\{long}\file.txt"),
+            format!(r"\\?\UNC\server\share\{long}\file.txt"),
+        ] {
+            let verbatim = PathBuf::from(verbatim);
+            assert_eq!(super::extended_length_path(&verbatim), verbatim);
+        }
+
+        // The file name is unaffected.
+        assert_eq!(
+            super::extended_length_path(&disk).file_name(),
+            disk.file_name()
+        );
+    }
+
     #[test]
     fn tags_from_filename() {
         let tags = super::tags_from_filename(Path::new("test.py"));
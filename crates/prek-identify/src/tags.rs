@@ -334,6 +334,7 @@ pub const TAG_SET_TEXT_OR_BINARY: TagSet = TagSet::new(&[21, 255]);
 pub const TAG_SET_EXECUTABLE_TEXT: TagSet = TagSet::new(&[74, 255]);
 pub const TAG_SET_JSON: TagSet = TagSet::new(&[135]);
 pub const TAG_SET_JSON5: TagSet = TagSet::new(&[136]);
+pub const TAG_SET_PYTHON: TagSet = TagSet::new(&[213]);
 pub const TAG_SET_TOML: TagSet = TagSet::new(&[260]);
 pub const TAG_SET_XML: TagSet = TagSet::new(&[297]);
 pub const TAG_SET_YAML: TagSet = TagSet::new(&[301]);
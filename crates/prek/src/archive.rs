@@ -23,13 +23,13 @@ use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
 use std::path::{Component, Path, PathBuf};
 
-use async_compression::tokio::bufread::{GzipDecoder, XzDecoder};
+use async_compression::tokio::bufread::{GzipDecoder, XzDecoder, ZstdDecoder};
 use async_zip::base::read::stream::ZipFileReader;
+use futures::StreamExt;
 use rustc_hash::FxHashSet;
 use tokio::io::{AsyncRead, BufReader};
-use tokio_tar::ArchiveBuilder;
+use tokio_tar::{ArchiveBuilder, EntryType};
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
-use tracing::warn;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -45,6 +45,28 @@ pub enum Error {
     NonSingularArchive(Vec<OsString>),
     #[error("The top-level of the archive must only contain a list directory, but it's empty")]
     EmptyArchive,
+    #[error(
+        "Expected `{}` in the archive, but the top-level of the archive contains: {top_level:?}",
+        subdir.display()
+    )]
+    MissingSubdir {
+        subdir: PathBuf,
+        top_level: Vec<OsString>,
+    },
+    #[error("Archive entry `{}` would be extracted outside of the target directory", .0.display())]
+    UnsafeEntry(PathBuf),
+    #[error(
+        "Archive entry `{}` links to `{}`, outside of the target directory",
+        entry.display(),
+        link.display()
+    )]
+    UnsafeLink { entry: PathBuf, link: PathBuf },
+    #[error("Failed to extract archive entry `{}`", entry.display())]
+    Entry {
+        entry: PathBuf,
+        #[source]
+        err: std::io::Error,
+    },
 }
 
 const DEFAULT_BUF_SIZE: usize = 128 * 1024;
@@ -126,32 +148,62 @@ pub fn strip_component(source: impl AsRef<Path>) -> Result<PathBuf, Error> {
     }
 }
 
+/// Select a known directory of an unpacked archive, for archives whose layout is known upfront.
+///
+/// `subdir` is relative to the top-level of the archive; `.` selects the top-level itself.
+pub fn select_subdir(source: impl AsRef<Path>, subdir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+    let (source, subdir) = (source.as_ref(), subdir.as_ref());
+    let path = source.join(subdir);
+    if path.is_dir() {
+        return Ok(path);
+    }
+
+    let mut top_level = fs_err::read_dir(source)?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    top_level.sort();
+    Err(Error::MissingSubdir {
+        subdir: subdir.to_path_buf(),
+        top_level,
+    })
+}
+
+/// Ensure the file path is safe to use as a [`Path`].
+///
+/// See: <https://docs.rs/zip/latest/zip/read/struct.ZipFile.html#method.enclosed_name>
+fn enclosed_name(file_name: &str) -> Option<PathBuf> {
+    if file_name.contains('\0') {
+        return None;
+    }
+    let path = PathBuf::from(file_name);
+    is_enclosed(&path).then_some(path)
+}
+
+/// Whether a relative path stays inside the directory it is relative to.
+fn is_enclosed(path: &Path) -> bool {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return false,
+            Component::ParentDir => {
+                let Some(parent) = depth.checked_sub(1) else {
+                    return false;
+                };
+                depth = parent;
+            }
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => (),
+        }
+    }
+    true
+}
+
 /// Unpack a `.zip` archive into the target directory, without requiring `Seek`.
 ///
 /// This is useful for unzipping files as they're being downloaded. If the archive
 /// is already fully on disk, consider using `unzip_archive`, which can use multiple
 /// threads to work faster in that case.
 pub async fn unzip<R: AsyncRead + Unpin>(reader: R, target: impl AsRef<Path>) -> Result<(), Error> {
-    /// Ensure the file path is safe to use as a [`Path`].
-    ///
-    /// See: <https://docs.rs/zip/latest/zip/read/struct.ZipFile.html#method.enclosed_name>
-    pub(crate) fn enclosed_name(file_name: &str) -> Option<PathBuf> {
-        if file_name.contains('\0') {
-            return None;
-        }
-        let path = PathBuf::from(file_name);
-        let mut depth = 0usize;
-        for component in path.components() {
-            match component {
-                Component::Prefix(_) | Component::RootDir => return None,
-                Component::ParentDir => depth = depth.checked_sub(1)?,
-                Component::Normal(_) => depth += 1,
-                Component::CurDir => (),
-            }
-        }
-        Some(path)
-    }
-
     let target = target.as_ref();
     let mut reader = futures::io::BufReader::with_capacity(DEFAULT_BUF_SIZE, reader.compat());
     let mut zip = ZipFileReader::new(&mut reader);
@@ -161,48 +213,46 @@ pub async fn unzip<R: AsyncRead + Unpin>(reader: R, target: impl AsRef<Path>) ->
 
     while let Some(mut entry) = zip.next_with_entry().await? {
         // Construct the (expected) path to the file on-disk.
-        let path = entry.reader().entry().filename().as_str()?;
+        let name = entry.reader().entry().filename().as_str()?;
 
         // Sanitize the file name to prevent directory traversal attacks.
-        let Some(path) = enclosed_name(path) else {
-            warn!("Skipping unsafe file name: {path}");
-
-            // Close current file prior to proceeding, as per:
-            // https://docs.rs/async_zip/0.0.16/async_zip/base/read/stream/
-            (.., zip) = entry.skip().await?;
-
-            // Store the current offset.
-            offset = zip.offset();
-
-            continue;
+        let Some(relative) = enclosed_name(name) else {
+            return Err(Error::UnsafeEntry(PathBuf::from(name)));
         };
 
-        let path = target.join(path);
+        let path = target.join(&relative);
         let is_dir = entry.reader().entry().dir()?;
 
         // Either create the directory or write the file to disk.
-        if is_dir {
-            if directories.insert(path.clone()) {
-                fs_err::tokio::create_dir_all(path).await?;
-            }
-        } else {
-            if let Some(parent) = path.parent() {
-                if directories.insert(parent.to_path_buf()) {
-                    fs_err::tokio::create_dir_all(parent).await?;
+        let written = async {
+            if is_dir {
+                if directories.insert(path.clone()) {
+                    fs_err::tokio::create_dir_all(path).await?;
                 }
-            }
-
-            // We don't know the file permissions here, because we haven't seen the central directory yet.
-            let file = fs_err::tokio::File::create(&path).await?;
-            let size = entry.reader().entry().uncompressed_size();
-            let mut writer = if let Ok(size) = usize::try_from(size) {
-                tokio::io::BufWriter::with_capacity(std::cmp::min(size, 1024 * 1024), file)
             } else {
-                tokio::io::BufWriter::new(file)
-            };
-            let mut reader = entry.reader_mut().compat();
-            tokio::io::copy(&mut reader, &mut writer).await?;
-        }
+                if let Some(parent) = path.parent() {
+                    if directories.insert(parent.to_path_buf()) {
+                        fs_err::tokio::create_dir_all(parent).await?;
+                    }
+                }
+
+                // We don't know the file permissions here, because we haven't seen the central directory yet.
+                let file = fs_err::tokio::File::create(&path).await?;
+                let size = entry.reader().entry().uncompressed_size();
+                let mut writer = if let Ok(size) = usize::try_from(size) {
+                    tokio::io::BufWriter::with_capacity(std::cmp::min(size, 1024 * 1024), file)
+                } else {
+                    tokio::io::BufWriter::new(file)
+                };
+                let mut reader = entry.reader_mut().compat();
+                tokio::io::copy(&mut reader, &mut writer).await?;
+            }
+            Ok::<_, std::io::Error>(())
+        };
+        written.await.map_err(|err| Error::Entry {
+            entry: relative,
+            err,
+        })?;
 
         // Close current file prior to proceeding, as per:
         // https://docs.rs/async_zip/0.0.16/async_zip/base/read/stream/
@@ -258,15 +308,7 @@ pub async fn untar_gz<R: AsyncRead + Unpin>(
 ) -> Result<(), Error> {
     let reader = BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
     let reader = GzipDecoder::new(reader);
-
-    let mut archive = ArchiveBuilder::new(reader)
-        .set_preserve_mtime(true)
-        .set_preserve_permissions(true)
-        .set_allow_external_symlinks(false)
-        .build();
-
-    archive.unpack(target.as_ref()).await?;
-    Ok(())
+    unpack_tar(reader, target.as_ref()).await
 }
 
 /// Unpack a `.tar.xz` archive into the target directory, without requiring `Seek`.
@@ -278,15 +320,19 @@ pub async fn untar_xz<R: AsyncRead + Unpin>(
 ) -> Result<(), Error> {
     let reader = BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
     let reader = XzDecoder::new(reader);
+    unpack_tar(reader, target.as_ref()).await
+}
 
-    let mut archive = ArchiveBuilder::new(reader)
-        .set_preserve_mtime(true)
-        .set_preserve_permissions(true)
-        .set_allow_external_symlinks(false)
-        .build();
-
-    archive.unpack(target.as_ref()).await?;
-    Ok(())
+/// Unpack a `.tar.zst` archive into the target directory, without requiring `Seek`.
+///
+/// This is useful for unpacking files as they're being downloaded.
+pub async fn untar_zst<R: AsyncRead + Unpin>(
+    reader: R,
+    target: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let reader = BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
+    let reader = ZstdDecoder::new(reader);
+    unpack_tar(reader, target.as_ref()).await
 }
 
 /// Unpack a `.tar` archive into the target directory, without requiring `Seek`.
@@ -294,18 +340,73 @@ pub async fn untar_xz<R: AsyncRead + Unpin>(
 /// This is useful for unpacking files as they're being downloaded.
 pub async fn untar<R: AsyncRead + Unpin>(reader: R, target: impl AsRef<Path>) -> Result<(), Error> {
     let reader = BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
+    unpack_tar(reader, target.as_ref()).await
+}
 
+/// Unpack a tar stream into the target directory.
+///
+/// Unlike [`tokio_tar::Archive::unpack`], which silently skips entries containing `..`, an entry
+/// whose path or link target is outside the target directory fails the whole extraction.
+async fn unpack_tar<R: AsyncRead + Unpin>(reader: R, target: &Path) -> Result<(), Error> {
     let mut archive = ArchiveBuilder::new(reader)
         .set_preserve_mtime(true)
         .set_preserve_permissions(true)
         .set_allow_external_symlinks(false)
         .build();
 
-    archive.unpack(target.as_ref()).await?;
+    fs_err::tokio::create_dir_all(target).await?;
+    let target = fs_err::tokio::canonicalize(target).await?;
+    let mut memo = FxHashSet::default();
+
+    // Directories are created last, deepest first, so that restrictive permissions on a
+    // directory don't prevent extracting its contents.
+    let mut directories = Vec::new();
+
+    let mut entries = archive.entries()?;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !is_enclosed(&path) {
+            return Err(Error::UnsafeEntry(path));
+        }
+
+        let entry_type = entry.header().entry_type();
+        if let Some(link) = entry.link_name()? {
+            // Symlinks are relative to the directory containing them, hard links to the root.
+            let resolved = match entry_type {
+                EntryType::Symlink => path.parent().unwrap_or(Path::new("")).join(&link),
+                _ => link.to_path_buf(),
+            };
+            if !is_enclosed(&resolved) {
+                return Err(Error::UnsafeLink {
+                    entry: path,
+                    link: link.into_owned(),
+                });
+            }
+        }
+
+        if entry_type == EntryType::Directory {
+            directories.push((path, entry));
+            continue;
+        }
+        entry
+            .unpack_in_raw(&target, &mut memo)
+            .await
+            .map_err(|err| Error::Entry { entry: path, err })?;
+    }
+
+    directories.sort_by(|(a, _), (b, _)| b.cmp(a));
+    for (path, mut entry) in directories {
+        entry
+            .unpack_in_raw(&target, &mut memo)
+            .await
+            .map_err(|err| Error::Entry { entry: path, err })?;
+    }
+
     Ok(())
 }
 
-/// Unpack a `.zip`, `.tar.gz`, `.tar.bz2`, `.tar.zst`, or `.tar.xz` archive into the target directory,
+/// Unpack a `.zip`, `.tar`, `.tar.gz`, `.tar.xz`, or `.tar.zst` archive into the target directory,
 /// without requiring `Seek`.
 pub async fn unpack<R: AsyncRead + Unpin>(
     reader: R,
@@ -317,6 +418,187 @@ pub async fn unpack<R: AsyncRead + Unpin>(
         ArchiveExtension::Tar => untar(reader, target).await,
         ArchiveExtension::TarGz => untar_gz(reader, target).await,
         ArchiveExtension::TarXz => untar_xz(reader, target).await,
+        ArchiveExtension::TarZst => untar_zst(reader, target).await,
         _ => Err(Error::UnsupportedArchive(target.as_ref().to_path_buf())),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::Result;
+    use async_compression::tokio::bufread::{XzEncoder, ZstdEncoder};
+    use tokio::io::AsyncReadExt;
+
+    use super::{ArchiveExtension, Error, select_subdir, strip_component, unpack};
+
+    /// Build an uncompressed tarball containing `root/` with a file and a nested file.
+    async fn build_tar(source: &Path) -> Result<Vec<u8>> {
+        let root = source.join("root");
+        fs_err::create_dir_all(root.join("bin"))?;
+        fs_err::write(root.join("README"), "hello")?;
+        fs_err::write(root.join("bin").join("tool"), "#!/bin/sh\necho tool\n")?;
+
+        let mut builder = tokio_tar::Builder::new(Vec::new());
+        builder.append_dir_all("root", &root).await?;
+        Ok(builder.into_inner().await?)
+    }
+
+    /// Build a tarball with a single entry, writing `path` and `link` into the header as is,
+    /// bypassing the path validation of [`tokio_tar::Header::set_path`].
+    async fn build_raw_tar(
+        entry_type: tokio_tar::EntryType,
+        path: &str,
+        link: Option<&str>,
+    ) -> Result<Vec<u8>> {
+        let data: &[u8] = if link.is_some() { b"" } else { b"evil" };
+        let mut header = tokio_tar::Header::new_gnu();
+        let gnu = header.as_gnu_mut().unwrap();
+        gnu.name[..path.len()].copy_from_slice(path.as_bytes());
+        if let Some(link) = link {
+            gnu.linkname[..link.len()].copy_from_slice(link.as_bytes());
+        }
+        header.set_entry_type(entry_type);
+        header.set_mode(0o644);
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+
+        let mut builder = tokio_tar::Builder::new(Vec::new());
+        builder.append(&header, data).await?;
+        Ok(builder.into_inner().await?)
+    }
+
+    async fn assert_round_trip(ext: ArchiveExtension, archive: &[u8]) -> Result<()> {
+        let target = tempfile::tempdir()?;
+        unpack(archive, ext, target.path()).await?;
+
+        let root = strip_component(target.path())?;
+        assert_eq!(root.file_name().unwrap(), "root");
+        assert_eq!(fs_err::read_to_string(root.join("README"))?, "hello");
+        assert_eq!(
+            fs_err::read_to_string(root.join("bin").join("tool"))?,
+            "#!/bin/sh\necho tool\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn archive_extension_from_path() {
+        assert_eq!(
+            ArchiveExtension::from_path("go1.22.linux-amd64.tar.xz").unwrap(),
+            ArchiveExtension::TarXz
+        );
+        assert_eq!(
+            ArchiveExtension::from_path("tool.txz").unwrap(),
+            ArchiveExtension::TarXz
+        );
+        assert_eq!(
+            ArchiveExtension::from_path("node-v20-linux-x64.tar.zst").unwrap(),
+            ArchiveExtension::TarZst
+        );
+        assert!(ArchiveExtension::from_path("tool.zst").is_err());
+    }
+
+    #[tokio::test]
+    async fn select_subdir_of_known_layout() -> Result<()> {
+        let source = tempfile::tempdir()?;
+        let tar = build_tar(source.path()).await?;
+        fs_err::write(source.path().join("extra"), "")?;
+
+        let target = tempfile::tempdir()?;
+        unpack(tar.as_slice(), ArchiveExtension::Tar, target.path()).await?;
+        fs_err::write(target.path().join("LICENSE"), "")?;
+
+        let bin = select_subdir(target.path(), "root/bin")?;
+        assert!(bin.join("tool").is_file());
+        assert!(select_subdir(target.path(), ".")?.join("LICENSE").is_file());
+
+        let err = select_subdir(target.path(), "dist/bin").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Expected `dist/bin` in the archive, but the top-level of the archive contains: ["LICENSE", "root"]"#
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tar_rejects_parent_dir_entry() -> Result<()> {
+        let archive = build_raw_tar(tokio_tar::EntryType::Regular, "../evil", None).await?;
+
+        let temp = tempfile::tempdir()?;
+        let target = temp.path().join("target");
+        let err = unpack(archive.as_slice(), ArchiveExtension::Tar, &target)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnsafeEntry(ref path) if path == Path::new("../evil")));
+        assert_eq!(
+            err.to_string(),
+            "Archive entry `../evil` would be extracted outside of the target directory"
+        );
+        assert!(!temp.path().join("evil").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tar_rejects_symlink_outside_target() -> Result<()> {
+        let archive = build_raw_tar(
+            tokio_tar::EntryType::Symlink,
+            "root/link",
+            Some("../../outside"),
+        )
+        .await?;
+
+        let temp = tempfile::tempdir()?;
+        let target = temp.path().join("target");
+        let err = unpack(archive.as_slice(), ArchiveExtension::Tar, &target)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnsafeLink { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Archive entry `root/link` links to `../../outside`, outside of the target directory"
+        );
+        assert!(fs_err::symlink_metadata(target.join("root/link")).is_err());
+
+        // A symlink that stays inside the target directory is fine.
+        let archive = build_raw_tar(
+            tokio_tar::EntryType::Symlink,
+            "root/link",
+            Some("../README"),
+        )
+        .await?;
+        unpack(archive.as_slice(), ArchiveExtension::Tar, &target).await?;
+        assert!(fs_err::symlink_metadata(target.join("root/link"))?.is_symlink());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tar_xz_round_trip() -> Result<()> {
+        let source = tempfile::tempdir()?;
+        let tar = build_tar(source.path()).await?;
+
+        let mut archive = Vec::new();
+        XzEncoder::new(tar.as_slice())
+            .read_to_end(&mut archive)
+            .await?;
+
+        assert_round_trip(ArchiveExtension::TarXz, &archive).await
+    }
+
+    #[tokio::test]
+    async fn tar_zst_round_trip() -> Result<()> {
+        let source = tempfile::tempdir()?;
+        let tar = build_tar(source.path()).await?;
+
+        let mut archive = Vec::new();
+        ZstdEncoder::new(tar.as_slice())
+            .read_to_end(&mut archive)
+            .await?;
+
+        assert_round_trip(ArchiveExtension::TarZst, &archive).await
+    }
+}
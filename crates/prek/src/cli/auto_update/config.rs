@@ -4,6 +4,7 @@ use std::sync::LazyLock;
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use regex::Regex;
+use rustc_hash::FxHashMap;
 use toml_edit::DocumentMut;
 
 use crate::fs::Simplified;
@@ -19,11 +20,13 @@ static TOML_REV_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\s*rev\s*=").expect("TOML rev regex must be valid"));
 
 static YAML_REV_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"^\s+rev:\s*['"]?[^\s#]+.*$"#).expect("YAML rev line regex must be valid")
+    Regex::new(r#"^\s+rev:\s*(&\S+\s+)?['"]?[^\s#]+.*$"#)
+        .expect("YAML rev line regex must be valid")
 });
 
+/// Captures: indent, spacing, optional `&anchor `, quote, value, trailing text, line ending.
 static YAML_REV_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"^(\s+)rev:(\s*)(['"]?)([^\s#]+)(.*)(\r?\n)$"#)
+    Regex::new(r#"^(\s+)rev:(\s*)(&\S+\s+)?(['"]?)([^\s#]+)(.*?)(\r?\n)?$"#)
         .expect("YAML rev regex must be valid")
 });
 
@@ -203,37 +206,99 @@ pub(super) fn render_updated_yaml_config(
         );
     }
 
-    for (line_no, revision) in rev_lines.iter().zip_eq(revisions) {
-        let Some(revision) = revision else {
-            continue;
-        };
+    // The line and new `rev` of each anchored `rev` value, by anchor name.
+    let mut anchors: FxHashMap<String, (usize, Option<&str>)> = FxHashMap::default();
 
+    for (line_no, revision) in rev_lines.iter().zip_eq(revisions) {
         let caps = YAML_REV_RE
             .captures(&lines[*line_no])
             .context("Failed to capture rev line")?;
 
-        let new_rev = serialize_yaml_scalar(&revision.rev, &caps[3])?;
+        // An alias shares the value of its anchor, which is rewritten in place instead, so
+        // both repos must be updated to the same revision.
+        if let Some(name) = caps[5].strip_prefix('*') {
+            check_rev_alias(path, *line_no, name, revision.as_ref(), &anchors)?;
+            continue;
+        }
+
+        let anchor = caps.get(3).map_or("", |m| m.as_str());
+        if let Some(name) = anchor.trim_end().strip_prefix('&') {
+            anchors.insert(
+                name.to_string(),
+                (*line_no, revision.as_ref().map(|r| r.rev.as_str())),
+            );
+        }
+
+        let Some(revision) = revision else {
+            continue;
+        };
+
+        let trailing = &caps[6];
+        let line_ending = caps.get(7).map_or("", |m| m.as_str());
+        let new_rev = serialize_yaml_scalar(&revision.rev, &caps[4])?;
 
         let comment = if let Some(frozen) = &revision.frozen {
             format!(
                 "{}# frozen: {frozen}",
-                inline_comment_spacing(&caps[5]).unwrap_or("  ")
+                inline_comment_spacing(trailing).unwrap_or("  ")
             )
-        } else if caps[5].trim_start().starts_with("# frozen:") {
+        } else if trailing.trim_start().starts_with("# frozen:") {
             String::new()
         } else {
-            caps[5].to_string()
+            trailing.to_string()
         };
 
         lines[*line_no] = format!(
-            "{}rev:{}{}{}{}",
-            &caps[1], &caps[2], new_rev, comment, &caps[6]
+            "{}rev:{}{anchor}{new_rev}{comment}{line_ending}",
+            &caps[1], &caps[2]
         );
     }
 
     Ok(lines.join(""))
 }
 
+/// Checks that a `rev: *name` alias ends up at the revision its repo is updated to.
+///
+/// The alias can't be rewritten without breaking the anchor, so its repo and the one holding
+/// the anchor must be updated together, to the same revision.
+fn check_rev_alias(
+    path: &Path,
+    line_no: usize,
+    name: &str,
+    revision: Option<&Revision>,
+    anchors: &FxHashMap<String, (usize, Option<&str>)>,
+) -> Result<()> {
+    let target = revision.map(|r| r.rev.as_str());
+    let Some(&(anchor_line_no, anchor_target)) = anchors.get(name) else {
+        if let Some(target) = target {
+            anyhow::bail!(
+                "Cannot update `rev: *{name}` on line {} of `{}` to `{target}`: the anchor `&{name}` is not a `rev` value",
+                line_no + 1,
+                path.user_display(),
+            );
+        }
+        return Ok(());
+    };
+    if anchor_target == target {
+        return Ok(());
+    }
+
+    let alias = match target {
+        Some(target) => format!("updated to `{target}`"),
+        None => "not updated".to_string(),
+    };
+    let anchor = match anchor_target {
+        Some(target) => format!("updated to `{target}`"),
+        None => "not updated".to_string(),
+    };
+    anyhow::bail!(
+        "Cannot update `rev: *{name}` on line {} of `{}`: its repo is {alias}, but the repo holding `&{name}` on line {} is {anchor}; update both repos together or replace the alias with its own `rev`",
+        line_no + 1,
+        path.user_display(),
+        anchor_line_no + 1,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::{render_updated_toml_config, render_updated_yaml_config};
@@ -286,6 +351,110 @@ mod tests {
         assert!(rendered.contains("rev: abc123   # frozen: v1.1.0\n"));
     }
 
+    #[test]
+    fn test_render_updated_yaml_config_preserves_comments_and_anchors() {
+        let config = indoc::indoc! {r"
+            # Top-level comment
+            default_stages: [pre-commit]
+            repos:
+              # First repo
+              - repo: https://example.com/repo-a
+                rev: &shared v1.0.0 # pinned together
+                hooks:
+                  - id: hook-a
+                    args: &args [--fix]  # keep me
+              - repo: https://example.com/repo-b
+                rev: *shared
+                hooks:
+                  - id: hook-b
+                    args: *args
+              - repo: https://example.com/repo-c
+                rev: 'v2.0.0'
+                hooks:
+                  - id: hook-c
+        "};
+
+        let rendered = render_updated_yaml_config(
+            Path::new(".pre-commit-config.yaml"),
+            config,
+            &[
+                Some(Revision {
+                    rev: "v1.1.0".to_string(),
+                    frozen: None,
+                }),
+                Some(Revision {
+                    rev: "v1.1.0".to_string(),
+                    frozen: None,
+                }),
+                Some(Revision {
+                    rev: "v2.1.0".to_string(),
+                    frozen: None,
+                }),
+            ],
+        )
+        .unwrap();
+
+        let expected = config
+            .replace("rev: &shared v1.0.0 #", "rev: &shared v1.1.0 #")
+            .replace("rev: 'v2.0.0'", "rev: 'v2.1.0'");
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_render_updated_yaml_config_rejects_diverging_rev_alias() {
+        let config = indoc::indoc! {r"
+            repos:
+              - repo: https://example.com/repo-a
+                rev: &shared v1.0.0
+                hooks:
+                  - id: hook-a
+              - repo: https://example.com/repo-b
+                rev: *shared
+                hooks:
+                  - id: hook-b
+        "};
+        let revision = |rev: &str| {
+            Some(Revision {
+                rev: rev.to_string(),
+                frozen: None,
+            })
+        };
+        let render = |revisions: &[Option<Revision>]| {
+            render_updated_yaml_config(Path::new(".pre-commit-config.yaml"), config, revisions)
+                .map_err(|err| err.to_string())
+        };
+
+        // The repos move to different revisions.
+        assert_eq!(
+            render(&[revision("v1.1.0"), revision("v2.0.0")]).unwrap_err(),
+            "Cannot update `rev: *shared` on line 7 of `.pre-commit-config.yaml`: its repo is updated to `v2.0.0`, but the repo holding `&shared` on line 3 is updated to `v1.1.0`; update both repos together or replace the alias with its own `rev`"
+        );
+        // `--repo` selects only the repo using the alias.
+        assert_eq!(
+            render(&[None, revision("v1.1.0")]).unwrap_err(),
+            "Cannot update `rev: *shared` on line 7 of `.pre-commit-config.yaml`: its repo is updated to `v1.1.0`, but the repo holding `&shared` on line 3 is not updated; update both repos together or replace the alias with its own `rev`"
+        );
+        // `--repo` selects only the repo holding the anchor.
+        assert!(render(&[revision("v1.1.0"), None]).is_err());
+    }
+
+    #[test]
+    fn test_render_updated_yaml_config_without_trailing_newline() {
+        let config = "repos:\n  - repo: https://example.com/repo\n    hooks: [{id: test-hook}]\n    rev: v1.0.0";
+
+        let rendered = render_updated_yaml_config(
+            Path::new(".pre-commit-config.yaml"),
+            config,
+            &[Some(Revision {
+                rev: "v1.1.0".to_string(),
+                frozen: None,
+            })],
+        )
+        .unwrap();
+
+        assert!(rendered.ends_with("    rev: v1.1.0"));
+    }
+
     #[test]
     fn test_render_updated_toml_config_preserves_existing_frozen_comment_spacing() {
         let config = indoc::indoc! {r#"
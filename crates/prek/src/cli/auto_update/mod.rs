@@ -18,6 +18,7 @@ use crate::printer::Printer;
 use crate::run::CONCURRENCY;
 use crate::settings::FilesystemOptions;
 use crate::store::Store;
+use crate::warn_user;
 use crate::workspace::{Project, Workspace};
 
 mod config;
@@ -190,6 +191,75 @@ enum RevisionSelection {
     SkippedDowngrade(SkippedDowngrade),
 }
 
+/// A `--repo` or `--exclude-repo` value.
+///
+/// Values are compared to the configured `repo` URL: exactly by default, as a glob when they
+/// contain glob metacharacters (e.g. `https://github.com/pre-commit/*`), or as a regex when
+/// prefixed with `re:`.
+enum RepoPattern {
+    Exact(String),
+    Glob(globset::GlobMatcher),
+    Regex(regex::Regex),
+}
+
+impl RepoPattern {
+    fn new(value: &str, option: &str) -> Result<Self> {
+        if let Some(regex) = value.strip_prefix("re:") {
+            return Ok(Self::Regex(
+                regex::Regex::new(regex)
+                    .with_context(|| format!("Invalid {option} regex `{regex}`"))?,
+            ));
+        }
+        if value.contains(['*', '?', '[', '{']) {
+            let glob = globset::Glob::new(value)
+                .with_context(|| format!("Invalid {option} pattern `{value}`"))?;
+            return Ok(Self::Glob(glob.compile_matcher()));
+        }
+        Ok(Self::Exact(value.to_string()))
+    }
+
+    fn is_match(&self, repo: &str) -> bool {
+        match self {
+            Self::Exact(exact) => exact == repo,
+            Self::Glob(glob) => glob.is_match(repo),
+            Self::Regex(regex) => regex.is_match(repo),
+        }
+    }
+}
+
+struct RepoFilters {
+    include: Vec<(String, RepoPattern)>,
+    exclude: Vec<RepoPattern>,
+}
+
+impl RepoFilters {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: include
+                .iter()
+                .map(|value| anyhow::Ok((value.clone(), RepoPattern::new(value, "--repo")?)))
+                .collect::<Result<_>>()?,
+            exclude: exclude
+                .iter()
+                .map(|value| RepoPattern::new(value, "--exclude-repo"))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    fn is_selected(&self, repo: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|(_, p)| p.is_match(repo)))
+            && !self.exclude.iter().any(|pattern| pattern.is_match(repo))
+    }
+
+    /// `--repo` values that match none of the configured repositories.
+    fn unmatched<'a>(&'a self, repos: &[&str]) -> impl Iterator<Item = &'a str> {
+        self.include
+            .iter()
+            .filter(|(_, pattern)| !repos.iter().any(|repo| pattern.is_match(repo)))
+            .map(|(value, _)| value.as_str())
+    }
+}
+
 struct TagFilters {
     global_include: GlobPatterns,
     global_exclude: GlobPatterns,
@@ -386,6 +456,10 @@ pub(crate) async fn auto_update(
     filesystem: Option<FilesystemOptions>,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    if store.is_offline() {
+        anyhow::bail!("offline: `prek auto-update` needs network access to fetch new revisions");
+    }
+
     let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
     // TODO: support selectors?
     let selectors = Selectors::default();
@@ -396,11 +470,18 @@ pub(crate) async fn auto_update(
     let jobs = if jobs == 0 { *CONCURRENCY } else { jobs };
     let reporter = AutoUpdateReporter::new(printer);
 
+    let repo_filters = RepoFilters::new(&filter_repos, &exclude_repos)?;
     let repo_sources = collect_repo_sources(&workspace, cooldown_days, filesystem.as_ref())?;
-    let sources = repo_sources.iter().filter(|repo_source| {
-        (filter_repos.is_empty() || filter_repos.iter().any(|repo| repo == repo_source.repo))
-            && !exclude_repos.iter().any(|repo| repo == repo_source.repo)
-    });
+    let repos = repo_sources
+        .iter()
+        .map(|repo_source| repo_source.repo)
+        .collect::<Vec<_>>();
+    for value in repo_filters.unmatched(&repos) {
+        warn_user!("`--repo {value}` does not match any repository in the config");
+    }
+    let sources = repo_sources
+        .iter()
+        .filter(|repo_source| repo_filters.is_selected(repo_source.repo));
     let outcomes: Vec<RepoUpdate<'_>> = futures::stream::iter(sources)
         .map(async |repo_source| {
             let progress = reporter.on_update_start(repo_source.repo);
@@ -455,6 +536,37 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn repo_filters_match_exact_glob_and_regex() -> Result<()> {
+        let filters = RepoFilters::new(
+            &[
+                "https://github.com/astral-sh/ruff-pre-commit".to_string(),
+                "https://github.com/pre-commit/*".to_string(),
+                r"re:^https://gitlab\.com/".to_string(),
+            ],
+            &["*/mirrors-*".to_string()],
+        )?;
+
+        assert!(filters.is_selected("https://github.com/astral-sh/ruff-pre-commit"));
+        assert!(filters.is_selected("https://github.com/pre-commit/pre-commit-hooks"));
+        assert!(filters.is_selected("https://gitlab.com/example/hooks"));
+        assert!(!filters.is_selected("https://github.com/pre-commit/mirrors-prettier"));
+        assert!(!filters.is_selected("https://github.com/astral-sh/uv-pre-commit"));
+
+        let repos = ["https://github.com/pre-commit/pre-commit-hooks"];
+        assert_eq!(
+            filters.unmatched(&repos).collect::<Vec<_>>(),
+            vec![
+                "https://github.com/astral-sh/ruff-pre-commit",
+                r"re:^https://gitlab\.com/"
+            ]
+        );
+
+        assert!(RepoFilters::new(&["re:(".to_string()], &[]).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn tag_filters_keep_all_tags_without_filters() {
         let filters = TagFilters::new(Vec::new(), Vec::new(), Vec::new(), Vec::new()).unwrap();
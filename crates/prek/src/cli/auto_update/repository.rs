@@ -14,13 +14,14 @@ use semver::Version;
 use tracing::{debug, trace};
 
 use crate::cli::auto_update::{CommitPresence, RevisionSelection, SkippedDowngrade, TagTimestamp};
+use crate::suggest::levenshtein_distance;
 use crate::{config, git};
 
 /// Initializes a temporary git repo and fetches the remote HEAD plus tags.
 pub(super) async fn setup_and_fetch_repo(repo_url: &str, repo_path: &Path) -> Result<()> {
     git::init_repo(repo_url, repo_path).await?;
-    git::git_cmd("git fetch")?
-        .arg("fetch")
+    let mut cmd = git::git_cmd("git fetch")?;
+    cmd.arg("fetch")
         .arg("origin")
         .arg("HEAD")
         .arg("--quiet")
@@ -29,9 +30,9 @@ pub(super) async fn setup_and_fetch_repo(repo_url: &str, repo_path: &Path) -> Re
         .current_dir(repo_path)
         .remove_git_envs()
         .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .await?;
+        .stderr(Stdio::null());
+    git::apply_auth(&mut cmd, repo_url);
+    cmd.status().await?;
 
     Ok(())
 }
@@ -332,41 +333,6 @@ pub(super) fn select_best_tag<'a>(
     candidates.into_iter().next()
 }
 
-fn levenshtein_distance(a: &str, b: &str) -> usize {
-    if a == b {
-        return 0;
-    }
-
-    let a_len = a.chars().count();
-    let b_len = b.chars().count();
-
-    if a_len == 0 {
-        return b_len;
-    }
-
-    if b_len == 0 {
-        return a_len;
-    }
-
-    let mut row = (0..=a_len).collect::<Vec<_>>();
-
-    for (b_index, b_char) in b.chars().enumerate() {
-        let mut previous = row[0];
-        row[0] = b_index + 1;
-
-        for (a_index, a_char) in a.chars().enumerate() {
-            let deletion = row[a_index + 1] + 1;
-            let insertion = row[a_index] + 1;
-            let substitution = previous + usize::from(a_char != b_char);
-
-            previous = row[a_index + 1];
-            row[a_index + 1] = deletion.min(insertion).min(substitution);
-        }
-    }
-
-    row[a_len]
-}
-
 /// Checks out the candidate manifest and verifies all configured hook ids still exist.
 pub(super) async fn checkout_and_validate_manifest(
     repo_path: &Path,
@@ -424,8 +390,8 @@ pub(super) async fn checkout_and_validate_manifest(
 #[cfg(test)]
 mod tests {
     use super::{
-        levenshtein_distance, list_tag_metadata, no_lazy_fetch_unsupported, resolve_bleeding_edge,
-        select_best_tag, select_update_revision,
+        list_tag_metadata, no_lazy_fetch_unsupported, resolve_bleeding_edge, select_best_tag,
+        select_update_revision,
     };
     use crate::cli::auto_update::{RevisionSelection, SkippedDowngrade};
     use crate::git;
@@ -500,16 +466,6 @@ mod tests {
         cmd
     }
 
-    #[test]
-    fn test_levenshtein_distance() {
-        assert_eq!(levenshtein_distance("", ""), 0);
-        assert_eq!(levenshtein_distance("", "v1.0.0"), 6);
-        assert_eq!(levenshtein_distance("v1.0.0", "v1.1.0"), 1);
-        assert_eq!(levenshtein_distance("v1.0.0", "foo-v1.1.0"), 5);
-        assert_eq!(levenshtein_distance("v1.0.0", "v1.0.0"), 0);
-        assert_eq!(levenshtein_distance("mañana", "manana"), 1);
-    }
-
     #[test]
     fn test_select_best_tag_prefers_closest_tag() {
         let tags = ["v1.1.0", "foo-v1.1.0", "v1"];
@@ -43,9 +43,11 @@ pub(super) fn collect_repo_sources<'a>(
                 .and_then(|options| options.cooldown_days),
         );
         let cooldown_days = settings.cooldown_days;
+        // Only repos declared in the project's own file can be rewritten; those inherited
+        // through `extends` have no `rev:` line there.
         let remote_count = project
             .config()
-            .repos
+            .declared_repos()
             .iter()
             .filter(|repo| matches!(repo, Repo::Remote(_)))
             .count();
@@ -67,7 +69,7 @@ pub(super) fn collect_repo_sources<'a>(
         }
 
         let mut remote_index = 0;
-        for repo in &project.config().repos {
+        for repo in project.config().declared_repos() {
             let Repo::Remote(remote_repo) = repo else {
                 continue;
             };
@@ -15,9 +15,13 @@ use crate::cli::ExitStatus;
 use crate::cli::cache_size::{dir_size_bytes, human_readable_bytes};
 use crate::cli::run::InstallCache;
 use crate::config::{self, Error as ConfigError, Repo as ConfigRepo, load_config};
-use crate::hook::{HOOK_MARKER, HookEnvKey, HookSpec, InstallInfo, Repo as HookRepo};
+use crate::fs::LockedFile;
+use crate::hook::{
+    HOOK_IN_USE_LOCK, HOOK_MARKER, HookEnvKey, HookSpec, InstallInfo, Repo as HookRepo,
+};
 use crate::printer::Printer;
 use crate::store::{CacheBucket, REPO_MARKER, Store, ToolBucket};
+use crate::workspace::WorkspaceCache;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum RemovalKind {
@@ -148,7 +152,9 @@ pub(crate) async fn cache_gc(
 ) -> Result<ExitStatus> {
     let _lock = store.lock_async().await?;
 
-    let tracked_configs = store.tracked_configs()?;
+    // Also consider configs from workspace discovery, which may not be tracked yet.
+    let mut tracked_configs = store.tracked_configs()?;
+    tracked_configs.extend(WorkspaceCache::cached_config_paths(store));
     if tracked_configs.is_empty() {
         writeln!(printer.stdout(), "{}", "Nothing to clean".bold())?;
         return Ok(ExitStatus::Success);
@@ -216,7 +222,14 @@ pub(crate) async fn cache_gc(
     // `InstallInfo.toolchain` (which is persisted in `.prek-hook.json`).
     for installed in install_cache.installed_hooks(store).await {
         let info = installed.info_ref();
-        if used_env_keys.iter().any(|k| k.matches_install_info(info)) {
+        let referenced = used_env_keys.iter().any(|k| k.matches_install_info(info));
+        // Never sweep an env that a concurrent run is using, even if no config references it.
+        let in_use = !referenced && hook_env_in_use(&info.env_path);
+        if in_use {
+            used_tools.extend(info.language.tool_buckets());
+            used_cache.extend(info.language.cache_buckets());
+        }
+        if referenced || in_use {
             if let Some(dir) = info
                 .env_path
                 .file_name()
@@ -385,9 +398,8 @@ fn hook_env_keys_from_config(store: &Store, config: &config::Config) -> Vec<Hook
                     let mut hook_spec = manifest_hook.clone();
                     hook_spec.apply_remote_hook_overrides(hook_config);
 
-                    match HookEnvKey::from_hook_spec(config, hook_spec, Some(&remote_dep)) {
-                        Ok(Some(key)) => keys.push(key),
-                        Ok(None) => {}
+                    match HookEnvKey::from_hook_spec(config, &hook_spec, Some(&remote_dep)) {
+                        Ok(env_keys) => keys.extend(env_keys),
                         Err(err) => {
                             warn!(hook = %hook_config.id, repo = %remote_dep, %err, "Failed to compute hook env key, skipping");
                         }
@@ -397,9 +409,8 @@ fn hook_env_keys_from_config(store: &Store, config: &config::Config) -> Vec<Hook
             ConfigRepo::Local(repo_config) => {
                 for hook in &repo_config.hooks {
                     let hook_spec = HookSpec::from(hook.clone());
-                    match HookEnvKey::from_hook_spec(config, hook_spec, None) {
-                        Ok(Some(key)) => keys.push(key),
-                        Ok(None) => {}
+                    match HookEnvKey::from_hook_spec(config, &hook_spec, None) {
+                        Ok(env_keys) => keys.extend(env_keys),
                         Err(err) => {
                             warn!(hook = %hook.id, %err, "Failed to compute hook env key, skipping");
                         }
@@ -413,6 +424,26 @@ fn hook_env_keys_from_config(store: &Store, config: &config::Config) -> Vec<Hook
     keys
 }
 
+/// Whether a running `prek` process holds the in-use lock of a hook env.
+fn hook_env_in_use(env_path: &Path) -> bool {
+    let lock_path = env_path.join(HOOK_IN_USE_LOCK);
+    if !lock_path.is_file() {
+        return false;
+    }
+
+    match LockedFile::try_acquire(&lock_path) {
+        Ok(Some(_lock)) => false,
+        Ok(None) => {
+            debug!(env = %env_path.display(), "Hook env is in use by another process, keeping");
+            true
+        }
+        Err(err) => {
+            warn!(env = %env_path.display(), %err, "Failed to check if hook env is in use, keeping");
+            true
+        }
+    }
+}
+
 fn mark_tool_versions_from_install_info(
     store: &Store,
     info: &InstallInfo,
@@ -715,13 +746,16 @@ fn label_for_entry(
 
 fn detail_lines_for_entry(
     kind: RemovalKind,
-    _repo_marker: Option<&RepoMarker>,
+    repo_marker: Option<&RepoMarker>,
     hook_marker: Option<&InstallInfo>,
 ) -> Vec<String> {
     const MAX_VALUE_CHARS: usize = 140;
 
     match kind {
-        RemovalKind::Repos => vec![],
+        RemovalKind::Repos => repo_marker
+            .and_then(|repo| repo.commit.as_deref())
+            .map(|commit| vec![format!("{}: {commit}", "commit".dimmed().bold())])
+            .unwrap_or_default(),
         RemovalKind::HookEnvs => {
             let Some(info) = hook_marker else {
                 return Vec::new();
@@ -757,6 +791,8 @@ fn detail_lines_for_entry(
 struct RepoMarker {
     repo: String,
     rev: String,
+    /// The commit a mutable `rev` resolved to at clone time.
+    commit: Option<String>,
 }
 
 fn read_repo_marker(root: &Path) -> Option<RepoMarker> {
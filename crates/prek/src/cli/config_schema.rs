@@ -0,0 +1,12 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::cli::ExitStatus;
+use crate::printer::Printer;
+use crate::schema::config_json_schema;
+
+pub(crate) fn config_schema(printer: Printer) -> Result<ExitStatus> {
+    write!(printer.stdout_important(), "{}", config_json_schema())?;
+    Ok(ExitStatus::Success)
+}
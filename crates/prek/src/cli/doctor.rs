@@ -0,0 +1,165 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
+
+use crate::cli::reporter::HookInstallReporter;
+use crate::cli::run::{InstallCache, install_hooks};
+use crate::cli::{ExitStatus, discover_workspace, init_selected_hooks};
+use crate::hook::{Hook, InstalledHook};
+use crate::printer::Printer;
+use crate::store::Store;
+
+/// Health of a single hook's installed environment.
+enum HookHealth {
+    Ok,
+    Missing,
+    Broken { env_path: PathBuf, error: String },
+}
+
+/// Run the language health check on every installed hook environment.
+///
+/// Unlike `warmup`, this never installs anything unless `fix` is set, in which
+/// case broken environments are removed and installed again.
+pub(crate) async fn doctor(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: Vec<String>,
+    skips: Vec<String>,
+    frozen: bool,
+    fix: bool,
+    refresh: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let (workspace, selectors) =
+        discover_workspace(store, config, &includes, &skips, frozen, refresh)?;
+
+    let _lock = store.lock_async().await?;
+    let mut hooks = init_selected_hooks(store, &workspace, &selectors, printer).await?;
+    hooks.retain(|h| h.needs_install_env());
+
+    let install_cache = InstallCache::new();
+    let mut results = Vec::with_capacity(hooks.len());
+    for hook in hooks {
+        let health = check_hook(store, &install_cache, &hook).await;
+        results.push((hook, health));
+    }
+
+    let width = results
+        .iter()
+        .map(|(hook, _)| hook.full_id().len())
+        .max()
+        .unwrap_or_default();
+    let language_width = results
+        .iter()
+        .map(|(hook, _)| hook.language.as_ref().len())
+        .max()
+        .unwrap_or_default();
+
+    for (hook, health) in &results {
+        let status = match health {
+            HookHealth::Ok => "ok".green().to_string(),
+            HookHealth::Missing => "not installed".dimmed().to_string(),
+            HookHealth::Broken { .. } => "FAIL".red().bold().to_string(),
+        };
+        writeln!(
+            printer.stdout(),
+            "{:<width$}  {:<language_width$}  {status}",
+            hook.full_id(),
+            hook.language.as_ref(),
+        )?;
+        if let HookHealth::Broken { error, .. } = health {
+            writeln!(printer.stdout(), "  {}", error.dimmed())?;
+        }
+    }
+
+    let broken: Vec<_> = results
+        .into_iter()
+        .filter_map(|(hook, health)| match health {
+            HookHealth::Broken { env_path, .. } => Some((hook, env_path)),
+            _ => None,
+        })
+        .collect();
+
+    if broken.is_empty() {
+        return Ok(ExitStatus::Success);
+    }
+
+    if !fix {
+        writeln!(
+            printer.stderr(),
+            "{}: {} hook environment{} failed the health check, run `prek doctor --fix` to reinstall",
+            "error".red().bold(),
+            broken.len(),
+            if broken.len() == 1 { "" } else { "s" },
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    // Hooks can share an environment; remove each broken one only once.
+    let mut removed = FxHashSet::default();
+    for (_, env_path) in &broken {
+        if removed.insert(env_path.clone()) {
+            fs_err::tokio::remove_dir_all(env_path)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to remove broken environment `{}`",
+                        env_path.display()
+                    )
+                })?;
+        }
+    }
+
+    let hooks: Vec<_> = broken.into_iter().map(|(hook, _)| hook).collect();
+    let num_hooks = hooks.len();
+    let reporter = HookInstallReporter::new(printer);
+    let installed_hooks = install_hooks(hooks, store, &reporter, &mut InstallCache::new()).await?;
+    reporter.on_complete();
+
+    for hook in &installed_hooks {
+        if let InstalledHook::Installed { info, .. } = hook {
+            info.check_health(store).await.with_context(|| {
+                format!(
+                    "Environment for hook `{}` is still unhealthy after reinstalling",
+                    hook.full_id()
+                )
+            })?;
+        }
+    }
+
+    writeln!(
+        printer.stderr(),
+        "{}: Reinstalled environments for {num_hooks} hook{}",
+        "success".green().bold(),
+        if num_hooks == 1 { "" } else { "s" },
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Find the store environment for a hook and check its health.
+///
+/// A healthy match wins over broken ones, so a hook is only reported broken
+/// when every matching environment fails.
+async fn check_hook(store: &Store, cache: &InstallCache, hook: &Hook) -> HookHealth {
+    let mut broken = None;
+    for env in cache.installed_hooks(store).await {
+        let info = env.info_ref();
+        if !info.matches(hook) {
+            continue;
+        }
+        match info.check_health(store).await {
+            Ok(()) => return HookHealth::Ok,
+            Err(err) => {
+                broken.get_or_insert(HookHealth::Broken {
+                    env_path: info.env_path.clone(),
+                    error: format!("{err:#}"),
+                });
+            }
+        }
+    }
+    broken.unwrap_or(HookHealth::Missing)
+}
@@ -31,13 +31,68 @@ pub(crate) async fn hook_impl(
     hook_type: HookType,
     hook_dir: Option<PathBuf>,
     skip_on_missing_config: bool,
+    legacy_last: bool,
     script_version: Option<usize>,
     args: Vec<OsString>,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    if EnvVars::is_set(EnvVars::PREK_RUNNING_LEGACY) {
+        anyhow::bail!(
+            "prek's Git shim is installed in migration mode\n\
+            run `prek install -f --hook-type {hook_type}` to reinstall the shim"
+        );
+    }
+
     let stdin = read_hook_stdin(hook_type).await?;
-    let legacy_code = run_legacy(hook_type, hook_dir.as_deref(), &args, &stdin).await?;
 
+    // Both the legacy hook and prek's hooks always run, in the order chosen at install
+    // time; the Git hook fails if either of them does.
+    let legacy_code = if legacy_last {
+        None
+    } else {
+        Some(run_legacy(hook_type, hook_dir.as_deref(), &args, &stdin).await?)
+    };
+
+    let status = run_hooks(
+        store,
+        config,
+        includes,
+        skips,
+        hook_type,
+        skip_on_missing_config,
+        script_version,
+        &args,
+        &stdin,
+        printer,
+    )
+    .await?;
+
+    let legacy_code = match legacy_code {
+        Some(code) => code,
+        None => run_legacy(hook_type, hook_dir.as_deref(), &args, &stdin).await?,
+    };
+
+    Ok(if !matches!(status, ExitStatus::Success) {
+        status
+    } else {
+        legacy_code.into()
+    })
+}
+
+/// Run prek's hooks for a Git hook invocation.
+#[allow(clippy::too_many_arguments)]
+async fn run_hooks(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: Vec<String>,
+    skips: Vec<String>,
+    hook_type: HookType,
+    skip_on_missing_config: bool,
+    script_version: Option<usize>,
+    args: &[OsString],
+    stdin: &[u8],
+    printer: Printer,
+) -> Result<ExitStatus> {
     if let Some(script_version) = script_version
         && script_version != cli::install::CUR_SCRIPT_VERSION
     {
@@ -65,7 +120,7 @@ pub(crate) async fn hook_impl(
     if let Some(ref config) = config {
         if !config.try_exists()? {
             return if allow_missing_config {
-                Ok(legacy_code.into())
+                Ok(ExitStatus::Success)
             } else {
                 eprintln!(
                     "{}: config file not found: `{}`",
@@ -83,7 +138,7 @@ pub(crate) async fn hook_impl(
         match Project::discover(config.as_deref(), &CWD) {
             Err(e @ workspace::Error::MissingConfigFile) => {
                 return if allow_missing_config {
-                    Ok(legacy_code.into())
+                    Ok(ExitStatus::Success)
                 } else {
                     eprintln!("{}: {e}", "error".red().bold());
                     warn_for_no_config();
@@ -111,43 +166,32 @@ pub(crate) async fn hook_impl(
             hook_type.to_string().cyan(),
             format_expected_args(expected_args),
             format_received_args(args.len()),
-            format_argument_dump(&args)
+            format_argument_dump(args)
         );
     }
 
-    let Some(run_args) = to_run_args(hook_type, &args, &stdin).await? else {
-        return Ok(legacy_code.into());
+    let Some(run_args) = to_run_args(hook_type, args, stdin).await? else {
+        return Ok(ExitStatus::Success);
     };
 
-    let status = cli::run(
+    cli::run(
         store,
-        config,
-        includes,
-        skips,
-        vec![],
-        vec![],
-        Some(hook_type.into()),
-        run_args.from_ref,
-        run_args.to_ref,
-        run_args.all_files,
-        vec![],
-        vec![],
-        false,
-        false,
-        flag(run_args.fail_fast, run_args.no_fail_fast),
-        false,
-        false,
-        run_args.extra,
-        false,
+        cli::RunSettings {
+            config,
+            includes,
+            skips,
+            hook_stage: Some(hook_type.into()),
+            from_ref: run_args.from_ref,
+            to_ref: run_args.to_ref,
+            all_files: run_args.all_files,
+            fail_fast: flag(run_args.fail_fast, run_args.no_fail_fast),
+            allow_missing_config,
+            extra_args: run_args.extra,
+            ..cli::RunSettings::default()
+        },
         printer,
     )
-    .await?;
-
-    Ok(if !matches!(status, ExitStatus::Success) {
-        status
-    } else {
-        legacy_code.into()
-    })
+    .await
 }
 
 fn hook_num_args(hook_type: HookType) -> RangeInclusive<usize> {
@@ -182,13 +226,6 @@ async fn run_legacy(
     args: &[OsString],
     stdin: &[u8],
 ) -> Result<u8> {
-    if EnvVars::is_set(EnvVars::PREK_RUNNING_LEGACY) {
-        anyhow::bail!(
-            "prek's Git shim is installed in migration mode\n\
-            run `prek install -f --hook-type {hook_type}` to reinstall the shim"
-        );
-    }
-
     // `prek hook-impl` without `--hook-dir` is likely invoked from Git 2.54+
     // config-based hooks, where there is no hook script directory to inspect.
     // Skip legacy hooks in that case.
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Write as _;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -6,6 +7,7 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use bstr::ByteSlice;
 use clap::ValueEnum;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use prek_consts::CONFIG_FILENAMES;
 use same_file::is_same_file;
@@ -15,9 +17,10 @@ use crate::cli::run;
 use crate::cli::run::InstallCache;
 use crate::cli::run::{SelectorSource, Selectors};
 use crate::cli::{ExitStatus, HookType};
-use crate::config::load_config;
+use crate::config::{self, Repo, Stage, Stages, is_mutable_rev, load_config};
 use crate::fs::{CWD, Simplified};
 use crate::git::{GIT_ROOT, git_cmd};
+use crate::hook::Hook;
 use crate::printer::Printer;
 use crate::store::Store;
 use crate::workspace::{Error as WorkspaceError, HookInitFilters, Project, Workspace};
@@ -30,12 +33,15 @@ pub(crate) async fn install(
     includes: Vec<String>,
     skips: Vec<String>,
     hook_types: Vec<HookType>,
+    auto_hook_types: bool,
     prepare_hooks: bool,
     overwrite: bool,
     allow_missing_config: bool,
+    legacy_last: bool,
     refresh: bool,
     printer: Printer,
     git_dir: Option<&Path>,
+    no_hooks_path: bool,
 ) -> Result<ExitStatus> {
     // Upstream `pre-commit` deliberately refuses to install whenever
     // `git config core.hooksPath` resolves to a non-empty value. It does not
@@ -54,6 +60,22 @@ pub(crate) async fn install(
     // are part of the repository's own Git setup. Only hooksPath values that
     // resolve entirely from external config still require the explicit
     // `--git-dir` escape hatch.
+    //
+    // `--no-hooks-path` is the same escape hatch pointed at the repository's own git
+    // directory, for setups where the `core.hooksPath` hooks chain to `.git/hooks`.
+    let common_dir;
+    let git_dir = if no_hooks_path {
+        common_dir = git::get_git_common_dir().await?;
+        if git::has_hooks_path_set().await? {
+            warn_user!(
+                "`core.hooksPath` is set, so Git will not run the shims in `{}` unless the configured hooks call them",
+                common_dir.join("hooks").user_display().cyan()
+            );
+        }
+        Some(common_dir.as_path())
+    } else {
+        git_dir
+    };
     if git_dir.is_none()
         && git::has_hooks_path_set().await?
         && !git::has_repo_hooks_path_set().await?
@@ -88,8 +110,6 @@ pub(crate) async fn install(
             None
         }
     };
-    let hook_types = get_hook_types(hook_types, project.as_ref(), config.as_deref());
-
     let hooks_path = if let Some(dir) = git_dir {
         dir.join("hooks")
     } else {
@@ -105,6 +125,17 @@ pub(crate) async fn install(
         None
     };
 
+    let hook_types = if auto_hook_types {
+        let (Some(project), Some(selectors)) = (&project, &selectors) else {
+            anyhow::bail!(
+                "`--auto-hook-types` requires a config file to derive the hook types from"
+            );
+        };
+        self::auto_hook_types(store, project, config.clone(), selectors, refresh, printer).await?
+    } else {
+        get_hook_types(hook_types, project.as_ref(), config.as_deref())
+    };
+
     for hook_type in hook_types {
         install_hook_script(
             project.as_ref(),
@@ -114,13 +145,17 @@ pub(crate) async fn install(
             &hooks_path,
             overwrite,
             allow_missing_config,
+            legacy_last,
             hook_mode,
             printer,
         )?;
     }
 
     if prepare_hooks {
-        self::prepare_hooks(store, config, includes, skips, refresh, printer).await?;
+        self::prepare_hooks(
+            store, config, includes, skips, false, false, refresh, printer,
+        )
+        .await?;
     }
 
     Ok(ExitStatus::Success)
@@ -131,36 +166,227 @@ pub(crate) async fn prepare_hooks(
     config: Option<PathBuf>,
     includes: Vec<String>,
     skips: Vec<String>,
+    frozen: bool,
+    dry_run: bool,
     refresh: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
-    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
-    let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), refresh)?;
+    let (workspace, selectors) =
+        discover_workspace(store, config, &includes, &skips, frozen, refresh)?;
+
+    if dry_run {
+        print_prepare_plan(store, &workspace, &selectors, printer).await?;
+        return Ok(ExitStatus::Success);
+    }
 
-    let reporter = HookInitReporter::new(printer);
     let _lock = store.lock_async().await?;
+    let filtered_hooks = init_selected_hooks(store, &workspace, &selectors, printer).await?;
+
+    let reporter = HookInstallReporter::new(printer);
+    let mut install_cache = InstallCache::new();
+    run::install_hooks(filtered_hooks, store, &reporter, &mut install_cache).await?;
+    reporter.on_complete();
+
+    Ok(ExitStatus::Success)
+}
+
+/// Discover the workspace and load the hook selectors, as `prepare-hooks`, `warmup` and
+/// `doctor` do before looking at hook environments.
+pub(crate) fn discover_workspace(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: &[String],
+    skips: &[String],
+    frozen: bool,
+    refresh: bool,
+) -> Result<(Workspace, Selectors)> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let selectors = Selectors::load(includes, skips, &workspace_root)?;
+    let discover = || Workspace::discover(store, workspace_root, config, Some(&selectors), refresh);
+    let workspace = if frozen {
+        // Mutable revs are reported as an error below, so loading the configs needn't warn.
+        let workspace = config::FROZEN_REVS.sync_scope((), discover)?;
+        ensure_immutable_revs(&workspace)?;
+        workspace
+    } else {
+        discover()?
+    };
+    Ok((workspace, selectors))
+}
 
+/// Initialize the hooks of `workspace` matched by `selectors`.
+///
+/// The caller should hold the store lock until it is done with the hook environments.
+pub(crate) async fn init_selected_hooks(
+    store: &Store,
+    workspace: &Workspace,
+    selectors: &Selectors,
+    printer: Printer,
+) -> Result<Vec<Arc<Hook>>> {
+    let reporter = HookInitReporter::new(printer);
     let hooks = workspace
         .init_hooks(
             store,
-            HookInitFilters::new(Some(&selectors), None),
+            HookInitFilters::new(Some(selectors), None),
             Some(&reporter),
         )
         .await
         .context("Failed to init hooks")?;
-    let filtered_hooks: Vec<_> = hooks
+    let hooks = hooks
         .into_iter()
         .filter(|h| selectors.matches_hook(h))
         .map(Arc::new)
         .collect();
 
-    let reporter = HookInstallReporter::new(printer);
-    let mut install_cache = InstallCache::new();
-    run::install_hooks(filtered_hooks, store, &reporter, &mut install_cache).await?;
-    reporter.on_complete();
+    selectors.report_unused();
 
-    Ok(ExitStatus::Success)
+    Ok(hooks)
+}
+
+/// Print the repos `prepare-hooks` would clone and the environments it would create.
+///
+/// Only repos already in the store are read, and nothing is cloned, downloaded or installed.
+async fn print_prepare_plan(
+    store: &Store,
+    workspace: &Workspace,
+    selectors: &Selectors,
+    printer: Printer,
+) -> Result<()> {
+    let (hooks, uncloned) = workspace
+        .init_cached_hooks(store, HookInitFilters::new(Some(selectors), None))
+        .await
+        .context("Failed to init hooks")?;
+    selectors.report_unused();
+
+    let install_cache = InstallCache::new();
+    let mut installed = 0usize;
+    // Hooks that would share one new environment, as `install_hooks` groups them.
+    let mut environments: Vec<Vec<Arc<Hook>>> = Vec::new();
+    for hook in hooks.into_iter().filter(|h| selectors.matches_hook(h)) {
+        let hook = Arc::new(hook);
+        if !hook.needs_install_env() {
+            continue;
+        }
+        if install_cache
+            .installed_hook(store, hook.clone())
+            .await
+            .is_some()
+        {
+            installed += 1;
+            continue;
+        }
+        if let Some(group) = environments
+            .iter_mut()
+            .find(|group| group[0].env_key() == hook.env_key())
+        {
+            group.push(hook);
+        } else {
+            environments.push(vec![hook]);
+        }
+    }
+
+    let mut stdout = printer.stdout_important();
+    if !uncloned.is_empty() {
+        writeln!(stdout, "{}", "Repos to clone:".bold())?;
+        for repo in &uncloned {
+            let note = if is_mutable_rev(&repo.rev) {
+                " (moving ref, resolved when cloned)"
+            } else {
+                ""
+            };
+            writeln!(stdout, "  {}@{}{note}", repo.repo.cyan(), repo.rev)?;
+        }
+    }
+
+    if !environments.is_empty() {
+        if !uncloned.is_empty() {
+            writeln!(stdout)?;
+        }
+        writeln!(stdout, "{}", "Environments to create:".bold())?;
+        for group in &environments {
+            let hook = &group[0];
+            let version = if hook.language_version.is_empty() {
+                "default"
+            } else {
+                hook.language_version.as_str()
+            };
+            writeln!(
+                stdout,
+                "  {} ({version}) for {}",
+                hook.language.cyan(),
+                group
+                    .iter()
+                    .map(|hook| format!("`{}`", hook.full_id()))
+                    .join(", "),
+            )?;
+            if !hook.additional_dependencies.is_empty() {
+                writeln!(
+                    stdout,
+                    "    additional dependencies: {}",
+                    hook.additional_dependencies.iter().sorted().join(", ")
+                )?;
+            }
+            let toolchain = if hook.language.tool_buckets().is_empty() {
+                "installed on the system"
+            } else if hook.language_request.allows_download() {
+                "found on the system or in the store, downloaded otherwise"
+            } else {
+                "installed on the system, never downloaded"
+            };
+            writeln!(stdout, "    toolchain: {toolchain}")?;
+        }
+    }
+
+    if uncloned.is_empty() && environments.is_empty() {
+        writeln!(stdout, "Nothing to clone or install")?;
+    }
+    if installed > 0 {
+        writeln!(
+            stdout,
+            "{installed} hook{} already installed",
+            if installed == 1 { " is" } else { "s are" }
+        )?;
+    }
+    if !uncloned.is_empty() {
+        writeln!(
+            stdout,
+            "{}",
+            "Environments for hooks of repos to clone are not listed; they are known after cloning."
+                .dimmed()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Fail if any remote repo in the workspace is pinned to a mutable `rev` (a branch or a
+/// moving tag), which would otherwise be resolved once at clone time and never updated.
+fn ensure_immutable_revs(workspace: &Workspace) -> Result<()> {
+    let mutable = workspace
+        .projects()
+        .iter()
+        .flat_map(|project| project.config().repos.iter())
+        .filter_map(|repo| match repo {
+            Repo::Remote(repo) if is_mutable_rev(&repo.rev) => Some(repo),
+            _ => None,
+        })
+        .unique_by(|repo| repo.key())
+        .map(|repo| format!("  {}: {}", repo.repo.cyan(), repo.rev.yellow()))
+        .join("\n");
+    if mutable.is_empty() {
+        return Ok(());
+    }
+
+    anyhow::bail!(
+        concat!(
+            "The following repos have mutable `rev` fields (moving tag / branch), which are not allowed with `--frozen`:\n",
+            "{}\n",
+            "\n{} Pin them to a tag or commit SHA, for example with `{}`.",
+        ),
+        mutable,
+        "hint:".yellow().bold(),
+        "prek auto-update".cyan(),
+    );
 }
 
 fn get_hook_types(
@@ -202,6 +428,97 @@ fn get_hook_types(
     hook_types
 }
 
+/// Why a hook type is installed by `--auto-hook-types`.
+#[derive(Default)]
+struct HookTypeReason {
+    /// The hook type is listed in `default_install_hook_types`.
+    default: bool,
+    /// IDs of the hooks running at the stage of the hook type.
+    hooks: Vec<String>,
+}
+
+/// Derive the hook types to install from the stages of the hooks in the config.
+///
+/// Hooks without `stages` run at every stage, so they only need a `pre-commit` shim. Hook types
+/// from `default_install_hook_types` are always included. Prints why each hook type is needed.
+async fn auto_hook_types(
+    store: &Store,
+    project: &Project,
+    config: Option<PathBuf>,
+    selectors: &Selectors,
+    refresh: bool,
+    printer: Printer,
+) -> Result<Vec<HookType>> {
+    let workspace = Workspace::discover(
+        store,
+        project.path().to_path_buf(),
+        config,
+        Some(selectors),
+        refresh,
+    )?;
+
+    let hooks = {
+        let reporter = HookInitReporter::new(printer);
+        let _lock = store.lock_async().await?;
+        workspace
+            .init_hooks(
+                store,
+                HookInitFilters::new(Some(selectors), None),
+                Some(&reporter),
+            )
+            .await
+            .context("Failed to init hooks")?
+    };
+
+    let mut reasons: BTreeMap<Stage, HookTypeReason> = BTreeMap::new();
+    for hook_type in project.config().default_install_hook_types.iter().flatten() {
+        reasons.entry(Stage::from(*hook_type)).or_default().default = true;
+    }
+    for hook in hooks.iter().filter(|hook| selectors.matches_hook(hook)) {
+        let stages = if hook.stages == Stages::ALL {
+            Stages::from([Stage::PreCommit])
+        } else {
+            hook.stages
+        };
+        for stage in stages.iter().filter(|stage| stage.hook_type().is_some()) {
+            let reason = reasons.entry(stage).or_default();
+            if !reason.hooks.contains(&hook.id) {
+                reason.hooks.push(hook.id.clone());
+            }
+        }
+    }
+
+    if reasons.is_empty() {
+        warn_user!("No hook runs at a stage with a Git shim, no shims to install");
+    }
+
+    let mut hook_types = Vec::with_capacity(reasons.len());
+    for (stage, reason) in reasons {
+        let Some(hook_type) = stage.hook_type() else {
+            continue;
+        };
+        let hooks = reason
+            .hooks
+            .iter()
+            .map(|id| format!("`{}`", id.cyan()))
+            .join(", ");
+        let needed_by = match (reason.default, reason.hooks.len()) {
+            (true, 0) => "`default_install_hook_types`".to_string(),
+            (true, _) => format!("`default_install_hook_types` and hooks {hooks}"),
+            (false, 1) => format!("hook {hooks}"),
+            (false, _) => format!("hooks {hooks}"),
+        };
+        writeln!(
+            printer.stdout(),
+            "Hook type `{}` is needed by {needed_by}",
+            hook_type.cyan()
+        )?;
+        hook_types.push(hook_type);
+    }
+
+    Ok(hook_types)
+}
+
 #[allow(clippy::fn_params_excessive_bools)]
 fn install_hook_script(
     project: Option<&Project>,
@@ -211,6 +528,7 @@ fn install_hook_script(
     hooks_path: &Path,
     overwrite: bool,
     skip_on_missing_config: bool,
+    legacy_last: bool,
     hook_mode: u32,
     printer: Printer,
 ) -> Result<()> {
@@ -226,6 +544,21 @@ fn install_hook_script(
             )?;
         } else {
             if !is_our_script(&hook_path)? {
+                // Moving the hook aside must not clobber a foreign script kept from an
+                // earlier install.
+                if legacy_path.try_exists()? && !is_our_script(&legacy_path)? {
+                    anyhow::bail!(
+                        concat!(
+                            "Refusing to move hook `{}` to `{}`, which already exists. Neither of them is managed by prek.\n",
+                            "\n{} Merge `{}` into `{}` and remove it, or use `--overwrite` to remove both.",
+                        ),
+                        hook_path.user_display().cyan(),
+                        legacy_path.user_display().cyan(),
+                        "hint:".yellow().bold(),
+                        hook_path.user_display().cyan(),
+                        legacy_path.user_display().cyan(),
+                    );
+                }
                 fs_err::rename(&hook_path, &legacy_path)?;
                 writeln!(
                     printer.stdout(),
@@ -244,8 +577,9 @@ fn install_hook_script(
         } else {
             writeln!(
                 printer.stdout(),
-                "Migration mode: prek will also run legacy hook `{}`. Use `--overwrite` to remove legacy hooks.",
-                legacy_path.user_display().yellow()
+                "Migration mode: prek will also run legacy hook `{}`{}. Use `--overwrite` to remove legacy hooks.",
+                legacy_path.user_display().yellow(),
+                if legacy_last { " after its hooks" } else { "" },
             )?;
         }
     }
@@ -315,6 +649,9 @@ fn install_hook_script(
     if skip_on_missing_config {
         args.push("--skip-on-missing-config".to_string());
     }
+    if legacy_last {
+        args.push("--legacy-last".to_string());
+    }
 
     let prek = std::env::current_exe()?;
     let prek = prek.simplified_display().to_string();
@@ -375,11 +712,17 @@ static PRIOR_HASHES: &[&str] = &[];
 static CURRENT_HASH: &str = "182c10f181da4464a3eec51b83331688";
 
 /// Checks if the script contains any of the hashes that `prek` has used in the past.
+///
+/// Foreign hooks may be binaries, so the content is not required to be UTF-8.
 fn is_our_script(hook_path: &Path) -> std::io::Result<bool> {
-    let content = fs_err::read_to_string(hook_path)?;
+    let content = fs_err::read(hook_path)?;
     Ok(std::iter::once(CURRENT_HASH)
         .chain(PRIOR_HASHES.iter().copied())
-        .any(|hash| content.contains(hash)))
+        .any(|hash| {
+            content
+                .windows(hash.len())
+                .any(|window| window == hash.as_bytes())
+        }))
 }
 
 pub(crate) async fn uninstall(
@@ -498,11 +841,14 @@ pub(crate) async fn init_template_dir(
         vec![],
         hook_types,
         false,
+        false,
         true,
         !requires_config,
+        false,
         refresh,
         printer,
         Some(&directory),
+        false,
     )
     .await?;
 
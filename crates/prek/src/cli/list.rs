@@ -10,10 +10,12 @@ use crate::cli::run::{GroupFilters, Selectors};
 use crate::cli::{ExitStatus, ListOutputFormat};
 use crate::config::{Language, Stage};
 use crate::fs::CWD;
+use crate::hook::Hook;
 use crate::printer::Printer;
 use crate::store::Store;
 use crate::workspace::{HookInitFilters, Workspace};
 
+/// Effective hook options after merging the manifest and the config.
 #[derive(Serialize)]
 struct SerializableHook {
     id: String,
@@ -23,6 +25,38 @@ struct SerializableHook {
     language: Language,
     description: Option<String>,
     stages: Vec<Stage>,
+    types: Vec<&'static str>,
+    types_or: Vec<&'static str>,
+    exclude_types: Vec<&'static str>,
+    always_run: bool,
+    /// The project path relative to the workspace root, `.` for the root project.
+    project: String,
+}
+
+impl From<Hook> for SerializableHook {
+    fn from(hook: Hook) -> Self {
+        let relative_path = hook.project().relative_path();
+        let project = if relative_path.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            relative_path.to_string_lossy().to_string()
+        };
+
+        Self {
+            full_id: hook.full_id(),
+            stages: hook.stages.iter().collect(),
+            types: hook.types.iter().collect(),
+            types_or: hook.types_or.iter().collect(),
+            exclude_types: hook.exclude_types.iter().collect(),
+            always_run: hook.always_run,
+            project,
+            id: hook.id,
+            name: hook.name,
+            alias: hook.alias,
+            language: hook.language,
+            description: hook.description,
+        }
+    }
 }
 
 pub(crate) async fn list(
@@ -121,25 +155,18 @@ pub(crate) async fn list(
         ListOutputFormat::Json => {
             let serializable_hooks: Vec<_> = filtered_hooks
                 .into_iter()
-                .map(|h| {
-                    let id = h.id.clone();
-                    let full_id = h.full_id();
-                    let stages = h.stages.iter().collect();
-                    SerializableHook {
-                        id,
-                        full_id,
-                        name: h.name,
-                        alias: h.alias,
-                        language: h.language,
-                        description: h.description,
-                        stages,
-                    }
-                })
+                .map(SerializableHook::from)
                 .collect();
 
             let json_output = serde_json::to_string_pretty(&serializable_hooks)?;
             writeln!(printer.stdout(), "{json_output}")?;
         }
+        ListOutputFormat::Jsonl => {
+            for hook in filtered_hooks {
+                let json_line = serde_json::to_string(&SerializableHook::from(hook))?;
+                writeln!(printer.stdout(), "{json_line}")?;
+            }
+        }
     }
 
     Ok(ExitStatus::Success)
@@ -43,16 +43,25 @@ pub(crate) fn list_builtins(
                 }
             }
         }
-        ListOutputFormat::Json => {
-            let serializable: Vec<_> = hooks
-                .map(|h| SerializableBuiltinHook {
-                    id: h.id,
-                    name: h.name,
-                    description: h.options.description,
-                })
-                .collect();
-            let json_output = serde_json::to_string_pretty(&serializable)?;
-            writeln!(printer.stdout_important(), "{json_output}")?;
+        ListOutputFormat::Json | ListOutputFormat::Jsonl => {
+            let serializable = hooks.map(|h| SerializableBuiltinHook {
+                id: h.id,
+                name: h.name,
+                description: h.options.description,
+            });
+            if matches!(output_format, ListOutputFormat::Jsonl) {
+                for hook in serializable {
+                    writeln!(
+                        printer.stdout_important(),
+                        "{}",
+                        serde_json::to_string(&hook)?
+                    )?;
+                }
+            } else {
+                let serializable: Vec<_> = serializable.collect();
+                let json_output = serde_json::to_string_pretty(&serializable)?;
+                writeln!(printer.stdout_important(), "{json_output}")?;
+            }
         }
     }
 
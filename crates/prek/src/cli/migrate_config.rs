@@ -0,0 +1,393 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use regex::Regex;
+use toml_edit::{Array, DocumentMut, Item, Key, Table, Value};
+
+use crate::cli::ExitStatus;
+use crate::config::Stage;
+use crate::fs::{CWD, Simplified};
+use crate::printer::Printer;
+use crate::workspace::{self, Project, Workspace};
+
+/// Top-level keys that `prek` accepts for `pre-commit` compatibility but otherwise ignores.
+///
+/// `ci` is not listed: it still configures pre-commit.ci.
+const NO_OP_KEYS: &[&str] = &["minimum_pre_commit_version"];
+
+/// Captures: prefix (indent and an optional `- `), separator.
+static YAML_SHA_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*(?:-\s+)?)sha(\s*:)").expect("YAML sha regex must be valid")
+});
+
+/// Captures: prefix (indent, an optional `- `, key and separator), value.
+static YAML_STAGES_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*(?:-\s+)?(?:default_)?stages\s*:\s*)(.*?)(\r?\n)?$")
+        .expect("YAML stages regex must be valid")
+});
+
+/// Captures: prefix (indent and `- `), quote, value, rest.
+static YAML_LIST_ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(\s*-\s+)(['"]?)([\w-]+)(['"]?\s*(?:#.*)?(?:\r?\n)?)$"#)
+        .expect("YAML list item regex must be valid")
+});
+
+static YAML_NO_OP_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([a-z_]+)\s*:").expect("YAML top-level key regex must be valid")
+});
+
+/// A single rewrite made to a config file.
+#[derive(Debug, PartialEq, Eq)]
+struct Change {
+    /// Where the change was made: a line number for YAML, a key path for TOML.
+    location: String,
+    description: String,
+}
+
+impl Change {
+    fn stage(location: String, alias: &str, stage: Stage) -> Self {
+        Self {
+            location,
+            description: format!("stage `{alias}` → `{stage}`"),
+        }
+    }
+
+    fn no_op_key(location: String, key: &str) -> Self {
+        Self {
+            location,
+            description: format!("removed `{key}`, which prek ignores"),
+        }
+    }
+}
+
+pub(crate) fn migrate_config(
+    configs: Vec<PathBuf>,
+    config: Option<&Path>,
+    dry_run: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // The config is not loaded first: pre-commit-era syntax such as `sha` does not parse.
+    let configs = if !configs.is_empty() {
+        configs
+    } else if let Some(config) = config {
+        vec![config.to_path_buf()]
+    } else {
+        let root = Workspace::find_root(None, &CWD)?;
+        vec![Project::find_config(&root).ok_or(workspace::Error::MissingConfigFile)?]
+    };
+
+    for path in configs {
+        let content = fs_err::read_to_string(&path)?;
+        let (migrated, changes) = match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => migrate_toml(&content)
+                .with_context(|| format!("Failed to parse `{}`", path.user_display()))?,
+            _ => migrate_yaml(&content),
+        };
+
+        if changes.is_empty() {
+            writeln!(
+                printer.stdout(),
+                "`{}` is already up to date",
+                path.user_display().cyan()
+            )?;
+            continue;
+        }
+
+        writeln!(
+            printer.stdout(),
+            "{} `{}`:",
+            if dry_run { "Would migrate" } else { "Migrated" },
+            path.user_display().cyan()
+        )?;
+        for change in &changes {
+            writeln!(
+                printer.stdout(),
+                "  {}: {}",
+                change.location.dimmed(),
+                change.description
+            )?;
+        }
+
+        if !dry_run {
+            fs_err::write(&path, migrated)?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Rewrites pre-commit-era syntax in a YAML config line by line, leaving comments, quoting
+/// and layout untouched.
+fn migrate_yaml(content: &str) -> (String, Vec<Change>) {
+    let mut lines = content
+        .split_inclusive('\n')
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+
+    let mut line_no = 0;
+    while line_no < lines.len() {
+        let line = lines[line_no].clone();
+
+        if let Some(caps) = YAML_NO_OP_KEY_RE.captures(&line)
+            && NO_OP_KEYS.contains(&&caps[1])
+        {
+            changes.push(Change::no_op_key(format!("line {}", line_no + 1), &caps[1]));
+            removed.push(line_no);
+        } else if YAML_SHA_RE.is_match(&line) {
+            lines[line_no] = YAML_SHA_RE.replace(&line, "${1}rev${2}").into_owned();
+            changes.push(Change {
+                location: format!("line {}", line_no + 1),
+                description: "`sha` → `rev`".to_string(),
+            });
+        } else if let Some(caps) = YAML_STAGES_RE.captures(&line) {
+            let value = &caps[2];
+            if value.starts_with('[') {
+                let (migrated, aliases) = migrate_flow_stages(value);
+                lines[line_no] = format!(
+                    "{}{migrated}{}",
+                    &caps[1],
+                    caps.get(3).map_or("", |m| m.as_str())
+                );
+                for (alias, stage) in aliases {
+                    changes.push(Change::stage(format!("line {}", line_no + 1), alias, stage));
+                }
+            } else if value.is_empty() || value.starts_with('#') {
+                // A block sequence: migrate the `- stage` items that follow.
+                let indent = indentation(&line);
+                let mut item_no = line_no + 1;
+                while let Some(item) = lines.get(item_no).cloned() {
+                    let Some(item_caps) = YAML_LIST_ITEM_RE
+                        .captures(&item)
+                        .filter(|_| indentation(&item) >= indent)
+                    else {
+                        break;
+                    };
+                    if let Some(stage) = Stage::from_legacy_alias(&item_caps[3]) {
+                        changes.push(Change::stage(
+                            format!("line {}", item_no + 1),
+                            &item_caps[3],
+                            stage,
+                        ));
+                        lines[item_no] =
+                            format!("{}{}{stage}{}", &item_caps[1], &item_caps[2], &item_caps[4]);
+                    }
+                    item_no += 1;
+                }
+                line_no = item_no;
+                continue;
+            }
+        }
+
+        line_no += 1;
+    }
+
+    for line_no in removed.into_iter().rev() {
+        lines.remove(line_no);
+    }
+
+    (lines.concat(), changes)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Migrates the stage names of a single-line flow sequence such as `[commit, push]`,
+/// returning the rewritten sequence and the aliases it replaced.
+fn migrate_flow_stages(value: &str) -> (String, Vec<(&str, Stage)>) {
+    let Some(end) = value.find(']') else {
+        return (value.to_string(), Vec::new());
+    };
+    let (list, rest) = value.split_at(end);
+
+    let mut aliases = Vec::new();
+    let items = list[1..]
+        .split(',')
+        .map(|item| {
+            let name = item.trim().trim_matches(|c| c == '\'' || c == '"');
+            match Stage::from_legacy_alias(name) {
+                Some(stage) => {
+                    aliases.push((name, stage));
+                    item.replacen(name, stage.as_ref(), 1)
+                }
+                None => item.to_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (format!("[{}{rest}", items.join(",")), aliases)
+}
+
+/// Rewrites pre-commit-era syntax in a TOML config, keeping formatting and comments.
+fn migrate_toml(content: &str) -> Result<(String, Vec<Change>)> {
+    let mut doc = content.parse::<DocumentMut>()?;
+    let mut changes = Vec::new();
+
+    for key in NO_OP_KEYS {
+        if doc.remove(key).is_some() {
+            changes.push(Change::no_op_key(format!("`{key}`"), key));
+        }
+    }
+
+    if let Some(stages) = doc.get_mut("default_stages").and_then(Item::as_array_mut) {
+        migrate_toml_stages(stages, "default_stages", &mut changes);
+    }
+
+    if let Some(repos) = doc.get_mut("repos").and_then(Item::as_array_of_tables_mut) {
+        for (repo_idx, repo) in repos.iter_mut().enumerate() {
+            let prefix = format!("repos[{repo_idx}]");
+            if !repo.contains_key("rev")
+                && let Some((key, sha)) = repo.remove_entry("sha")
+            {
+                let mut rev = Key::new("rev");
+                *rev.leaf_decor_mut() = key.leaf_decor().clone();
+                repo.insert_formatted(&rev, sha);
+                changes.push(Change {
+                    location: format!("`{prefix}`"),
+                    description: "`sha` → `rev`".to_string(),
+                });
+            }
+            migrate_toml_hooks(repo, &prefix, &mut changes);
+        }
+    }
+
+    Ok((doc.to_string(), changes))
+}
+
+fn migrate_toml_hooks(repo: &mut Table, prefix: &str, changes: &mut Vec<Change>) {
+    let Some(hooks) = repo.get_mut("hooks") else {
+        return;
+    };
+
+    if let Some(hooks) = hooks.as_array_of_tables_mut() {
+        for (hook_idx, hook) in hooks.iter_mut().enumerate() {
+            if let Some(stages) = hook.get_mut("stages").and_then(Item::as_array_mut) {
+                let location = format!("{prefix}.hooks[{hook_idx}].stages");
+                migrate_toml_stages(stages, &location, changes);
+            }
+        }
+    } else if let Some(hooks) = hooks.as_array_mut() {
+        for (hook_idx, hook) in hooks.iter_mut().enumerate() {
+            if let Some(stages) = hook
+                .as_inline_table_mut()
+                .and_then(|hook| hook.get_mut("stages"))
+                .and_then(Value::as_array_mut)
+            {
+                let location = format!("{prefix}.hooks[{hook_idx}].stages");
+                migrate_toml_stages(stages, &location, changes);
+            }
+        }
+    }
+}
+
+fn migrate_toml_stages(stages: &mut Array, location: &str, changes: &mut Vec<Change>) {
+    for value in stages.iter_mut() {
+        let Some(stage) = value.as_str().and_then(Stage::from_legacy_alias) else {
+            continue;
+        };
+        changes.push(Change::stage(
+            format!("`{location}`"),
+            value.as_str().unwrap_or_default(),
+            stage,
+        ));
+        let decor = value.decor().clone();
+        *value = Value::from(stage.as_ref());
+        *value.decor_mut() = decor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_toml, migrate_yaml};
+
+    #[test]
+    fn migrate_yaml_keeps_comments_and_layout() {
+        let config = indoc::indoc! {r"
+            # Migrated from pre-commit.
+            minimum_pre_commit_version: '2.9.0'
+            default_stages: [commit, 'push']  # keep me
+            repos:
+              - repo: https://example.com/repo
+                sha: v1.0.0 # pinned
+                hooks:
+                  - id: test-hook
+                    stages:
+                      - merge-commit
+                      - manual
+        "};
+
+        let (migrated, changes) = migrate_yaml(config);
+
+        assert_eq!(
+            migrated,
+            indoc::indoc! {r"
+                # Migrated from pre-commit.
+                default_stages: [pre-commit, 'pre-push']  # keep me
+                repos:
+                  - repo: https://example.com/repo
+                    rev: v1.0.0 # pinned
+                    hooks:
+                      - id: test-hook
+                        stages:
+                          - pre-merge-commit
+                          - manual
+            "}
+        );
+        assert_eq!(changes.len(), 5);
+        assert_eq!(changes[0].location, "line 2");
+        assert_eq!(changes[4].location, "line 10");
+    }
+
+    #[test]
+    fn migrate_yaml_leaves_current_config_alone() {
+        let config = indoc::indoc! {r"
+            default_stages: [pre-commit]
+            repos:
+              - repo: https://example.com/repo
+                rev: v1.0.0
+                hooks:
+                  - id: test-hook
+                    stages: [pre-push]
+        "};
+
+        let (migrated, changes) = migrate_yaml(config);
+
+        assert_eq!(migrated, config);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn migrate_toml_stage_aliases() {
+        let config = indoc::indoc! {r#"
+            minimum_pre_commit_version = "2.9.0"
+            default_stages = ["commit"] # keep me
+
+            [[repos]]
+            repo = "local"
+            hooks = [
+              { id = "test-hook", name = "test", language = "system", entry = "true", stages = ["push", "manual"] },
+            ]
+        "#};
+
+        let (migrated, changes) = migrate_toml(config).unwrap();
+
+        assert_eq!(
+            migrated,
+            indoc::indoc! {r#"
+                default_stages = ["pre-commit"] # keep me
+
+                [[repos]]
+                repo = "local"
+                hooks = [
+                  { id = "test-hook", name = "test", language = "system", entry = "true", stages = ["pre-push", "manual"] },
+                ]
+            "#}
+        );
+        assert_eq!(changes.len(), 3);
+    }
+}
@@ -17,11 +17,15 @@ mod cache_clean;
 mod cache_gc;
 mod cache_size;
 mod completion;
+#[cfg(feature = "schemars")]
+mod config_schema;
+mod doctor;
 mod hook_impl;
 mod identify;
 mod install;
 mod list;
 mod list_builtins;
+mod migrate_config;
 pub mod reporter;
 pub mod run;
 mod sample_config;
@@ -29,6 +33,7 @@ mod sample_config;
 mod self_update;
 mod try_repo;
 mod validate;
+mod warmup;
 mod yaml_to_toml;
 
 pub(crate) use auto_update::auto_update;
@@ -36,17 +41,24 @@ pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_gc::cache_gc;
 pub(crate) use cache_size::cache_size;
 use completion::selector_completer;
+#[cfg(feature = "schemars")]
+pub(crate) use config_schema::config_schema;
+pub(crate) use doctor::doctor;
 pub(crate) use hook_impl::hook_impl;
 pub(crate) use identify::identify;
-pub(crate) use install::{init_template_dir, install, prepare_hooks, uninstall};
+pub(crate) use install::{
+    discover_workspace, init_selected_hooks, init_template_dir, install, prepare_hooks, uninstall,
+};
 pub(crate) use list::list;
 pub(crate) use list_builtins::list_builtins;
-pub(crate) use run::run;
+pub(crate) use migrate_config::migrate_config;
+pub(crate) use run::{RunSettings, run};
 pub(crate) use sample_config::sample_config;
 #[cfg(feature = "self-update")]
 pub(crate) use self_update::self_update;
 pub(crate) use try_repo::try_repo;
 pub(crate) use validate::{validate_configs, validate_manifest};
+pub(crate) use warmup::warmup;
 pub(crate) use yaml_to_toml::yaml_to_toml;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -189,6 +201,13 @@ pub(crate) struct GlobalArgs {
     #[arg(global = true, long)]
     pub(crate) refresh: bool,
 
+    /// Disable network access.
+    ///
+    /// Only repositories and toolchains already in the cache are used; anything that would
+    /// need a download fails immediately instead. Can also be enabled with `PREK_OFFLINE=1`.
+    #[arg(global = true, long)]
+    pub(crate) offline: bool,
+
     /// Display the concise help for this command.
     #[arg(global = true, short, long, action = ArgAction::HelpShort)]
     help: (),
@@ -255,7 +274,19 @@ pub(crate) enum Command {
     /// This command does not install Git shims. To install the Git shims
     /// along with the hook environments in one command, use `prek install --prepare-hooks`.
     #[command(alias = "install-hooks")]
-    PrepareHooks(PrepareHooksArgs),
+    PrepareHooks(PrepareHooksCommandArgs),
+    /// Prepare environments for all hooks and verify that they are healthy.
+    ///
+    /// Like `prek prepare-hooks`, but also runs each language's health check on
+    /// every environment, so broken installs are reported up front. This is
+    /// intended for warming up caches in CI.
+    Warmup(PrepareHooksArgs),
+    /// Check the health of installed hook environments.
+    ///
+    /// Reports whether each hook's environment is still usable, for example
+    /// after upgrading a system Python or Node. Exits with a non-zero status
+    /// if any environment is broken. Nothing is installed unless `--fix` is given.
+    Doctor(DoctorArgs),
     /// Run hooks.
     Run(Box<RunArgs>),
     /// List hooks configured in the current workspace.
@@ -266,8 +297,18 @@ pub(crate) enum Command {
     ValidateConfig(ValidateConfigArgs),
     /// Validate `.pre-commit-hooks.yaml` files.
     ValidateManifest(ValidateManifestArgs),
+    /// Rewrite pre-commit-era syntax in configuration files.
+    ///
+    /// Renames the legacy `commit`, `push` and `merge-commit` stages, renames `sha` to `rev`,
+    /// and removes keys that prek ignores, keeping comments and formatting.
+    MigrateConfig(MigrateConfigArgs),
     /// Produce a sample configuration file (prek.toml or .pre-commit-config.yaml).
     SampleConfig(SampleConfigArgs),
+    /// Print the JSON schema of the configuration file.
+    ///
+    /// The schema describes both prek.toml and .pre-commit-config.yaml, and can be associated with
+    /// those files in editors for completion and validation.
+    ConfigSchema,
     /// Auto-update the `rev` field of repositories in the config file to the latest version.
     #[command(alias = "autoupdate")]
     AutoUpdate(AutoUpdateArgs),
@@ -295,6 +336,7 @@ pub(crate) enum Command {
 }
 
 #[derive(Debug, Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct InstallArgs {
     /// Include the specified hooks or projects.
     ///
@@ -325,11 +367,15 @@ pub(crate) struct InstallArgs {
     /// - `project-path:hook-id`: Skip only the specified hook from the specified project
     ///
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    /// Selectors in a `.prek-skip` file, found in the current directory or a parent up to the git root, are always added.
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
 
     /// Overwrite existing Git shims.
-    #[arg(short = 'f', long)]
+    ///
+    /// Hooks not managed by prek are removed instead of being kept as `<hook>.legacy`,
+    /// and so are existing `<hook>.legacy` scripts.
+    #[arg(short = 'f', long, visible_alias = "force")]
     pub(crate) overwrite: bool,
 
     /// Also prepare environments for all hooks used in the config file.
@@ -349,10 +395,29 @@ pub(crate) struct InstallArgs {
     #[arg(short = 't', long = "hook-type", value_name = "HOOK_TYPE", value_enum)]
     pub(crate) hook_types: Vec<HookType>,
 
+    /// Install the Git shims needed by the `stages` of the hooks in the config file.
+    ///
+    /// Installs a shim for each stage a selected hook runs at, in addition to
+    /// `default_install_hook_types`. Hooks without `stages` only need a `pre-commit` shim,
+    /// and `manual` hooks need none. Remote repos are cloned to read their hook definitions.
+    #[arg(long, conflicts_with = "hook_types")]
+    pub(crate) auto_hook_types: bool,
+
     /// Allow a missing configuration file.
     #[arg(long)]
     pub(crate) allow_missing_config: bool,
 
+    /// Run an existing non-prek Git hook before prek's hooks. This is the default.
+    ///
+    /// Hooks that already exist are kept next to the shim as `<hook>.legacy` and run with
+    /// it. The commit is rejected if either of them fails.
+    #[arg(long, overrides_with = "legacy_last")]
+    pub(crate) legacy_first: bool,
+
+    /// Run an existing non-prek Git hook after prek's hooks.
+    #[arg(long, overrides_with = "legacy_first")]
+    pub(crate) legacy_last: bool,
+
     /// Install Git shims into the `hooks` subdirectory of the given git directory (`<GIT_DIR>/hooks/`).
     ///
     /// When this flag is used, `prek install` bypasses the safety check that normally
@@ -366,6 +431,13 @@ pub(crate) struct InstallArgs {
         value_parser = PathBufValueParser::new().map(expand_tilde),
     )]
     pub(crate) git_dir: Option<PathBuf>,
+
+    /// Install Git shims into the repository's own `.git/hooks`, ignoring `core.hooksPath`.
+    ///
+    /// Like `--git-dir`, this bypasses the `core.hooksPath` safety check. Git keeps running
+    /// hooks from `core.hooksPath`, so use this when those hooks chain to `.git/hooks`.
+    #[arg(long, conflicts_with = "git_dir")]
+    pub(crate) no_hooks_path: bool,
 }
 
 #[derive(Debug, Args)]
@@ -399,8 +471,41 @@ pub(crate) struct PrepareHooksArgs {
     /// - `project-path:hook-id`: Skip only the specified hook from the specified project
     ///
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    /// Selectors in a `.prek-skip` file, found in the current directory or a parent up to the git root, are always added.
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
+
+    /// Fail if any remote repo uses a mutable `rev` (a branch or a moving tag).
+    ///
+    /// Without this flag, a mutable `rev` is resolved to a commit once, when the repo is first
+    /// cloned, and that commit is reused until the cache is cleaned. Use `prek auto-update`
+    /// to pin such repos to a tag or commit SHA.
+    #[arg(long)]
+    pub(crate) frozen: bool,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct PrepareHooksCommandArgs {
+    #[command(flatten)]
+    pub(crate) hooks: PrepareHooksArgs,
+
+    /// Print what would be cloned and installed, without doing it.
+    ///
+    /// Lists the remote repos to clone, the hook environments to create with their additional
+    /// dependencies, and the toolchains they need. Hooks of repos that are not cloned yet are
+    /// only known after cloning, so they are not listed.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct DoctorArgs {
+    #[command(flatten)]
+    pub(crate) hooks: PrepareHooksArgs,
+
+    /// Reinstall hook environments that fail the health check.
+    #[arg(long)]
+    pub(crate) fix: bool,
 }
 
 #[derive(Debug, Args)]
@@ -448,7 +553,17 @@ pub(crate) struct RunExtraArgs {
     pub(crate) pre_rebase_upstream: Option<String>,
     #[arg(long, hide = true)]
     pub(crate) pre_rebase_branch: Option<String>,
-    #[arg(long, hide = true, required_if_eq_any = [("stage", "prepare-commit-msg"), ("stage", "commit-msg")])]
+    /// Run the `commit-msg` hooks against a commit message file, the way git does when committing.
+    ///
+    /// Implies `--stage commit-msg`, unless `--stage prepare-commit-msg` is given. Useful to test
+    /// commit message linters against a draft message.
+    #[arg(
+        long = "commit-msg-file",
+        alias = "commit-msg-filename",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        required_if_eq_any = [("stage", "prepare-commit-msg"), ("stage", "commit-msg")],
+    )]
     pub(crate) commit_msg_filename: Option<String>,
     #[arg(long, hide = true)]
     pub(crate) prepare_commit_message_source: Option<String>,
@@ -498,6 +613,7 @@ pub(crate) struct RunOptions {
     /// - `project-path:hook-id`: Skip only the specified hook from the specified project
     ///
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    /// Selectors in a `.prek-skip` file, found in the current directory or a parent up to the git root, are always added.
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
 
@@ -546,10 +662,43 @@ pub(crate) struct RunOptions {
     #[arg(long, conflicts_with_all = ["all_files", "files", "directory", "from_ref", "to_ref"])]
     pub(crate) last_commit: bool,
 
+    /// Run hooks on files changed since the merge-base of `HEAD` and the given ref.
+    ///
+    /// Useful on feature branches, e.g. `--since main`. Fails if the histories are unrelated.
+    #[arg(
+        long,
+        value_name = "REF",
+        conflicts_with_all = ["all_files", "files", "directory", "from_ref", "to_ref", "last_commit"],
+        value_hint = ValueHint::Other
+    )]
+    pub(crate) since: Option<String>,
+
+    /// Also pass files deleted in the ref range to hooks.
+    ///
+    /// Applies to `--from-ref`/`--to-ref`, `--last-commit` and `--since`. Deleted files have no
+    /// file type tags, so they only reach hooks that set `types: []`. Renamed files are always
+    /// reported under their new path.
+    #[arg(long, conflicts_with_all = ["all_files", "files", "directory"])]
+    pub(crate) include_deleted: bool,
+
+    /// Also pass untracked files that are not ignored by `.gitignore` to hooks.
+    ///
+    /// Applies to `--all-files`, `--directory` and the staged files used by default. Useful
+    /// before the first commit, when new files are not tracked yet.
+    #[arg(long, conflicts_with_all = ["from_ref", "to_ref", "last_commit", "since"])]
+    pub(crate) include_untracked: bool,
+
     /// When hooks fail, run `git diff` directly afterward.
     #[arg(long)]
     pub(crate) show_diff_on_failure: bool,
 
+    /// Print a diff of the changes builtin fixer hooks would make, instead of modifying files.
+    ///
+    /// Fixers that would change a file fail with the diff as their output. Other hooks run as
+    /// usual.
+    #[arg(long)]
+    pub(crate) show_diff: bool,
+
     /// Stop running hooks after the first failure.
     #[arg(long)]
     pub(crate) fail_fast: bool,
@@ -558,14 +707,41 @@ pub(crate) struct RunOptions {
     #[arg(long, hide = true, overrides_with = "fail_fast")]
     pub(crate) no_fail_fast: bool,
 
+    /// Run every hook even after failures, and print a summary of the hook outcomes at the end.
+    ///
+    /// Overrides `fail_fast` set in the config, for the whole config and for individual hooks.
+    #[arg(long, conflicts_with = "fail_fast")]
+    pub(crate) keep_going: bool,
+
     /// Do not run the hooks, but print the hooks that would have been run.
-    #[arg(long)]
-    pub(crate) dry_run: bool,
+    ///
+    /// Each hook reports how many files it would receive after filtering; use `--verbose` to
+    /// list the files as well. Use `--dry-run=json` to print the plan as JSON instead, including
+    /// the hooks skipped for having no files or not running at the selected stage.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text"
+    )]
+    pub(crate) dry_run: Option<DryRunFormat>,
+
+    /// Do not run the hooks, but explain which files the hook with this id would receive.
+    ///
+    /// Every candidate file is listed with the reason it is included or excluded: the project or
+    /// hook `files` and `exclude` patterns, `types`, `types_or` and `exclude_types`, or ownership
+    /// by a nested orphan project. Hooks with this id that do not run at the selected stage are
+    /// reported as well.
+    #[arg(long, value_name = "HOOK", conflicts_with = "dry_run")]
+    pub(crate) explain: Option<String>,
 
     #[command(flatten)]
     pub(crate) extra: RunExtraArgs,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct RunArgs {
     #[command(flatten)]
@@ -576,9 +752,8 @@ pub(crate) struct RunArgs {
     /// When specified, only hooks configured for that stage (for example `manual`,
     /// `pre-commit`, or `pre-push`) will run.
     /// When not specified and no group filter is active, this command starts with
-    /// hooks eligible for `pre-commit`. If no hook is selected and the command
-    /// named hook IDs, those same IDs are matched again against hooks configured
-    /// for `manual`. With `--group` or `--no-group`, omitting the stage lets
+    /// hooks eligible for `pre-commit`, and hooks named by id run whatever their
+    /// stages, unless they only run at `commit-msg` or `prepare-commit-msg`. With `--group` or `--no-group`, omitting the stage lets
     /// hooks from any configured stage match, using the default file input mode;
     /// hooks that only run at `commit-msg` or `prepare-commit-msg` are ignored.
     #[arg(long, value_enum, alias = "hook-stage")]
@@ -595,6 +770,119 @@ pub(crate) struct RunArgs {
     /// Can be specified multiple times. Exclusion wins over inclusion.
     #[arg(long = "no-group", value_name = "GROUP")]
     pub(crate) no_groups: Vec<String>,
+
+    /// Read the files to run hooks on from stdin, separated by NUL characters.
+    ///
+    /// Use this with tools like `git diff -z --name-only` or `find -print0`.
+    #[arg(long, conflicts_with_all = ["all_files", "files", "from_ref", "to_ref", "last_commit", "since"])]
+    pub(crate) files_from_stdin: bool,
+
+    /// Replace the store path and git root in hook output with `<store>` and `<root>`.
+    ///
+    /// Useful to keep CI logs short and comparable across machines.
+    #[arg(long)]
+    pub(crate) relativize_paths: bool,
+
+    /// Exit with a non-zero status if prek emitted any warnings during the run.
+    ///
+    /// Hooks still run as usual; the exit status is checked after they complete.
+    #[arg(long)]
+    pub(crate) fail_on_warnings: bool,
+
+    /// The output format.
+    ///
+    /// `github` additionally emits GitHub Actions annotations for failed hooks, so that
+    /// `path:line: message` findings show up inline on pull requests. Defaults to `github`
+    /// when `GITHUB_ACTIONS=true`, and `text` otherwise.
+    #[arg(long, value_enum)]
+    pub(crate) output_format: Option<RunOutputFormat>,
+
+    /// Send a desktop notification when the run completes.
+    ///
+    /// Nothing is sent under CI or when no display is available.
+    #[arg(long)]
+    pub(crate) notify: bool,
+
+    /// Re-run a failing hook up to `N` times before reporting it as failed.
+    ///
+    /// Overrides the `retries` option of every hook. Only the output of the final attempt is
+    /// shown, unless `--verbose` is set. Avoid retrying hooks that modify files, as a retry
+    /// would see the already fixed files.
+    #[arg(long, value_name = "N")]
+    pub(crate) retries: Option<u32>,
+
+    /// Kill a hook and report it as failed when it runs for longer than `SECONDS`.
+    ///
+    /// Applies to hooks that don't set a `timeout` of their own. `0` disables the timeout.
+    #[arg(long, value_name = "SECONDS")]
+    pub(crate) timeout: Option<u64>,
+
+    /// Run at most `N` hook processes of `LANGUAGE` at the same time, e.g. `python=2`.
+    ///
+    /// Overrides `default_language_concurrency` from the config. The limit is shared by all
+    /// hooks of the language and never exceeds `PREK_MAX_CONCURRENCY`. Can be specified
+    /// multiple times.
+    #[arg(long, value_name = "LANGUAGE=N")]
+    pub(crate) language_concurrency: Vec<LanguageConcurrency>,
+
+    /// Start each workspace project as soon as the projects nested inside it have finished.
+    ///
+    /// By default, projects run one depth level at a time. With this flag, projects in
+    /// unrelated directories no longer wait for each other, bounded by `PREK_MAX_CONCURRENCY`.
+    /// A parent project still waits for its nested projects, so hooks never modify the same
+    /// file concurrently. Results are printed in the usual project order once all projects
+    /// finish.
+    #[arg(long)]
+    pub(crate) parallel_projects: bool,
+
+    /// Write a `JUnit` XML report of the hook results to the given path.
+    ///
+    /// Each hook becomes a test case named after its id, grouped by project. Failed hooks
+    /// include their output, and hooks that did not run, such as those without matching
+    /// files, are marked as skipped. The exit code is unaffected.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub(crate) report_junit: Option<PathBuf>,
+
+    /// Write the files that hooks modified during the run to the given path, one per line.
+    ///
+    /// Paths are relative to the git root, so the list can be passed to `git add`. Files that
+    /// already had unstaged changes are only listed if a hook changed them further.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub(crate) modified_files: Option<PathBuf>,
+
+    /// Print how long each hook took after the run, slowest first.
+    ///
+    /// The report lists the number of files passed to each hook and its wall-clock duration,
+    /// along with the time spent cloning repositories and installing hook environments.
+    /// Use `--timings=json` to print the report as JSON after the hook output, or
+    /// `--timings-file` to write it to a file.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text"
+    )]
+    pub(crate) timings: Option<TimingsFormat>,
+
+    /// Write the timings report as JSON to the given path, apart from the run output.
+    ///
+    /// This can be combined with `--timings` to also print the report.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub(crate) timings_file: Option<PathBuf>,
+
+    /// Run `cacheable` hooks on every matched file, ignoring the results of previous runs.
+    ///
+    /// The cache is neither read nor updated. Can also be enabled with `PREK_NO_RESULT_CACHE=1`.
+    #[arg(long)]
+    pub(crate) no_cache: bool,
+
+    /// Exit successfully without running anything if no configuration file is found.
+    ///
+    /// Can also be enabled with `PREK_ALLOW_NO_CONFIG=1`.
+    #[arg(long)]
+    pub(crate) allow_missing_config: bool,
 }
 
 #[derive(Debug, Clone, Default, Args)]
@@ -614,8 +902,8 @@ pub(crate) struct TryRepoArgs {
     /// When specified, only hooks configured for that stage (for example `manual`,
     /// `pre-commit`, or `pre-push`) will run.
     /// When not specified, this command starts with hooks eligible for
-    /// `pre-commit`. If no hook is selected and the command named hook IDs,
-    /// those same IDs are matched again against hooks configured for `manual`.
+    /// `pre-commit`, and hooks named by id run whatever their stages, unless
+    /// they only run at `commit-msg` or `prepare-commit-msg`.
     #[arg(long, value_enum, alias = "hook-stage")]
     pub(crate) stage: Option<Stage>,
 }
@@ -625,6 +913,60 @@ pub(crate) struct TryRepoArgs {
 pub(crate) enum ListOutputFormat {
     #[default]
     Text,
+    /// A pretty-printed JSON array.
+    Json,
+    /// One compact JSON object per line.
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum RunOutputFormat {
+    /// Human-readable output only.
+    Text,
+    /// Human-readable output plus GitHub Actions workflow commands.
+    Github,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum DryRunFormat {
+    /// The usual hook output, with what each hook would run on.
+    Text,
+    /// A pretty-printed JSON object.
+    Json,
+}
+
+/// A `LANGUAGE=N` value of `prek run --language-concurrency`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LanguageConcurrency {
+    pub(crate) language: Language,
+    pub(crate) limit: usize,
+}
+
+impl std::str::FromStr for LanguageConcurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (language, limit) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected `LANGUAGE=N`, got `{s}`"))?;
+        let language = <Language as clap::ValueEnum>::from_str(language.trim(), true)
+            .map_err(|_| format!("unknown language `{}`", language.trim()))?;
+        let limit = limit
+            .trim()
+            .parse::<std::num::NonZeroUsize>()
+            .map_err(|_| format!("expected a positive number of processes, got `{limit}`"))?;
+        Ok(Self {
+            language,
+            limit: limit.get(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum TimingsFormat {
+    /// A human-readable table.
+    Text,
+    /// A pretty-printed JSON object.
     Json,
 }
 
@@ -674,6 +1016,7 @@ pub(crate) struct ListArgs {
     /// - `project-path:hook-id`: Skip only the specified hook from the specified project
     ///
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    /// Selectors in a `.prek-skip` file, found in the current directory or a parent up to the git root, are always added.
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
 
@@ -715,6 +1058,24 @@ pub(crate) struct ValidateConfigArgs {
     /// The path to the configuration file.
     #[arg(value_name = "CONFIG")]
     pub(crate) configs: Vec<PathBuf>,
+    /// Treat unexpected keys as errors instead of warnings.
+    ///
+    /// Keys kept for `pre-commit` compatibility, such as `minimum_pre_commit_version` and `ci`,
+    /// are still allowed.
+    #[arg(long)]
+    pub(crate) strict: bool,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct MigrateConfigArgs {
+    /// The configuration files to migrate.
+    ///
+    /// Defaults to the configuration file of the current project.
+    #[arg(value_name = "CONFIG", value_hint = ValueHint::FilePath)]
+    pub(crate) configs: Vec<PathBuf>,
+    /// Print the changes without writing them.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
 }
 
 #[derive(Debug, Args)]
@@ -777,9 +1138,14 @@ pub(crate) struct AutoUpdateArgs {
     #[arg(long)]
     pub(crate) freeze: bool,
     /// Only update this repository. This option may be specified multiple times.
+    ///
+    /// Besides an exact repository URL, this accepts a glob pattern such as
+    /// `'https://github.com/pre-commit/*'`, or a regex prefixed with `re:`.
     #[arg(long, value_name = "REPO", conflicts_with = "exclude_repo")]
     pub(crate) repo: Vec<String>,
     /// Do not update this repository. This option may be specified multiple times.
+    ///
+    /// Accepts the same glob and `re:` patterns as `--repo`.
     #[arg(long, value_name = "REPO")]
     pub(crate) exclude_repo: Vec<String>,
     /// Only consider tags matching this glob pattern. This option may be specified multiple times.
@@ -862,6 +1228,7 @@ pub(crate) struct HookImplArgs {
     /// - `project-path:hook-id`: Skip only the specified hook from the specified project
     ///
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    /// Selectors in a `.prek-skip` file, found in the current directory or a parent up to the git root, are always added.
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
     #[arg(long)]
@@ -870,6 +1237,9 @@ pub(crate) struct HookImplArgs {
     pub(crate) hook_dir: Option<PathBuf>,
     #[arg(long)]
     pub(crate) skip_on_missing_config: bool,
+    /// Run the legacy hook after prek's hooks instead of before.
+    #[arg(long)]
+    pub(crate) legacy_last: bool,
     /// The prek version that installs the hook.
     #[arg(long)]
     pub(crate) script_version: Option<usize>,
@@ -927,6 +1297,9 @@ pub(crate) enum CacheCommand {
     /// Show the location of the prek cache.
     Dir,
     /// Remove unused cached repositories, hook environments, and other data.
+    ///
+    /// Entries are kept if they are referenced by a config file `prek` has seen, or if another
+    /// `prek` process is currently using them.
     GC(CacheGcArgs),
     /// Remove all prek cached data.
     Clean,
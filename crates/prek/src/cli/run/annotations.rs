@@ -0,0 +1,209 @@
+//! GitHub Actions workflow commands for failed hooks.
+//!
+//! Builtin hooks that know where a problem is, such as `check-merge-conflict` and
+//! `debug-statements`, report it as a [`Finding`] that becomes an `::error file=...,line=...::`
+//! annotation, which GitHub shows inline on the pull request. The output of other hooks is
+//! parsed on a best-effort basis: lines shaped like `path:line: message` or
+//! `path:line:col: message` are annotated the same way, and a failed hook whose output has no
+//! such line gets a single plain `::error::` command carrying its output.
+
+use std::path::{Path, PathBuf};
+
+use crate::fs::PathClean;
+use crate::hook::Hook;
+use crate::run::Finding;
+
+/// A `path:line[:col]: message` location found in hook output.
+#[derive(Debug, PartialEq, Eq)]
+struct Location<'a> {
+    path: &'a str,
+    line: usize,
+    col: Option<usize>,
+    message: &'a str,
+}
+
+fn parse_number(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn parse_location(line: &str) -> Option<Location<'_>> {
+    // Don't split inside a Windows drive prefix such as `C:\`.
+    let bytes = line.as_bytes();
+    let skip = if bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+    {
+        2
+    } else {
+        0
+    };
+
+    let (path, rest) = line[skip..].split_once(':')?;
+    let path = &line[..skip + path.len()];
+    if path.is_empty() || path.starts_with(char::is_whitespace) || path.ends_with(' ') {
+        return None;
+    }
+
+    let (line_number, rest) = rest.split_once(':')?;
+    let line_number = parse_number(line_number)?;
+
+    let (col, message) = match rest.split_once(':') {
+        Some((col, message)) if parse_number(col).is_some() => (parse_number(col), message),
+        _ => (None, rest),
+    };
+    let message = message.trim();
+    if message.is_empty() {
+        return None;
+    }
+
+    Some(Location {
+        path,
+        line: line_number,
+        col,
+        message,
+    })
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property of a workflow command.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Resolve a path printed by the hook to a path relative to the git root, as GitHub expects.
+fn annotation_path(path: &Path, work_dir: &Path, git_root: &Path) -> PathBuf {
+    let path = work_dir.join(path).clean();
+    match path.strip_prefix(git_root) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
+/// Format the `file=` property of a workflow command.
+fn file_property(path: &Path, work_dir: &Path, git_root: &Path) -> String {
+    let file = annotation_path(path, work_dir, git_root);
+    let mut file = file.to_string_lossy().into_owned();
+    if cfg!(windows) {
+        file = file.replace('\\', "/");
+    }
+    escape_property(&file)
+}
+
+/// Format an `::error` command for a problem at a line of a file.
+fn line_annotation(
+    file: &str,
+    line: usize,
+    col: Option<usize>,
+    title: &str,
+    message: &str,
+) -> String {
+    let col = col.map(|col| format!(",col={col}")).unwrap_or_default();
+    format!(
+        "::error file={file},line={line}{col},title={title}::{}",
+        escape_data(message)
+    )
+}
+
+/// Build the workflow commands reporting a failed hook.
+pub(crate) fn github_annotations(
+    hook: &Hook,
+    output: &str,
+    findings: &[Finding],
+    git_root: &Path,
+) -> Vec<String> {
+    let title = escape_property(&hook.name);
+    let work_dir = hook.work_dir();
+    let mut commands: Vec<String> = if findings.is_empty() {
+        output
+            .lines()
+            .filter_map(parse_location)
+            .map(|location| {
+                let file = file_property(Path::new(location.path), work_dir, git_root);
+                line_annotation(&file, location.line, location.col, &title, location.message)
+            })
+            .collect()
+    } else {
+        findings
+            .iter()
+            .map(|finding| {
+                let file = file_property(&finding.path, work_dir, git_root);
+                // GitHub counts columns from 1.
+                let col = finding.col.map(|col| col + 1);
+                line_annotation(&file, finding.line, col, &title, &finding.message)
+            })
+            .collect()
+    };
+
+    if commands.is_empty() {
+        let output = output.trim();
+        let message = if output.is_empty() {
+            format!("Hook `{}` failed", hook.id)
+        } else {
+            output.to_string()
+        };
+        commands.push(format!("::error title={title}::{}", escape_data(&message)));
+    }
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_locations() {
+        assert_eq!(
+            parse_location("src/main.py:12:5: E501 line too long"),
+            Some(Location {
+                path: "src/main.py",
+                line: 12,
+                col: Some(5),
+                message: "E501 line too long",
+            })
+        );
+        assert_eq!(
+            parse_location("lib.rs:3: unused import"),
+            Some(Location {
+                path: "lib.rs",
+                line: 3,
+                col: None,
+                message: "unused import",
+            })
+        );
+        assert_eq!(
+            parse_location(r"C:\repo\a.py:1:2: bad"),
+            Some(Location {
+                path: r"C:\repo\a.py",
+                line: 1,
+                col: Some(2),
+                message: "bad",
+            })
+        );
+        // `message` may itself contain colons.
+        assert_eq!(
+            parse_location("a.py:7: error: name not defined").map(|l| l.message),
+            Some("error: name not defined")
+        );
+        assert_eq!(parse_location("Fixing a.py"), None);
+        assert_eq!(parse_location("error: something failed"), None);
+        assert_eq!(parse_location("a.py:12:"), None);
+        assert_eq!(parse_location("  a.py:12: indented"), None);
+    }
+
+    #[test]
+    fn escape_workflow_command_values() {
+        assert_eq!(escape_data("50% done\nnext"), "50%25 done%0Anext");
+        assert_eq!(escape_property("a:b,c"), "a%3Ab%2Cc");
+    }
+}
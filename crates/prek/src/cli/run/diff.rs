@@ -1,12 +1,17 @@
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use rustc_hash::FxHashMap;
 
-use crate::git;
+use crate::cli::run::result_cache::content_hash;
+use crate::git::{self, GIT_ROOT};
 
 pub(super) struct DiffTracker<'a> {
     path: &'a Path,
     baseline: DiffBaseline,
+    /// Set when the files modified by hooks are collected.
+    files: Option<ModifiedFiles>,
 }
 
 enum DiffBaseline {
@@ -15,11 +20,19 @@ enum DiffBaseline {
     Snapshot(Vec<u8>),
 }
 
+struct ModifiedFiles {
+    /// Content hashes of the files that differ from the index, `None` until captured.
+    /// Deleted files have no hash.
+    dirty: Option<FxHashMap<PathBuf, Option<u64>>>,
+    modified: BTreeSet<PathBuf>,
+}
+
 impl<'a> DiffTracker<'a> {
     pub(super) fn clean_baseline(path: &'a Path) -> Self {
         Self {
             path,
             baseline: DiffBaseline::Clean,
+            files: None,
         }
     }
 
@@ -27,13 +40,36 @@ impl<'a> DiffTracker<'a> {
         Self {
             path,
             baseline: DiffBaseline::Unknown,
+            files: None,
         }
     }
 
+    /// Also collect which files the hooks modified, see [`DiffTracker::into_modified_files`].
+    #[must_use]
+    pub(super) fn track_modified_files(mut self, enabled: bool) -> Self {
+        self.files = enabled.then(|| ModifiedFiles {
+            // A clean worktree has no dirty files to begin with.
+            dirty: matches!(self.baseline, DiffBaseline::Clean).then(FxHashMap::default),
+            modified: BTreeSet::new(),
+        });
+        self
+    }
+
+    /// The files modified by hooks, relative to the git root.
+    pub(super) fn into_modified_files(self) -> BTreeSet<PathBuf> {
+        self.files.map(|files| files.modified).unwrap_or_default()
+    }
+
     pub(super) async fn prepare_for_group(&mut self, may_modify_files: bool) -> Result<()> {
         if may_modify_files && let DiffBaseline::Unknown = self.baseline {
             self.baseline = DiffBaseline::Snapshot(git::get_diff(self.path).await?);
         }
+        if may_modify_files
+            && let Some(files) = &mut self.files
+            && files.dirty.is_none()
+        {
+            files.dirty = Some(dirty_files(self.path).await?);
+        }
         Ok(())
     }
 
@@ -42,6 +78,30 @@ impl<'a> DiffTracker<'a> {
         may_modify_files: bool,
         all_skipped: bool,
     ) -> Result<bool> {
+        let changed = self.diff_changed(may_modify_files, all_skipped).await?;
+
+        if changed && let Some(files) = &mut self.files {
+            // Files that changed content, started to differ from the index, or were
+            // restored to the index content were all rewritten by the group.
+            let current = dirty_files(self.path).await?;
+            let previous = files.dirty.take().unwrap_or_default();
+            for (path, hash) in &current {
+                if previous.get(path) != Some(hash) {
+                    files.modified.insert(path.clone());
+                }
+            }
+            for path in previous.into_keys() {
+                if !current.contains_key(&path) {
+                    files.modified.insert(path);
+                }
+            }
+            files.dirty = Some(current);
+        }
+
+        Ok(changed)
+    }
+
+    async fn diff_changed(&mut self, may_modify_files: bool, all_skipped: bool) -> Result<bool> {
         // Read-only groups and fully skipped groups cannot change files, so avoid
         // asking git about the working tree.
         if !may_modify_files || all_skipped {
@@ -84,3 +144,14 @@ impl<'a> DiffTracker<'a> {
         }
     }
 }
+
+/// Content hashes of the files under `path` that differ from the index.
+async fn dirty_files(path: &Path) -> Result<FxHashMap<PathBuf, Option<u64>>> {
+    let git_root = GIT_ROOT.as_ref()?;
+    let mut hashes = FxHashMap::default();
+    for file in git::diff_file_names(path).await? {
+        let hash = content_hash(&git_root.join(&file)).await;
+        hashes.insert(file, hash);
+    }
+    Ok(hashes)
+}
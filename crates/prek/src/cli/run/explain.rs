@@ -0,0 +1,242 @@
+//! Per-file explanation of hook file selection printed by `--explain`.
+//!
+//! Everything here goes through the same project ownership, [`FilenameFilter`] and
+//! [`FileTagFilter`](super::FileTagFilter) checks the runner uses, so the reasons printed
+//! match what a real run would do.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use prek_identify::TagSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::cli::run::filter::{FilenameFilter, FilenameRejection, TagRejection};
+use crate::cli::run::{FileTagCache, HookFileFilter, ProjectFiles, RunInput};
+use crate::config::Stage;
+use crate::hook::Hook;
+use crate::printer::Printer;
+use crate::workspace::{Project, Workspace};
+
+/// Why a candidate file is not passed to a hook.
+enum Exclusion<'a> {
+    /// The file belongs to a nested orphan project.
+    Orphan(&'a Project),
+    /// The project-level `files` or `exclude` pattern rejects the file.
+    Project(FilenameRejection),
+    /// The hook-level `files` or `exclude` pattern rejects the file.
+    Hook(FilenameRejection),
+    /// The file type could not be identified.
+    Unidentified,
+    /// A `types`, `types_or` or `exclude_types` selector rejects the file.
+    Tags {
+        rejection: TagRejection,
+        file_tags: &'a TagSet,
+    },
+}
+
+impl Exclusion<'_> {
+    fn describe(&self, hook: &Hook) -> String {
+        match self {
+            Self::Orphan(project) => format!("owned by orphan project `{project}`"),
+            Self::Project(FilenameRejection::NotIncluded) => {
+                "not matched by the project `files` pattern".to_string()
+            }
+            Self::Project(FilenameRejection::Excluded) => {
+                "matched by the project `exclude` pattern".to_string()
+            }
+            Self::Hook(FilenameRejection::NotIncluded) => "not matched by `files`".to_string(),
+            Self::Hook(FilenameRejection::Excluded) => "matched by `exclude`".to_string(),
+            Self::Unidentified => "file type could not be identified".to_string(),
+            Self::Tags {
+                rejection,
+                file_tags,
+            } => {
+                let (message, tags) = match rejection {
+                    TagRejection::Types => (
+                        "missing `types`",
+                        hook.types
+                            .iter()
+                            .filter(|tag| !contains(file_tags, tag))
+                            .join(", "),
+                    ),
+                    TagRejection::TypesOr => {
+                        ("no tag from `types_or`", hook.types_or.iter().join(", "))
+                    }
+                    TagRejection::ExcludeTypes => (
+                        "matched `exclude_types`",
+                        hook.exclude_types
+                            .iter()
+                            .filter(|tag| contains(file_tags, tag))
+                            .join(", "),
+                    ),
+                };
+                format!(
+                    "{message} [{tags}] (file tags: {})",
+                    file_tags.iter().join(", ")
+                )
+            }
+        }
+    }
+}
+
+fn contains(tags: &TagSet, tag: &str) -> bool {
+    TagSet::from_tags([tag]).is_subset(tags)
+}
+
+/// Print why each selected hook with `hook_id` was not run at this stage.
+///
+/// Returns whether any such hook was found.
+pub(super) fn explain_stage_mismatches(
+    hook_id: &str,
+    stage_mismatches: &[Arc<Hook>],
+    stage: Option<Stage>,
+    printer: Printer,
+) -> Result<bool> {
+    let mut found = false;
+    for hook in stage_mismatches.iter().filter(|hook| hook.id == hook_id) {
+        found = true;
+        let reason = if let Some(stage) = stage {
+            format!("not configured for stage `{stage}`")
+        } else {
+            "only runs at the `commit-msg` or `prepare-commit-msg` stage".to_string()
+        };
+        writeln!(
+            printer.stdout_important(),
+            "Hook `{}` in project `{}` is skipped: {reason} (stages: {})",
+            hook.id.cyan(),
+            hook.project(),
+            hook.stages,
+        )?;
+    }
+    Ok(found)
+}
+
+/// Print every candidate file of each hook in `hooks` and why it is or is not selected.
+pub(super) fn explain_files(
+    workspace: &Workspace,
+    input: &RunInput,
+    tag_cache: &FileTagCache,
+    hooks: &[&Arc<Hook>],
+    printer: Printer,
+) -> Result<()> {
+    let RunInput::Files(files) = input else {
+        for hook in hooks {
+            writeln!(
+                printer.stdout_important(),
+                "Hook `{}` in project `{}` receives the commit message file instead of the selected files",
+                hook.id.cyan(),
+                hook.project(),
+            )?;
+        }
+        return Ok(());
+    };
+
+    for hook in hooks {
+        let project = hook.project();
+        writeln!(
+            printer.stdout_important(),
+            "Hook `{}` in project `{project}`:",
+            hook.id.cyan(),
+        )?;
+
+        let owners = orphan_owners(workspace, files, project);
+        let project_filter = FilenameFilter::new(
+            project.config().files.as_ref(),
+            project.config().exclude.as_ref(),
+        );
+        let hook_filter = HookFileFilter::new(hook);
+        let mut candidates = 0;
+        let mut selected = 0;
+
+        for (file_idx, filename) in files.iter().enumerate() {
+            let Ok(relative) = filename.strip_prefix(project.relative_path()) else {
+                continue;
+            };
+            candidates += 1;
+
+            let exclusion = if let Some(owner) = owners.get(filename.as_path()) {
+                Some(Exclusion::Orphan(owner))
+            } else if let Some(rejection) = project_filter.rejection(relative) {
+                Some(Exclusion::Project(rejection))
+            } else if let Some(rejection) = hook_filter.filename_rejection(relative) {
+                Some(Exclusion::Hook(rejection))
+            } else if let Some(file_tags) = tag_cache.tags(file_idx) {
+                hook_filter
+                    .tag_rejection(file_tags)
+                    .map(|rejection| Exclusion::Tags {
+                        rejection,
+                        file_tags,
+                    })
+            } else {
+                Some(Exclusion::Unidentified)
+            };
+
+            match exclusion {
+                None => {
+                    selected += 1;
+                    writeln!(
+                        printer.stdout_important(),
+                        "  {} {}",
+                        "included".green(),
+                        relative.display()
+                    )?;
+                }
+                Some(exclusion) => {
+                    writeln!(
+                        printer.stdout_important(),
+                        "  {} {}: {}",
+                        "excluded".yellow(),
+                        relative.display(),
+                        exclusion.describe(hook)
+                    )?;
+                }
+            }
+        }
+
+        let always_run = if hook.always_run {
+            ", runs anyway because of `always_run`"
+        } else {
+            ""
+        };
+        writeln!(
+            printer.stdout_important(),
+            "  {selected} of {candidates} files selected{always_run}",
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Map files claimed by orphan projects nested in `project` to the project owning them.
+///
+/// Projects are visited in the same order as the runner, which hands each file to the first
+/// orphan project containing it.
+fn orphan_owners<'a>(
+    workspace: &'a Workspace,
+    files: &'a [PathBuf],
+    project: &Project,
+) -> FxHashMap<&'a Path, &'a Project> {
+    let mut owners = FxHashMap::default();
+    let mut consumed_files = FxHashSet::default();
+    for other in workspace.all_projects() {
+        if other.as_ref() == project {
+            break;
+        }
+        let mut newly_consumed_files = FxHashSet::default();
+        ProjectFiles::consume_for_project(
+            files.iter(),
+            other,
+            Some(&consumed_files),
+            &mut newly_consumed_files,
+        );
+        for filename in &newly_consumed_files {
+            owners.insert(*filename, other.as_ref());
+        }
+        consumed_files.extend(newly_consumed_files);
+    }
+    owners
+}
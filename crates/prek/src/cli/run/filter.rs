@@ -3,14 +3,17 @@ use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use itertools::{Either, Itertools};
+use prek_consts::PREKIGNORE;
 use prek_consts::env_vars::EnvVars;
 use prek_identify::{TagSet, tags_from_path};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::{debug, error, instrument};
 
 use crate::config::{FilePattern, Stage};
-use crate::fs::PathClean;
+use crate::fs::{PathClean, Simplified};
 use crate::git::GIT_ROOT;
 use crate::hook::Hook;
 use crate::workspace::Project;
@@ -28,20 +31,34 @@ impl<'a> FilenameFilter<'a> {
     }
 
     pub(crate) fn matches(&self, filename: &Path) -> bool {
+        self.rejection(filename).is_none()
+    }
+
+    /// Return which pattern leaves `filename` out, if any.
+    pub(crate) fn rejection(&self, filename: &Path) -> Option<FilenameRejection> {
         if let Some(pattern) = &self.include {
             if !pattern.is_match(filename) {
-                return false;
+                return Some(FilenameRejection::NotIncluded);
             }
         }
         if let Some(pattern) = &self.exclude {
             if pattern.is_match(filename) {
-                return false;
+                return Some(FilenameRejection::Excluded);
             }
         }
-        true
+        None
     }
 }
 
+/// Why a [`FilenameFilter`] left a file out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum FilenameRejection {
+    /// The file does not match the `files` pattern.
+    NotIncluded,
+    /// The file matches the `exclude` pattern.
+    Excluded,
+}
+
 /// Filter files by tags.
 pub(crate) struct FileTagFilter<'a> {
     all: Option<&'a TagSet>,
@@ -63,23 +80,41 @@ impl<'a> FileTagFilter<'a> {
         }
     }
 
+    /// Return whether a file with `file_types` has every tag in `types`, at least one tag in
+    /// `types_or` (unless it is empty), and no tag in `exclude_types`, as in `pre-commit`.
     pub(crate) fn matches(&self, file_types: &TagSet) -> bool {
+        self.rejection(file_types).is_none()
+    }
+
+    /// Return which type selector leaves a file with `file_types` out, if any.
+    pub(crate) fn rejection(&self, file_types: &TagSet) -> Option<TagRejection> {
         if self.all.is_some_and(|s| !s.is_subset(file_types)) {
-            return false;
+            return Some(TagRejection::Types);
         }
         if self
             .any
             .is_some_and(|s| !s.is_empty() && s.is_disjoint(file_types))
         {
-            return false;
+            return Some(TagRejection::TypesOr);
         }
         if self.exclude.is_some_and(|s| !s.is_disjoint(file_types)) {
-            return false;
+            return Some(TagRejection::ExcludeTypes);
         }
-        true
+        None
     }
 }
 
+/// Why a [`FileTagFilter`] left a file out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum TagRejection {
+    /// The file lacks a tag from `types`.
+    Types,
+    /// The file has no tag from `types_or`.
+    TypesOr,
+    /// The file has a tag from `exclude_types`.
+    ExcludeTypes,
+}
+
 pub(crate) struct HookFileFilter<'a> {
     filename: FilenameFilter<'a>,
     tags: FileTagFilter<'a>,
@@ -105,7 +140,18 @@ impl<'a> HookFileFilter<'a> {
         tags.is_some_and(|tags| self.tags.matches(tags))
     }
 
+    pub(crate) fn filename_rejection(&self, filename: &Path) -> Option<FilenameRejection> {
+        self.filename.rejection(filename)
+    }
+
+    pub(crate) fn tag_rejection(&self, tags: &TagSet) -> Option<TagRejection> {
+        self.tags.rejection(tags)
+    }
+
     /// Return whether a project-owned file passes this hook's file and tag filters.
+    ///
+    /// Path patterns are checked first, so files rejected by `files`/`exclude` are never
+    /// opened for tag classification.
     pub(crate) fn matches_project_file<'p>(
         &self,
         file: &ProjectFile<'p>,
@@ -168,6 +214,12 @@ impl<'a> FileTagCache<'a> {
                 let path = self.paths[file_idx];
                 match tags_from_path(path) {
                     Ok(tags) => Some(tags),
+                    // Files deleted in a ref range (`--include-deleted`) have no type tags.
+                    Err(prek_identify::Error::Io(err))
+                        if err.kind() == std::io::ErrorKind::NotFound =>
+                    {
+                        Some(TagSet::default())
+                    }
                     Err(err) => {
                         error!(filename = ?path.display(), error = %err, "Failed to get tags");
                         None
@@ -176,6 +228,15 @@ impl<'a> FileTagCache<'a> {
             })
             .as_ref()
     }
+
+    /// Number of files whose tags have been computed so far.
+    #[cfg(test)]
+    fn classified_count(&self) -> usize {
+        self.tags_by_file
+            .iter()
+            .filter(|cell| cell.get().is_some())
+            .count()
+    }
 }
 
 pub(crate) struct ProjectFiles<'a> {
@@ -339,12 +400,19 @@ impl<'a> ProjectFiles<'a> {
 }
 
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct CollectOptions {
     pub(crate) input_mode: RunInputMode,
     pub(crate) from_ref: Option<String>,
     pub(crate) to_ref: Option<String>,
+    /// Include files deleted between `from_ref` and `to_ref`.
+    pub(crate) include_deleted: bool,
+    /// Add untracked, non-ignored files to the files collected from git.
+    pub(crate) include_untracked: bool,
     pub(crate) all_files: bool,
     pub(crate) files: Vec<String>,
+    /// Use `files` as the file set even if it is empty, instead of falling back to staged files.
+    pub(crate) files_from_stdin: bool,
     pub(crate) directories: Vec<String>,
     pub(crate) commit_msg_filename: Option<String>,
 }
@@ -408,8 +476,11 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         input_mode,
         from_ref,
         to_ref,
+        include_deleted,
+        include_untracked,
         all_files,
         files,
+        files_from_stdin,
         directories,
         commit_msg_filename,
     } = opts;
@@ -439,8 +510,11 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         root,
         from_ref,
         to_ref,
+        include_deleted,
+        include_untracked,
         all_files,
         files,
+        files_from_stdin,
         directories,
     )
     .await?;
@@ -457,6 +531,18 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         })
         .collect::<Vec<_>>();
 
+    // Files under directories listed in `.prekignore` never reach any hook, the same way
+    // those directories are skipped during project discovery.
+    let mut prekignore = PrekIgnore::new(root);
+    let before = filenames.len();
+    filenames.retain(|filename| !prekignore.is_ignored(filename));
+    if filenames.len() < before {
+        debug!(
+            "Skipped {} files matched by `{PREKIGNORE}`",
+            before - filenames.len()
+        );
+    }
+
     // Sort filenames if in tests to make the order consistent.
     if EnvVars::is_set(EnvVars::PREK_INTERNAL__SORT_FILENAMES) {
         filenames.sort_unstable();
@@ -465,6 +551,81 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
     Ok(RunInput::Files(filenames))
 }
 
+/// Matcher for the `.prekignore` files under the workspace root.
+///
+/// Like `.gitignore`, each `.prekignore` applies to its directory and all subdirectories,
+/// and the nearest file with a matching pattern decides whether a path is ignored.
+struct PrekIgnore<'a> {
+    root: &'a Path,
+    /// Parsed `.prekignore` per directory relative to the root, `None` if there is none.
+    matchers: FxHashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl<'a> PrekIgnore<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            matchers: FxHashMap::default(),
+        }
+    }
+
+    fn matcher(&mut self, dir: &Path) -> Option<&Gitignore> {
+        let root = self.root;
+        self.matchers
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let dir = root.join(dir);
+                let path = dir.join(PREKIGNORE);
+                if !path.is_file() {
+                    return None;
+                }
+                let mut builder = GitignoreBuilder::new(&dir);
+                if let Some(err) = builder.add(&path) {
+                    warn_user!("Failed to read `{}`: {err}", path.user_display());
+                }
+                builder
+                    .build()
+                    .inspect_err(|err| {
+                        warn_user!("Failed to parse `{}`: {err}", path.user_display());
+                    })
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    /// Check whether a path relative to the root is ignored.
+    fn is_ignored(&mut self, filename: &Path) -> bool {
+        let path = self.root.join(filename);
+        for dir in filename.ancestors().skip(1) {
+            let Some(matcher) = self.matcher(dir) else {
+                continue;
+            };
+            match matcher.matched_path_or_any_parents(&path, false) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+/// Parse a NUL-separated list of paths, as produced by `git ls-files -z` or `find -print0`.
+pub(crate) fn parse_nul_separated_files(input: &[u8]) -> Result<Vec<String>> {
+    input
+        .split(|&b| b == b'\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| {
+            String::from_utf8(path.to_vec()).with_context(|| {
+                format!(
+                    "Path read from stdin is not valid UTF-8: `{}`",
+                    String::from_utf8_lossy(path)
+                )
+            })
+        })
+        .collect()
+}
+
 fn adjust_relative_path(path: &str, new_cwd: &Path) -> Result<PathBuf, std::io::Error> {
     let absolute = std::path::absolute(path)?.clean();
     fs::relative_to(absolute, new_cwd)
@@ -472,17 +633,22 @@ fn adjust_relative_path(path: &str, new_cwd: &Path) -> Result<PathBuf, std::io::
 
 /// Collect files to run hooks on.
 /// Returns a list of file paths relative to the git root.
+#[allow(clippy::fn_params_excessive_bools)]
 async fn collect_files_from_args(
     git_root: &Path,
     workspace_root: &Path,
     from_ref: Option<String>,
     to_ref: Option<String>,
+    include_deleted: bool,
+    include_untracked: bool,
     all_files: bool,
     files: Vec<String>,
+    files_from_stdin: bool,
     directories: Vec<String>,
 ) -> Result<Vec<PathBuf>> {
     if let (Some(from_ref), Some(to_ref)) = (from_ref, to_ref) {
-        let files = git::get_changed_files(&from_ref, &to_ref, workspace_root).await?;
+        let files =
+            git::get_changed_files(&from_ref, &to_ref, workspace_root, include_deleted).await?;
         debug!(
             "Files changed between {} and {}: {}",
             from_ref,
@@ -492,7 +658,7 @@ async fn collect_files_from_args(
         return Ok(files);
     }
 
-    if !files.is_empty() || !directories.is_empty() {
+    if files_from_stdin || !files.is_empty() || !directories.is_empty() {
         // By default, `pre-commit` add `types: [file]` for all hooks,
         // so `pre-commit` will ignore user provided directories.
         // We do the same here for compatibility.
@@ -526,7 +692,10 @@ async fn collect_files_from_args(
 
         for dir in directories {
             let dir = adjust_relative_path(&dir, git_root)?;
-            let dir_files = git::ls_files(git_root, &dir).await?;
+            let mut dir_files = git::ls_files(git_root, &dir).await?;
+            if include_untracked {
+                dir_files.extend(git::ls_untracked_files(git_root, &dir).await?);
+            }
             for file in dir_files {
                 let file = fs::normalize_path(file);
                 exists.insert(file);
@@ -538,8 +707,13 @@ async fn collect_files_from_args(
     }
 
     if all_files {
-        let files = git::ls_files(git_root, workspace_root).await?;
+        let mut files = git::ls_files(git_root, workspace_root).await?;
         debug!("All files in the workspace: {}", files.len());
+        if include_untracked {
+            let untracked = git::ls_untracked_files(git_root, workspace_root).await?;
+            debug!("Untracked files in the workspace: {}", untracked.len());
+            files.extend(untracked);
+        }
         return Ok(files);
     }
 
@@ -549,8 +723,13 @@ async fn collect_files_from_args(
         return Ok(files);
     }
 
-    let files = git::get_staged_files(workspace_root).await?;
+    let mut files = git::get_staged_files(workspace_root).await?;
     debug!("Staged files: {}", files.len());
+    if include_untracked {
+        let untracked = git::ls_untracked_files(git_root, workspace_root).await?;
+        debug!("Untracked files in the workspace: {}", untracked.len());
+        files.extend(untracked);
+    }
 
     Ok(files)
 }
@@ -572,6 +751,114 @@ mod tests {
         FilePattern::regex(pattern).unwrap()
     }
 
+    #[test]
+    fn parse_nul_separated_files_handles_special_characters() -> anyhow::Result<()> {
+        let files = parse_nul_separated_files(b"a.py\0dir/with space.txt\0new\nline.md\0")?;
+        assert_eq!(files, ["a.py", "dir/with space.txt", "new\nline.md"]);
+
+        assert!(parse_nul_separated_files(b"")?.is_empty());
+        assert!(parse_nul_separated_files(b"bad-\xff.py\0").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hook_file_filter_skips_tags_for_excluded_files() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let vendor = temp_dir.path().join("vendor");
+        fs_err::create_dir_all(&vendor)?;
+        fs_err::write(vendor.join("lib.py"), "print('vendored')\n")?;
+        fs_err::write(temp_dir.path().join("main.py"), "print('main')\n")?;
+
+        let paths = [vendor.join("lib.py"), temp_dir.path().join("main.py")];
+        let tag_cache = FileTagCache::from_paths(paths.iter().map(PathBuf::as_path));
+
+        let exclude = glob_pattern("vendor/**");
+        let types = TagSet::from_tags(["python"]);
+        let filter = HookFileFilter {
+            filename: FilenameFilter::new(None, Some(&exclude)),
+            tags: FileTagFilter::new(Some(&types), None, None),
+        };
+
+        let excluded = ProjectFile::new(0, Path::new("vendor/lib.py"));
+        assert!(!filter.matches_project_file(&excluded, &tag_cache));
+        assert_eq!(tag_cache.classified_count(), 0);
+
+        let included = ProjectFile::new(1, Path::new("main.py"));
+        assert!(filter.matches_project_file(&included, &tag_cache));
+        assert_eq!(tag_cache.classified_count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn file_tag_filter_combines_types_types_or_and_exclude_types() {
+        let python = TagSet::from_tags(["file", "text", "python"]);
+        let markdown = TagSet::from_tags(["file", "text", "markdown"]);
+        let yaml = TagSet::from_tags(["file", "text", "yaml"]);
+        let binary = TagSet::from_tags(["file", "binary"]);
+        let empty = TagSet::default();
+
+        let matching = |types: &TagSet, types_or: &TagSet, exclude_types: &TagSet| {
+            let filter = FileTagFilter::new(Some(types), Some(types_or), Some(exclude_types));
+            [&python, &markdown, &yaml, &binary]
+                .into_iter()
+                .map(|tags| filter.matches(tags))
+                .collect::<Vec<_>>()
+        };
+
+        let text = TagSet::from_tags(["text"]);
+        let py_or_md = TagSet::from_tags(["python", "markdown"]);
+        let text_python = TagSet::from_tags(["text", "python"]);
+
+        // No filters match every file.
+        assert_eq!(matching(&empty, &empty, &empty), [true, true, true, true]);
+        // Every tag in `types` is required.
+        assert_eq!(matching(&text, &empty, &empty), [true, true, true, false]);
+        assert_eq!(
+            matching(&text_python, &empty, &empty),
+            [true, false, false, false]
+        );
+        // Any tag in `types_or` is enough.
+        assert_eq!(
+            matching(&empty, &py_or_md, &empty),
+            [true, true, false, false]
+        );
+        // `types` and `types_or` must both be satisfied.
+        let binary_tag = TagSet::from_tags(["binary"]);
+        assert_eq!(
+            matching(&text, &py_or_md, &empty),
+            [true, true, false, false]
+        );
+        assert_eq!(
+            matching(&binary_tag, &py_or_md, &empty),
+            [false, false, false, false]
+        );
+        // `exclude_types` wins over both.
+        let markdown_tag = TagSet::from_tags(["markdown"]);
+        assert_eq!(
+            matching(&text, &py_or_md, &markdown_tag),
+            [true, false, false, false]
+        );
+        assert_eq!(matching(&empty, &empty, &text), [false, false, false, true]);
+    }
+
+    #[test]
+    fn file_tag_filter_reports_first_rejecting_selector() {
+        let pyi = TagSet::from_tags(["file", "text", "pyi"]);
+        let python = TagSet::from_tags(["python"]);
+        let text = TagSet::from_tags(["text"]);
+
+        let filter = FileTagFilter::new(Some(&python), None, None);
+        assert_eq!(filter.rejection(&pyi), Some(TagRejection::Types));
+        let filter = FileTagFilter::new(None, Some(&python), None);
+        assert_eq!(filter.rejection(&pyi), Some(TagRejection::TypesOr));
+        let filter = FileTagFilter::new(Some(&text), None, Some(&text));
+        assert_eq!(filter.rejection(&pyi), Some(TagRejection::ExcludeTypes));
+        let filter = FileTagFilter::new(Some(&text), None, Some(&python));
+        assert_eq!(filter.rejection(&pyi), None);
+    }
+
     #[test]
     fn filename_filter_supports_glob_include_and_exclude() {
         let include = glob_pattern("src/**/*.rs");
@@ -625,4 +912,22 @@ mod tests {
 
         assert!(!filter.matches(path));
     }
+
+    #[test]
+    fn prekignore_nearest_file_wins() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::write(dir.path().join(PREKIGNORE), "vendor/\n*.gen\n")?;
+        fs_err::create_dir_all(dir.path().join("sub"))?;
+        fs_err::write(dir.path().join("sub").join(PREKIGNORE), "!keep.gen\n")?;
+
+        let mut prekignore = PrekIgnore::new(dir.path());
+        assert!(prekignore.is_ignored(Path::new("vendor/lib.py")));
+        assert!(prekignore.is_ignored(Path::new("vendor/deep/lib.py")));
+        assert!(prekignore.is_ignored(Path::new("out.gen")));
+        assert!(prekignore.is_ignored(Path::new("sub/other.gen")));
+        assert!(!prekignore.is_ignored(Path::new("sub/keep.gen")));
+        assert!(!prekignore.is_ignored(Path::new("src/main.py")));
+
+        Ok(())
+    }
 }
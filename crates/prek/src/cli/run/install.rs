@@ -188,11 +188,11 @@ impl CachedInstallInfo {
         &self.info
     }
 
-    async fn ensure_healthy(&self) -> bool {
+    async fn ensure_healthy(&self, store: &Store) -> bool {
         let info = self.info.clone();
         *self
             .health
-            .get_or_init(async move || match info.check_health().await {
+            .get_or_init(async move || match info.check_health(store).await {
                 Ok(()) => true,
                 Err(err) => {
                     warn!(
@@ -246,7 +246,7 @@ impl InstallCache {
         hook: Arc<Hook>,
     ) -> Option<InstalledHook> {
         for env in self.installed_hooks(store).await {
-            if env.matches(&hook) && env.ensure_healthy().await {
+            if env.matches(&hook) && env.ensure_healthy(store).await {
                 return Some(InstalledHook::Installed {
                     hook,
                     info: env.info(),
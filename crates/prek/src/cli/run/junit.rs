@@ -0,0 +1,241 @@
+//! `JUnit` XML reports of hook results, for CI systems that aggregate test results.
+//!
+//! Every hook that was considered becomes a `<testcase>` named after the hook id, with the
+//! project it belongs to as its `classname`. Projects map to `<testsuite>` elements in the
+//! order they were run.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::writer::{ElementWriter, Writer};
+
+use crate::fs::Simplified;
+
+#[derive(Debug)]
+enum Outcome {
+    Passed,
+    Failed { message: String, output: String },
+    Skipped { message: &'static str },
+}
+
+#[derive(Debug)]
+struct TestCase {
+    name: String,
+    classname: String,
+    time: Duration,
+    outcome: Outcome,
+}
+
+/// Hook results collected during a run, written as `JUnit` XML once the run completes.
+#[derive(Debug)]
+pub(super) struct JunitReport {
+    path: PathBuf,
+    cases: Vec<TestCase>,
+}
+
+impl JunitReport {
+    pub(super) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            cases: Vec::new(),
+        }
+    }
+
+    pub(super) fn passed(&mut self, name: &str, classname: &str, time: Duration) {
+        self.push(name, classname, time, Outcome::Passed);
+    }
+
+    /// Record a failed hook, keeping its output with escape sequences and other control
+    /// characters removed, since XML 1.0 cannot represent them.
+    pub(super) fn failed(
+        &mut self,
+        name: &str,
+        classname: &str,
+        time: Duration,
+        message: String,
+        output: &[u8],
+    ) {
+        let output = String::from_utf8_lossy(output);
+        let output = anstream::adapter::strip_str(&output).to_string().replace(
+            |c: char| c.is_control() && !matches!(c, '\t' | '\n' | '\r'),
+            "",
+        );
+        self.push(name, classname, time, Outcome::Failed { message, output });
+    }
+
+    pub(super) fn skipped(&mut self, name: &str, classname: &str, message: &'static str) {
+        self.push(
+            name,
+            classname,
+            Duration::ZERO,
+            Outcome::Skipped { message },
+        );
+    }
+
+    fn push(&mut self, name: &str, classname: &str, time: Duration, outcome: Outcome) {
+        self.cases.push(TestCase {
+            name: name.to_string(),
+            classname: classname.to_string(),
+            time,
+            outcome,
+        });
+    }
+
+    pub(super) fn write(&self) -> Result<()> {
+        let xml = self.to_xml()?;
+        fs_err::write(&self.path, xml).with_context(|| {
+            format!(
+                "Failed to write JUnit report to `{}`",
+                self.path.user_display()
+            )
+        })
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn to_xml(&self) -> io::Result<String> {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+        let suites = self.cases.iter().chunk_by(|case| case.classname.as_str());
+        let summary = summary_attributes("prek", self.cases.iter());
+        with_summary(writer.create_element("testsuites"), &summary).write_inner_content(
+            |writer| {
+                for (classname, cases) in &suites {
+                    let cases = cases.collect::<Vec<_>>();
+                    let summary = summary_attributes(classname, cases.iter().copied());
+                    with_summary(writer.create_element("testsuite"), &summary)
+                        .write_inner_content(|writer| {
+                            for case in cases {
+                                write_test_case(writer, case)?;
+                            }
+                            Ok(())
+                        })?;
+                }
+                Ok(())
+            },
+        )?;
+
+        let mut xml = String::from_utf8(writer.into_inner()).map_err(io::Error::other)?;
+        xml.push('\n');
+        Ok(xml)
+    }
+}
+
+fn summary_attributes<'a>(
+    name: &str,
+    cases: impl Iterator<Item = &'a TestCase>,
+) -> Vec<(&'static str, String)> {
+    let (mut tests, mut failures, mut skipped, mut time) = (0, 0, 0, Duration::ZERO);
+    for case in cases {
+        tests += 1;
+        time += case.time;
+        match case.outcome {
+            Outcome::Passed => {}
+            Outcome::Failed { .. } => failures += 1,
+            Outcome::Skipped { .. } => skipped += 1,
+        }
+    }
+
+    vec![
+        ("name", name.to_string()),
+        ("tests", tests.to_string()),
+        ("failures", failures.to_string()),
+        ("errors", "0".to_string()),
+        ("skipped", skipped.to_string()),
+        ("time", format_time(time)),
+    ]
+}
+
+fn with_summary<'a>(
+    element: ElementWriter<'a, Vec<u8>>,
+    summary: &[(&'static str, String)],
+) -> ElementWriter<'a, Vec<u8>> {
+    summary.iter().fold(element, |element, (key, value)| {
+        element.with_attribute((*key, value.as_str()))
+    })
+}
+
+fn write_test_case(writer: &mut Writer<Vec<u8>>, case: &TestCase) -> io::Result<()> {
+    let element = writer
+        .create_element("testcase")
+        .with_attribute(("name", case.name.as_str()))
+        .with_attribute(("classname", case.classname.as_str()))
+        .with_attribute(("time", format_time(case.time).as_str()));
+
+    match &case.outcome {
+        Outcome::Passed => {
+            element.write_empty()?;
+        }
+        Outcome::Failed { message, output } => {
+            element.write_inner_content(|writer| {
+                writer
+                    .create_element("failure")
+                    .with_attribute(("message", message.as_str()))
+                    .write_text_content(BytesText::new(output))?;
+                Ok(())
+            })?;
+        }
+        Outcome::Skipped { message } => {
+            element.write_inner_content(|writer| {
+                writer
+                    .create_element("skipped")
+                    .with_attribute(("message", *message))
+                    .write_empty()?;
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn format_time(time: Duration) -> String {
+    format!("{:.3}", time.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::JunitReport;
+
+    #[test]
+    fn junit_report_xml() {
+        let mut report = JunitReport::new(PathBuf::from("report.xml"));
+        report.passed("ruff", ".", Duration::from_millis(1500));
+        report.failed(
+            "mypy",
+            ".",
+            Duration::from_millis(250),
+            "exit code: 1".to_string(),
+            b"\x1b[31ma.py:1: error: <bad> & \"worse\"\x1b[0m\n",
+        );
+        report.skipped("typos", "docs", "no files to check");
+
+        insta::assert_snapshot!(report.to_xml().unwrap(), @r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="prek" tests="3" failures="1" errors="0" skipped="1" time="1.750">
+          <testsuite name="." tests="2" failures="1" errors="0" skipped="0" time="1.750">
+            <testcase name="ruff" classname="." time="1.500"/>
+            <testcase name="mypy" classname="." time="0.250">
+              <failure message="exit code: 1">a.py:1: error: &lt;bad&gt; &amp; &quot;worse&quot;
+        </failure>
+            </testcase>
+          </testsuite>
+          <testsuite name="docs" tests="1" failures="0" errors="0" skipped="1" time="0.000">
+            <testcase name="typos" classname="docs" time="0.000">
+              <skipped message="no files to check"/>
+            </testcase>
+          </testsuite>
+        </testsuites>
+        "#);
+    }
+}
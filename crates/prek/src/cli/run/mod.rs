@@ -1,17 +1,25 @@
 pub(crate) use filter::{
     CollectOptions, FileTagCache, FileTagFilter, HookFileFilter, ProjectFiles, RunInput,
-    collect_run_input,
+    RunInputMode, collect_run_input, parse_nul_separated_files,
 };
 pub(crate) use install::{InstallCache, install_hooks};
 pub(crate) use reporter::{HookRunReporter, project_status_marker};
-pub(crate) use run::run;
+pub(crate) use run::{RunSettings, run};
 pub(crate) use selector::{ConfiguredHook, GroupFilters, SelectorSource, Selectors};
 
+mod annotations;
 mod diff;
+mod explain;
 mod filter;
 mod install;
+mod junit;
 mod keeper;
+mod notify;
+mod outcomes;
+mod plan;
 mod reporter;
+mod result_cache;
 #[allow(clippy::module_inception)]
 mod run;
 mod selector;
+mod timings;
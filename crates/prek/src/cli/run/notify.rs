@@ -0,0 +1,124 @@
+//! Desktop notifications for `prek run --notify`.
+
+use anyhow::Result;
+use prek_consts::env_vars::EnvVars;
+use tracing::debug;
+
+/// The notification sent when a run completes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct RunNotification {
+    pub(super) summary: String,
+    pub(super) body: String,
+}
+
+impl RunNotification {
+    pub(super) fn new(hooks: usize, failed: usize) -> Self {
+        let summary = if failed == 0 {
+            "prek: all passed".to_string()
+        } else {
+            format!(
+                "prek: {failed} hook{} failed",
+                if failed == 1 { "" } else { "s" }
+            )
+        };
+        let body = format!(
+            "{} of {hooks} hook{} passed",
+            hooks - failed,
+            if hooks == 1 { "" } else { "s" }
+        );
+        Self { summary, body }
+    }
+}
+
+pub(super) trait Notifier {
+    fn notify(&self, notification: &RunNotification) -> Result<()>;
+}
+
+/// Shows notifications on the desktop via the platform notification service.
+pub(super) struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, notification: &RunNotification) -> Result<()> {
+        notify_rust::Notification::new()
+            .appname("prek")
+            .summary(&notification.summary)
+            .body(&notification.body)
+            .show()?;
+        Ok(())
+    }
+}
+
+/// Whether there is a desktop to notify, which is never the case in CI.
+pub(super) fn desktop_available() -> bool {
+    if EnvVars::is_under_ci() {
+        return false;
+    }
+    if cfg!(any(target_os = "macos", windows)) {
+        return true;
+    }
+    EnvVars::is_set(EnvVars::DISPLAY) || EnvVars::is_set(EnvVars::WAYLAND_DISPLAY)
+}
+
+/// Send a notification for a completed run. Failing to notify never fails the run.
+pub(super) fn notify_run_complete(notifier: &impl Notifier, hooks: usize, failed: usize) {
+    let notification = RunNotification::new(hooks, failed);
+    if let Err(err) = notifier.notify(&notification) {
+        debug!("Failed to send desktop notification: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingNotifier(RefCell<Vec<RunNotification>>);
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, notification: &RunNotification) -> Result<()> {
+            self.0.borrow_mut().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    struct FailingNotifier;
+
+    impl Notifier for FailingNotifier {
+        fn notify(&self, _notification: &RunNotification) -> Result<()> {
+            anyhow::bail!("no notification service")
+        }
+    }
+
+    #[test]
+    fn notification_reflects_outcome() {
+        let notifier = RecordingNotifier::default();
+        notify_run_complete(&notifier, 5, 0);
+        notify_run_complete(&notifier, 5, 3);
+        notify_run_complete(&notifier, 1, 1);
+
+        assert_eq!(
+            notifier.0.into_inner(),
+            vec![
+                RunNotification {
+                    summary: "prek: all passed".to_string(),
+                    body: "5 of 5 hooks passed".to_string(),
+                },
+                RunNotification {
+                    summary: "prek: 3 hooks failed".to_string(),
+                    body: "2 of 5 hooks passed".to_string(),
+                },
+                RunNotification {
+                    summary: "prek: 1 hook failed".to_string(),
+                    body: "0 of 1 hook passed".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn notification_errors_are_ignored() {
+        notify_run_complete(&FailingNotifier, 2, 1);
+    }
+}
@@ -0,0 +1,83 @@
+//! Summary of hook outcomes printed after a run with `--keep-going`.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use crate::hook::Hook;
+use crate::printer::Printer;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum Outcome {
+    Passed,
+    Failed,
+    /// The hook had no files to check, was cached, or the run was a dry run.
+    Skipped,
+}
+
+#[derive(Debug)]
+struct HookOutcome {
+    id: String,
+    project: String,
+    outcome: Outcome,
+}
+
+/// The outcome of every hook of the run, in the order they were reported.
+#[derive(Debug, Default)]
+pub(super) struct Outcomes {
+    hooks: Vec<HookOutcome>,
+}
+
+impl Outcomes {
+    pub(super) fn record(&mut self, hook: &Hook, outcome: Outcome) {
+        self.hooks.push(HookOutcome {
+            id: hook.id.clone(),
+            project: hook.project().to_string(),
+            outcome,
+        });
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.hooks
+            .iter()
+            .filter(|hook| hook.outcome == outcome)
+            .count()
+    }
+
+    pub(super) fn render(self, printer: Printer) -> Result<()> {
+        let show_project = self.hooks.iter().any(|hook| hook.project != ".");
+        let name = |hook: &HookOutcome| {
+            if show_project {
+                format!("{}:{}", hook.project, hook.id)
+            } else {
+                hook.id.clone()
+            }
+        };
+
+        let failed = self.count(Outcome::Failed);
+        let mut stdout = printer.stdout_important();
+        writeln!(stdout, "\n{}", "Summary:".bold())?;
+        writeln!(stdout, "  {:<8} {}", "Passed", self.count(Outcome::Passed))?;
+        writeln!(stdout, "  {:<8} {failed}", "Failed")?;
+        writeln!(
+            stdout,
+            "  {:<8} {}",
+            "Skipped",
+            self.count(Outcome::Skipped)
+        )?;
+
+        if failed > 0 {
+            writeln!(stdout, "\n{}", "Failed hooks:".bold())?;
+            for hook in self
+                .hooks
+                .iter()
+                .filter(|hook| hook.outcome == Outcome::Failed)
+            {
+                writeln!(stdout, "  {}", name(hook).red())?;
+            }
+        }
+
+        Ok(())
+    }
+}
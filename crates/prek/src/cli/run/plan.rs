@@ -0,0 +1,84 @@
+//! Machine-readable hook execution plan printed by `--dry-run=json`.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::hook::Hook;
+use crate::printer::Printer;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum SkipReason {
+    /// No files matched the hook's filters.
+    NoFiles,
+    /// The hook is not configured for the stage being run.
+    StageMismatch,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedHook {
+    id: String,
+    project: String,
+    /// Whether the hook would run.
+    run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skip_reason: Option<SkipReason>,
+    /// Number of files the hook would receive after filtering.
+    files: usize,
+    always_run: bool,
+}
+
+impl PlannedHook {
+    fn new(hook: &Hook, skip_reason: Option<SkipReason>, files: usize) -> Self {
+        Self {
+            id: hook.id.clone(),
+            project: hook.project().to_string(),
+            run: skip_reason.is_none(),
+            skip_reason,
+            files,
+            always_run: hook.always_run,
+        }
+    }
+}
+
+/// Hooks a dry run would run, in run order, followed by the selected hooks skipped for the
+/// stage.
+#[derive(Debug, Serialize)]
+pub(super) struct DryRunPlan {
+    hooks: Vec<PlannedHook>,
+    #[serde(skip)]
+    stage_mismatches: Vec<PlannedHook>,
+}
+
+impl DryRunPlan {
+    pub(super) fn new<'a>(stage_mismatches: impl IntoIterator<Item = &'a Hook>) -> Self {
+        Self {
+            hooks: Vec::new(),
+            stage_mismatches: stage_mismatches
+                .into_iter()
+                .map(|hook| PlannedHook::new(hook, Some(SkipReason::StageMismatch), 0))
+                .collect(),
+        }
+    }
+
+    pub(super) fn would_run(&mut self, hook: &Hook, files: usize) {
+        self.hooks.push(PlannedHook::new(hook, None, files));
+    }
+
+    pub(super) fn no_files(&mut self, hook: &Hook) {
+        self.hooks
+            .push(PlannedHook::new(hook, Some(SkipReason::NoFiles), 0));
+    }
+
+    pub(super) fn render(mut self, printer: Printer) -> Result<()> {
+        self.hooks.append(&mut self.stage_mismatches);
+        writeln!(
+            printer.stdout_important(),
+            "{}",
+            serde_json::to_string_pretty(&self)?
+        )?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::collections::hash_map::Entry;
+use std::fmt::Write as _;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use console::{Term, strip_ansi_codes};
 use indicatif::{ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::cli::reporter::{ProgressReporter, SPINNER_TICKS, set_current_reporter};
@@ -103,7 +104,47 @@ impl HookBar {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Hook output forwarded to stdout as the hook writes it.
+///
+/// Output is forwarded a line at a time, so lines from hooks running concurrently do not
+/// interleave mid-line.
+#[derive(Debug)]
+struct StreamedOutput {
+    hook: HookKey,
+    /// Printed before every line, naming the hook when several hooks may run at once.
+    prefix: String,
+    /// Output after the last newline, held back until the line is complete.
+    partial: Vec<u8>,
+}
+
+impl StreamedOutput {
+    fn new(hook: HookKey, prefix: String) -> Self {
+        Self {
+            hook,
+            prefix,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Append an output chunk and return the lines it completed.
+    fn push_chunk(&mut self, chunk: &[u8]) -> Vec<u8> {
+        self.partial.extend_from_slice(chunk);
+        match self.partial.iter().rposition(|&byte| byte == b'\n') {
+            Some(end) => {
+                let rest = self.partial.split_off(end + 1);
+                std::mem::replace(&mut self.partial, rest)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Return the output of an unterminated last line.
+    fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.partial)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct HookKey {
     project_idx: usize,
     hook_idx: usize,
@@ -369,6 +410,13 @@ pub(crate) struct HookRunReporter {
     running: Mutex<FxHashMap<usize, HookBar>>,
     /// Per-project layout and completed-hook state.
     groups: Mutex<HookGroups>,
+    /// Whether hook output is forwarded to stdout live, and whether lines are labelled with
+    /// the hook id.
+    stream_output: Option<bool>,
+    /// Live output of running hooks, keyed like `running`.
+    streams: Mutex<FxHashMap<usize, StreamedOutput>>,
+    /// Hooks whose output has been forwarded live.
+    streamed: Mutex<FxHashSet<HookKey>>,
 }
 
 impl HookRunReporter {
@@ -383,9 +431,32 @@ impl HookRunReporter {
             show_project_headers,
             running: Mutex::default(),
             groups: Mutex::default(),
+            stream_output: None,
+            streams: Mutex::default(),
+            streamed: Mutex::default(),
         }
     }
 
+    /// Forward hook output to stdout as it is written, instead of only previewing its tail.
+    ///
+    /// With `label_lines`, each line is prefixed with the hook id, for runs where hooks may
+    /// run concurrently. Hooks with a `log_file` are not streamed.
+    pub(crate) fn with_streamed_output(mut self, label_lines: bool) -> Self {
+        self.stream_output = Some(label_lines);
+        self
+    }
+
+    /// Whether output of `hook` has been forwarded live, and so is already shown.
+    ///
+    /// Hooks that run in process, such as builtin hooks, return their output at the end
+    /// instead.
+    pub(crate) fn streamed_output(&self, hook: &Hook) -> bool {
+        self.streamed
+            .lock()
+            .unwrap()
+            .contains(&HookKey::from_hook(hook))
+    }
+
     pub fn on_run_start(&self, hook: &Hook, len: usize) -> usize {
         let id = self.reporter.next_id();
         let progress_len = if len == 0 { 1 } else { len as u64 };
@@ -409,9 +480,39 @@ impl HookRunReporter {
         group.active_tail = Some(id);
 
         running.insert(id, HookBar::new(hook, progress));
+
+        if let Some(prefix) = self.stream_prefix(hook) {
+            self.streams
+                .lock()
+                .unwrap()
+                .insert(id, StreamedOutput::new(HookKey::from_hook(hook), prefix));
+        }
         id
     }
 
+    /// The prefix of streamed output lines of `hook`, or `None` if its output is not streamed.
+    fn stream_prefix(&self, hook: &Hook) -> Option<String> {
+        let label_lines = self.stream_output?;
+        if hook.log_file.is_some() {
+            return None;
+        }
+        Some(if label_lines {
+            format!("{} ", format!("[{}]", hook.id).dimmed())
+        } else {
+            "  ".to_string()
+        })
+    }
+
+    /// Print a note between the streamed output of `hook`, such as a retry notice.
+    pub(crate) fn on_stream_note(&self, hook: &Hook, note: &str) {
+        if !self.streamed_output(hook) {
+            return;
+        }
+        if let Some(prefix) = self.stream_prefix(hook) {
+            self.print_streamed(&prefix, note.as_bytes());
+        }
+    }
+
     fn project_header(&self, hook: &Hook) -> Option<ProgressBar> {
         if !self.show_project_headers {
             return None;
@@ -477,6 +578,18 @@ impl HookRunReporter {
     }
 
     fn on_run_output(&self, id: usize, chunk: &[u8]) {
+        let streamed = {
+            let mut streams = self.streams.lock().unwrap();
+            streams
+                .get_mut(&id)
+                .map(|stream| (stream.hook, stream.prefix.clone(), stream.push_chunk(chunk)))
+        };
+        if let Some((hook, prefix, lines)) = streamed {
+            self.streamed.lock().unwrap().insert(hook);
+            self.print_streamed(&prefix, &lines);
+            return;
+        }
+
         let width = self.dots.saturating_sub(HOOK_OUTPUT_PREVIEW_PREFIX.width());
         let update = {
             let mut running = self.running.lock().unwrap();
@@ -513,6 +626,12 @@ impl HookRunReporter {
     }
 
     pub fn on_run_complete(&self, id: usize) {
+        let streamed = self.streams.lock().unwrap().remove(&id);
+        if let Some(mut stream) = streamed {
+            let rest = stream.finish();
+            self.print_streamed(&stream.prefix, &rest);
+        }
+
         let mut completed = {
             let mut running = self.running.lock().unwrap();
             running.remove(&id).unwrap()
@@ -590,6 +709,19 @@ impl HookRunReporter {
         }
     }
 
+    fn print_streamed(&self, prefix: &str, lines: &[u8]) {
+        if lines.is_empty() {
+            return;
+        }
+        let text = String::from_utf8_lossy(lines);
+        self.suspend(|| {
+            let mut stdout = self.reporter.printer.stdout();
+            for line in text.lines() {
+                let _ = writeln!(stdout, "{prefix}{line}");
+            }
+        });
+    }
+
     /// Temporarily suspend progress rendering while emitting normal output.
     ///
     /// This helps prevent the progress UI from being corrupted by concurrent writes.
@@ -792,6 +924,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn streamed_output_forwards_complete_lines() {
+        let mut stream = StreamedOutput::new(
+            HookKey {
+                project_idx: 0,
+                hook_idx: 0,
+            },
+            String::new(),
+        );
+        assert_eq!(stream.push_chunk(b"first li"), b"");
+        assert_eq!(
+            stream.push_chunk(b"ne\nsecond\nthi"),
+            b"first line\nsecond\n"
+        );
+        assert_eq!(stream.push_chunk(b"rd"), b"");
+        assert_eq!(stream.finish(), b"third");
+        assert_eq!(stream.finish(), b"");
+    }
+
     #[test]
     fn hidden_summary_shows_total_and_result_breakdown() {
         let completed = CompletedBars {
@@ -0,0 +1,146 @@
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+use seahash::SeaHasher;
+use tracing::{debug, warn};
+
+use crate::hook::Hook;
+use crate::store::{CacheBucket, Store};
+
+/// Content hashes of the files that last passed a `cacheable` hook, persisted across runs.
+///
+/// Entries are keyed by the file path relative to the hook's working directory. The cache
+/// file itself is keyed by everything that can change the hook's result, so editing the hook
+/// config or updating its repo `rev` starts from an empty cache.
+pub(crate) struct ResultCache {
+    path: PathBuf,
+    passed: FxHashMap<String, u64>,
+}
+
+impl ResultCache {
+    pub(crate) fn load(store: &Store, hook: &Hook) -> Self {
+        let path = store
+            .cache_path(CacheBucket::Prek)
+            .join("results")
+            .join(hook_digest(hook));
+
+        let passed = match fs_err::read(&path) {
+            Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+                warn!(%err, path = %path.display(), "Ignoring invalid hook result cache");
+                FxHashMap::default()
+            }),
+            Err(_) => FxHashMap::default(),
+        };
+
+        Self { path, passed }
+    }
+
+    /// Remove the files whose content is unchanged since they last passed.
+    pub(crate) async fn retain_changed(&self, work_dir: &Path, filenames: &mut Vec<&Path>) {
+        if self.passed.is_empty() {
+            return;
+        }
+
+        let mut changed = Vec::with_capacity(filenames.len());
+        for filename in filenames.drain(..) {
+            let cached = match filename.to_str().and_then(|key| self.passed.get(key)) {
+                Some(&cached) => content_hash(&work_dir.join(filename)).await == Some(cached),
+                None => false,
+            };
+            if !cached {
+                changed.push(filename);
+            }
+        }
+        *filenames = changed;
+    }
+
+    /// Record the current content of the files as passing, and save the cache.
+    ///
+    /// Hashes are taken after the hook ran, so a fixer that rewrote a file records the
+    /// rewritten content.
+    pub(crate) async fn record_passed(&mut self, work_dir: &Path, filenames: &[&Path]) {
+        for filename in filenames {
+            let Some(key) = filename.to_str() else {
+                continue;
+            };
+            match content_hash(&work_dir.join(filename)).await {
+                Some(hash) => {
+                    self.passed.insert(key.to_string(), hash);
+                }
+                None => {
+                    self.passed.remove(key);
+                }
+            }
+        }
+
+        if let Err(err) = self.save() {
+            warn!(%err, path = %self.path.display(), "Failed to save hook result cache");
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        // Write atomically, so an interrupted run never leaves a truncated cache behind.
+        let parent = self.path.parent().expect("cache file must have a parent");
+        fs_err::create_dir_all(parent)?;
+        let mut file = tempfile::NamedTempFile::new_in(parent)?;
+        serde_json::to_writer(&mut file, &self.passed)?;
+        file.persist(&self.path)?;
+        debug!(
+            path = %self.path.display(),
+            "Saved {} passing files to hook result cache",
+            self.passed.len()
+        );
+        Ok(())
+    }
+}
+
+pub(super) async fn content_hash(path: &Path) -> Option<u64> {
+    let content = fs_err::tokio::read(path).await.ok()?;
+    let mut hasher = SeaHasher::new();
+    hasher.write(&content);
+    Some(hasher.finish())
+}
+
+/// Digest of everything that can change a hook's result for an unchanged file.
+///
+/// This includes the contents of local files named in the hook's `entry`, such as a `script`.
+fn hook_digest(hook: &Hook) -> String {
+    let mut hasher = SeaHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    hook.project().config_file().hash(&mut hasher);
+    hook.repo().to_string().hash(&mut hasher);
+    hook.id.hash(&mut hasher);
+    hook.entry.raw().hash(&mut hasher);
+    format!("{:?}", hook.entry.shell()).hash(&mut hasher);
+    hook.args.hash(&mut hasher);
+    hook.language.hash(&mut hasher);
+    format!("{:?}", hook.language_request).hash(&mut hasher);
+    hook.additional_dependencies
+        .iter()
+        .sorted()
+        .collect::<Vec<_>>()
+        .hash(&mut hasher);
+    hook.env
+        .iter()
+        .sorted()
+        .collect::<Vec<_>>()
+        .hash(&mut hasher);
+    format!("{:?}", hook.pass_filenames).hash(&mut hasher);
+    format!("{:?}", hook.stdin_from).hash(&mut hasher);
+    // Scripts of local hooks are not pinned by a `rev`, so editing one must change the digest.
+    if !hook.is_remote() {
+        for word in shlex::split(hook.entry.raw()).unwrap_or_default() {
+            if Path::new(&word).is_absolute() {
+                continue;
+            }
+            if let Ok(content) = fs_err::read(hook.work_dir().join(&word)) {
+                word.hash(&mut hasher);
+                hasher.write(&content);
+            }
+        }
+    }
+    hex::encode(hasher.finish().to_le_bytes())
+}
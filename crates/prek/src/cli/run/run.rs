@@ -1,9 +1,12 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fmt::Write as _;
-use std::io::Write as _;
+use std::io::{Read as _, Write as _};
 use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use futures::stream::{FuturesUnordered, StreamExt};
@@ -19,48 +22,133 @@ use tracing::{debug, error, trace};
 use unicode_width::UnicodeWidthStr;
 
 use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
+use crate::cli::run::annotations::github_annotations;
 use crate::cli::run::diff::DiffTracker;
+use crate::cli::run::explain;
 use crate::cli::run::filter::{RunInputMode, stage_uses_message_file_input};
 use crate::cli::run::install::{InstallCache, install_hooks};
+use crate::cli::run::junit::JunitReport;
 use crate::cli::run::keeper::WorkTreeKeeper;
+use crate::cli::run::notify::{DesktopNotifier, desktop_available, notify_run_complete};
+use crate::cli::run::outcomes::{Outcome, Outcomes};
+use crate::cli::run::plan::DryRunPlan;
+use crate::cli::run::result_cache::ResultCache;
+use crate::cli::run::timings::Timings;
 use crate::cli::run::{
     CollectOptions, FileTagCache, GroupFilters, HookFileFilter, HookRunReporter, ProjectFiles,
-    RunInput, Selectors, collect_run_input, project_status_marker,
+    RunInput, Selectors, collect_run_input, parse_nul_separated_files, project_status_marker,
 };
-use crate::cli::{ExitStatus, RunExtraArgs};
-use crate::config::{PassFilenames, Stage};
-use crate::fs::CWD;
+use crate::cli::{
+    DryRunFormat, ExitStatus, LanguageConcurrency, RunExtraArgs, RunOutputFormat, TimingsFormat,
+};
+use crate::config::{Language, PassFilenames, Stage, StdinFrom};
+use crate::fs::{CWD, LockedFile, Simplified};
 use crate::git::GIT_ROOT;
 use crate::hook::{Hook, InstalledHook};
 use crate::printer::Printer;
-use crate::run::{CONCURRENCY, USE_COLOR};
+use crate::run::{
+    CONCURRENCY, Finding, Findings, HookDeadline, HookRunContext, LanguageLimits, USE_COLOR,
+};
 use crate::store::Store;
-use crate::workspace::{HookInitFilters, Project, Workspace};
-use crate::{fs, git, hooks, warn_user};
+use crate::workspace::{self, HookInitFilters, Project, Workspace};
+use crate::{fs, git, hooks, process, warn_user, warnings};
+
+/// The settings of a single `prek run`, shared by `prek run`, `prek try-repo` and git hooks.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct RunSettings {
+    pub(crate) config: Option<PathBuf>,
+    pub(crate) includes: Vec<String>,
+    pub(crate) skips: Vec<String>,
+    pub(crate) groups: Vec<String>,
+    pub(crate) no_groups: Vec<String>,
+    pub(crate) hook_stage: Option<Stage>,
+    pub(crate) from_ref: Option<String>,
+    pub(crate) to_ref: Option<String>,
+    pub(crate) all_files: bool,
+    pub(crate) files: Vec<String>,
+    pub(crate) files_from_stdin: bool,
+    pub(crate) directories: Vec<String>,
+    pub(crate) last_commit: bool,
+    pub(crate) since: Option<String>,
+    pub(crate) include_deleted: bool,
+    pub(crate) include_untracked: bool,
+    pub(crate) show_diff_on_failure: bool,
+    pub(crate) show_diff: bool,
+    pub(crate) fail_fast: Option<bool>,
+    pub(crate) keep_going: bool,
+    pub(crate) dry_run: Option<DryRunFormat>,
+    pub(crate) explain: Option<String>,
+    pub(crate) allow_missing_config: bool,
+    pub(crate) relativize_paths: bool,
+    pub(crate) fail_on_warnings: bool,
+    pub(crate) output_format: Option<RunOutputFormat>,
+    pub(crate) notify: bool,
+    pub(crate) retries: Option<u32>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) language_concurrency: Vec<LanguageConcurrency>,
+    pub(crate) parallel_projects: bool,
+    pub(crate) report_junit: Option<PathBuf>,
+    pub(crate) modified_files: Option<PathBuf>,
+    pub(crate) timings: Option<TimingsFormat>,
+    pub(crate) timings_file: Option<PathBuf>,
+    pub(crate) no_cache: bool,
+    pub(crate) refresh: bool,
+    pub(crate) extra_args: RunExtraArgs,
+    pub(crate) verbose: bool,
+}
 
-#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn run(
     store: &Store,
-    config: Option<PathBuf>,
-    includes: Vec<String>,
-    skips: Vec<String>,
-    groups: Vec<String>,
-    no_groups: Vec<String>,
-    hook_stage: Option<Stage>,
-    from_ref: Option<String>,
-    to_ref: Option<String>,
-    all_files: bool,
-    files: Vec<String>,
-    directories: Vec<String>,
-    last_commit: bool,
-    show_diff_on_failure: bool,
-    fail_fast: Option<bool>,
-    dry_run: bool,
-    refresh: bool,
-    extra_args: RunExtraArgs,
-    verbose: bool,
+    settings: RunSettings,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    let RunSettings {
+        config,
+        includes,
+        skips,
+        groups,
+        no_groups,
+        hook_stage,
+        from_ref,
+        to_ref,
+        all_files,
+        files,
+        files_from_stdin,
+        directories,
+        last_commit,
+        since,
+        include_deleted,
+        include_untracked,
+        show_diff_on_failure,
+        show_diff,
+        fail_fast,
+        keep_going,
+        dry_run,
+        explain,
+        allow_missing_config,
+        relativize_paths,
+        fail_on_warnings,
+        output_format,
+        notify,
+        retries,
+        timeout,
+        language_concurrency,
+        parallel_projects,
+        report_junit,
+        modified_files,
+        timings,
+        timings_file,
+        no_cache,
+        refresh,
+        mut extra_args,
+        verbose,
+    } = settings;
+
+    // `--keep-going` overrides `fail_fast` of the config; hook-level `fail_fast` is ignored below.
+    let fail_fast = if keep_going { Some(false) } else { fail_fast };
+    let result_cache = !no_cache && !EnvVars::is_set(EnvVars::PREK_NO_RESULT_CACHE);
+
     // Convert `--last-commit` to `HEAD~1..HEAD`
     let (from_ref, to_ref) = if last_commit {
         (Some("HEAD~1".to_string()), Some("HEAD".to_string()))
@@ -68,6 +156,32 @@ pub(crate) async fn run(
         (from_ref, to_ref)
     };
 
+    // The working directory changes to the workspace root before hooks run.
+    let report_junit = report_junit.map(|path| CWD.join(path));
+    let modified_files = modified_files.map(|path| CWD.join(path));
+    let timings_file = timings_file.map(|path| CWD.join(path));
+
+    // A commit message file runs the `commit-msg` hooks unless another message stage is given.
+    let hook_stage = if let Some(filename) = extra_args.commit_msg_filename.as_mut() {
+        let stage = hook_stage.unwrap_or(Stage::CommitMsg);
+        if !stage_uses_message_file_input(stage) {
+            anyhow::bail!(
+                "`--commit-msg-file` can only be used with `--stage commit-msg` or `--stage prepare-commit-msg`, not `--stage {stage}`"
+            );
+        }
+        let path = CWD.join(&*filename);
+        if !path.is_file() {
+            anyhow::bail!(
+                "Commit message file `{}` does not exist",
+                path.user_display().cyan()
+            );
+        }
+        *filename = path.to_string_lossy().into_owned();
+        Some(stage)
+    } else {
+        hook_stage
+    };
+
     // Prevent recursive post-checkout hooks.
     if hook_stage == Some(Stage::PostCheckout)
         && EnvVars::is_set(EnvVars::PREK_INTERNAL__SKIP_POST_CHECKOUT)
@@ -78,24 +192,77 @@ pub(crate) async fn run(
     // Ensure we are in a git repository.
     LazyLock::force(&GIT_ROOT).as_ref()?;
 
-    let should_stash = !all_files && files.is_empty() && directories.is_empty();
+    // Convert `--since <ref>` to `<merge-base>..HEAD`
+    let (from_ref, to_ref) = if let Some(since) = since {
+        let merge_base = git::get_merge_base("HEAD", &since)
+            .await
+            .with_context(|| format!("Failed to resolve `--since {since}`"))?;
+        (Some(merge_base), Some("HEAD".to_string()))
+    } else {
+        (from_ref, to_ref)
+    };
+
+    let files = if files_from_stdin {
+        let mut input = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut input)
+            .context("Failed to read file list from stdin")?;
+        parse_nul_separated_files(&input)?
+    } else {
+        files
+    };
+
+    let should_stash =
+        !all_files && !files_from_stdin && files.is_empty() && directories.is_empty();
 
     // Check if we have unresolved merge conflict files and fail fast.
     if should_stash && git::has_unmerged_paths().await? {
         anyhow::bail!("You have unmerged paths. Resolve them before running prek");
     }
 
-    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let allow_missing_config =
+        allow_missing_config || EnvVars::is_set(EnvVars::PREK_ALLOW_NO_CONFIG);
+    if allow_missing_config
+        && let Some(config) = &config
+        && !config.try_exists()?
+    {
+        debug!(
+            "Config file `{}` not found, nothing to run",
+            config.display()
+        );
+        return Ok(ExitStatus::Success);
+    }
+
+    let workspace_root = match Workspace::find_root(config.as_deref(), &CWD) {
+        Err(workspace::Error::MissingConfigFile) if allow_missing_config => {
+            debug!("No config file found, nothing to run");
+            return Ok(ExitStatus::Success);
+        }
+        result => result?,
+    };
     let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
     let group_filters = GroupFilters::parse(&groups, &no_groups)?;
     let has_group_filters = group_filters.has_filters();
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), refresh)?;
+    let workspace =
+        match Workspace::discover(store, workspace_root, config, Some(&selectors), refresh) {
+            Err(workspace::Error::MissingConfigFile) if allow_missing_config => {
+                debug!("No config file found, nothing to run");
+                return Ok(ExitStatus::Success);
+            }
+            result => result?,
+        };
 
     if should_stash {
         workspace.check_configs_staged().await?;
     }
 
+    let language_limits = LanguageLimits::new(resolve_language_concurrency(
+        &workspace,
+        &language_concurrency,
+    ));
+
     let reporter = HookInitReporter::new(printer);
+    let setup_start = std::time::Instant::now();
     let hooks = {
         let _lock = store.lock_async().await?;
         store.track_configs(
@@ -108,12 +275,15 @@ pub(crate) async fn run(
         workspace
             .init_hooks(
                 store,
-                HookInitFilters::new(Some(&selectors), Some(&group_filters)),
+                // Without an explicit stage, hooks run as `pre-commit` hooks by default.
+                HookInitFilters::new(Some(&selectors), Some(&group_filters))
+                    .with_stage(hook_stage.unwrap_or(Stage::PreCommit)),
                 Some(&reporter),
             )
             .await
             .context("Failed to init hooks")?
     };
+    let setup_duration = setup_start.elapsed();
     let selected_hooks: Vec<_> = hooks
         .into_iter()
         .filter(|h| selectors.matches_hook(h))
@@ -145,22 +315,47 @@ pub(crate) async fn run(
 
     let (stage_filter, input_mode) =
         infer_stage_and_input_mode(hook_stage, has_group_filters, &selected_hooks, &selectors);
-    let filtered_hooks: Vec<Arc<Hook>> = if let Some(stage_filter) = stage_filter {
-        selected_hooks
-            .iter()
-            .filter(|h| h.stages.contains(stage_filter))
-            .cloned()
-            .collect()
-    } else {
-        // Group selection without an explicit stage uses normal file input, so
-        // hooks that can only consume Git message files cannot run correctly.
-        selected_hooks
-            .into_iter()
-            .filter(|hook| !uses_only_message_file_input(hook))
-            .collect()
-    };
+    let (filtered_hooks, stage_mismatches): (Vec<Arc<Hook>>, Vec<Arc<Hook>>) =
+        if let Some(stage_filter) = stage_filter {
+            // Without an explicit `--stage`, hooks named by id run whatever their stages,
+            // as long as they can use normal file input.
+            selected_hooks.into_iter().partition(|h| {
+                h.stages.contains(stage_filter)
+                    || (hook_stage.is_none()
+                        && selectors.names_hook(h)
+                        && !uses_only_message_file_input(h))
+            })
+        } else {
+            // Group selection without an explicit stage uses normal file input, so
+            // hooks that can only consume Git message files cannot run correctly.
+            selected_hooks
+                .into_iter()
+                .partition(|hook| !uses_only_message_file_input(hook))
+        };
+    if let Some(hook_id) = &explain {
+        let found =
+            explain::explain_stage_mismatches(hook_id, &stage_mismatches, stage_filter, printer)?;
+        if !filtered_hooks.iter().any(|hook| hook.id == *hook_id) {
+            if found {
+                return Ok(ExitStatus::Success);
+            }
+            writeln!(
+                printer.stderr(),
+                "{}: No hook with id `{}` found after filtering with the given selectors",
+                "error".red().bold(),
+                hook_id.cyan(),
+            )?;
+            return Ok(ExitStatus::Failure);
+        }
+    }
+
+    let dry_run_plan = (dry_run == Some(DryRunFormat::Json))
+        .then(|| DryRunPlan::new(stage_mismatches.iter().map(AsRef::as_ref)));
 
     if filtered_hooks.is_empty() {
+        if let Some(plan) = dry_run_plan {
+            plan.render(printer)?;
+        }
         if let Some(stage) = stage_filter {
             debug!("No hooks found for stage {stage} after filtering, exit early");
         } else {
@@ -195,8 +390,11 @@ pub(crate) async fn run(
             input_mode,
             from_ref,
             to_ref,
+            include_deleted,
+            include_untracked,
             all_files,
             files,
+            files_from_stdin,
             directories,
             commit_msg_filename: extra_args.commit_msg_filename,
         },
@@ -217,7 +415,16 @@ pub(crate) async fn run(
     } else {
         FileTagCache::default()
     };
-    let installed_hooks = ensure_hooks_installed(
+    if let Some(hook_id) = &explain {
+        let hooks = filtered_hooks
+            .iter()
+            .filter(|hook| hook.id == *hook_id)
+            .collect::<Vec<_>>();
+        explain::explain_files(&workspace, &input, &tag_cache, &hooks, printer)?;
+        return Ok(ExitStatus::Success);
+    }
+    let install_start = std::time::Instant::now();
+    let (installed_hooks, _env_locks) = ensure_hooks_installed(
         store,
         printer,
         &workspace,
@@ -226,21 +433,75 @@ pub(crate) async fn run(
         &filtered_hooks,
     )
     .await?;
+    let install_duration = install_start.elapsed();
 
-    run_hooks(
+    let relativizer = if relativize_paths {
+        Some(PathRelativizer::new(store.path(), GIT_ROOT.as_ref()?))
+    } else {
+        None
+    };
+
+    // Emit GitHub Actions annotations when running as a GitHub Actions step, unless told otherwise.
+    let output_format = output_format.unwrap_or_else(|| {
+        if EnvVars::var(EnvVars::GITHUB_ACTIONS).is_ok_and(|value| value == "true") {
+            RunOutputFormat::Github
+        } else {
+            RunOutputFormat::Text
+        }
+    });
+
+    // The JSON plan replaces the usual hook output.
+    let hooks_printer = if dry_run_plan.is_some() && printer != Printer::Silent {
+        Printer::Quiet
+    } else {
+        printer
+    };
+    let summary = run_hooks(
         &workspace,
         &input,
         &tag_cache,
         &installed_hooks,
         store,
         show_diff_on_failure,
+        show_diff,
+        result_cache,
+        language_limits,
         fail_fast,
-        dry_run,
+        keep_going,
+        dry_run.is_some(),
         should_stash,
         verbose,
-        printer,
+        retries,
+        timeout,
+        parallel_projects,
+        report_junit,
+        modified_files,
+        (timings.is_some() || timings_file.is_some())
+            .then(|| Timings::new(timings, timings_file, setup_duration, install_duration)),
+        dry_run_plan,
+        relativizer,
+        output_format,
+        hooks_printer,
     )
-    .await
+    .await?;
+    let status = summary.status();
+
+    if notify && desktop_available() {
+        notify_run_complete(&DesktopNotifier, summary.hooks, summary.failed);
+    }
+
+    let warnings = warnings::count();
+    if fail_on_warnings && warnings > 0 && status == ExitStatus::Success {
+        writeln!(
+            printer.stderr(),
+            "{}: prek emitted {warnings} warning{} and `--fail-on-warnings` is set",
+            "error".red().bold(),
+            if warnings == 1 { "" } else { "s" },
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    Ok(status)
 }
 
 fn infer_stage_and_input_mode(
@@ -271,6 +532,30 @@ fn infer_stage_and_input_mode(
     (Some(stage), RunInputMode::from(stage))
 }
 
+/// Merge `default_language_concurrency` of every project, keeping the lowest limit for each
+/// language, then apply `--language-concurrency` on top.
+fn resolve_language_concurrency(
+    workspace: &Workspace,
+    overrides: &[LanguageConcurrency],
+) -> FxHashMap<Language, usize> {
+    let mut limits: FxHashMap<Language, usize> = FxHashMap::default();
+    for project in workspace.projects() {
+        let Some(languages) = &project.config().default_language_concurrency else {
+            continue;
+        };
+        for (language, limit) in languages {
+            limits
+                .entry(*language)
+                .and_modify(|current| *current = (*current).min(limit.get()))
+                .or_insert(limit.get());
+        }
+    }
+    for LanguageConcurrency { language, limit } in overrides {
+        limits.insert(*language, *limit);
+    }
+    limits
+}
+
 fn uses_only_message_file_input(hook: &Hook) -> bool {
     !hook.stages.is_empty() && hook.stages.iter().all(stage_uses_message_file_input)
 }
@@ -328,7 +613,8 @@ fn set_env_vars(from_ref: Option<&String>, to_ref: Option<&String>, args: &RunEx
 ///
 /// Hooks that do not need an environment are returned as-is. Hooks that need an
 /// environment first try the install cache; only cache misses are filtered
-/// against the run input before installation.
+/// against the run input before installation. The returned locks mark the environments
+/// as in use and must be held while the hooks run.
 async fn ensure_hooks_installed<'paths>(
     store: &Store,
     printer: Printer,
@@ -336,7 +622,7 @@ async fn ensure_hooks_installed<'paths>(
     input: &'paths RunInput,
     tag_cache: &FileTagCache<'paths>,
     hooks: &[Arc<Hook>],
-) -> Result<Vec<InstalledHook>> {
+) -> Result<(Vec<InstalledHook>, Vec<LockedFile>)> {
     let env_hooks = hooks
         .iter()
         .filter(|hook| hook.needs_install_env())
@@ -344,10 +630,11 @@ async fn ensure_hooks_installed<'paths>(
         .collect::<Vec<_>>();
 
     if env_hooks.is_empty() {
-        return Ok(hooks
+        let installed_hooks = hooks
             .iter()
             .map(|hook| InstalledHook::NoNeedInstall(hook.clone()))
-            .collect());
+            .collect();
+        return Ok((installed_hooks, Vec::new()));
     }
 
     let _lock = store.lock_async().await?;
@@ -378,14 +665,29 @@ async fn ensure_hooks_installed<'paths>(
         }
     }
 
-    Ok(hooks
+    let installed_hooks = hooks
         .iter()
         .map(|hook| {
             installed_by_hook
                 .remove(&hook_key(hook))
                 .unwrap_or_else(|| InstalledHook::NoNeedInstall(hook.clone()))
         })
-        .collect())
+        .collect::<Vec<_>>();
+
+    // Taken while still holding the store lock, so `cache gc` cannot sweep these envs in between.
+    let mut env_locks = Vec::new();
+    let mut locked_envs = FxHashSet::default();
+    for hook in &installed_hooks {
+        if hook
+            .env_path()
+            .is_some_and(|path| locked_envs.insert(path.to_path_buf()))
+            && let Some(lock) = hook.lock_env_in_use()?
+        {
+            env_locks.push(lock);
+        }
+    }
+
+    Ok((installed_hooks, env_locks))
 }
 
 /// Return the missing environment hooks that should actually be installed.
@@ -500,12 +802,25 @@ async fn run_hooks<'paths>(
     hooks: &[InstalledHook],
     store: &Store,
     show_diff_on_failure: bool,
+    show_diff: bool,
+    result_cache: bool,
+    language_limits: LanguageLimits,
     fail_fast: Option<bool>,
+    keep_going: bool,
     dry_run: bool,
     worktree_cleaned: bool,
     verbose: bool,
+    retries: Option<u32>,
+    timeout: Option<u64>,
+    parallel_projects: bool,
+    report_junit: Option<PathBuf>,
+    modified_files: Option<PathBuf>,
+    timings: Option<Timings>,
+    dry_run_plan: Option<DryRunPlan>,
+    relativizer: Option<PathRelativizer>,
+    output_format: RunOutputFormat,
     printer: Printer,
-) -> Result<ExitStatus> {
+) -> Result<RunSummary> {
     debug_assert!(!hooks.is_empty(), "No hooks to run");
 
     // Group hooks by project to run them in order of their depth in the workspace.
@@ -524,11 +839,50 @@ async fn run_hooks<'paths>(
     let mut session = HookRunSession::new(
         hooks,
         store,
+        show_diff,
+        result_cache,
+        language_limits,
         dry_run,
         verbose,
+        retries,
+        timeout,
         show_project_headers,
+        report_junit.map(JunitReport::new),
+        modified_files,
+        timings,
+        keep_going.then(Outcomes::default),
+        dry_run_plan,
+        relativizer,
+        output_format,
         printer,
     );
+    if parallel_projects {
+        let project_runs = workspace
+            .all_projects()
+            .iter()
+            .map(|project| {
+                project_to_hooks
+                    .remove(project.as_ref())
+                    .map(|hooks| ProjectRun::new(project, hooks, fail_fast, keep_going))
+            })
+            .collect();
+        let project_results = session
+            .run_projects_nested_first(
+                workspace.all_projects(),
+                project_runs,
+                input,
+                tag_cache,
+                worktree_cleaned,
+            )
+            .await?;
+
+        for project_result in project_results {
+            session.finish_project_run(project_result, show_project_headers)?;
+        }
+
+        return session.finish(workspace, show_diff_on_failure).await;
+    }
+
     let mut consumed_files = FxHashSet::default();
 
     for projects in ProjectDepthGroups::new(workspace.all_projects()) {
@@ -537,7 +891,7 @@ async fn run_hooks<'paths>(
         let mut project_runs = Vec::new();
 
         for project in projects {
-            let Some(mut hooks) = project_to_hooks.remove(project.as_ref()) else {
+            let Some(hooks) = project_to_hooks.remove(project.as_ref()) else {
                 if let RunInput::Files(files) = input {
                     ProjectFiles::consume_for_project(
                         files.iter(),
@@ -549,17 +903,7 @@ async fn run_hooks<'paths>(
                 continue;
             };
 
-            // Sort hooks by priority (lower number means higher priority).
-            // If two hooks have the same priority, preserve their original order from the config.
-            hooks.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.idx.cmp(&b.idx)));
-
-            project_runs.push(ProjectRun {
-                project,
-                project_fail_fast: fail_fast
-                    .or_else(|| project.config().fail_fast)
-                    .unwrap_or(false),
-                groups: PriorityGroups::new(hooks).collect(),
-            });
+            project_runs.push(ProjectRun::new(project, hooks, fail_fast, keep_going));
         }
 
         let project_results = session
@@ -622,13 +966,39 @@ impl<'a> Iterator for ProjectDepthGroups<'a> {
 struct ProjectRun<'project> {
     project: &'project Project,
     project_fail_fast: bool,
+    /// Ignore `fail_fast` of the project and its hooks.
+    keep_going: bool,
     groups: Vec<Vec<InstalledHook>>,
 }
 
+impl<'project> ProjectRun<'project> {
+    fn new(
+        project: &'project Project,
+        mut hooks: Vec<InstalledHook>,
+        fail_fast: Option<bool>,
+        keep_going: bool,
+    ) -> Self {
+        // Sort hooks by priority (lower number means higher priority).
+        // If two hooks have the same priority, preserve their original order from the config.
+        hooks.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.idx.cmp(&b.idx)));
+
+        Self {
+            project,
+            project_fail_fast: fail_fast
+                .or_else(|| project.config().fail_fast)
+                .unwrap_or(false),
+            keep_going,
+            groups: PriorityGroups::new(hooks).collect(),
+        }
+    }
+}
+
 struct ProjectRunResult<'project, 'paths> {
     project: &'project Project,
     groups: Vec<ProjectGroupRunResult>,
     consumed_files: FxHashSet<&'paths Path>,
+    /// Files modified by the project's hooks, when collected for `--modified-files`.
+    modified_files: BTreeSet<PathBuf>,
     stop_after_level: bool,
 }
 
@@ -667,36 +1037,86 @@ impl ProjectGroupRunResult {
 #[allow(clippy::struct_excessive_bools)]
 struct HookRunSession<'a> {
     store: &'a Store,
-    reporter: HookRunReporter,
+    context: HookRunContext,
     status_printer: StatusPrinter,
     printer: Printer,
     dry_run: bool,
     verbose: bool,
-    success: bool,
+    /// Overrides the `retries` of every hook when set.
+    retries: Option<u32>,
+    /// The `timeout` of hooks that don't set one.
+    timeout: Option<u64>,
+    relativizer: Option<PathRelativizer>,
+    output_format: RunOutputFormat,
+    junit_report: Option<JunitReport>,
+    /// Where to write the files modified by hooks, if requested.
+    modified_files_output: Option<PathBuf>,
+    /// Files modified by hooks, relative to the git root.
+    modified_files: BTreeSet<PathBuf>,
+    timings: Option<Timings>,
+    outcomes: Option<Outcomes>,
+    dry_run_plan: Option<DryRunPlan>,
+    hooks_run: usize,
+    hooks_failed: usize,
     file_modified: bool,
 }
 
 impl<'a> HookRunSession<'a> {
+    #[allow(clippy::fn_params_excessive_bools)]
     fn new(
         hooks: &[InstalledHook],
         store: &'a Store,
+        show_diff: bool,
+        result_cache: bool,
+        language_limits: LanguageLimits,
         dry_run: bool,
         verbose: bool,
+        retries: Option<u32>,
+        timeout: Option<u64>,
         show_project_headers: bool,
+        junit_report: Option<JunitReport>,
+        modified_files_output: Option<PathBuf>,
+        timings: Option<Timings>,
+        outcomes: Option<Outcomes>,
+        dry_run_plan: Option<DryRunPlan>,
+        relativizer: Option<PathRelativizer>,
+        output_format: RunOutputFormat,
         printer: Printer,
     ) -> Self {
         let status_printer = StatusPrinter::for_hooks(hooks, printer);
-        let reporter =
+        let mut reporter =
             HookRunReporter::new(printer, status_printer.bar_len(), show_project_headers);
+        // Stream output live with `--verbose`, which shows every attempt of a retried hook as
+        // well; buffered output keeps the results of concurrent hooks apart otherwise.
+        if verbose && !dry_run {
+            reporter = reporter.with_streamed_output(hooks.len() > 1);
+        }
 
         Self {
             store,
-            reporter,
+            context: HookRunContext {
+                reporter,
+                show_diff,
+                result_cache,
+                language_limits,
+                findings: Findings::default(),
+            },
             status_printer,
             printer,
             dry_run,
             verbose,
-            success: true,
+            retries,
+            timeout,
+            relativizer,
+            output_format,
+            junit_report,
+            modified_files_output,
+            modified_files: BTreeSet::new(),
+            timings,
+            outcomes,
+            dry_run_plan,
+            hooks_run: 0,
+            hooks_failed: 0,
             file_modified: false,
         }
     }
@@ -711,7 +1131,7 @@ impl<'a> HookRunSession<'a> {
             return Ok(());
         }
 
-        self.reporter.suspend(|| {
+        self.context.reporter.suspend(|| {
             writeln!(
                 self.status_printer.printer().stdout(),
                 "{} {}",
@@ -748,7 +1168,9 @@ impl<'a> HookRunSession<'a> {
                     )
                     .await;
                 if let Ok(result) = &result {
-                    self.reporter.on_project_complete(project, result.failed());
+                    self.context
+                        .reporter
+                        .on_project_complete(project, result.failed());
                 }
                 result.map(|result| (idx, result))
             });
@@ -763,6 +1185,101 @@ impl<'a> HookRunSession<'a> {
         Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
+    /// Runs every project as soon as all projects nested inside its directory have finished.
+    ///
+    /// Nested projects see the same files as their parent (or consume them, for orphan
+    /// projects), so they keep running before it. Projects in unrelated directories have
+    /// disjoint files and run concurrently, sharing one semaphore. Results are returned in
+    /// the order of `projects`, skipping projects that never started after a fail-fast stop.
+    async fn run_projects_nested_first<'project, 'paths>(
+        &self,
+        projects: &'project [Arc<Project>],
+        mut project_runs: Vec<Option<ProjectRun<'project>>>,
+        input: &'paths RunInput,
+        tag_cache: &FileTagCache<'paths>,
+        worktree_cleaned: bool,
+    ) -> Result<Vec<ProjectRunResult<'project, 'paths>>> {
+        let is_nested = |inner: usize, outer: usize| {
+            inner != outer && projects[inner].path().starts_with(projects[outer].path())
+        };
+
+        let semaphore = Rc::new(Semaphore::new(*CONCURRENCY));
+        let mut pending: Vec<usize> = (0..projects.len()).collect();
+        let mut unfinished = vec![true; projects.len()];
+        let mut modified_files = vec![false; projects.len()];
+        let mut results: Vec<Option<ProjectRunResult>> = std::iter::repeat_with(|| None)
+            .take(projects.len())
+            .collect();
+        let mut consumed_files = FxHashSet::default();
+        let mut runs = FuturesUnordered::new();
+        let mut stop = false;
+
+        loop {
+            while !stop
+                && let Some(pos) = pending.iter().position(|&idx| {
+                    !(0..projects.len()).any(|other| unfinished[other] && is_nested(other, idx))
+                })
+            {
+                let idx = pending.remove(pos);
+                let project = projects[idx].as_ref();
+
+                let Some(project_run) = project_runs[idx].take() else {
+                    if let RunInput::Files(files) = input {
+                        let mut project_consumed_files = FxHashSet::default();
+                        ProjectFiles::consume_for_project(
+                            files.iter(),
+                            project,
+                            Some(&consumed_files),
+                            &mut project_consumed_files,
+                        );
+                        consumed_files.extend(project_consumed_files);
+                    }
+                    unfinished[idx] = false;
+                    continue;
+                };
+
+                // Only nested projects run inside this project's directory, so their changes
+                // are the only ones that can leave a diff behind in it.
+                let clean_baseline = worktree_cleaned
+                    && !(0..projects.len())
+                        .any(|other| modified_files[other] && is_nested(other, idx));
+                let consumed_files = consumed_files.clone();
+                let semaphore = Rc::clone(&semaphore);
+                runs.push(async move {
+                    let result = self
+                        .run_project(
+                            project_run,
+                            input,
+                            &consumed_files,
+                            tag_cache,
+                            clean_baseline,
+                            semaphore,
+                        )
+                        .await;
+                    if let Ok(result) = &result {
+                        self.context
+                            .reporter
+                            .on_project_complete(project, result.failed());
+                    }
+                    result.map(|result| (idx, result))
+                });
+            }
+
+            let Some(result) = runs.next().await else {
+                break;
+            };
+            let (idx, result) = result?;
+
+            unfinished[idx] = false;
+            modified_files[idx] = result.groups.iter().any(|group| group.modified_files);
+            consumed_files.extend(result.consumed_files.iter().copied());
+            stop |= result.stop_after_level;
+            results[idx] = Some(result);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
     async fn run_project<'project, 'paths>(
         &self,
         project_run: ProjectRun<'project>,
@@ -792,7 +1309,8 @@ impl<'a> HookRunSession<'a> {
             DiffTracker::clean_baseline(project_run.project.path())
         } else {
             DiffTracker::unknown_baseline(project_run.project.path())
-        };
+        }
+        .track_modified_files(self.modified_files_output.is_some());
 
         let mut groups = Vec::new();
         let mut stop_after_level = false;
@@ -824,7 +1342,8 @@ impl<'a> HookRunSession<'a> {
                 modified_files: group_modified_files,
             };
             self.update_live_priority_group(&group);
-            stop_after_level = group.should_stop_project(project_run.project_fail_fast);
+            stop_after_level =
+                !project_run.keep_going && group.should_stop_project(project_run.project_fail_fast);
             groups.push(group);
 
             if stop_after_level {
@@ -836,6 +1355,7 @@ impl<'a> HookRunSession<'a> {
             project: project_run.project,
             groups,
             consumed_files: project_consumed_files,
+            modified_files: diff_tracker.into_modified_files(),
             stop_after_level,
         })
     }
@@ -854,23 +1374,39 @@ impl<'a> HookRunSession<'a> {
             group_hooks.iter().map(|hook| &hook.id).collect::<Vec<_>>()
         );
 
-        let mut runs = FuturesUnordered::new();
-        for hook in group_hooks {
-            runs.push(run_hook(
+        // `require_serial` and `exclusive` hooks run alone: the rest of the group runs
+        // concurrently first, then each of them runs by itself.
+        let (serial_hooks, concurrent_hooks): (Vec<_>, Vec<_>) = group_hooks
+            .into_iter()
+            .partition(|hook| hook.require_serial || hook.exclusive);
+
+        let run = |hook| {
+            run_hook(
                 hook,
                 project_input,
                 tag_cache,
                 self.store,
                 self.dry_run,
-                &self.reporter,
+                self.verbose,
+                self.retries,
+                self.timeout,
+                &self.context,
                 Rc::clone(&semaphore),
-            ));
-        }
+            )
+        };
 
+        let mut runs = concurrent_hooks
+            .into_iter()
+            .map(run)
+            .collect::<FuturesUnordered<_>>();
         let mut group_results = Vec::new();
         while let Some(result) = runs.next().await {
             group_results.push(result?);
         }
+
+        for hook in serial_hooks {
+            group_results.push(run(hook).await?);
+        }
         Ok(group_results)
     }
 
@@ -885,7 +1421,9 @@ impl<'a> HookRunSession<'a> {
             };
 
             if !status.is_skipped() {
-                self.reporter.on_run_result(&result.hook, status.as_bool());
+                self.context
+                    .reporter
+                    .on_run_result(&result.hook, status.as_bool());
             }
         }
     }
@@ -901,6 +1439,7 @@ impl<'a> HookRunSession<'a> {
             show_project_headers,
         )?;
         let hook_prefix = if show_project_headers { "  " } else { "" };
+        self.modified_files.extend(project_result.modified_files);
 
         for group in project_result.groups {
             self.finish_priority_group(group, hook_prefix)?;
@@ -923,8 +1462,9 @@ impl<'a> HookRunSession<'a> {
 
         self.file_modified |= modified_files;
 
-        self.reporter.clear_completed();
-        self.reporter
+        self.context.reporter.clear_completed();
+        self.context
+            .reporter
             .suspend(|| self.render_priority_group(&results, modified_files, hook_prefix))?;
 
         for RunResult { status, .. } in &results {
@@ -933,7 +1473,43 @@ impl<'a> HookRunSession<'a> {
             } else {
                 status.as_bool()
             };
-            self.success &= ok;
+            self.hooks_run += 1;
+            if !ok {
+                self.hooks_failed += 1;
+            }
+        }
+
+        if let Some(report) = &mut self.junit_report {
+            record_junit_results(report, &results, modified_files);
+        }
+
+        if let Some(outcomes) = &mut self.outcomes {
+            for result in &results {
+                let outcome = if result.status.is_skipped() {
+                    Outcome::Skipped
+                } else if modified_files || !result.status.as_bool() {
+                    Outcome::Failed
+                } else {
+                    Outcome::Passed
+                };
+                outcomes.record(&result.hook, outcome);
+            }
+        }
+
+        if let Some(timings) = &mut self.timings {
+            for result in results.iter().filter(|result| !result.status.is_skipped()) {
+                timings.record(&result.hook, result.files, result.duration);
+            }
+        }
+
+        if let Some(plan) = &mut self.dry_run_plan {
+            for result in &results {
+                if result.status == RunStatus::NoFiles {
+                    plan.no_files(&result.hook);
+                } else {
+                    plan.would_run(&result.hook, result.files);
+                }
+            }
         }
 
         Ok(())
@@ -993,7 +1569,7 @@ impl<'a> HookRunSession<'a> {
             self.status_printer
                 .write(&result.hook.name, &prefix, status)?;
 
-            if matches!(status, RunStatus::NoFiles) {
+            if matches!(status, RunStatus::NoFiles | RunStatus::Cached) {
                 continue;
             }
 
@@ -1002,7 +1578,10 @@ impl<'a> HookRunSession<'a> {
                 _ => self.printer.stdout(),
             };
 
-            if self.verbose || result.hook.verbose || status == RunStatus::Failed {
+            if self.verbose
+                || result.hook.verbose
+                || matches!(status, RunStatus::Failed | RunStatus::DryRun)
+            {
                 writeln!(
                     stdout,
                     "{detail_prefix}{}",
@@ -1031,7 +1610,7 @@ impl<'a> HookRunSession<'a> {
                 }
 
                 let output = result.output.trim_ascii();
-                if !output.is_empty() {
+                if !output.is_empty() && !self.context.reporter.streamed_output(&result.hook) {
                     if let Some(file) = result.hook.log_file.as_deref() {
                         let mut file = fs_err::OpenOptions::new()
                             .create(true)
@@ -1046,6 +1625,10 @@ impl<'a> HookRunSession<'a> {
                             writeln!(stdout)?;
                         }
                         let text = String::from_utf8_lossy(output);
+                        let text = match &self.relativizer {
+                            Some(relativizer) => relativizer.apply(&text),
+                            None => text,
+                        };
                         for line in text.lines() {
                             if line.is_empty() {
                                 if show_group_ui {
@@ -1062,19 +1645,33 @@ impl<'a> HookRunSession<'a> {
                     }
                 }
             }
+
+            if self.output_format == RunOutputFormat::Github && status == RunStatus::Failed {
+                let output = String::from_utf8_lossy(&result.output);
+                for command in
+                    github_annotations(&result.hook, &output, &result.findings, GIT_ROOT.as_ref()?)
+                {
+                    writeln!(stdout, "{command}")?;
+                }
+            }
         }
 
         Ok(())
     }
 
     async fn finish(
-        &self,
+        &mut self,
         workspace: &Workspace,
         show_diff_on_failure: bool,
-    ) -> Result<ExitStatus> {
-        self.reporter.on_complete();
+    ) -> Result<RunSummary> {
+        self.context.reporter.on_complete();
 
-        if !self.success && show_diff_on_failure && self.file_modified {
+        if let Some(report) = &self.junit_report {
+            report.write()?;
+            debug!("Wrote JUnit report to `{}`", report.path().display());
+        }
+
+        if self.hooks_failed > 0 && show_diff_on_failure && self.file_modified {
             if EnvVars::is_under_ci() {
                 writeln!(
                     self.printer.stdout(),
@@ -1113,10 +1710,82 @@ impl<'a> HookRunSession<'a> {
                 .await?;
         }
 
-        if self.success {
-            Ok(ExitStatus::Success)
+        if let Some(path) = &self.modified_files_output {
+            let mut content = String::new();
+            for file in &self.modified_files {
+                content.push_str(&file.to_string_lossy());
+                content.push('\n');
+            }
+            fs_err::write(path, content)?;
+        }
+
+        if let Some(outcomes) = self.outcomes.take() {
+            outcomes.render(self.printer)?;
+        }
+
+        if let Some(timings) = self.timings.take() {
+            timings.render(self.printer)?;
+        }
+
+        if let Some(plan) = self.dry_run_plan.take() {
+            plan.render(self.printer)?;
+        }
+
+        Ok(RunSummary {
+            hooks: self.hooks_run,
+            failed: self.hooks_failed,
+        })
+    }
+}
+
+fn record_junit_results(report: &mut JunitReport, results: &[RunResult], modified_files: bool) {
+    let single_hook_modified_files = results.len() == 1 && modified_files;
+
+    for result in results {
+        let name = &result.hook.id;
+        let classname = result.hook.project().to_string();
+        match result.status {
+            RunStatus::NoFiles => report.skipped(name, &classname, "no files to check"),
+            RunStatus::Cached => report.skipped(name, &classname, "cached"),
+            RunStatus::DryRun => report.skipped(name, &classname, "dry run"),
+            RunStatus::Failed => report.failed(
+                name,
+                &classname,
+                result.duration,
+                format!("exit code: {}", result.exit_status),
+                &result.output,
+            ),
+            RunStatus::Success if modified_files => {
+                let message = if single_hook_modified_files {
+                    "files were modified by this hook"
+                } else {
+                    "files were modified by hooks in the same priority group"
+                };
+                report.failed(
+                    name,
+                    &classname,
+                    result.duration,
+                    message.to_string(),
+                    &result.output,
+                );
+            }
+            RunStatus::Success => report.passed(name, &classname, result.duration),
+        }
+    }
+}
+
+/// How many of the hooks that ran failed.
+struct RunSummary {
+    hooks: usize,
+    failed: usize,
+}
+
+impl RunSummary {
+    fn status(&self) -> ExitStatus {
+        if self.failed == 0 {
+            ExitStatus::Success
         } else {
-            Ok(ExitStatus::Failure)
+            ExitStatus::Failure
         }
     }
 }
@@ -1194,6 +1863,10 @@ impl<'a> ProjectHookInput<'a> {
 
     fn run_input_for_hook(&self, hook: &Hook, tag_cache: &FileTagCache<'a>) -> HookRunInput<'a> {
         match self {
+            Self::Files(project_files) if hook.stdin_from == StdinFrom::Files => {
+                // Matched files are fed on stdin, so they are needed even without arguments.
+                HookRunInput::with_filenames(project_files.matching_filenames(hook, tag_cache))
+            }
             Self::Files(project_files) => match hook.pass_filenames {
                 // Always-run hooks without filename arguments run regardless of file matches.
                 PassFilenames::None if hook.always_run => HookRunInput::without_filenames(true),
@@ -1288,15 +1961,85 @@ enum RunStatus {
     Failed,
     DryRun,
     NoFiles,
+    /// Every matched file passed a `cacheable` hook before and is unchanged since.
+    Cached,
 }
 
 impl RunStatus {
     fn as_bool(self) -> bool {
-        matches!(self, Self::Success | Self::NoFiles | Self::DryRun)
+        matches!(
+            self,
+            Self::Success | Self::NoFiles | Self::DryRun | Self::Cached
+        )
     }
 
     fn is_skipped(self) -> bool {
-        matches!(self, Self::DryRun | Self::NoFiles)
+        matches!(self, Self::DryRun | Self::NoFiles | Self::Cached)
+    }
+}
+
+/// Replaces the store path and git root in hook output with `<store>` and `<root>`.
+struct PathRelativizer {
+    replacements: Vec<(String, &'static str)>,
+}
+
+impl PathRelativizer {
+    fn new(store: &Path, git_root: &Path) -> Self {
+        let mut replacements = vec![
+            (store.to_string_lossy().into_owned(), "<store>"),
+            (git_root.to_string_lossy().into_owned(), "<root>"),
+        ];
+        replacements.retain(|(path, _)| !path.is_empty());
+        // Replace the longer path first, in case one path is nested in the other.
+        replacements.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+        Self { replacements }
+    }
+
+    fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for (path, placeholder) in &self.replacements {
+            if let Some(replaced) = Self::replace_path(&text, path, placeholder) {
+                text = Cow::Owned(replaced);
+            }
+        }
+        text
+    }
+
+    /// Replace `path` where it starts a path in `text`, or `None` if it never does.
+    ///
+    /// The path mentioned in the text must start with `path` component-wise, so that
+    /// `/repo` doesn't rewrite `/repo-old/file` or `/other/repo/file`.
+    fn replace_path(text: &str, path: &str, placeholder: &str) -> Option<String> {
+        let is_delimiter = |c: char| c.is_whitespace() || "'\"`:;,=()[]{}<>".contains(c);
+
+        let mut replaced = String::new();
+        let mut last = 0;
+        for (start, _) in text.match_indices(path) {
+            if start < last
+                || text[..start]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| !is_delimiter(c))
+            {
+                continue;
+            }
+            let after = start + path.len();
+            let end = text[after..]
+                .find(is_delimiter)
+                .map_or(text.len(), |i| after + i);
+            if Path::new(&text[start..end]).strip_prefix(path).is_err() {
+                continue;
+            }
+            replaced.push_str(&text[last..start]);
+            replaced.push_str(placeholder);
+            last = after;
+        }
+
+        if last == 0 {
+            return None;
+        }
+        replaced.push_str(&text[last..]);
+        Some(replaced)
     }
 }
 
@@ -1311,6 +2054,7 @@ impl StatusPrinter {
     const SKIPPED: &'static str = "Skipped";
     const DRY_RUN: &'static str = "Dry Run";
     const NO_FILES: &'static str = "(no files to check)";
+    const CACHED: &'static str = "(unchanged)";
 
     fn for_hooks<T>(hooks: &[T], printer: Printer) -> Self
     where
@@ -1349,6 +2093,11 @@ impl StatusPrinter {
                 Self::SKIPPED.black().on_cyan().to_string(),
                 Self::SKIPPED.width(),
             ),
+            RunStatus::Cached => (
+                Self::CACHED,
+                Self::PASSED.on_green().to_string(),
+                Self::PASSED.width(),
+            ),
             RunStatus::DryRun => (
                 "",
                 Self::DRY_RUN.on_yellow().to_string(),
@@ -1382,9 +2131,13 @@ impl StatusPrinter {
 struct RunResult {
     hook: InstalledHook,
     status: RunStatus,
+    /// The number of files passed to the hook.
+    files: usize,
     duration: std::time::Duration,
     exit_status: i32,
     output: Vec<u8>,
+    /// Findings the hook reported at known lines, see [`Findings`].
+    findings: Vec<Finding>,
 }
 
 impl RunResult {
@@ -1392,9 +2145,11 @@ impl RunResult {
         Self {
             hook,
             status,
+            files: 0,
             duration: std::time::Duration::ZERO,
             exit_status: 0,
             output: Vec::new(),
+            findings: Vec::new(),
         }
     }
 }
@@ -1405,11 +2160,18 @@ async fn run_hook(
     tag_cache: &FileTagCache<'_>,
     store: &Store,
     dry_run: bool,
-    reporter: &HookRunReporter,
+    verbose: bool,
+    retries: Option<u32>,
+    timeout: Option<u64>,
+    context: &HookRunContext,
     semaphore: Rc<Semaphore>,
 ) -> Result<RunResult> {
+    // An exclusive hook takes every permit, so hooks of concurrently running projects
+    // finish first and no other hook starts until it is done.
     let _permit = if dry_run {
         None
+    } else if hook.exclusive {
+        Some(semaphore.acquire(*CONCURRENCY).await)
     } else {
         Some(semaphore.acquire(1).await)
     };
@@ -1427,29 +2189,85 @@ async fn run_hook(
     if !matched && !hook.always_run {
         return Ok(RunResult::from_status(hook, RunStatus::NoFiles));
     }
+
+    let mut result_cache = None;
+    if hook.cacheable
+        && context.result_cache
+        && !dry_run
+        && let HookRunInput::Filenames(filenames) = &mut input
+    {
+        let cache = ResultCache::load(store, &hook);
+        cache.retain_changed(hook.project().path(), filenames).await;
+        trace!(
+            "Files for hook `{}` not passed before: {}/{filename_count}",
+            hook.id,
+            filenames.len()
+        );
+        if filenames.is_empty() {
+            return Ok(RunResult::from_status(hook, RunStatus::Cached));
+        }
+        result_cache = Some(cache);
+    }
+
+    let files = input.filename_count();
     let start = std::time::Instant::now();
-    input.shuffle();
+    // Stdin-fed hooks run once per file, so there are no argument batches to balance.
+    if hook.stdin_from == StdinFrom::None {
+        input.shuffle();
+    }
 
+    let mut findings = Vec::new();
     let (exit_status, hook_output) = if dry_run {
-        (0, dry_run_hook(&hook, &input)?)
+        let list_files = verbose || hook.verbose;
+        (0, dry_run_hook(&hook, &input, list_files)?)
     } else {
-        match &input {
-            HookRunInput::Filenames(filenames) => {
-                hook.language.run(&hook, filenames, store, reporter).await
-            }
-            HookRunInput::Filename(filename) => {
-                let filenames = [filename.as_path()];
-                hook.language.run(&hook, &filenames, store, reporter).await
+        let retries = retries.unwrap_or(hook.retries);
+        let timeout = hook
+            .timeout
+            .or(timeout)
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        let log_attempts = verbose || hook.verbose;
+        let mut attempt_outputs = Vec::new();
+        let mut attempt = 0;
+        loop {
+            let (exit_status, output) =
+                execute_hook_with_timeout(&hook, &input, store, context, timeout)
+                    .await
+                    .with_context(|| format!("Failed to run hook `{hook}`"))?;
+            // Only findings of the final attempt are reported.
+            findings = context.findings.take(&hook);
+            if exit_status == 0 || attempt >= retries {
+                // Only the final attempt is shown, unless every attempt is logged.
+                attempt_outputs.extend(output);
+                break (exit_status, attempt_outputs);
             }
-            HookRunInput::WithoutFilenames { .. } => {
-                hook.language.run(&hook, &[], store, reporter).await
+
+            attempt += 1;
+            debug!(
+                "Hook `{}` exited with code {exit_status}, retrying ({attempt}/{retries})",
+                hook.id
+            );
+            if log_attempts {
+                attempt_outputs.extend(output);
+                let note = format!(
+                    "prek: attempt {attempt} failed with exit code {exit_status}, retrying"
+                );
+                writeln!(attempt_outputs, "\n{note}\n")?;
+                context.reporter.on_stream_note(&hook, &note);
             }
         }
-        .with_context(|| format!("Failed to run hook `{hook}`"))?
     };
 
     let duration = start.elapsed();
 
+    if exit_status == 0
+        && let Some(mut cache) = result_cache
+        && let HookRunInput::Filenames(filenames) = &input
+    {
+        cache.record_passed(hook.project().path(), filenames).await;
+    }
+
     let run_status = if dry_run {
         RunStatus::DryRun
     } else if exit_status == 0 {
@@ -1461,29 +2279,178 @@ async fn run_hook(
     Ok(RunResult {
         hook,
         status: run_status,
+        files,
         duration,
         exit_status,
         output: hook_output,
+        findings,
     })
 }
 
-fn dry_run_hook(hook: &InstalledHook, input: &HookRunInput<'_>) -> Result<Vec<u8>> {
-    let mut output = Vec::new();
-    let filename_count = input.filename_count();
-    if filename_count != 0 {
-        writeln!(output, "`{hook}` would be run on {filename_count} files:")?;
+/// Run a hook once on its input, killing it once it has run for longer than `timeout`.
+///
+/// A hook that times out is reported as failed, with the output it wrote until then.
+async fn execute_hook_with_timeout(
+    hook: &InstalledHook,
+    input: &HookRunInput<'_>,
+    store: &Store,
+    context: &HookRunContext,
+    timeout: Option<Duration>,
+) -> Result<(i32, Vec<u8>)> {
+    let Some(timeout) = timeout else {
+        return execute_hook(hook, input, store, context).await;
+    };
+
+    let result = crate::run::HOOK_DEADLINE
+        .scope(
+            HookDeadline::after(timeout),
+            execute_hook(hook, input, store, context),
+        )
+        .await;
+    let Err(err) = result else {
+        return result;
+    };
+    match err.downcast::<process::Error>() {
+        Ok(process::Error::TimedOut {
+            timeout,
+            mut output,
+            ..
+        }) => {
+            if !output.is_empty() && !output.ends_with(b"\n") {
+                output.push(b'\n');
+            }
+            writeln!(output, "prek: hook timed out after {}s", timeout.as_secs())?;
+            Ok((1, output))
+        }
+        Ok(err) => Err(err.into()),
+        Err(err) => Err(err),
     }
+}
+
+/// Run a hook once on its input, returning its exit status and output.
+async fn execute_hook(
+    hook: &InstalledHook,
+    input: &HookRunInput<'_>,
+    store: &Store,
+    context: &HookRunContext,
+) -> Result<(i32, Vec<u8>)> {
+    if let Some(working_directory) = &hook.working_directory {
+        if !working_directory.is_dir() {
+            anyhow::bail!(
+                "Working directory `{}` does not exist",
+                working_directory.user_display()
+            );
+        }
 
-    match input {
-        HookRunInput::Filenames(filenames) => {
-            for filename in filenames {
-                writeln!(output, "- {}", filename.display())?;
+        // Filenames are relative to the project root, but the hook runs in its working directory.
+        let relative = |filename: &Path| {
+            fs::relative_to(hook.project().path().join(filename), working_directory)
+        };
+        let filenames;
+        let input = match input {
+            HookRunInput::Filenames(input) => {
+                filenames = input
+                    .iter()
+                    .map(|filename| relative(filename))
+                    .collect::<Result<Vec<_>, _>>()?;
+                HookRunInput::with_filenames(filenames.iter().map(PathBuf::as_path))
+            }
+            HookRunInput::Filename(filename) => HookRunInput::with_filename(relative(filename)?),
+            HookRunInput::WithoutFilenames { matched } => HookRunInput::without_filenames(*matched),
+        };
+        return execute_hook_in_work_dir(hook, &input, store, context).await;
+    }
+
+    execute_hook_in_work_dir(hook, input, store, context).await
+}
+
+/// Run a hook once on input that is relative to its working directory.
+async fn execute_hook_in_work_dir(
+    hook: &InstalledHook,
+    input: &HookRunInput<'_>,
+    store: &Store,
+    context: &HookRunContext,
+) -> Result<(i32, Vec<u8>)> {
+    if hook.stdin_from == StdinFrom::Files {
+        match input {
+            HookRunInput::Filenames(filenames) => {
+                run_hook_per_file_stdin(hook, filenames, store, context).await
+            }
+            HookRunInput::Filename(filename) => {
+                run_hook_per_file_stdin(hook, &[filename.as_path()], store, context).await
+            }
+            HookRunInput::WithoutFilenames { .. } => {
+                hook.language.run(hook, &[], store, context).await
             }
         }
-        HookRunInput::Filename(filename) => {
-            writeln!(output, "- {}", filename.display())?;
+    } else {
+        match input {
+            HookRunInput::Filenames(filenames) => {
+                hook.language.run(hook, filenames, store, context).await
+            }
+            HookRunInput::Filename(filename) => {
+                let filenames = [filename.as_path()];
+                hook.language.run(hook, &filenames, store, context).await
+            }
+            HookRunInput::WithoutFilenames { .. } => {
+                hook.language.run(hook, &[], store, context).await
+            }
         }
-        HookRunInput::WithoutFilenames { .. } => {}
+    }
+}
+
+/// Run a `stdin_from: files` hook once per file, with the file's content on stdin.
+///
+/// No filenames are passed as arguments; outputs are concatenated in file order.
+async fn run_hook_per_file_stdin(
+    hook: &InstalledHook,
+    filenames: &[&Path],
+    store: &Store,
+    context: &HookRunContext,
+) -> Result<(i32, Vec<u8>)> {
+    let mut combined_status = 0;
+    let mut combined_output = Vec::new();
+    for filename in filenames {
+        let path = hook.work_dir().join(filename);
+        let (status, output) = crate::run::HOOK_STDIN
+            .scope(path, hook.language.run(hook, &[], store, context))
+            .await?;
+        combined_status |= status;
+        combined_output.extend(output);
+    }
+    Ok((combined_status, combined_output))
+}
+
+/// Describe the files a hook would receive, after all filtering, without running it.
+///
+/// The files themselves are only listed when `list_files` is set.
+fn dry_run_hook(
+    hook: &InstalledHook,
+    input: &HookRunInput<'_>,
+    list_files: bool,
+) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut filenames = match input {
+        HookRunInput::Filenames(filenames) => filenames.clone(),
+        HookRunInput::Filename(filename) => vec![filename.as_path()],
+        HookRunInput::WithoutFilenames { .. } => {
+            writeln!(output, "`{hook}` would be run without filenames")?;
+            return Ok(output);
+        }
+    };
+
+    let count = filenames.len();
+    let plural = if count == 1 { "" } else { "s" };
+    if !list_files {
+        writeln!(output, "`{hook}` would be run on {count} file{plural}")?;
+        return Ok(output);
+    }
+
+    writeln!(output, "`{hook}` would be run on {count} file{plural}:")?;
+    // Filenames are shuffled for batching; list them in a stable order.
+    filenames.sort_unstable();
+    for filename in filenames {
+        writeln!(output, "- {}", filename.display())?;
     }
 
     Ok(output)
@@ -1493,6 +2460,39 @@ fn dry_run_hook(hook: &InstalledHook, input: &HookRunInput<'_>) -> Result<Vec<u8
 mod tests {
     use super::*;
 
+    #[test]
+    fn path_relativizer_replaces_store_and_root() {
+        let relativizer = PathRelativizer::new(Path::new("/repo/.cache/prek"), Path::new("/repo"));
+
+        assert_eq!(
+            relativizer
+                .apply("/repo/.cache/prek/hooks/python-abc/bin/python failed on /repo/src/main.py"),
+            "<store>/hooks/python-abc/bin/python failed on <root>/src/main.py"
+        );
+        assert!(matches!(
+            relativizer.apply("nothing to see"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn path_relativizer_matches_whole_path_components() {
+        let relativizer = PathRelativizer::new(Path::new("/cache/prek"), Path::new("/repo/foo"));
+
+        assert_eq!(
+            relativizer.apply("/repo/foobar/x and /repo/foo-old/y differ from /repo/foo/z"),
+            "/repo/foobar/x and /repo/foo-old/y differ from <root>/z"
+        );
+        assert_eq!(
+            relativizer.apply("/other/repo/foo/file:1: '/repo/foo': error"),
+            "/other/repo/foo/file:1: '<root>': error"
+        );
+        assert!(matches!(
+            relativizer.apply("/repo/food/file"),
+            Cow::Borrowed(_)
+        ));
+    }
+
     #[test]
     fn status_printer_write_dots_saturates_instead_of_underflow() {
         let status_printer = StatusPrinter {
@@ -1,19 +1,22 @@
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use crate::config::validate_group_name;
 use crate::hook::Hook;
-use crate::warn_user;
+use crate::{suggest, warn_user};
 
 use anyhow::anyhow;
 use itertools::Itertools;
+use prek_consts::PREK_SKIP_FILE;
 use prek_consts::env_vars::EnvVars;
 use rustc_hash::FxHashSet;
-use tracing::trace;
+use tracing::{debug, trace};
 
-use crate::fs::PathClean;
+use crate::fs::{CWD, PathClean};
+use crate::git::GIT_ROOT;
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct ConfiguredHook<'a> {
@@ -61,6 +64,13 @@ pub(crate) enum Error {
         #[source]
         source: anyhow::Error,
     },
+
+    #[error("Failed to read skip file `{path}`")]
+    SkipFile {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -68,6 +78,7 @@ pub(crate) enum SelectorSource {
     CliArg,
     CliFlag(&'static str),
     EnvVar(&'static str),
+    File(&'static str),
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +129,7 @@ impl Selector {
             SelectorSource::CliArg => Cow::Borrowed(&self.original),
             SelectorSource::CliFlag(flag) => Cow::Owned(format!("{}={}", flag, self.original)),
             SelectorSource::EnvVar(var) => Cow::Owned(format!("{}={}", var, self.original)),
+            SelectorSource::File(file) => Cow::Owned(format!("{} (from {file})", self.original)),
         }
     }
 
@@ -126,6 +138,7 @@ impl Selector {
             SelectorSource::CliArg => self.to_string(),
             SelectorSource::CliFlag(flag) => format!("{flag}={self}"),
             SelectorSource::EnvVar(var) => format!("{var}={self}"),
+            SelectorSource::File(file) => format!("{self} (from {file})"),
         }
     }
 
@@ -222,11 +235,11 @@ impl Selectors {
 
         let skips = load_skips(skips, workspace_root, RealFileSystem)?;
 
-        trace!(
+        debug!(
             "Skip selectors: `{}`",
             skips
                 .iter()
-                .map(ToString::to_string)
+                .map(Selector::as_normalized_flag)
                 .collect::<Vec<_>>()
                 .join(", ")
         );
@@ -265,9 +278,21 @@ impl Selectors {
             })
     }
 
+    /// Check if an include selector names this hook by id, rather than selecting it through
+    /// its project.
+    pub(crate) fn names_hook(&self, hook: &Hook) -> bool {
+        self.includes.iter().any(|include| {
+            matches!(
+                include.expr,
+                SelectorExpr::HookId(_) | SelectorExpr::ProjectHook { .. }
+            ) && include.matches_hook(hook)
+        })
+    }
+
     /// Check if a hook matches any of the selection criteria.
     pub(crate) fn matches_hook(&self, hook: &Hook) -> bool {
         let mut usage = self.usage.lock().unwrap();
+        usage.seen_hook_ids.insert(hook.id.clone());
 
         // Always check every selector to track usage
         let mut skipped = false;
@@ -520,6 +545,8 @@ impl GroupFilters {
 struct FilterUsage {
     used_includes: FxHashSet<usize>,
     used_excludes: FxHashSet<usize>,
+    /// Ids of all hooks checked against the selectors, to suggest fixes for mistyped ids.
+    seen_hook_ids: BTreeSet<String>,
 }
 
 impl FilterUsage {
@@ -551,6 +578,18 @@ impl FilterUsage {
             .filter(|(idx, _)| !self.used_excludes.contains(idx))
     }
 
+    /// A "did you mean" suffix for a selector naming a hook id that matched nothing.
+    fn suggestion(&self, selector: &Selector) -> String {
+        let (SelectorExpr::HookId(hook_id) | SelectorExpr::ProjectHook { hook_id, .. }) =
+            &selector.expr
+        else {
+            return String::new();
+        };
+        suggest::closest_match(hook_id, self.seen_hook_ids.iter().map(String::as_str))
+            .map(|closest| format!(", did you mean `{closest}`?"))
+            .unwrap_or_default()
+    }
+
     fn report_unused(&self, selectors: &Selectors) {
         let unused = self
             .unused_includes(&selectors.includes)
@@ -562,14 +601,15 @@ impl FilterUsage {
             [(_, selector)] => {
                 let flag = selector.as_flag();
                 let normalized = selector.as_normalized_flag();
+                let suggestion = self.suggestion(selector);
                 if flag == normalized {
                     warn_user!(
-                        "selector `{flag}` did not match any {}",
+                        "selector `{flag}` did not match any {}{suggestion}",
                         selector.kind_str()
                     );
                 } else {
                     warn_user!(
-                        "selector `{flag}` ({}) did not match any {}",
+                        "selector `{flag}` ({}) did not match any {}{suggestion}",
                         format!("normalized to `{normalized}`").dimmed(),
                         selector.kind_str()
                     );
@@ -581,11 +621,12 @@ impl FilterUsage {
                     .map(|(_, sel)| {
                         let flag = sel.as_flag();
                         let normalized = sel.as_normalized_flag();
+                        let suggestion = self.suggestion(sel);
                         if flag == normalized {
-                            format!("  - `{flag}`")
+                            format!("  - `{flag}`{suggestion}")
                         } else {
                             format!(
-                                "  - `{flag}` ({})",
+                                "  - `{flag}` ({}){suggestion}",
                                 format!("normalized to `{normalized}`").dimmed()
                             )
                         }
@@ -718,11 +759,43 @@ fn normalize_path<FS: FileSystem>(
     Ok(rel_path.to_path_buf())
 }
 
-/// Parse skip selectors from CLI args and environment variables
+/// Parse skip selectors from CLI args or environment variables, plus the skip file.
 pub(crate) fn load_skips<FS: FileSystem>(
     cli_skips: &[String],
     workspace_root: &Path,
     fs: FS,
+) -> Result<Vec<Selector>, Error> {
+    let mut selectors = load_cli_or_env_skips(cli_skips, workspace_root, fs)?;
+
+    if let Ok(git_root) = GIT_ROOT.as_ref()
+        && let Some(path) = find_skip_file(&CWD, git_root)
+    {
+        debug!("Reading skip selectors from `{}`", path.display());
+        let content = fs_err::read_to_string(&path).map_err(|err| Error::SkipFile {
+            path: path.display().to_string(),
+            source: err,
+        })?;
+        for skip in parse_skip_file(&content).unique() {
+            if selectors.iter().any(|selector| selector.original == skip) {
+                continue;
+            }
+            selectors.push(parse_single_selector(
+                skip,
+                workspace_root,
+                SelectorSource::File(PREK_SKIP_FILE),
+                fs,
+            )?);
+        }
+    }
+
+    Ok(selectors)
+}
+
+/// Parse skip selectors from CLI args, or else from environment variables.
+fn load_cli_or_env_skips<FS: FileSystem>(
+    cli_skips: &[String],
+    workspace_root: &Path,
+    fs: FS,
 ) -> Result<Vec<Selector>, Error> {
     let prek_skip = EnvVars::var(EnvVars::PREK_SKIP);
     let skip = EnvVars::var(EnvVars::SKIP);
@@ -758,6 +831,25 @@ fn parse_comma_separated(input: &str) -> impl Iterator<Item = &str> {
     input.split(',').map(str::trim).filter(|s| !s.is_empty())
 }
 
+/// Find the nearest skip file in `cwd` or one of its parents, up to `git_root`.
+fn find_skip_file(cwd: &Path, git_root: &Path) -> Option<PathBuf> {
+    if !cwd.starts_with(git_root) {
+        return None;
+    }
+    cwd.ancestors()
+        .take_while(|dir| dir.starts_with(git_root))
+        .map(|dir| dir.join(PREK_SKIP_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Parse newline-separated selectors, ignoring blank lines and `#` comments.
+fn parse_skip_file(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1066,5 +1158,45 @@ mod tests {
             expr: SelectorExpr::HookId("black".to_string()),
         };
         assert_eq!(selector.as_flag(), "SKIP=black");
+
+        let selector = Selector {
+            source: SelectorSource::File(".prek-skip"),
+            original: "black".to_string(),
+            expr: SelectorExpr::HookId("black".to_string()),
+        };
+        assert_eq!(selector.as_flag(), "black (from .prek-skip)");
+    }
+
+    #[test]
+    fn test_parse_skip_file() {
+        let content = "# Machine-specific skips\nblack\n\n  src/backend:lint  \n# ruff\n";
+        assert_eq!(
+            parse_skip_file(content).collect::<Vec<_>>(),
+            ["black", "src/backend:lint"]
+        );
+    }
+
+    #[test]
+    fn test_find_skip_file() -> anyhow::Result<()> {
+        let fs = create_test_workspace()?;
+        let root = fs.root();
+        let cwd = root.join("src/backend");
+
+        assert_eq!(find_skip_file(&cwd, root), None);
+
+        fs_err::write(root.join(PREK_SKIP_FILE), "black\n")?;
+        assert_eq!(find_skip_file(&cwd, root), Some(root.join(PREK_SKIP_FILE)));
+
+        // The nearest file wins.
+        fs_err::write(root.join("src").join(PREK_SKIP_FILE), "ruff\n")?;
+        assert_eq!(
+            find_skip_file(&cwd, root),
+            Some(root.join("src").join(PREK_SKIP_FILE))
+        );
+
+        // Files outside the git root are not considered.
+        assert_eq!(find_skip_file(&cwd, &root.join("src/backend")), None);
+
+        Ok(())
     }
 }
@@ -0,0 +1,149 @@
+//! Per-hook timing report printed after a run with `--timings`.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::TimingsFormat;
+use crate::hook::Hook;
+use crate::printer::Printer;
+
+#[derive(Debug, Serialize)]
+struct HookTiming {
+    id: String,
+    project: String,
+    files: usize,
+    #[serde(serialize_with = "serialize_secs")]
+    duration: Duration,
+}
+
+/// Time spent preparing and running hooks, with hooks sorted slowest first.
+#[derive(Debug, Serialize)]
+pub(super) struct Timings {
+    /// How to print the report, if at all.
+    #[serde(skip)]
+    format: Option<TimingsFormat>,
+    /// Where to write the report as JSON, for `--timings-file`.
+    #[serde(skip)]
+    file: Option<PathBuf>,
+    /// Time spent loading configs and cloning remote repositories.
+    #[serde(serialize_with = "serialize_secs")]
+    setup: Duration,
+    /// Time spent installing hook environments.
+    #[serde(serialize_with = "serialize_secs")]
+    install: Duration,
+    hooks: Vec<HookTiming>,
+}
+
+fn serialize_secs<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+impl Timings {
+    pub(super) fn new(
+        format: Option<TimingsFormat>,
+        file: Option<PathBuf>,
+        setup: Duration,
+        install: Duration,
+    ) -> Self {
+        Self {
+            format,
+            file,
+            setup,
+            install,
+            hooks: Vec::new(),
+        }
+    }
+
+    pub(super) fn record(&mut self, hook: &Hook, files: usize, duration: Duration) {
+        self.hooks.push(HookTiming {
+            id: hook.id.clone(),
+            project: hook.project().to_string(),
+            files,
+            duration,
+        });
+    }
+
+    pub(super) fn render(mut self, printer: Printer) -> Result<()> {
+        self.hooks
+            .sort_by(|a, b| b.duration.cmp(&a.duration).then_with(|| a.id.cmp(&b.id)));
+
+        if let Some(path) = &self.file {
+            fs_err::write(path, serde_json::to_string_pretty(&self)?)?;
+        }
+
+        match self.format {
+            None => {}
+            Some(TimingsFormat::Json) => {
+                writeln!(
+                    printer.stdout_important(),
+                    "{}",
+                    serde_json::to_string_pretty(&self)?
+                )?;
+            }
+            Some(TimingsFormat::Text) => {
+                let show_project = self.hooks.iter().any(|hook| hook.project != ".");
+                let name = |hook: &HookTiming| {
+                    if show_project {
+                        format!("{}:{}", hook.project, hook.id)
+                    } else {
+                        hook.id.clone()
+                    }
+                };
+                let width = self
+                    .hooks
+                    .iter()
+                    .map(|hook| name(hook).len())
+                    .chain(["Environment install".len()])
+                    .max()
+                    .unwrap_or_default();
+
+                let mut stdout = printer.stdout();
+                writeln!(stdout, "\n{}", "Timings:".bold())?;
+                writeln!(
+                    stdout,
+                    "  {:<width$}  {:>8}",
+                    "Repository setup",
+                    format_secs(self.setup)
+                )?;
+                writeln!(
+                    stdout,
+                    "  {:<width$}  {:>8}",
+                    "Environment install",
+                    format_secs(self.install)
+                )?;
+                if self.hooks.is_empty() {
+                    return Ok(());
+                }
+                writeln!(stdout)?;
+                writeln!(
+                    stdout,
+                    "  {}",
+                    format!("{:<width$}  {:>8}  {:>6}", "Hook", "Duration", "Files").dimmed()
+                )?;
+                for hook in &self.hooks {
+                    writeln!(
+                        stdout,
+                        "  {:<width$}  {:>8}  {:>6}",
+                        name(hook),
+                        format_secs(hook.duration),
+                        hook.files
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_secs(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f64())
+}
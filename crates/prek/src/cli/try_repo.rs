@@ -9,7 +9,7 @@ use tempfile::TempDir;
 use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Value};
 
 use crate::cli::run::Selectors;
-use crate::cli::{ExitStatus, RunOptions, flag};
+use crate::cli::{ExitStatus, RunOptions, RunSettings, flag};
 use crate::config::{self, Stage};
 use crate::git;
 use crate::git::GIT_ROOT;
@@ -29,6 +29,28 @@ async fn get_head_rev(repo: &Path) -> Result<String> {
     Ok(head_rev)
 }
 
+/// Resolve `HEAD` of a local repo, or `None` if it is not a git repo or has no commits yet.
+async fn get_local_head_rev(repo: &Path) -> Result<Option<String>> {
+    if !repo.join(".git").exists() {
+        return Ok(None);
+    }
+    let output = git::git_cmd("get head rev")?
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg("HEAD")
+        .current_dir(repo)
+        .check(false)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
 async fn clone_and_commit(repo_path: &Path, head_rev: &str, tmp_dir: &Path) -> Result<PathBuf> {
     let shadow = tmp_dir.join("shadow-repo");
     git::git_cmd("clone shadow repo")?
@@ -65,13 +87,46 @@ async fn clone_and_commit(repo_path: &Path, head_rev: &str, tmp_dir: &Path) -> R
     let mut add_u_cmd = git::git_cmd("add unstaged to shadow")?;
     add_u_cmd
         .arg("add")
-        .arg("--update") // Update tracked files
+        .arg("--all") // Update tracked files and add untracked ones, except ignored files
         .current_dir(repo_path)
         .env("GIT_INDEX_FILE", &index_path)
         .env("GIT_OBJECT_DIRECTORY", &objects_path)
         .output()
         .await?;
 
+    commit_shadow(&shadow).await?;
+
+    Ok(shadow)
+}
+
+/// Create a shadow repo holding the working tree of a directory that is not a git repo or has
+/// no commits yet.
+async fn init_and_commit(repo_path: &Path, tmp_dir: &Path) -> Result<PathBuf> {
+    let shadow = tmp_dir.join("shadow-repo");
+    git::git_cmd("init shadow repo")?
+        .arg("init")
+        .arg("--template=")
+        .arg(&shadow)
+        .output()
+        .await?;
+
+    git::git_cmd("add working tree to shadow")?
+        .arg("--git-dir")
+        .arg(shadow.join(".git"))
+        .arg("--work-tree")
+        .arg(repo_path)
+        .arg("add")
+        .arg("--all")
+        .current_dir(repo_path)
+        .output()
+        .await?;
+
+    commit_shadow(&shadow).await?;
+
+    Ok(shadow)
+}
+
+async fn commit_shadow(shadow: &Path) -> Result<()> {
     git::git_cmd("git commit")?
         .arg("commit")
         .arg("-m")
@@ -79,7 +134,7 @@ async fn clone_and_commit(repo_path: &Path, head_rev: &str, tmp_dir: &Path) -> R
         .arg("--no-gpg-sign")
         .arg("--no-edit")
         .arg("--no-verify")
-        .current_dir(&shadow)
+        .current_dir(shadow)
         .env("GIT_AUTHOR_NAME", "prek test")
         .env("GIT_AUTHOR_EMAIL", "test@example.com")
         .env("GIT_COMMITTER_NAME", "prek test")
@@ -87,7 +142,7 @@ async fn clone_and_commit(repo_path: &Path, head_rev: &str, tmp_dir: &Path) -> R
         .output()
         .await?;
 
-    Ok(shadow)
+    Ok(())
 }
 
 async fn prepare_repo_and_rev<'a>(
@@ -105,7 +160,14 @@ async fn prepare_repo_and_rev<'a>(
 
     // Get HEAD revision
     let head_rev = if is_local {
-        get_head_rev(repo_path).await?
+        let Some(head_rev) = get_local_head_rev(repo_path).await? else {
+            // Nothing committed yet, use the working tree as is.
+            warn_user!("Creating temporary repo from the working tree...");
+            let shadow = init_and_commit(repo_path, tmp_dir).await?;
+            let head_rev = get_head_rev(&shadow).await?;
+            return Ok((Cow::Owned(shadow.to_string_lossy().into_owned()), head_rev));
+        };
+        head_rev
     } else {
         // For remote repositories, use ls-remote
         let head_rev = git::git_cmd("get head rev")?
@@ -124,7 +186,12 @@ async fn prepare_repo_and_rev<'a>(
     };
 
     // If repo is a local repo with uncommitted changes, create a shadow repo to commit the changes.
-    if is_local && git::has_diff("HEAD", repo_path).await? {
+    if is_local
+        && (git::has_diff("HEAD", repo_path).await?
+            || !git::ls_untracked_files(repo_path, Path::new("."))
+                .await?
+                .is_empty())
+    {
         warn_user!("Creating temporary repo with uncommitted changes...");
         let shadow = clone_and_commit(repo_path, &head_rev, tmp_dir).await?;
         let head_rev = get_head_rev(&shadow).await?;
@@ -160,6 +227,7 @@ fn render_repo_config_toml(repo_path: &str, rev: &str, hooks: Vec<String>) -> St
 }
 
 pub(crate) async fn try_repo(
+    store: &Store,
     config: Option<PathBuf>,
     repo: String,
     rev: Option<String>,
@@ -173,14 +241,15 @@ pub(crate) async fn try_repo(
         warn_user!("`--config` option is ignored when using `try-repo`");
     }
 
-    let store = Store::from_settings()?;
     let tmp_dir = TempDir::with_prefix_in("try-repo-", store.scratch_path())?;
 
     let (repo_path, rev) = prepare_repo_and_rev(&repo, rev.as_deref(), tmp_dir.path())
         .await
         .context("Failed to determine repository and revision")?;
 
-    let store = Store::from_path(tmp_dir.path()).init()?;
+    let store = Store::from_path(tmp_dir.path())
+        .init()?
+        .with_offline(store.is_offline());
     let repo_config = config::RemoteRepo::new(repo_path.to_string(), rev.clone(), vec![]);
     let repo_clone_path = store.clone_repo(&repo_config, None).await?;
 
@@ -209,24 +278,29 @@ pub(crate) async fn try_repo(
 
     crate::cli::run(
         &store,
-        Some(config_file),
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        stage,
-        run_args.from_ref,
-        run_args.to_ref,
-        run_args.all_files,
-        run_args.files,
-        run_args.directory,
-        run_args.last_commit,
-        run_args.show_diff_on_failure,
-        flag(run_args.fail_fast, run_args.no_fail_fast),
-        run_args.dry_run,
-        refresh,
-        run_args.extra,
-        verbose,
+        RunSettings {
+            config: Some(config_file),
+            hook_stage: stage,
+            from_ref: run_args.from_ref,
+            to_ref: run_args.to_ref,
+            all_files: run_args.all_files,
+            files: run_args.files,
+            directories: run_args.directory,
+            last_commit: run_args.last_commit,
+            since: run_args.since,
+            include_deleted: run_args.include_deleted,
+            include_untracked: run_args.include_untracked,
+            show_diff_on_failure: run_args.show_diff_on_failure,
+            show_diff: run_args.show_diff,
+            fail_fast: flag(run_args.fail_fast, run_args.no_fail_fast),
+            keep_going: run_args.keep_going,
+            dry_run: run_args.dry_run,
+            explain: run_args.explain,
+            refresh,
+            extra_args: run_args.extra,
+            verbose,
+            ..RunSettings::default()
+        },
         printer,
     )
     .await
@@ -1,17 +1,26 @@
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt::Write;
 use std::iter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use anyhow::Result;
 use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::cli::ExitStatus;
-use crate::config::{read_config, read_manifest};
+use crate::config::{Manifest, read_config, read_config_strict, read_manifest};
+use crate::fs::Simplified;
+use crate::hooks::MetaHooks;
 use crate::printer::Printer;
 use crate::warn_user;
 
-pub(crate) fn validate_configs(configs: Vec<PathBuf>, printer: Printer) -> Result<ExitStatus> {
+pub(crate) fn validate_configs(
+    configs: Vec<PathBuf>,
+    strict: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
     let mut status = ExitStatus::Success;
 
     if configs.is_empty() {
@@ -20,7 +29,12 @@ pub(crate) fn validate_configs(configs: Vec<PathBuf>, printer: Printer) -> Resul
     }
 
     for config in configs {
-        if let Err(err) = read_config(&config) {
+        let result = if strict {
+            read_config_strict(&config)
+        } else {
+            read_config(&config)
+        };
+        if let Err(err) = result {
             writeln!(printer.stderr(), "{}: {}", "error".red().bold(), err)?;
             for source in iter::successors(err.source(), |&err| err.source()) {
                 writeln!(
@@ -53,18 +67,26 @@ pub(crate) fn validate_manifest(manifests: Vec<PathBuf>, printer: Printer) -> Re
         return Ok(ExitStatus::Success);
     }
 
-    for manifest in manifests {
-        if let Err(err) = read_manifest(&manifest) {
-            writeln!(printer.stderr(), "{}: {}", "error".red().bold(), err)?;
-            for source in iter::successors(err.source(), |&err| err.source()) {
-                writeln!(
-                    printer.stderr(),
-                    "  {}: {}",
-                    "caused by".red().bold(),
-                    source
-                )?;
+    for path in manifests {
+        match read_manifest(&path) {
+            Ok(manifest) => {
+                for issue in manifest_issues(&path, &manifest)? {
+                    writeln!(printer.stderr(), "{}: {}", "error".red().bold(), issue)?;
+                    status = ExitStatus::Failure;
+                }
+            }
+            Err(err) => {
+                writeln!(printer.stderr(), "{}: {}", "error".red().bold(), err)?;
+                for source in iter::successors(err.source(), |&err| err.source()) {
+                    writeln!(
+                        printer.stderr(),
+                        "  {}: {}",
+                        "caused by".red().bold(),
+                        source
+                    )?;
+                }
+                status = ExitStatus::Failure;
             }
-            status = ExitStatus::Failure;
         }
     }
 
@@ -78,3 +100,66 @@ pub(crate) fn validate_manifest(manifests: Vec<PathBuf>, printer: Printer) -> Re
 
     Ok(status)
 }
+
+/// Find problems in a manifest that parses fine but cannot be used as intended: duplicate ids,
+/// ids that shadow meta hooks, and config-only keys.
+///
+/// Builtin hook ids are not reported, since they intentionally mirror the ids of
+/// `pre-commit/pre-commit-hooks`.
+fn manifest_issues(path: &Path, manifest: &Manifest) -> Result<Vec<String>> {
+    let content = fs_err::read_to_string(path)?;
+    let display = path.user_display();
+    let mut id_lines = id_lines(&content);
+    let mut line_of = |id: &str| {
+        id_lines
+            .get_mut(id)
+            .and_then(VecDeque::pop_front)
+            .map(|line| format!(" (line {line})"))
+            .unwrap_or_default()
+    };
+
+    let mut issues = Vec::new();
+    let mut seen = FxHashSet::default();
+    for hook in &manifest.hooks {
+        let id = hook.id.as_str();
+        let line = line_of(id);
+        if !seen.insert(id) {
+            issues.push(format!(
+                "Duplicate hook id `{}` in `{}`{line}",
+                id.cyan(),
+                display.cyan()
+            ));
+        }
+        if MetaHooks::from_str(id).is_ok() {
+            issues.push(format!(
+                "Hook id `{}` in `{}`{line} is reserved for the meta hook of the same name",
+                id.cyan(),
+                display.cyan()
+            ));
+        }
+        if hook.options._unused_keys.contains_key("priority") {
+            issues.push(format!(
+                "Hook `{}` in `{}`{line} sets `priority`, which is only allowed in config files",
+                id.cyan(),
+                display.cyan()
+            ));
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Line numbers of block-style `id:` entries, keyed by id in the order they appear.
+fn id_lines(content: &str) -> FxHashMap<&str, VecDeque<usize>> {
+    let mut lines: FxHashMap<&str, VecDeque<usize>> = FxHashMap::default();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim_start();
+        let line = line.strip_prefix('-').unwrap_or(line).trim_start();
+        let Some(id) = line.strip_prefix("id:") else {
+            continue;
+        };
+        let id = id.trim().trim_matches(['"', '\'']);
+        lines.entry(id).or_default().push_back(idx + 1);
+    }
+    lines
+}
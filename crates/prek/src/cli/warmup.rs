@@ -0,0 +1,85 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
+
+use crate::cli::reporter::HookInstallReporter;
+use crate::cli::run::{InstallCache, install_hooks};
+use crate::cli::{ExitStatus, discover_workspace, init_selected_hooks};
+use crate::hook::InstalledHook;
+use crate::printer::Printer;
+use crate::store::Store;
+
+/// Prepare every hook environment and verify that each one is healthy.
+///
+/// This is like `prepare-hooks`, but also runs each language's health check on
+/// the resulting environments, so broken installs fail here instead of at the
+/// first `prek run`.
+pub(crate) async fn warmup(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: Vec<String>,
+    skips: Vec<String>,
+    frozen: bool,
+    refresh: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let (workspace, selectors) =
+        discover_workspace(store, config, &includes, &skips, frozen, refresh)?;
+
+    let _lock = store.lock_async().await?;
+    let filtered_hooks = init_selected_hooks(store, &workspace, &selectors, printer).await?;
+
+    let reporter = HookInstallReporter::new(printer);
+    let mut install_cache = InstallCache::new();
+    let installed_hooks =
+        install_hooks(filtered_hooks, store, &reporter, &mut install_cache).await?;
+    reporter.on_complete();
+
+    // Hooks with the same environment key share one environment; check each only once.
+    let mut seen = FxHashSet::default();
+    let mut checked = 0usize;
+    let mut unhealthy = 0usize;
+    for hook in &installed_hooks {
+        let InstalledHook::Installed { info, .. } = hook else {
+            continue;
+        };
+        if !seen.insert(info.env_path.clone()) {
+            continue;
+        }
+
+        checked += 1;
+        if let Err(err) = info.check_health(store).await {
+            unhealthy += 1;
+            writeln!(
+                printer.stderr(),
+                "{}: Environment for hook `{}` is unhealthy: {err:#}",
+                "error".red().bold(),
+                hook.full_id(),
+            )?;
+        }
+    }
+
+    if unhealthy > 0 {
+        writeln!(
+            printer.stderr(),
+            "{}: {unhealthy} of {checked} environment{} failed the health check",
+            "error".red().bold(),
+            if checked == 1 { "" } else { "s" },
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    writeln!(
+        printer.stderr(),
+        "{}: Prepared {checked} healthy environment{} for {} hook{}",
+        "success".green().bold(),
+        if checked == 1 { "" } else { "s" },
+        installed_hooks.len(),
+        if installed_hooks.len() == 1 { "" } else { "s" },
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -1,18 +1,19 @@
 use std::collections::BTreeMap;
 use std::error::Error as _;
 use std::fmt::Display;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use fancy_regex::Regex;
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use itertools::Itertools;
 use prek_identify::TagSet;
-use rustc_hash::FxHashMap;
-use serde::de::{DeserializeSeed, Error as DeError, MapAccess, Visitor};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::{DeserializeSeed, Error as DeError, IntoDeserializer, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use strum::EnumCount;
 
+use crate::cli::run::RunInputMode;
 use crate::fs::Simplified;
 use crate::install_source::InstallSource;
 use crate::version;
@@ -48,24 +49,37 @@ where
 pub(crate) struct GlobPatterns {
     patterns: Vec<String>,
     set: GlobSet,
+    /// Patterns prefixed with `!`, which exclude paths matched by `set`.
+    ignore: GlobSet,
 }
 
 impl GlobPatterns {
     pub(crate) fn new(patterns: Vec<String>) -> Result<Self, globset::Error> {
         let mut builder = GlobSetBuilder::new();
+        let mut ignore = GlobSetBuilder::new();
         for pattern in &patterns {
-            builder.add(Glob::new(pattern)?);
+            if let Some(negated) = pattern.strip_prefix('!') {
+                ignore.add(Glob::new(negated)?);
+            } else {
+                builder.add(Glob::new(pattern)?);
+            }
         }
         let set = builder.build()?;
-        Ok(Self { patterns, set })
+        let ignore = ignore.build()?;
+        Ok(Self {
+            patterns,
+            set,
+            ignore,
+        })
     }
 
     pub(crate) fn is_empty(&self) -> bool {
         self.patterns.is_empty()
     }
 
+    /// A path matches if any positive pattern matches and no `!` pattern does.
     pub(crate) fn is_match(&self, path: &Path) -> bool {
-        self.set.is_match(path)
+        self.set.is_match(path) && !self.ignore.is_match(path)
     }
 }
 
@@ -224,6 +238,133 @@ impl FilePattern {
             FilePattern::Glob(globs) => globs.is_match(path),
         }
     }
+
+    /// Whether this is a regex that backtracks excessively on adversarial paths.
+    ///
+    /// Patterns the linear-time `regex` engine accepts never backtrack. Others are checked for
+    /// unbounded repetitions whose body can be split ambiguously between iterations.
+    fn backtracks_excessively(&self) -> bool {
+        let FilePattern::Regex(regex) = self else {
+            return false;
+        };
+        if regex::Regex::new(regex.as_str()).is_ok() {
+            return false;
+        }
+
+        fancy_regex::Expr::parse_tree(regex.as_str())
+            .is_ok_and(|tree| has_ambiguous_repeat(&tree.expr))
+    }
+}
+
+/// Whether `expr` contains an unbounded repetition of an ambiguous body, like `(\w+/?)+`.
+///
+/// The backtracking engine tries every way to split a run of characters between the iterations
+/// of such a repetition, which takes exponential time on paths that don't match.
+fn has_ambiguous_repeat(expr: &fancy_regex::Expr) -> bool {
+    use fancy_regex::Expr;
+
+    match expr {
+        Expr::Repeat { child, hi, .. } if *hi == usize::MAX && is_ambiguous_body(child) => true,
+        // Atomic groups never backtrack into their content.
+        Expr::AtomicGroup(_) => false,
+        _ => expr.children_iter().any(has_ambiguous_repeat),
+    }
+}
+
+/// Whether `expr` consists of a variable repetition and otherwise only parts that can match
+/// the empty string.
+fn is_ambiguous_body(expr: &fancy_regex::Expr) -> bool {
+    use fancy_regex::Expr;
+
+    match expr {
+        Expr::Repeat { lo, hi, .. } => hi > lo,
+        Expr::Group(inner) => is_ambiguous_body(inner),
+        Expr::Alt(branches) => {
+            branches.iter().any(is_ambiguous_body) || has_overlapping_literals(branches)
+        }
+        Expr::Concat(items) => items.iter().enumerate().any(|(idx, item)| {
+            is_ambiguous_body(item)
+                && items
+                    .iter()
+                    .enumerate()
+                    .all(|(other, item)| other == idx || matches_empty(item))
+        }),
+        _ => false,
+    }
+}
+
+/// Whether the literal branches of an alternation can spell the same text in more than one
+/// way when repeated, like `a|aa` or `ab|a|ba`.
+///
+/// This is the Sardinas-Patterson test for unique decodability: follow the suffixes left over
+/// when one sequence of branches is a prefix of another, and report an overlap if a leftover
+/// suffix is itself a whole branch.
+fn has_overlapping_literals(branches: &[fancy_regex::Expr]) -> bool {
+    let mut words = branches.iter().filter_map(literal_text).collect::<Vec<_>>();
+    let count = words.len();
+    words.sort_unstable();
+    words.dedup();
+    if words.len() < count {
+        return true;
+    }
+
+    let dangling = |left: &[String], right: &[String]| {
+        let mut suffixes = FxHashSet::default();
+        for l in left {
+            for r in right {
+                if l != r && r.starts_with(l.as_str()) {
+                    suffixes.insert(r[l.len()..].to_string());
+                }
+            }
+        }
+        suffixes
+    };
+
+    let mut seen = FxHashSet::default();
+    let mut suffixes = dangling(&words, &words);
+    while !suffixes.is_empty() {
+        if suffixes.iter().any(|suffix| words.contains(suffix)) {
+            return true;
+        }
+        let current = suffixes
+            .into_iter()
+            .filter(|suffix| seen.insert(suffix.clone()))
+            .collect::<Vec<_>>();
+        suffixes = dangling(&current, &words);
+        suffixes.extend(dangling(&words, &current));
+    }
+    false
+}
+
+/// The text a branch matches, if it is a plain (case-sensitive) literal.
+fn literal_text(expr: &fancy_regex::Expr) -> Option<String> {
+    use fancy_regex::Expr;
+
+    match expr {
+        Expr::Literal { val, casei: false } if !val.is_empty() => Some(val.clone()),
+        Expr::Group(inner) => literal_text(inner),
+        Expr::Concat(items) => items
+            .iter()
+            .map(literal_text)
+            .collect::<Option<Vec<_>>>()
+            .map(|parts| parts.concat()),
+        _ => None,
+    }
+}
+
+fn matches_empty(expr: &fancy_regex::Expr) -> bool {
+    use fancy_regex::Expr;
+
+    match expr {
+        Expr::Empty | Expr::Assertion(_) | Expr::LookAround(..) => true,
+        Expr::Literal { val, .. } => val.is_empty(),
+        Expr::Repeat { child, lo, .. } => *lo == 0 || matches_empty(child),
+        Expr::Group(inner) => matches_empty(inner),
+        Expr::AtomicGroup(inner) => matches_empty(inner),
+        Expr::Concat(items) => items.iter().all(matches_empty),
+        Expr::Alt(branches) => branches.iter().any(matches_empty),
+        _ => false,
+    }
 }
 
 impl Display for FilePattern {
@@ -394,34 +535,103 @@ impl Stage {
     fn from_index(index: u32) -> Self {
         Self::ORDER[index as usize]
     }
+
+    /// The Git hook type that runs this stage, or `None` for `manual`.
+    pub(crate) const fn hook_type(self) -> Option<HookType> {
+        match self {
+            Self::Manual => None,
+            Self::CommitMsg => Some(HookType::CommitMsg),
+            Self::PostCheckout => Some(HookType::PostCheckout),
+            Self::PostCommit => Some(HookType::PostCommit),
+            Self::PostMerge => Some(HookType::PostMerge),
+            Self::PostRewrite => Some(HookType::PostRewrite),
+            Self::PreCommit => Some(HookType::PreCommit),
+            Self::PreMergeCommit => Some(HookType::PreMergeCommit),
+            Self::PrePush => Some(HookType::PrePush),
+            Self::PreRebase => Some(HookType::PreRebase),
+            Self::PrepareCommitMsg => Some(HookType::PrepareCommitMsg),
+        }
+    }
+
+    /// Map a legacy stage name, kept for compatibility with old pre-commit configs, to its stage.
+    ///
+    /// The legacy names are the `serde` aliases of the stages.
+    pub(crate) fn from_legacy_alias(name: &str) -> Option<Self> {
+        let stage =
+            Self::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(name))
+                .ok()?;
+        (stage.as_ref() != name).then_some(stage)
+    }
+
+    /// The legacy name of this stage, the inverse of [`Stage::from_legacy_alias`].
+    pub(crate) const fn legacy_alias(self) -> Option<&'static str> {
+        match self {
+            Self::PreCommit => Some("commit"),
+            Self::PreMergeCommit => Some("merge-commit"),
+            Self::PrePush => Some("push"),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct Stages(u16);
+/// A set of stages.
+///
+/// Stages that were written with their legacy name are remembered, so the config loader can
+/// warn about them; this does not take part in comparisons.
+#[derive(Clone, Copy)]
+pub(crate) struct Stages {
+    bits: u16,
+    legacy: u16,
+}
 
 impl Stages {
     const ALL_BITS: u16 = (1u16 << Stage::COUNT) - 1;
 
-    pub(crate) const ALL: Self = Self(Self::ALL_BITS);
+    pub(crate) const ALL: Self = Self {
+        bits: Self::ALL_BITS,
+        legacy: 0,
+    };
 
     pub(crate) fn iter(self) -> impl Iterator<Item = Stage> {
-        let mut bits = self.0;
-        std::iter::from_fn(move || {
-            if bits == 0 {
-                return None;
-            }
-            let index = bits.trailing_zeros();
-            bits &= bits - 1;
-            Some(Stage::from_index(index))
-        })
+        iter_bits(self.bits)
     }
 
     pub(crate) fn is_empty(self) -> bool {
-        self.0 == 0
+        self.bits == 0
     }
 
     pub(crate) fn contains(self, stage: Stage) -> bool {
-        (self.0 & stage.bit()) != 0
+        (self.bits & stage.bit()) != 0
+    }
+
+    /// The stages written with a legacy name, like `commit`, along with that name.
+    pub(crate) fn legacy_aliases(self) -> impl Iterator<Item = (&'static str, Stage)> {
+        iter_bits(self.legacy).filter_map(|stage| Some((stage.legacy_alias()?, stage)))
+    }
+}
+
+fn iter_bits(mut bits: u16) -> impl Iterator<Item = Stage> {
+    std::iter::from_fn(move || {
+        if bits == 0 {
+            return None;
+        }
+        let index = bits.trailing_zeros();
+        bits &= bits - 1;
+        Some(Stage::from_index(index))
+    })
+}
+
+impl PartialEq for Stages {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl Eq for Stages {}
+
+impl std::hash::Hash for Stages {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
     }
 }
 
@@ -447,7 +657,10 @@ impl std::fmt::Debug for Stages {
 impl From<Vec<Stage>> for Stages {
     fn from(value: Vec<Stage>) -> Self {
         let bits = value.into_iter().fold(0, |bits, stage| bits | stage.bit());
-        Self(bits & Self::ALL_BITS)
+        Self {
+            bits: bits & Self::ALL_BITS,
+            legacy: 0,
+        }
     }
 }
 
@@ -462,8 +675,42 @@ impl<'de> Deserialize<'de> for Stages {
     where
         D: Deserializer<'de>,
     {
-        let stages = Vec::<Stage>::deserialize(deserializer)?;
-        Ok(Self::from(stages))
+        /// A stage, and whether it was written with its legacy name.
+        struct WrittenStage(Stage, bool);
+
+        impl<'de> Deserialize<'de> for WrittenStage {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct WrittenStageVisitor;
+
+                impl Visitor<'_> for WrittenStageVisitor {
+                    type Value = WrittenStage;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a stage name")
+                    }
+
+                    fn visit_str<E: DeError>(self, v: &str) -> Result<WrittenStage, E> {
+                        let stage =
+                            Stage::deserialize(IntoDeserializer::<E>::into_deserializer(v))?;
+                        Ok(WrittenStage(stage, stage.as_ref() != v))
+                    }
+                }
+
+                deserializer.deserialize_str(WrittenStageVisitor)
+            }
+        }
+
+        let mut stages = Self { bits: 0, legacy: 0 };
+        for WrittenStage(stage, legacy) in Vec::<WrittenStage>::deserialize(deserializer)? {
+            stages.bits |= stage.bit();
+            if legacy {
+                stages.legacy |= stage.bit();
+            }
+        }
+        Ok(stages)
     }
 }
 
@@ -542,6 +789,19 @@ pub(crate) enum Shell {
     Cmd,
 }
 
+/// Where a hook process reads its standard input from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename_all = "lowercase"))]
+pub(crate) enum StdinFrom {
+    /// Stdin is empty.
+    #[default]
+    None,
+    /// Run the hook once per matched file, with the file's content on stdin.
+    Files,
+}
+
 /// Common hook options.
 #[derive(Debug, Clone, Default, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -587,9 +847,34 @@ pub(crate) struct HookOptions {
     pub log_file: Option<String>,
     /// Run the hook entry through a predefined shell adapter.
     pub shell: Option<Shell>,
+    /// Feed matched files to the hook on stdin, one invocation per file, instead of as arguments.
+    /// Default is `none`.
+    pub stdin_from: Option<StdinFrom>,
+    /// Skip files whose content has not changed since they last passed this hook.
+    /// Only safe for hooks whose result depends solely on the content of each file.
+    /// Default is false.
+    pub cacheable: Option<bool>,
+    /// Re-run the hook up to this many times when it exits with a non-zero status.
+    /// Not suitable for hooks that modify files, as a retry would see the already fixed files.
+    /// Default is 0.
+    pub retries: Option<u32>,
+    /// Kill the hook and report it as failed when it runs for longer than this many seconds.
+    /// `0` disables the timeout.
+    /// Default is no timeout.
+    pub timeout: Option<u64>,
+    /// Run the hook process in this directory, relative to the config file.
+    /// Filenames passed to the hook are made relative to it.
+    /// Default is the directory of the config file.
+    pub working_directory: Option<String>,
     /// This hook will execute using a single process instead of in parallel.
     /// Default is false.
     pub require_serial: Option<bool>,
+    /// Run this hook alone, with no other hook of the run running at the same time.
+    /// Default is false.
+    pub exclusive: Option<bool>,
+    /// Run the hook inside a container created from this image, with the project mounted at `/src`.
+    /// Only supported for `system` and `script` hooks.
+    pub container_image: Option<String>,
     /// Select which Git hook stages this hook runs for.
     /// Default all stages are selected.
     /// See <https://pre-commit.com/#confining-hooks-to-run-at-certain-stages>.
@@ -633,7 +918,14 @@ impl HookOptions {
             language_version,
             log_file,
             shell,
+            stdin_from,
+            cacheable,
+            retries,
+            timeout,
+            working_directory,
             require_serial,
+            exclusive,
+            container_image,
             stages,
             verbose,
             minimum_prek_version,
@@ -666,13 +958,68 @@ pub(crate) struct ManifestHook {
     pub options: HookOptions,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case")]
-#[serde(transparent)]
+/// The hooks published by a repository in its `.pre-commit-hooks.yaml`.
+///
+/// The manifest is a list of hooks, as in `pre-commit`. It may also be a mapping with the list
+/// under `hooks` and a `minimum_prek_version` that applies to every hook of the repository.
+#[derive(Debug, Clone)]
 pub(crate) struct Manifest {
+    /// The minimum version of prek required to use any hook of this repository.
+    pub minimum_prek_version: Option<String>,
     pub hooks: Vec<ManifestHook>,
 }
 
+impl<'de> Deserialize<'de> for Manifest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        struct ManifestTable {
+            #[serde(deserialize_with = "deserialize_and_validate_minimum_version", default)]
+            minimum_prek_version: Option<String>,
+            hooks: Vec<ManifestHook>,
+        }
+
+        struct ManifestVisitor;
+
+        impl<'de> Visitor<'de> for ManifestVisitor {
+            type Value = Manifest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a list of hooks or a mapping with `hooks`")
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                Ok(Manifest {
+                    minimum_prek_version: None,
+                    hooks: Deserialize::deserialize(serde::de::value::SeqAccessDeserializer::new(
+                        seq,
+                    ))?,
+                })
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let table =
+                    ManifestTable::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                Ok(Manifest {
+                    minimum_prek_version: table.minimum_prek_version,
+                    hooks: table.hooks,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(ManifestVisitor)
+    }
+}
+
 /// A remote hook in the configuration file.
 ///
 /// All keys in manifest hook dict are valid in a config hook dict, but are optional.
@@ -1144,6 +1491,63 @@ pub(crate) struct AutoUpdateOptions {
     pub(crate) cooldown_days: Option<u8>,
 }
 
+/// One or more base configuration files for `extends`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) enum Extends {
+    One(PathBuf),
+    Many(Vec<PathBuf>),
+}
+
+impl Extends {
+    fn paths(&self) -> &[PathBuf] {
+        match self {
+            Self::One(path) => std::slice::from_ref(path),
+            Self::Many(paths) => paths,
+        }
+    }
+}
+
+/// A `default_language_version` entry: one version, or versions per stage.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) enum DefaultLanguageVersion {
+    Version(String),
+    /// Versions keyed by stage. Stages not listed use the hook's own default.
+    Stages(FxHashMap<Stage, String>),
+}
+
+impl DefaultLanguageVersion {
+    /// The version for hooks run at `stage`. Without a stage, only the flat form applies.
+    pub(crate) fn for_stage(&self, stage: Option<Stage>) -> Option<&str> {
+        match self {
+            Self::Version(version) => Some(version),
+            Self::Stages(versions) => stage
+                .and_then(|stage| versions.get(&stage))
+                .map(String::as_str),
+        }
+    }
+
+    /// Every version this entry can resolve to, with `None` for the hook's own default.
+    pub(crate) fn candidates(&self) -> Vec<Option<&str>> {
+        match self {
+            Self::Version(version) => vec![Some(version)],
+            Self::Stages(versions) => {
+                let mut candidates = versions
+                    .values()
+                    .map(|version| Some(version.as_str()))
+                    .collect::<Vec<_>>();
+                candidates.push(None);
+                candidates.sort_unstable();
+                candidates.dedup();
+                candidates
+            }
+        }
+    }
+}
+
 // TODO: warn sensible regex
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1156,6 +1560,10 @@ pub(crate) struct AutoUpdateOptions {
     schemars(extend("x-tombi-toml-version" = "v1.1.0")),
 )]
 pub(crate) struct Config {
+    /// Base configuration files to inherit from, relative to this file.
+    /// Repos from the base files come before the repos declared here, and top-level
+    /// settings declared here override those from the base files.
+    pub extends: Option<Extends>,
     /// Default settings for `prek auto-update` in this project.
     pub auto_update: Option<AutoUpdateOptions>,
     pub repos: Vec<Repo>,
@@ -1163,7 +1571,13 @@ pub(crate) struct Config {
     /// Default is `[pre-commit]`.
     pub default_install_hook_types: Option<Vec<HookType>>,
     /// A mapping from language to the default `language_version`.
-    pub default_language_version: Option<FxHashMap<Language, String>>,
+    /// A language may also map stages to versions, to use a different version per stage.
+    pub default_language_version: Option<FxHashMap<Language, DefaultLanguageVersion>>,
+    /// A mapping from language to the maximum number of hook processes of that language
+    /// that may run at the same time. Heavyweight runtimes like `python` and `node` default
+    /// to half the available concurrency; other languages are only limited by it.
+    /// When projects in a workspace disagree, the lowest limit wins.
+    pub default_language_concurrency: Option<FxHashMap<Language, std::num::NonZeroUsize>>,
     /// A configuration-wide default for the stages property of hooks.
     /// Default to all stages.
     pub default_stages: Option<Stages>,
@@ -1184,10 +1598,106 @@ pub(crate) struct Config {
     /// any parent projects that contain them.
     pub orphan: Option<bool>,
 
+    /// Number of leading `repos` inherited through `extends` rather than declared in this file.
+    #[serde(skip)]
+    inherited_repos: usize,
+
     #[serde(skip_serializing, flatten)]
     _unused_keys: BTreeMap<String, serde_json::Value>,
 }
 
+impl Config {
+    /// The id and options of every hook configured in any repo.
+    fn hook_options(&self) -> impl Iterator<Item = (&str, &HookOptions)> {
+        self.repos
+            .iter()
+            .flat_map(|repo| -> Vec<(&str, &HookOptions)> {
+                match repo {
+                    Repo::Remote(remote) => {
+                        remote.hooks.iter().map(|h| (&*h.id, &h.options)).collect()
+                    }
+                    Repo::Local(local) => {
+                        local.hooks.iter().map(|h| (&*h.id, &h.options)).collect()
+                    }
+                    Repo::Meta(meta) => meta.hooks.iter().map(|h| (&*h.id, &h.options)).collect(),
+                    Repo::Builtin(builtin) => {
+                        builtin.hooks.iter().map(|h| (&*h.id, &h.options)).collect()
+                    }
+                }
+            })
+    }
+
+    /// The repos declared in this config file itself, without those inherited from `extends`.
+    pub(crate) fn declared_repos(&self) -> &[Repo] {
+        &self.repos[self.inherited_repos..]
+    }
+
+    /// The default `language_version` for hooks of `language` run at `stage`.
+    pub(crate) fn language_version_default(
+        &self,
+        language: Language,
+        stage: Option<Stage>,
+    ) -> Option<&str> {
+        self.default_language_version
+            .as_ref()?
+            .get(&language)?
+            .for_stage(stage)
+    }
+
+    /// Resolve relative repo paths against the directory of the config file that declared them.
+    ///
+    /// This ensures paths like `../hook-repo` are resolved from where the config file lives,
+    /// not from the process's current working directory.
+    pub(crate) fn resolve_relative_repos(&mut self, config_dir: &Path) {
+        for repo in &mut self.repos {
+            if let Repo::Remote(remote) = repo {
+                let repo_path = Path::new(&remote.repo);
+                if !remote.repo.starts_with("http://")
+                    && !remote.repo.starts_with("https://")
+                    && repo_path.is_relative()
+                {
+                    let resolved = config_dir.join(repo_path);
+                    if resolved.is_dir() {
+                        remote.repo = resolved.to_string_lossy().into_owned();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Merge a base config into this one: base repos come first, and settings set here win.
+    fn inherit(&mut self, base: Config) {
+        self.inherited_repos = base.repos.len();
+        let mut repos = base.repos;
+        repos.append(&mut self.repos);
+        self.repos = repos;
+
+        if let Some(mut languages) = base.default_language_version {
+            languages.extend(self.default_language_version.take().unwrap_or_default());
+            self.default_language_version = Some(languages);
+        }
+        if let Some(mut languages) = base.default_language_concurrency {
+            languages.extend(self.default_language_concurrency.take().unwrap_or_default());
+            self.default_language_concurrency = Some(languages);
+        }
+
+        self.auto_update = self.auto_update.take().or(base.auto_update);
+        self.default_install_hook_types = self
+            .default_install_hook_types
+            .take()
+            .or(base.default_install_hook_types);
+        self.default_stages = self.default_stages.or(base.default_stages);
+        self.files = self.files.take().or(base.files);
+        self.exclude = self.exclude.take().or(base.exclude);
+        self.fail_fast = self.fail_fast.or(base.fail_fast);
+        self.minimum_prek_version = self
+            .minimum_prek_version
+            .take()
+            .or(base.minimum_prek_version);
+        self.orphan = self.orphan.or(base.orphan);
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
     #[error(transparent)]
@@ -1198,6 +1708,23 @@ pub(crate) enum Error {
 
     #[error("Failed to parse `{0}`")]
     Toml(String, #[source] Box<toml::de::Error>),
+
+    #[error("Base config `{base}` extended by `{config}` does not exist")]
+    MissingBase { base: String, config: String },
+
+    #[error("Config `{0}` extends itself: {1}")]
+    ExtendsCycle(String, String),
+
+    #[error(
+        "`{0}` contains an unresolved merge conflict marker on line {1}, resolve the conflict and try again"
+    )]
+    MergeConflict(String, usize),
+
+    #[error(
+        "Unexpected keys in `{0}`: {keys}",
+        keys = .1.iter().map(|key| format!("`{key}`")).join(", ")
+    )]
+    UnexpectedKeys(String, Vec<String>),
 }
 
 impl Error {
@@ -1310,26 +1837,229 @@ fn warn_unused_paths(path: &Path, entries: &[String]) {
     }
 }
 
-/// Read the configuration file from the given path.
+/// Warn about `files` and `exclude` regexes that could hang a run on unlucky paths.
+fn warn_backtracking_patterns(path: &Path, config: &Config) {
+    let mut patterns = vec![
+        ("top-level `files` regex".to_string(), config.files.as_ref()),
+        (
+            "top-level `exclude` regex".to_string(),
+            config.exclude.as_ref(),
+        ),
+    ];
+    for (id, options) in config.hook_options() {
+        patterns.push((
+            format!("`files` regex of hook `{id}`"),
+            options.files.as_ref(),
+        ));
+        patterns.push((
+            format!("`exclude` regex of hook `{id}`"),
+            options.exclude.as_ref(),
+        ));
+    }
+
+    for (location, pattern) in patterns {
+        if let Some(pattern @ FilePattern::Regex(regex)) = pattern
+            && pattern.backtracks_excessively()
+        {
+            warn_user!(
+                "The {location} in `{}` backtracks excessively on some paths and may hang `prek run`, consider simplifying it: `{}`",
+                path.user_display().cyan(),
+                regex.as_str().yellow()
+            );
+        }
+    }
+}
+
+/// Read the configuration file from the given path, merging in any `extends` base files.
 pub(crate) fn load_config(path: &Path) -> Result<Config, Error> {
-    let content = fs_err::read_to_string(path)?;
+    load_config_with_bases(path, &mut Vec::new(), &mut Vec::new())
+}
 
-    let config = match path.extension() {
-        Some(ext) if ext.eq_ignore_ascii_case("toml") => toml::from_str(&content)
-            .map_err(|e| Error::Toml(path.user_display().to_string(), Box::new(e)))?,
-        _ => serde_saphyr::from_str(&content)
-            .map_err(|e| Error::Yaml(path.user_display().to_string(), Box::new(e)))?,
+/// Load a config and its bases recursively.
+///
+/// `stack` holds the canonical paths currently being loaded, to detect cycles. `loaded` holds
+/// every base already merged, so a base shared by several others is only included once.
+fn load_config_with_bases(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    loaded: &mut Vec<PathBuf>,
+) -> Result<Config, Error> {
+    let mut config = parse_config_file(path)?;
+    let Some(extends) = config.extends.clone() else {
+        return Ok(config);
     };
 
+    let canonical = dunce::canonicalize(path)?;
+    stack.push(canonical);
+
+    let config_dir = path.parent().expect("config file must have a parent");
+    let mut merged: Option<Config> = None;
+    for base_path in extends.paths() {
+        let base_path = config_dir.join(base_path);
+        let Ok(canonical) = dunce::canonicalize(&base_path) else {
+            return Err(Error::MissingBase {
+                base: base_path.user_display().to_string(),
+                config: path.user_display().to_string(),
+            });
+        };
+        if let Some(start) = stack.iter().position(|p| *p == canonical) {
+            let chain = stack[start..]
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| format!("`{}`", p.user_display()))
+                .join(" -> ");
+            return Err(Error::ExtendsCycle(
+                stack[start].user_display().to_string(),
+                chain,
+            ));
+        }
+        if loaded.contains(&canonical) {
+            continue;
+        }
+        loaded.push(canonical);
+
+        let mut base = load_config_with_bases(&base_path, stack, loaded)?;
+        base.resolve_relative_repos(base_path.parent().expect("config file must have a parent"));
+        if let Some(previous) = merged.take() {
+            base.inherit(previous);
+        }
+        merged = Some(base);
+    }
+    stack.pop();
+
+    if let Some(base) = merged {
+        config.inherit(base);
+    }
     Ok(config)
 }
 
+/// Find the first line of a git merge conflict, as left behind by a half-resolved merge.
+///
+/// A `=======` line only counts when a `>>>>>>>` marker follows, since it is otherwise valid text.
+fn find_merge_conflict(content: &str) -> Option<usize> {
+    let is_marker = |line: &str, marker: &str| {
+        line.strip_prefix(marker)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    };
+
+    let mut separator = None;
+    for (idx, line) in content.lines().enumerate() {
+        let line_number = idx + 1;
+        if is_marker(line, "<<<<<<<") {
+            return Some(line_number);
+        }
+        if is_marker(line, ">>>>>>>") {
+            return Some(separator.unwrap_or(line_number));
+        }
+        if line.trim_end() == "=======" {
+            separator.get_or_insert(line_number);
+        }
+    }
+    None
+}
+
+fn parse_config_content<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    content: &str,
+) -> Result<T, Error> {
+    match path.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("toml") => toml::from_str(content)
+            .map_err(|e| Error::Toml(path.user_display().to_string(), Box::new(e))),
+        _ => serde_saphyr::from_str(content)
+            .map_err(|e| Error::Yaml(path.user_display().to_string(), Box::new(e))),
+    }
+}
+
+fn parse_config_file(path: &Path) -> Result<Config, Error> {
+    let content = fs_err::read_to_string(path)?;
+
+    if let Some(line) = find_merge_conflict(&content) {
+        return Err(Error::MergeConflict(path.user_display().to_string(), line));
+    }
+
+    parse_config_content(path, &content)
+}
+
+/// Warn about legacy stage aliases, such as `commit` for `pre-commit`.
+fn warn_legacy_stage_aliases(path: &Path, config: &Config) {
+    let aliases = config
+        .default_stages
+        .into_iter()
+        .chain(
+            config
+                .hook_options()
+                .filter_map(|(_, options)| options.stages),
+        )
+        .flat_map(Stages::legacy_aliases)
+        .unique()
+        .map(|(alias, stage)| format!("`{}` (use `{}`)", alias.yellow(), stage.cyan()))
+        .join(", ");
+    if aliases.is_empty() {
+        return;
+    }
+    warn_user!(
+        "Deprecated stage names in `{}`: {aliases}",
+        path.user_display().cyan()
+    );
+}
+
+/// Warn about hooks that ask for filenames but only run at stages that never pass any.
+fn warn_hooks_without_file_stages(path: &Path, config: &Config) {
+    for (id, options) in config.hook_options() {
+        if !matches!(
+            options.pass_filenames,
+            Some(PassFilenames::All | PassFilenames::Limited(_))
+        ) {
+            continue;
+        }
+        let Some(stages) = options.stages.or(config.default_stages) else {
+            continue;
+        };
+        if !stages.is_empty()
+            && !stages
+                .iter()
+                .any(|stage| RunInputMode::from(stage) != RunInputMode::NoFiles)
+        {
+            warn_user!(
+                "Hook `{}` in `{}` sets `pass_filenames` but only runs at stages without file input ({}), so it will never receive files",
+                id.cyan(),
+                path.user_display().cyan(),
+                stages
+            );
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Set while loading configs for a `--frozen` command, which rejects mutable revs with
+    /// an error of its own instead of the usual warning.
+    pub(crate) static FROZEN_REVS: ();
+}
+
 /// Read the configuration file from the given path, and warn about certain issues.
 pub(crate) fn read_config(path: &Path) -> Result<Config, Error> {
+    read_config_impl(path, false)
+}
+
+/// Like [`read_config`], but unexpected keys are an error instead of a warning.
+pub(crate) fn read_config_strict(path: &Path) -> Result<Config, Error> {
+    read_config_impl(path, true)
+}
+
+fn read_config_impl(path: &Path, strict: bool) -> Result<Config, Error> {
     let config = load_config(path)?;
 
     let unused_paths = collect_unused_paths(&config);
+    if strict && !unused_paths.is_empty() {
+        return Err(Error::UnexpectedKeys(
+            path.user_display().to_string(),
+            unused_paths,
+        ));
+    }
     warn_unused_paths(path, &unused_paths);
+    warn_backtracking_patterns(path, &config);
+    warn_legacy_stage_aliases(path, &config);
+    warn_hooks_without_file_stages(path, &config);
 
     // Check for mutable revs and warn the user.
     let repos_has_mutable_rev = config
@@ -1337,17 +2067,14 @@ pub(crate) fn read_config(path: &Path) -> Result<Config, Error> {
         .iter()
         .filter_map(|repo| {
             if let Repo::Remote(repo) = repo {
-                let rev = &repo.rev;
-                // A rev is considered mutable if it doesn't contain a '.' (like a version)
-                // and is not a hexadecimal string (like a commit SHA).
-                if !rev.contains('.') && !looks_like_sha(rev) {
+                if is_mutable_rev(&repo.rev) {
                     return Some(repo);
                 }
             }
             None
         })
         .collect::<Vec<_>>();
-    if !repos_has_mutable_rev.is_empty() {
+    if !repos_has_mutable_rev.is_empty() && FROZEN_REVS.try_with(|()| ()).is_err() {
         let msg = repos_has_mutable_rev
             .iter()
             .map(|repo| format!("{}: {}", repo.repo.cyan(), repo.rev.yellow()))
@@ -1379,6 +2106,14 @@ pub(crate) fn read_manifest(path: &Path) -> Result<Manifest, Error> {
     Ok(manifest)
 }
 
+/// Check if a `rev` looks like a moving reference (a branch or a floating tag).
+///
+/// A rev is considered mutable if it doesn't contain a '.' (like a version)
+/// and is not a hexadecimal string (like a commit SHA).
+pub(crate) fn is_mutable_rev(rev: &str) -> bool {
+    !rev.contains('.') && !looks_like_sha(rev)
+}
+
 /// Check if a string looks like a git SHA-1.
 pub(crate) fn looks_like_sha(s: &str) -> bool {
     !s.is_empty() && s.as_bytes().iter().all(u8::is_ascii_hexdigit)
@@ -1427,6 +2162,55 @@ mod tests {
         "current version `[CURRENT_VERSION]`",
     );
 
+    #[test]
+    fn merge_conflict_markers() {
+        let conflicted = indoc::indoc! {r"
+            repos:
+            <<<<<<< HEAD
+              - repo: https://github.com/pre-commit/pre-commit-hooks
+                rev: v5.0.0
+            =======
+              - repo: https://github.com/pre-commit/pre-commit-hooks
+                rev: v6.0.0
+            >>>>>>> feature
+                hooks:
+                  - id: trailing-whitespace
+        "};
+        assert_eq!(find_merge_conflict(conflicted), Some(2));
+
+        // The opening marker was removed, but the rest was left behind.
+        let half_resolved = "repos: []\n=======\nfiles: x\n>>>>>>> feature\n";
+        assert_eq!(find_merge_conflict(half_resolved), Some(2));
+
+        // A lone separator is valid text, e.g. inside a block scalar.
+        let clean = "repos: []\ndescription: |\n  Title\n  =======\n";
+        assert_eq!(find_merge_conflict(clean), None);
+        assert_eq!(find_merge_conflict("files: '<<<<<<<<'\n"), None);
+    }
+
+    #[test]
+    fn regex_backtracking_check() {
+        let backtracks = |pattern: &str| {
+            FilePattern::regex(pattern)
+                .unwrap()
+                .backtracks_excessively()
+        };
+
+        // Plain and well-behaved fancy patterns are fine.
+        assert!(!backtracks(r"^(a+)+$"));
+        assert!(!backtracks(r"^(?!vendor/).*\.py$"));
+        assert!(!backtracks(r"(?<!test_)\w+\.py$"));
+        assert!(!backtracks(r"^(?:\w+/)*(?<!test_)\w+\.py$"));
+        assert!(!backtracks(r"^(?>\w+/?)+(?<!/)\.py$"));
+        // Nested quantifiers that the backtracking engine has to explore.
+        assert!(backtracks(r"^(\w+/?)+(?<!/)\.py$"));
+        assert!(backtracks(r"^(?!vendor/)(?:a*|b)*c$"));
+        // Alternatives that can split the same text in several ways.
+        assert!(backtracks(r"^(a|aa)+\1$"));
+        assert!(backtracks(r"^(?:ab|a|ba)+(?<!x)$"));
+        assert!(!backtracks(r"^(?:ab|cd)+(?<!x)$"));
+    }
+
     #[test]
     fn stages_deserialize_empty_as_empty() {
         #[derive(Debug, Deserialize)]
@@ -1440,6 +2224,37 @@ mod tests {
         assert!(!parsed.stages.contains(Stage::PreCommit));
     }
 
+    #[test]
+    fn stages_deserialize_legacy_aliases() {
+        #[derive(Debug, Deserialize)]
+        struct Wrapper {
+            stages: Stages,
+        }
+
+        let parsed: Wrapper = serde_saphyr::from_str("stages: [commit, push, manual]\n")
+            .expect("legacy stage aliases should parse");
+        assert_eq!(
+            parsed.stages,
+            Stages::from([Stage::PreCommit, Stage::PrePush, Stage::Manual])
+        );
+
+        assert_eq!(
+            Stage::from_legacy_alias("merge-commit"),
+            Some(Stage::PreMergeCommit)
+        );
+        assert_eq!(Stage::from_legacy_alias("pre-commit"), None);
+        for stage in Stage::ORDER {
+            if let Some(alias) = stage.legacy_alias() {
+                assert_eq!(Stage::from_legacy_alias(alias), Some(stage));
+            }
+        }
+        assert_eq!(
+            parsed.stages.legacy_aliases().collect::<Vec<_>>(),
+            [("commit", Stage::PreCommit), ("push", Stage::PrePush)]
+        );
+        assert_eq!(Stage::from_legacy_alias("unknown"), None);
+    }
+
     #[test]
     fn config_default_stages_deserialize_empty_as_empty() {
         let parsed: Config =
@@ -1593,6 +2408,30 @@ mod tests {
         assert!(!pattern.is_match(Path::new("")));
     }
 
+    #[test]
+    fn glob_list_with_negation() {
+        let pattern = serde_saphyr::from_str::<FilePattern>(indoc::indoc! {r#"
+            glob: ["src/**/*.rs", "!src/**/generated_*.rs"]
+        "#})
+        .unwrap();
+        assert!(pattern.is_match(Path::new("src/main.rs")));
+        assert!(pattern.is_match(Path::new("src/nested/lib.rs")));
+        assert!(!pattern.is_match(Path::new("src/generated_api.rs")));
+        assert!(!pattern.is_match(Path::new("src/nested/generated_types.rs")));
+        assert!(!pattern.is_match(Path::new("tests/main.rs")));
+        assert_eq!(
+            pattern.to_string(),
+            "glob: [src/**/*.rs, !src/**/generated_*.rs]"
+        );
+    }
+
+    #[test]
+    fn glob_list_with_only_negation_matches_nothing() {
+        let pattern = FilePattern::glob(vec!["!*.rs".to_string()]).unwrap();
+        assert!(!pattern.is_match(Path::new("main.rs")));
+        assert!(!pattern.is_match(Path::new("README.md")));
+    }
+
     #[test]
     fn invalid_glob_pattern_errors() {
         let err = serde_saphyr::from_str::<FilePattern>("glob: \"[\"")
@@ -1937,6 +2776,54 @@ mod tests {
         insta::assert_debug_snapshot!(result);
     }
 
+    #[test]
+    fn default_language_version_per_stage() -> Result<()> {
+        let yaml = indoc::indoc! { r"
+            default_language_version:
+              node: '22'
+              python:
+                pre-commit: '3.11'
+                push: '3.13'
+            repos: []
+        "};
+        let config = serde_saphyr::from_str::<Config>(yaml)?;
+
+        assert_eq!(
+            config.language_version_default(Language::Node, Some(Stage::PrePush)),
+            Some("22")
+        );
+        assert_eq!(
+            config.language_version_default(Language::Python, Some(Stage::PreCommit)),
+            Some("3.11")
+        );
+        assert_eq!(
+            config.language_version_default(Language::Python, Some(Stage::PrePush)),
+            Some("3.13")
+        );
+        assert_eq!(
+            config.language_version_default(Language::Python, Some(Stage::Manual)),
+            None
+        );
+        assert_eq!(
+            config.language_version_default(Language::Python, None),
+            None
+        );
+        assert_eq!(
+            config.default_language_version.unwrap()[&Language::Python].candidates(),
+            vec![None, Some("3.11"), Some("3.13")]
+        );
+
+        let yaml = indoc::indoc! { r"
+            default_language_version:
+              python:
+                not-a-stage: '3.11'
+            repos: []
+        "};
+        assert!(serde_saphyr::from_str::<Config>(yaml).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_yaml_config() -> Result<()> {
         let config = read_config(Path::new("tests/fixtures/uv-pre-commit-config.yaml"))?;
@@ -2131,6 +3018,36 @@ mod tests {
               |
             ");
         });
+
+        // Test that a manifest mapping can set minimum_prek_version for the whole repo
+        let yaml = indoc::indoc! {r"
+            minimum_prek_version: '0.0.1'
+            hooks:
+              - id: test-hook
+                name: Test Hook
+                entry: echo test
+                language: system
+        "};
+        let manifest = serde_saphyr::from_str::<Manifest>(yaml).unwrap();
+        assert_eq!(manifest.minimum_prek_version.as_deref(), Some("0.0.1"));
+        assert_eq!(manifest.hooks.len(), 1);
+
+        let yaml = indoc::indoc! {r"
+            minimum_prek_version: '10.0.0'
+            hooks: []
+        "};
+        let err = serde_saphyr::from_str::<Manifest>(yaml).unwrap_err();
+        insta::with_settings!({ filters => vec![VERSION_FILTER] }, {
+            insta::assert_snapshot!(err, @"
+            error: line 1 column 23: Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
+             --> <input>:1:23
+              |
+            1 | minimum_prek_version: '10.0.0'
+              |                       ^ Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
+            2 | hooks: []
+              |
+            ");
+        });
     }
 
     #[test]
@@ -2390,4 +3307,96 @@ mod tests {
         let result = serde_saphyr::from_str::<Config>(yaml);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn extends_merges_base_configs() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let shared = dir.path().join("shared");
+        fs_err::create_dir_all(shared.join("hook-repo"))?;
+        fs_err::write(
+            shared.join("base.yaml"),
+            indoc::indoc! {r"
+            fail_fast: true
+            default_language_version:
+              python: '3.11'
+              node: '20'
+            repos:
+              - repo: ./hook-repo
+                rev: v1.0.0
+                hooks:
+                  - id: base-remote
+              - repo: local
+                hooks:
+                  - id: base-local
+                    name: base local
+                    entry: echo
+                    language: system
+            "},
+        )?;
+        let config_path = dir.path().join(".pre-commit-config.yaml");
+        fs_err::write(
+            &config_path,
+            indoc::indoc! {r"
+            extends: shared/base.yaml
+            fail_fast: false
+            default_language_version:
+              node: '22'
+            repos:
+              - repo: local
+                hooks:
+                  - id: child-local
+                    name: child local
+                    entry: echo
+                    language: system
+            "},
+        )?;
+
+        let config = load_config(&config_path)?;
+        assert_eq!(config.fail_fast, Some(false));
+        assert_eq!(
+            config.language_version_default(Language::Python, None),
+            Some("3.11")
+        );
+        assert_eq!(
+            config.language_version_default(Language::Node, None),
+            Some("22")
+        );
+
+        assert_eq!(config.repos.len(), 3);
+        let Repo::Remote(remote) = &config.repos[0] else {
+            panic!("expected a remote repo");
+        };
+        // Relative repo paths stay anchored to the base file that declared them.
+        assert_eq!(Path::new(&remote.repo), shared.join("hook-repo").as_path());
+        let Repo::Local(local) = &config.repos[2] else {
+            panic!("expected a local repo");
+        };
+        assert_eq!(local.hooks[0].id, "child-local");
+
+        Ok(())
+    }
+
+    #[test]
+    fn extends_rejects_cycles() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::write(dir.path().join("a.yaml"), "extends: b.yaml\nrepos: []\n")?;
+        fs_err::write(dir.path().join("b.yaml"), "extends: [a.yaml]\nrepos: []\n")?;
+
+        let err = load_config(&dir.path().join("a.yaml")).unwrap_err();
+        assert!(matches!(err, Error::ExtendsCycle(..)), "{err}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn extends_missing_base() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config_path = dir.path().join(".pre-commit-config.yaml");
+        fs_err::write(&config_path, "extends: missing.yaml\nrepos: []\n")?;
+
+        let err = load_config(&config_path).unwrap_err();
+        assert!(matches!(err, Error::MissingBase { .. }), "{err}");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,279 @@
+//! Expansion of `$VAR`, `${VAR}` and `${VAR:-default}` references in hook `entry` and `env`.
+//!
+//! Unset variables without a default expand to an empty string, and `$$` is a literal `$`.
+//! A `$` that does not start a valid reference (for example `$1` or `$@`) is kept as is.
+
+use std::borrow::Cow;
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+enum Reference<'a> {
+    /// `$$`, which expands to a literal `$`.
+    Escape,
+    Var {
+        name: &'a str,
+        default: Option<&'a str>,
+    },
+}
+
+impl Reference<'_> {
+    fn expand(&self, lookup: &impl Fn(&str) -> Option<String>) -> String {
+        match self {
+            Self::Escape => "$".to_string(),
+            Self::Var { name, default } => match (lookup(name), default) {
+                // Like the shell, `:-` also applies to variables that are set but empty.
+                (Some(value), Some(default)) if value.is_empty() => (*default).to_string(),
+                (Some(value), _) => value,
+                (None, Some(default)) => (*default).to_string(),
+                (None, None) => {
+                    debug!("Environment variable `{name}` is not set, expanding to empty");
+                    String::new()
+                }
+            },
+        }
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse the reference at the start of `input`, which must begin with `$`.
+///
+/// Returns the reference and its length in bytes, or `None` if the `$` is literal.
+fn parse_reference(input: &str) -> Option<(Reference<'_>, usize)> {
+    let rest = input.strip_prefix('$')?;
+    if rest.starts_with('$') {
+        return Some((Reference::Escape, 2));
+    }
+
+    if let Some(braced) = rest.strip_prefix('{') {
+        let end = braced.find('}')?;
+        let body = &braced[..end];
+        let (name, default) = match body.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (body, None),
+        };
+        return is_name(name).then_some((Reference::Var { name, default }, end + 3));
+    }
+
+    let len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..len];
+    is_name(name).then_some((
+        Reference::Var {
+            name,
+            default: None,
+        },
+        len + 1,
+    ))
+}
+
+/// Expand variable references in a plain string, such as an `env` value.
+pub(crate) fn expand(input: &str, lookup: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    if !input.contains('$') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(pos) = rest.find('$') {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some((reference, len)) = parse_reference(rest) {
+            output.push_str(&reference.expand(&lookup));
+            rest = &rest[len..];
+        } else {
+            output.push('$');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+    Cow::Owned(output)
+}
+
+/// Expand variable references in a shell-style command line before it is split into words.
+///
+/// Only unquoted references are expanded. Quoted text is left untouched, so entries like
+/// `bash -c 'echo "$1"'` or `bash -c "for f in \"$@\"; do echo $f; done"` reach the inner
+/// shell as written. Expanded values are quoted so they stay a single word.
+pub(crate) fn expand_words(input: &str, lookup: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    if !input.contains('$') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut chars = input.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '\'' if !in_double => {
+                in_single = !in_single;
+                output.push(c);
+            }
+            '"' if !in_single => {
+                in_double = !in_double;
+                output.push(c);
+            }
+            '\\' if !in_single => {
+                output.push(c);
+                if let Some((_, next)) = chars.next() {
+                    output.push(next);
+                }
+            }
+            '$' if !in_single && !in_double => {
+                let Some((reference, len)) = parse_reference(&input[pos..]) else {
+                    output.push(c);
+                    continue;
+                };
+                if matches!(reference, Reference::Escape) {
+                    output.push('$');
+                } else {
+                    let value = reference.expand(&lookup);
+                    if !value.is_empty() {
+                        // Quote so the value stays one word; NUL bytes cannot be quoted.
+                        output.push_str(&shlex::try_quote(&value).unwrap_or(Cow::Borrowed(&value)));
+                    }
+                }
+                // Skip the rest of the reference; `pos` is at the `$`.
+                while chars.peek().is_some_and(|(next, _)| *next < pos + len) {
+                    chars.next();
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Look up a variable in the process environment.
+///
+/// Unlike `EnvVars::var`, this does not fall back to `PRE_COMMIT_*` names: references in
+/// user config should see exactly what the process environment holds.
+pub(crate) fn process_var(name: &str) -> Option<String> {
+    #[allow(clippy::disallowed_methods)]
+    std::env::var(name).ok()
+}
+
+/// Expand hook `env` values against the process environment.
+///
+/// A value may reference other keys of the same map, which are themselves expanded against
+/// the process environment only. A key referencing itself sees the process value, so
+/// `PATH: "$PATH:/extra"` extends the inherited `PATH`.
+pub(crate) fn expand_env(env: &FxHashMap<String, String>) -> FxHashMap<String, String> {
+    expand_env_with(env, process_var)
+}
+
+/// Expand hook `env` values against `base`, which is consulted instead of the process
+/// environment.
+///
+/// This lets values reference variables prek sets for the hook's language environment,
+/// such as `$VIRTUAL_ENV` or the hook's own `$PATH`.
+pub(crate) fn expand_env_with(
+    env: &FxHashMap<String, String>,
+    base: impl Fn(&str) -> Option<String>,
+) -> FxHashMap<String, String> {
+    let siblings: FxHashMap<&str, Cow<'_, str>> = env
+        .iter()
+        .map(|(key, value)| (key.as_str(), expand(value, &base)))
+        .collect();
+
+    env.iter()
+        .map(|(key, value)| {
+            let value = expand(value, |name| {
+                if name == key {
+                    return base(name);
+                }
+                siblings
+                    .get(name)
+                    .map(ToString::to_string)
+                    .or_else(|| base(name))
+            });
+            (key.clone(), value.into_owned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/user".to_string()),
+            "SPACED" => Some("a b".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expand_plain() {
+        assert_eq!(expand("no vars", lookup), "no vars");
+        assert_eq!(expand("$HOME/bin", lookup), "/home/user/bin");
+        assert_eq!(expand("${HOME}bin", lookup), "/home/userbin");
+        assert_eq!(expand("${MISSING:-/opt}/bin", lookup), "/opt/bin");
+        assert_eq!(expand("${EMPTY:-fallback}", lookup), "fallback");
+        assert_eq!(expand("${HOME:-/opt}", lookup), "/home/user");
+        assert_eq!(expand("x${MISSING}y$MISSING", lookup), "xy");
+        assert_eq!(expand("cost: $$5", lookup), "cost: $5");
+        assert_eq!(expand("$$HOME", lookup), "$HOME");
+        assert_eq!(expand("$1 $@ $ ${", lookup), "$1 $@ $ ${");
+    }
+
+    #[test]
+    fn expand_shell_words() {
+        assert_eq!(
+            expand_words("$HOME/bin/lint --flag", lookup),
+            "/home/user/bin/lint --flag"
+        );
+        // Quoted text is left for the hook's own shell.
+        assert_eq!(
+            expand_words(r#"bash -c 'echo $HOME' "$HOME" $HOME"#, lookup),
+            r#"bash -c 'echo $HOME' "$HOME" /home/user"#
+        );
+        let entry = r#"bash -c "for f in \"$@\"; do echo $f ${HOME}; done" --"#;
+        assert_eq!(expand_words(entry, lookup), entry);
+        assert_eq!(
+            expand_words(r#"sh -c "echo $1 $HOME" $HOME"#, lookup),
+            r#"sh -c "echo $1 $HOME" /home/user"#
+        );
+        // Values containing spaces stay one word.
+        let expanded = expand_words("echo $SPACED ${SPACED}", lookup);
+        assert_eq!(shlex::split(&expanded).unwrap(), vec!["echo", "a b", "a b"]);
+        assert_eq!(expand_words(r"echo \$HOME $$", lookup), r"echo \$HOME $");
+    }
+
+    #[test]
+    fn expand_env_references_siblings() {
+        let env = FxHashMap::from_iter([
+            (
+                "TOOL_HOME".to_string(),
+                "${PREK_TEST_UNSET_VAR:-/opt/tool}".to_string(),
+            ),
+            ("TOOL_BIN".to_string(), "$TOOL_HOME/bin".to_string()),
+        ]);
+        let expanded = expand_env(&env);
+        assert_eq!(expanded["TOOL_HOME"], "/opt/tool");
+        assert_eq!(expanded["TOOL_BIN"], "/opt/tool/bin");
+    }
+
+    #[test]
+    fn expand_env_with_base() {
+        let env = FxHashMap::from_iter([
+            ("PATH".to_string(), "$PATH:/extra".to_string()),
+            ("MYPYPATH".to_string(), "$VIRTUAL_ENV/stubs".to_string()),
+        ]);
+        let expanded = expand_env_with(&env, |name| match name {
+            "PATH" => Some("/venv/bin:/usr/bin".to_string()),
+            "VIRTUAL_ENV" => Some("/venv".to_string()),
+            _ => None,
+        });
+        assert_eq!(expanded["PATH"], "/venv/bin:/usr/bin:/extra");
+        assert_eq!(expanded["MYPYPATH"], "/venv/stubs");
+    }
+}
@@ -183,6 +183,28 @@ impl LockedFile {
 
         Ok(Self { file, path })
     }
+
+    /// Acquire a shared lock using a file at the provided path.
+    ///
+    /// Any number of shared locks can be held at once; they only exclude [`LockedFile::try_acquire`].
+    pub fn acquire_shared_blocking(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let path = path.as_ref().to_path_buf();
+        let file = fs_err::File::create(&path)?;
+        file.file().lock_shared()?;
+        trace!(path = %path.display(), "Acquired shared lock");
+        Ok(Self { file, path })
+    }
+
+    /// Try to acquire an exclusive lock without waiting, returning `None` if the lock is held.
+    pub fn try_acquire(path: impl AsRef<Path>) -> Result<Option<Self>, std::io::Error> {
+        let path = path.as_ref().to_path_buf();
+        let file = fs_err::File::create(&path)?;
+        match file.file().try_lock() {
+            Ok(()) => Ok(Some(Self { file, path })),
+            Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+            Err(std::fs::TryLockError::Error(err)) => Err(err),
+        }
+    }
 }
 
 impl Drop for LockedFile {
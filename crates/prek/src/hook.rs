@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::BTreeSet;
+use std::ffi::OsStr;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
@@ -16,8 +17,10 @@ use tracing::trace;
 
 use crate::config::{
     self, BuiltinHook, Config, FilePattern, HookOptions, Language, LocalHook, ManifestHook,
-    MetaHook, PassFilenames, RemoteHook, Stages, read_manifest,
+    MetaHook, PassFilenames, RemoteHook, Stage, Stages, StdinFrom, read_manifest,
 };
+use crate::env_expand;
+use crate::fs::{LockedFile, PathClean};
 use crate::hook_entry::HookEntry;
 use crate::languages::version::LanguageRequest;
 use crate::languages::{ShellSupport, extract_metadata};
@@ -80,13 +83,12 @@ impl HookSpec {
         self.options.update(&config.options);
     }
 
-    pub(crate) fn apply_project_defaults(&mut self, config: &Config) {
-        let language = self.language;
+    /// Fill in project-wide defaults, resolving per-stage language versions for `stage`.
+    pub(crate) fn apply_project_defaults(&mut self, config: &Config, stage: Option<Stage>) {
         if self.options.language_version.is_none() {
             self.options.language_version = config
-                .default_language_version
-                .as_ref()
-                .and_then(|v| v.get(&language).cloned());
+                .language_version_default(self.language, stage)
+                .map(ToString::to_string);
         }
 
         if self
@@ -223,15 +225,23 @@ impl Repo {
         }
     }
 
-    /// Get a hook by id.
-    pub(crate) fn get_hook(&self, id: &str) -> Option<&HookSpec> {
-        let hooks = match self {
+    fn hooks(&self) -> &[HookSpec] {
+        match self {
             Repo::Remote { hooks, .. } => hooks,
             Repo::Local { hooks } => hooks,
             Repo::Meta { hooks } => hooks,
             Repo::Builtin { hooks } => hooks,
-        };
-        hooks.iter().find(|hook| hook.id == id)
+        }
+    }
+
+    /// Get a hook by id.
+    pub(crate) fn get_hook(&self, id: &str) -> Option<&HookSpec> {
+        self.hooks().iter().find(|hook| hook.id == id)
+    }
+
+    /// Ids of the hooks provided by the repo, in manifest order.
+    pub(crate) fn hook_ids(&self) -> impl Iterator<Item = &str> {
+        self.hooks().iter().map(|hook| hook.id.as_str())
     }
 }
 
@@ -252,6 +262,8 @@ pub(crate) struct HookBuilder {
     hook_spec: HookSpec,
     // The index of the hook in the project configuration.
     idx: usize,
+    // The stage being run, for stage-specific defaults.
+    stage: Option<Stage>,
 }
 
 impl HookBuilder {
@@ -266,9 +278,17 @@ impl HookBuilder {
             repo,
             hook_spec,
             idx,
+            stage: None,
         }
     }
 
+    /// Resolve stage-specific project defaults for hooks run at `stage`.
+    #[must_use]
+    pub(crate) fn with_stage(mut self, stage: Option<Stage>) -> Self {
+        self.stage = stage;
+        self
+    }
+
     /// Check the hook configuration.
     fn check(&self) -> Result<(), Error> {
         let language = self.hook_spec.language;
@@ -276,6 +296,8 @@ impl HookBuilder {
             language_version,
             additional_dependencies,
             shell,
+            working_directory,
+            container_image,
             ..
         } = &self.hook_spec.options;
         let additional_dependencies = additional_dependencies
@@ -304,6 +326,17 @@ impl HookBuilder {
                     ),
                 });
             }
+
+            for dependency in additional_dependencies {
+                language
+                    .check_dependency(dependency)
+                    .map_err(|err| Error::Hook {
+                        hook: self.hook_spec.id.clone(),
+                        error: err.context(format!(
+                            "Invalid `additional_dependencies` entry `{dependency}` for language `{language}`"
+                        )),
+                    })?;
+            }
         }
 
         if !language.supports_language_version() {
@@ -319,6 +352,28 @@ impl HookBuilder {
             }
         }
 
+        if working_directory.is_some() {
+            match self.repo.as_ref() {
+                Repo::Meta { .. } => {
+                    return Err(Error::Hook {
+                        hook: self.hook_spec.id.clone(),
+                        error: anyhow::anyhow!(
+                            "Hook specified `working_directory` but meta hooks always run in the workspace root",
+                        ),
+                    });
+                }
+                Repo::Builtin { .. } => {
+                    return Err(Error::Hook {
+                        hook: self.hook_spec.id.clone(),
+                        error: anyhow::anyhow!(
+                            "Hook specified `working_directory` but builtin hooks always run in the workspace root",
+                        ),
+                    });
+                }
+                Repo::Remote { .. } | Repo::Local { .. } => {}
+            }
+        }
+
         if shell.is_some() {
             match self.repo.as_ref() {
                 Repo::Meta { .. } => {
@@ -350,12 +405,40 @@ impl HookBuilder {
             }
         }
 
+        if container_image.is_some() {
+            if !matches!(language, Language::System | Language::Script) {
+                return Err(Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!(
+                        "Hook specified `container_image` but the language `{language}` does not support running in a container, only `system` and `script` do",
+                    ),
+                });
+            }
+            if shell.is_some() {
+                return Err(Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!(
+                        "Hook specified both `container_image` and `shell`, which cannot be combined",
+                    ),
+                });
+            }
+            if language == Language::Script && matches!(self.repo.as_ref(), Repo::Remote { .. }) {
+                return Err(Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!(
+                        "Hook specified `container_image` but remote `script` hooks cannot run in a container, as the script is not inside the mounted project",
+                    ),
+                });
+            }
+        }
+
         Ok(())
     }
 
     /// Build the hook.
     pub(crate) async fn build(mut self) -> Result<Hook, Error> {
-        self.hook_spec.apply_project_defaults(self.project.config());
+        self.hook_spec
+            .apply_project_defaults(self.project.config(), self.stage);
 
         self.check()?;
 
@@ -377,7 +460,14 @@ impl HookBuilder {
         let always_run = options.always_run.unwrap_or(false);
         let fail_fast = options.fail_fast.unwrap_or(false);
         let pass_filenames = options.pass_filenames.unwrap_or(PassFilenames::All);
+        let stdin_from = options.stdin_from.unwrap_or_default();
+        let cacheable = options.cacheable.unwrap_or(false);
+        let retries = options.retries.unwrap_or(0);
+        let working_directory = options
+            .working_directory
+            .map(|dir| self.project.path().join(dir).clean());
         let require_serial = options.require_serial.unwrap_or(false);
+        let exclusive = options.exclusive.unwrap_or(false);
         let verbose = options.verbose.unwrap_or(false);
         let stages = options.stages.unwrap_or(Stages::ALL);
         let shell = options.shell;
@@ -392,7 +482,12 @@ impl HookBuilder {
             error: anyhow::anyhow!(e),
         })?;
 
-        let entry = HookEntry::new(self.hook_spec.id.clone(), self.hook_spec.entry, shell);
+        let entry = HookEntry::new(
+            self.hook_spec.id.clone(),
+            self.hook_spec.entry,
+            shell,
+            env.clone(),
+        );
 
         let priority = self
             .hook_spec
@@ -412,6 +507,7 @@ impl HookBuilder {
             groups,
             entry,
             stages,
+            language_version,
             language_request,
             additional_dependencies,
             alias,
@@ -423,7 +519,14 @@ impl HookBuilder {
             always_run,
             fail_fast,
             pass_filenames,
+            stdin_from,
+            cacheable,
+            retries,
+            timeout: options.timeout,
+            working_directory,
             require_serial,
+            exclusive,
+            container_image: options.container_image,
             verbose,
             files: options.files,
             exclude: options.exclude,
@@ -471,10 +574,23 @@ pub(crate) struct Hook {
     pub always_run: bool,
     pub fail_fast: bool,
     pub pass_filenames: PassFilenames,
+    pub stdin_from: StdinFrom,
+    pub cacheable: bool,
+    pub retries: u32,
+    /// Seconds the hook may run for, `0` meaning no timeout.
+    pub timeout: Option<u64>,
+    /// The absolute directory to run the hook process in, if not the project root.
+    pub working_directory: Option<PathBuf>,
     pub description: Option<String>,
+    /// The configured `language_version`, empty if unset.
+    pub language_version: String,
     pub language_request: LanguageRequest,
     pub log_file: Option<String>,
     pub require_serial: bool,
+    /// Run without any other hook running concurrently, across all projects.
+    pub exclusive: bool,
+    /// The image to run the hook in, instead of on the host.
+    pub container_image: Option<String>,
     pub stages: Stages,
     pub verbose: bool,
     pub minimum_prek_version: Option<String>,
@@ -516,8 +632,12 @@ impl Hook {
     }
 
     /// Get the path where the hook should be executed.
+    ///
+    /// This is the project root unless the hook sets `working_directory`.
     pub(crate) fn work_dir(&self) -> &Path {
-        self.project.path()
+        self.working_directory
+            .as_deref()
+            .unwrap_or_else(|| self.project.path())
     }
 
     pub(crate) fn is_remote(&self) -> bool {
@@ -557,6 +677,21 @@ impl Hook {
         })
     }
 
+    /// The hook's `env` with `$VAR` references expanded, for spawning the hook process.
+    ///
+    /// `language_env` holds the variables the language sets for the hook process, such as its
+    /// `PATH` or `VIRTUAL_ENV`. References to them see those values instead of the process
+    /// environment, so `PATH: "$PATH:/extra"` keeps the hook's own tools on `PATH`.
+    pub(crate) fn env_vars(&self, language_env: &[(&str, &OsStr)]) -> FxHashMap<String, String> {
+        env_expand::expand_env_with(&self.env, |name| {
+            language_env
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string_lossy().into_owned())
+                .or_else(|| env_expand::process_var(name))
+        })
+    }
+
     /// Dependencies to pass to language dependency installers.
     ///
     /// For remote hooks, this includes the local path to the cloned repository so that
@@ -581,7 +716,7 @@ pub(crate) struct HookEnvKey {
 
 /// Borrowed form of [`HookEnvKey`] for comparing a hook to an existing installation
 /// without allocating/cloning dependency sets.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct HookEnvKeyRef<'a> {
     pub(crate) language: Language,
     pub(crate) dependencies: &'a FxHashSet<String>,
@@ -621,33 +756,37 @@ fn matches_install_info(
 }
 
 impl HookEnvKey {
-    /// Compute the key used to match an installed hook environment.
+    /// Compute the keys used to match installed environments of a hook.
     ///
-    /// Returns `Ok(None)` if this hook does not install an environment.
+    /// A per-stage `default_language_version` yields a key for every version it can resolve to.
+    /// Returns no keys if this hook does not install an environment.
     pub(crate) fn from_hook_spec(
         config: &Config,
-        mut hook_spec: HookSpec,
+        hook_spec: &HookSpec,
         remote_repo_dependency: Option<&str>,
-    ) -> Result<Option<Self>> {
+    ) -> Result<Vec<Self>> {
         let language = hook_spec.language;
         if !language.supports_install_env() {
-            return Ok(None);
+            return Ok(Vec::new());
         }
 
-        hook_spec.apply_project_defaults(config);
-        hook_spec.options.language_version.get_or_insert_default();
-        hook_spec
-            .options
-            .additional_dependencies
-            .get_or_insert_default();
-
-        let request = hook_spec.options.language_version.as_deref().unwrap_or("");
-        let language_request = LanguageRequest::parse(language, request).with_context(|| {
-            format!(
-                "Invalid language_version `{request}` for hook `{}`",
-                hook_spec.id
-            )
-        })?;
+        let requests = match hook_spec.options.language_version.as_deref() {
+            Some(request) => vec![request],
+            None => config
+                .default_language_version
+                .as_ref()
+                .and_then(|versions| versions.get(&language))
+                .map_or_else(
+                    || vec![""],
+                    |version| {
+                        version
+                            .candidates()
+                            .into_iter()
+                            .map(|request| request.unwrap_or(""))
+                            .collect()
+                    },
+                ),
+        };
 
         let additional_dependencies: FxHashSet<String> = hook_spec
             .options
@@ -657,11 +796,23 @@ impl HookEnvKey {
 
         let dependencies = env_key_dependencies(&additional_dependencies, remote_repo_dependency);
 
-        Ok(Some(Self {
-            language,
-            dependencies,
-            language_request,
-        }))
+        requests
+            .into_iter()
+            .map(|request| {
+                let language_request =
+                    LanguageRequest::parse(language, request).with_context(|| {
+                        format!(
+                            "Invalid language_version `{request}` for hook `{}`",
+                            hook_spec.id
+                        )
+                    })?;
+                Ok(Self {
+                    language,
+                    dependencies: dependencies.clone(),
+                    language_request,
+                })
+            })
+            .collect()
     }
 
     pub(crate) fn matches_install_info(&self, info: &InstallInfo) -> bool {
@@ -714,6 +865,8 @@ impl Display for InstalledHook {
 }
 
 pub(crate) const HOOK_MARKER: &str = ".prek-hook.json";
+/// Shared-locked by runs using a hook environment, so `cache gc` never removes it mid-run.
+pub(crate) const HOOK_IN_USE_LOCK: &str = ".prek-in-use.lock";
 
 impl InstalledHook {
     /// Get the path to the environment where the hook is installed.
@@ -724,6 +877,13 @@ impl InstalledHook {
         }
     }
 
+    /// Mark the hook environment as in use until the returned lock is dropped.
+    pub(crate) fn lock_env_in_use(&self) -> std::io::Result<Option<LockedFile>> {
+        self.env_path()
+            .map(|env_path| LockedFile::acquire_shared_blocking(env_path.join(HOOK_IN_USE_LOCK)))
+            .transpose()
+    }
+
     /// Get the directory the toolchain is installed in.
     pub(crate) fn toolchain_dir(&self) -> Option<&Path> {
         match self {
@@ -818,8 +978,8 @@ impl InstallInfo {
         Ok(info)
     }
 
-    pub(crate) async fn check_health(&self) -> Result<()> {
-        self.language.check_health(self).await
+    pub(crate) async fn check_health(&self, store: &Store) -> Result<()> {
+        self.language.check_health(self, store).await
     }
 
     pub(crate) fn with_language_version(&mut self, version: semver::Version) -> &mut Self {
@@ -933,7 +1093,7 @@ mod tests {
         };
 
         hook_spec.apply_remote_hook_overrides(&hook_override);
-        hook_spec.apply_project_defaults(project.config());
+        hook_spec.apply_project_defaults(project.config(), None);
 
         let builder = HookBuilder::new(project.clone(), repo, hook_spec, 7);
         let hook = builder.build().await?;
@@ -944,14 +1104,18 @@ mod tests {
                 relative_path: "",
                 idx: 0,
                 config: Config {
+                    extends: None,
                     auto_update: None,
                     repos: [],
                     default_install_hook_types: None,
                     default_language_version: Some(
                         {
-                            Python: "python3.12",
+                            Python: Version(
+                                "python3.12",
+                            ),
                         },
                     ),
+                    default_language_concurrency: None,
                     default_stages: Some(
                         Stages(manual),
                     ),
@@ -960,6 +1124,7 @@ mod tests {
                     fail_fast: None,
                     minimum_prek_version: None,
                     orphan: None,
+                    inherited_repos: 0,
                     _unused_keys: {},
                 },
                 ..
@@ -1000,9 +1165,15 @@ mod tests {
             always_run: true,
             fail_fast: false,
             pass_filenames: None,
+            stdin_from: None,
+            cacheable: false,
+            retries: 0,
+            timeout: None,
+            working_directory: None,
             description: Some(
                 "desc",
             ),
+            language_version: "python3.12",
             language_request: Python(
                 MajorMinor(
                     3,
@@ -1011,6 +1182,8 @@ mod tests {
             ),
             log_file: None,
             require_serial: false,
+            exclusive: false,
+            container_image: None,
             stages: Stages(manual),
             verbose: true,
             minimum_prek_version: None,
@@ -1072,11 +1245,61 @@ mod tests {
             options: HookOptions::default(),
         };
 
-        hook_spec.apply_project_defaults(&config);
+        hook_spec.apply_project_defaults(&config, None);
 
         assert_eq!(hook_spec.options.stages, Some(Stages::ALL));
     }
 
+    #[tokio::test]
+    async fn hook_builder_resolves_default_language_version_per_stage() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let config_path = temp.path().join(PRE_COMMIT_CONFIG_YAML);
+        fs_err::write(
+            &config_path,
+            indoc::indoc! {r"
+                repos: []
+                default_language_version:
+                  python:
+                    pre-commit: '3.11'
+                    pre-push: '3.13'
+            "},
+        )?;
+
+        let project = Arc::new(Project::from_config_file(
+            Cow::Borrowed(&config_path),
+            None,
+        )?);
+        let repo = Arc::new(Repo::Local { hooks: vec![] });
+        let hook_spec = HookSpec {
+            id: "test-hook".to_string(),
+            name: "test-hook".to_string(),
+            entry: "python3 -c 'print(1)'".to_string(),
+            language: Language::Python,
+            priority: None,
+            groups: None,
+            options: HookOptions::default(),
+        };
+
+        for (stage, request) in [
+            (Some(Stage::PreCommit), "3.11"),
+            (Some(Stage::PrePush), "3.13"),
+            (Some(Stage::Manual), ""),
+            (None, ""),
+        ] {
+            let hook = HookBuilder::new(project.clone(), repo.clone(), hook_spec.clone(), 0)
+                .with_stage(stage)
+                .build()
+                .await?;
+            assert_eq!(
+                hook.language_request,
+                LanguageRequest::parse(Language::Python, request)?,
+                "stage: {stage:?}"
+            );
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn hook_builder_preserves_explicit_empty_default_stages() -> Result<()> {
         let temp = tempfile::tempdir()?;
@@ -2,9 +2,11 @@ use std::ffi::OsStr;
 use std::ops::Deref;
 use std::path::Path;
 
+use rustc_hash::FxHashMap;
 use tempfile::TempDir;
 
 use crate::config::Shell;
+use crate::env_expand;
 use crate::hook::Error;
 use crate::languages::resolve_command;
 use crate::store::Store;
@@ -54,10 +56,16 @@ pub(crate) enum HookEntry {
 }
 
 impl HookEntry {
-    pub(crate) fn new(hook: String, entry: String, shell: Option<Shell>) -> Self {
+    /// Create an entry; `env` is the hook's `env`, used to expand variables in direct entries.
+    pub(crate) fn new(
+        hook: String,
+        entry: String,
+        shell: Option<Shell>,
+        env: FxHashMap<String, String>,
+    ) -> Self {
         match shell {
             Some(shell) => Self::Shell(ShellHookEntry { hook, entry, shell }),
-            None => Self::Direct(DirectHookEntry { hook, entry }),
+            None => Self::Direct(DirectHookEntry { hook, entry, env }),
         }
     }
 
@@ -110,12 +118,21 @@ impl HookEntry {
             Self::Shell(entry) => Some(entry.shell),
         }
     }
+
+    /// Get the original entry string.
+    pub(crate) fn raw(&self) -> &str {
+        match self {
+            Self::Direct(entry) => entry.raw(),
+            Self::Shell(entry) => &entry.entry,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct DirectHookEntry {
     hook: String,
     entry: String,
+    env: FxHashMap<String, String>,
 }
 
 impl DirectHookEntry {
@@ -139,9 +156,18 @@ impl DirectHookEntry {
         Ok(PreparedHookEntry::direct(resolve_command(split, env_path)))
     }
 
-    /// Split the entry into a list of commands.
+    /// Expand environment variables and split the entry into a list of commands.
+    ///
+    /// Variables are looked up in the hook's `env` first, then in the process environment.
+    /// Shell entries are passed to the shell as written, so the shell expands them instead.
     pub(crate) fn split(&self) -> Result<Vec<String>, Error> {
-        let splits = shlex::split(&self.entry).ok_or_else(|| Error::Hook {
+        let env = env_expand::expand_env(&self.env);
+        let entry = env_expand::expand_words(&self.entry, |name| {
+            env.get(name)
+                .cloned()
+                .or_else(|| env_expand::process_var(name))
+        });
+        let splits = shlex::split(&entry).ok_or_else(|| Error::Hook {
             hook: self.hook.clone(),
             error: anyhow::anyhow!("Failed to parse entry `{}` as commands", &self.entry),
         })?;
@@ -4,10 +4,10 @@ use std::str::FromStr;
 use anyhow::Result;
 use prek_identify::tags;
 
-use crate::cli::run::HookRunReporter;
 use crate::config::{BuiltinHook, FilePattern, HookOptions, PassFilenames, Stage};
 use crate::hook::Hook;
 use crate::hooks::pre_commit_hooks;
+use crate::run::HookRunContext;
 use crate::store::Store;
 
 mod check_illegal_windows_names;
@@ -29,6 +29,7 @@ mod check_json5;
 #[strum(serialize_all = "kebab-case")]
 pub(crate) enum BuiltinHooks {
     CheckAddedLargeFiles,
+    CheckBuiltinLiterals,
     CheckCaseConflict,
     CheckExecutablesHaveShebangs,
     CheckIllegalWindowsNames,
@@ -41,15 +42,19 @@ pub(crate) enum BuiltinHooks {
     CheckVcsPermalinks,
     CheckXml,
     CheckYaml,
+    DebugStatements,
     DestroyedSymlinks,
     DetectPrivateKey,
     EndOfFileFixer,
     FileContentsSorter,
     FixByteOrderMarker,
+    FixEncodingPragma,
     ForbidNewSubmodules,
     MixedLineEnding,
+    NameTestsTest,
     NoCommitToBranch,
     PrettyFormatJson,
+    SortSimpleYaml,
     TrailingWhitespace,
 }
 
@@ -59,11 +64,14 @@ impl BuiltinHooks {
             Self::EndOfFileFixer
             | Self::FileContentsSorter
             | Self::FixByteOrderMarker
+            | Self::FixEncodingPragma
             | Self::MixedLineEnding
             | Self::PrettyFormatJson
+            | Self::SortSimpleYaml
             | Self::TrailingWhitespace => true,
 
             Self::CheckAddedLargeFiles
+            | Self::CheckBuiltinLiterals
             | Self::CheckCaseConflict
             | Self::CheckExecutablesHaveShebangs
             | Self::CheckIllegalWindowsNames
@@ -76,9 +84,11 @@ impl BuiltinHooks {
             | Self::CheckVcsPermalinks
             | Self::CheckXml
             | Self::CheckYaml
+            | Self::DebugStatements
             | Self::DestroyedSymlinks
             | Self::DetectPrivateKey
             | Self::ForbidNewSubmodules
+            | Self::NameTestsTest
             | Self::NoCommitToBranch => false,
         }
     }
@@ -88,13 +98,17 @@ impl BuiltinHooks {
         _store: &Store,
         hook: &Hook,
         filenames: &[&Path],
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
+        let show_diff = context.show_diff;
         let result = match self {
             Self::CheckAddedLargeFiles => {
                 pre_commit_hooks::check_added_large_files(hook, filenames).await
             }
+            Self::CheckBuiltinLiterals => {
+                pre_commit_hooks::check_builtin_literals(hook, filenames).await
+            }
             Self::CheckCaseConflict => pre_commit_hooks::check_case_conflict(hook, filenames).await,
             Self::CheckExecutablesHaveShebangs => {
                 pre_commit_hooks::check_executables_have_shebangs(hook, filenames).await
@@ -105,7 +119,7 @@ impl BuiltinHooks {
             Self::CheckJson => pre_commit_hooks::check_json(hook, filenames).await,
             Self::CheckJson5 => check_json5::check_json5(hook, filenames).await,
             Self::CheckMergeConflict => {
-                pre_commit_hooks::check_merge_conflict(hook, filenames).await
+                pre_commit_hooks::check_merge_conflict(hook, filenames, context).await
             }
             Self::CheckShebangScriptsAreExecutable => {
                 pre_commit_hooks::check_shebang_scripts_are_executable(hook, filenames).await
@@ -117,26 +131,42 @@ impl BuiltinHooks {
             }
             Self::CheckXml => pre_commit_hooks::check_xml(hook, filenames).await,
             Self::CheckYaml => pre_commit_hooks::check_yaml(hook, filenames).await,
+            Self::DebugStatements => {
+                pre_commit_hooks::debug_statements(hook, filenames, context).await
+            }
             Self::DestroyedSymlinks => pre_commit_hooks::destroyed_symlinks(hook, filenames).await,
             Self::DetectPrivateKey => pre_commit_hooks::detect_private_key(hook, filenames).await,
-            Self::EndOfFileFixer => pre_commit_hooks::fix_end_of_file(hook, filenames).await,
+            Self::EndOfFileFixer => {
+                pre_commit_hooks::fix_end_of_file(hook, filenames, show_diff).await
+            }
             Self::FileContentsSorter => {
-                pre_commit_hooks::file_contents_sorter(hook, filenames).await
+                pre_commit_hooks::file_contents_sorter(hook, filenames, show_diff).await
             }
             Self::FixByteOrderMarker => {
-                pre_commit_hooks::fix_byte_order_marker(hook, filenames).await
+                pre_commit_hooks::fix_byte_order_marker(hook, filenames, show_diff).await
+            }
+            Self::FixEncodingPragma => {
+                pre_commit_hooks::fix_encoding_pragma(hook, filenames, show_diff).await
             }
             Self::ForbidNewSubmodules => {
                 pre_commit_hooks::forbid_new_submodules(hook, filenames).await
             }
-            Self::MixedLineEnding => pre_commit_hooks::mixed_line_ending(hook, filenames).await,
+            Self::MixedLineEnding => {
+                pre_commit_hooks::mixed_line_ending(hook, filenames, show_diff).await
+            }
+            Self::NameTestsTest => pre_commit_hooks::name_tests_test(hook, filenames),
             Self::NoCommitToBranch => pre_commit_hooks::no_commit_to_branch(hook).await,
-            Self::PrettyFormatJson => pre_commit_hooks::pretty_format_json(hook, filenames).await,
+            Self::PrettyFormatJson => {
+                pre_commit_hooks::pretty_format_json(hook, filenames, show_diff).await
+            }
+            Self::SortSimpleYaml => {
+                pre_commit_hooks::sort_simple_yaml(hook, filenames, show_diff).await
+            }
             Self::TrailingWhitespace => {
-                pre_commit_hooks::fix_trailing_whitespace(hook, filenames).await
+                pre_commit_hooks::fix_trailing_whitespace(hook, filenames, show_diff).await
             }
         };
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
         result
     }
 }
@@ -157,6 +187,21 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::CheckBuiltinLiterals => BuiltinHook {
+                id: "check-builtin-literals".to_string(),
+                name: "check builtin type constructor use".to_string(),
+                entry: "check-builtin-literals".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "requires literal syntax when initializing empty or zero python builtin types."
+                            .to_string(),
+                    ),
+                    types: Some(tags::TAG_SET_PYTHON),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::CheckCaseConflict => BuiltinHook {
                 id: "check-case-conflict".to_string(),
                 name: "check for case conflicts".to_string(),
@@ -323,6 +368,21 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::DebugStatements => BuiltinHook {
+                id: "debug-statements".to_string(),
+                name: "debug statements (python)".to_string(),
+                entry: "debug-statements".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "checks for debugger imports and py37+ `breakpoint()` calls in python source."
+                            .to_string(),
+                    ),
+                    types: Some(tags::TAG_SET_PYTHON),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::DestroyedSymlinks => BuiltinHook {
                 id: "destroyed-symlinks".to_string(),
                 name: "detect destroyed symlinks".to_string(),
@@ -393,6 +453,20 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::FixEncodingPragma => BuiltinHook {
+                id: "fix-encoding-pragma".to_string(),
+                name: "fix python encoding pragma".to_string(),
+                entry: "fix-encoding-pragma".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "adds # -*- coding: utf-8 -*- to the top of python files.".to_string(),
+                    ),
+                    types: Some(tags::TAG_SET_PYTHON),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::ForbidNewSubmodules => BuiltinHook {
                  id: "forbid-new-submodules".to_string(),
                  name: "forbid new submodules".to_string(),
@@ -417,6 +491,21 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::NameTestsTest => BuiltinHook {
+                id: "name-tests-test".to_string(),
+                name: "python tests naming".to_string(),
+                entry: "name-tests-test".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some("verifies that test files are named correctly.".to_string()),
+                    files: Some(
+                        FilePattern::regex(r"(^|/)tests/.+\.py$")
+                            .expect("builtin files regex must be valid"),
+                    ),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::NoCommitToBranch => BuiltinHook {
                 id: "no-commit-to-branch".to_string(),
                 name: "don't commit to branch".to_string(),
@@ -442,6 +531,21 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::SortSimpleYaml => BuiltinHook {
+                id: "sort-simple-yaml".to_string(),
+                name: "sort simple yaml files".to_string(),
+                entry: "sort-simple-yaml".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "sorts simple yaml files which consist only of top-level keys, preserving comments and blocks."
+                            .to_string(),
+                    ),
+                    files: Some(FilePattern::Never),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::TrailingWhitespace => BuiltinHook {
                 id: "trailing-whitespace".to_string(),
                 name: "trim trailing whitespace".to_string(),
@@ -7,12 +7,12 @@ use anyhow::{Context, Result};
 use itertools::Itertools;
 use prek_consts::CONFIG_FILENAMES;
 
-use crate::cli::run::HookRunReporter;
 use crate::cli::run::{
     CollectOptions, FileTagCache, FileTagFilter, HookFileFilter, ProjectFiles, collect_run_input,
 };
 use crate::config::{self, FilePattern, HookOptions, Language, MetaHook};
 use crate::hook::Hook;
+use crate::run::HookRunContext;
 use crate::store::Store;
 use crate::workspace::{HookInitFilters, Project};
 
@@ -39,15 +39,15 @@ impl MetaHooks {
         store: &Store,
         hook: &Hook,
         filenames: &[&Path],
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
         let result = match self {
             Self::CheckHooksApply => check_hooks_apply(store, hook, filenames).await,
             Self::CheckUselessExcludes => check_useless_excludes(hook, filenames).await,
             Self::Identity => Ok(identity(hook, filenames)),
         };
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
         result
     }
 }
@@ -94,6 +94,10 @@ impl MetaHook {
 }
 
 /// Ensures that the configured hooks apply to at least one file in the repository.
+///
+/// Files are selected with the same project and hook filters as `prek run`, so a hook is
+/// reported exactly when `--all-files` would always skip it. Hooks that run without files
+/// (`always_run`) and `fail` hooks are not checked.
 pub(crate) async fn check_hooks_apply(
     store: &Store,
     hook: &Hook,
@@ -137,12 +141,11 @@ pub(crate) async fn check_hooks_apply(
         let mut remaining = matches.len();
 
         ProjectFiles::visit_for_project(input.iter(), hooks[0].project(), None, None, |file| {
-            let tags = file.tags(&tag_cache);
             for (matched, filter) in matches.iter_mut().zip(&filters) {
                 if *matched {
                     continue;
                 }
-                if filter.matches_filename(file.hook_path()) && filter.matches_tags(tags) {
+                if filter.matches_project_file(&file, &tag_cache) {
                     *matched = true;
                     remaining -= 1;
                 }
@@ -236,7 +239,7 @@ fn excludes_any(
         .any(|f| matches_patterns(f.as_ref(), include, exclude))
 }
 
-/// Ensures that exclude directives apply to any file in the repository.
+/// Ensures that `exclude` and `files` directives apply to any file in the repository.
 pub(crate) async fn check_useless_excludes(
     hook: &Hook,
     filenames: &[&Path],
@@ -282,7 +285,10 @@ pub(crate) async fn check_useless_excludes(
         for repo in &config.repos {
             extend_hook_options(repo, &mut hook_options);
         }
-        if hook_options.iter().all(|(_, opts)| opts.exclude.is_none()) {
+        if hook_options
+            .iter()
+            .all(|(_, opts)| opts.exclude.is_none() && opts.files.is_none())
+        {
             continue;
         }
 
@@ -300,20 +306,47 @@ pub(crate) async fn check_useless_excludes(
             .iter()
             .map(|(_, opts)| opts.exclude.is_none())
             .collect::<Vec<_>>();
-        let mut remaining = exclude_matches.iter().filter(|matched| !**matched).count();
+        let mut files_matches = hook_options
+            .iter()
+            .map(|(_, opts)| opts.files.is_none())
+            .collect::<Vec<_>>();
+        let mut remaining = exclude_matches
+            .iter()
+            .chain(&files_matches)
+            .filter(|matched| !**matched)
+            .count();
 
         ProjectFiles::visit_for_project(input_workspace.iter(), &project, None, None, |file| {
-            let tags = file.tags(&tag_cache);
+            for (files_matched, (_, opts)) in files_matches.iter_mut().zip(&hook_options) {
+                if !*files_matched
+                    && opts
+                        .files
+                        .as_ref()
+                        .is_some_and(|files| files.is_match(file.hook_path()))
+                {
+                    *files_matched = true;
+                    remaining -= 1;
+                }
+            }
+
             for ((matched, (_, opts)), tag_filter) in exclude_matches
                 .iter_mut()
                 .zip(&hook_options)
                 .zip(&tag_filters)
             {
-                if *matched || tags.is_none_or(|tags| !tag_filter.matches(tags)) {
+                if *matched {
                     continue;
                 }
 
-                if matches_patterns(file.hook_path(), opts.files.as_ref(), opts.exclude.as_ref()) {
+                // Check the cheap path patterns before classifying the file.
+                if !matches_patterns(file.hook_path(), opts.files.as_ref(), opts.exclude.as_ref()) {
+                    continue;
+                }
+
+                if file
+                    .tags(&tag_cache)
+                    .is_some_and(|tags| tag_filter.matches(tags))
+                {
                     *matched = true;
                     remaining -= 1;
                 }
@@ -326,7 +359,21 @@ pub(crate) async fn check_useless_excludes(
             ControlFlow::Continue(())
         });
 
-        for ((hook_id, opts), exclude_matches) in hook_options.iter().zip(exclude_matches) {
+        for (((hook_id, opts), exclude_matches), files_matches) in
+            hook_options.iter().zip(exclude_matches).zip(files_matches)
+        {
+            if !files_matches {
+                code = 1;
+                let display = opts
+                    .files
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                writeln!(
+                    &mut output,
+                    "The files pattern `{display}` for `{hook_id}` does not match any files"
+                )?;
+            }
             if !exclude_matches {
                 code = 1;
                 let display = opts
@@ -345,13 +392,16 @@ pub(crate) async fn check_useless_excludes(
     Ok((code, output))
 }
 
-/// Prints all arguments passed to the hook. Useful for debugging.
-pub fn identity(_hook: &Hook, filenames: &[&Path]) -> (i32, Vec<u8>) {
+/// Prints all arguments passed to the hook, one per line. Useful for debugging.
+///
+/// Like `pre-commit`, the hook's `args` come first, followed by the filenames.
+pub fn identity(hook: &Hook, filenames: &[&Path]) -> (i32, Vec<u8>) {
     (
         0,
-        filenames
+        hook.args
             .iter()
-            .map(|f| f.to_string_lossy())
+            .map(|arg| arg.as_str().into())
+            .chain(filenames.iter().map(|f| f.to_string_lossy()))
             .join("\n")
             .into_bytes(),
     )
@@ -1,15 +1,17 @@
 use std::future::Future;
+use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::LazyLock;
 
 use prek_consts::env_vars::EnvVars;
+use similar::TextDiff;
 
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, Repo};
 pub(crate) use crate::hooks::builtin_hooks::BuiltinHooks;
 pub(crate) use crate::hooks::meta_hooks::MetaHooks;
 use crate::hooks::pre_commit_hooks::{PreCommitHooks, is_pre_commit_hooks};
+use crate::run::{CONCURRENCY, Finding, HookRunContext};
 use crate::store::Store;
 
 mod builtin_hooks;
@@ -18,6 +20,39 @@ mod pre_commit_hooks;
 
 static NO_FAST_PATH: LazyLock<bool> = LazyLock::new(|| EnvVars::is_set(EnvVars::PREK_NO_FAST_PATH));
 
+/// Write the fixed content of a file, as builtin fixers do.
+///
+/// With `show_diff` (`prek run --show-diff`), the file is left untouched and a unified diff of
+/// the change is returned instead, to be used as the hook output.
+pub(crate) async fn write_fixed(
+    path: &Path,
+    filename: &Path,
+    original: &[u8],
+    fixed: &[u8],
+    show_diff: bool,
+) -> anyhow::Result<Option<String>> {
+    if show_diff {
+        return Ok(Some(unified_diff(
+            &String::from_utf8_lossy(original),
+            &String::from_utf8_lossy(fixed),
+            filename,
+        )));
+    }
+
+    fs_err::tokio::write(path, fixed).await?;
+    Ok(None)
+}
+
+/// A unified diff between two versions of a file, with three lines of context.
+pub(crate) fn unified_diff(original: &str, fixed: &str, filename: &Path) -> String {
+    let filename = filename.display().to_string();
+    TextDiff::from_lines(original, fixed)
+        .unified_diff()
+        .context_radius(3)
+        .header(&filename, &filename)
+        .to_string()
+}
+
 /// Returns true if the hook has a builtin Rust implementation.
 pub fn check_fast_path(hook: &Hook) -> bool {
     fast_path_hook(hook).is_some()
@@ -27,6 +62,10 @@ fn fast_path_hook(hook: &Hook) -> Option<PreCommitHooks> {
     if *NO_FAST_PATH {
         return None;
     }
+    // Builtin implementations expect filenames relative to the project root.
+    if hook.working_directory.is_some() {
+        return None;
+    }
 
     let Repo::Remote { url, .. } = hook.repo() else {
         return None;
@@ -59,16 +98,16 @@ pub async fn run_fast_path(
     _store: &Store,
     hook: &Hook,
     filenames: &[&Path],
-    reporter: &HookRunReporter,
+    context: &HookRunContext,
 ) -> anyhow::Result<(i32, Vec<u8>)> {
-    let progress = reporter.on_run_start(hook, filenames.len());
+    let progress = context.reporter.on_run_start(hook, filenames.len());
 
     let Some(implemented) = fast_path_hook(hook) else {
         unreachable!("run_fast_path requires a supported pre-commit hook");
     };
-    let result = implemented.run(hook, filenames).await;
+    let result = implemented.run(hook, filenames, context).await;
 
-    reporter.on_run_complete(progress);
+    context.reporter.on_run_complete(progress);
 
     result
 }
@@ -100,3 +139,37 @@ where
 
     Ok((code, output))
 }
+
+/// Run `check` on each file like [`run_concurrent_file_checks`], for checks that find problems
+/// at known lines of a file.
+///
+/// Findings are printed as `path:line[:col]: message` and reported to `context`, so that
+/// `prek run --output-format=github` annotates them without parsing the output.
+pub(crate) async fn run_concurrent_finding_checks<'a, I, F, Fut>(
+    hook: &Hook,
+    filenames: I,
+    context: &HookRunContext,
+    check: F,
+) -> anyhow::Result<(i32, Vec<u8>)>
+where
+    I: IntoIterator<Item = &'a Path>,
+    F: Fn(&'a Path) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<Finding>>>,
+{
+    use futures::{StreamExt, TryStreamExt};
+
+    let findings: Vec<Finding> = futures::stream::iter(filenames)
+        .map(check)
+        .buffered(*CONCURRENCY)
+        .try_concat()
+        .await?;
+
+    let mut output = Vec::new();
+    for finding in &findings {
+        writeln!(output, "{finding}")?;
+    }
+    let code = i32::from(!findings.is_empty());
+    context.findings.report(hook, findings);
+
+    Ok((code, output))
+}
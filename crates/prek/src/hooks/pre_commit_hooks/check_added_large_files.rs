@@ -41,9 +41,14 @@ pub(crate) async fn check_added_large_files(
         candidate_filenames.as_slice()
     };
 
-    // Builtin hooks receive project-relative filenames, so git attribute lookups need to run
-    // from the project root for nested `.gitattributes` files to apply.
-    let lfs_files = get_lfs_files(hook.work_dir(), filenames).await?;
+    // `--enforce-all` applies the limit to git-lfs files as well.
+    let lfs_files = if args.enforce_all {
+        FxHashSet::default()
+    } else {
+        // Builtin hooks receive project-relative filenames, so git attribute lookups need to run
+        // from the project root for nested `.gitattributes` files to apply.
+        get_lfs_files(hook.work_dir(), filenames).await?
+    };
 
     let filenames = filenames
         .iter()
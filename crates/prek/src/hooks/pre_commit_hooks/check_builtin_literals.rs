@@ -0,0 +1,266 @@
+use std::io::Write;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use clap::Parser;
+use regex::bytes::Regex;
+
+use super::debug_statements::{LiteralMasker, is_definition};
+use crate::hook::Hook;
+use crate::hooks::run_concurrent_file_checks;
+use crate::run::CONCURRENCY;
+
+/// Builtin types and the literal that replaces their empty call, matching upstream.
+const BUILTIN_TYPES: &[(&str, &str)] = &[
+    ("complex", "0j"),
+    ("dict", "{}"),
+    ("float", "0.0"),
+    ("int", "0"),
+    ("list", "[]"),
+    ("str", "''"),
+    ("tuple", "()"),
+];
+
+static CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(complex|dict|float|int|list|str|tuple)\s*\(")
+        .expect("builtin call regex must be valid")
+});
+static KEYWORD_ARG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:\*\*|\w+\s*=[^=])").expect("keyword regex must be valid"));
+
+#[derive(Parser)]
+#[command(disable_help_subcommand = true)]
+#[command(disable_version_flag = true)]
+#[command(disable_help_flag = true)]
+struct Args {
+    /// Builtin types whose calls are not reported.
+    #[arg(long, value_delimiter = ',')]
+    ignore: Vec<String>,
+    #[arg(long, overrides_with = "no_allow_dict_kwargs")]
+    allow_dict_kwargs: bool,
+    #[arg(long, overrides_with = "allow_dict_kwargs")]
+    no_allow_dict_kwargs: bool,
+}
+
+impl Args {
+    fn checked(&self, name: &str) -> bool {
+        !self.ignore.iter().any(|ignored| ignored.trim() == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct BuiltinCall {
+    line: usize,
+    col: usize,
+    name: &'static str,
+    literal: &'static str,
+}
+
+pub(crate) async fn check_builtin_literals(
+    hook: &Hook,
+    filenames: &[&Path],
+) -> Result<(i32, Vec<u8>)> {
+    let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
+
+    run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
+        check_file(hook.project().relative_path(), filename, &args)
+    })
+    .await
+}
+
+async fn check_file(file_base: &Path, filename: &Path, args: &Args) -> Result<(i32, Vec<u8>)> {
+    let content = fs_err::tokio::read(file_base.join(filename)).await?;
+
+    let mut output = Vec::new();
+    let calls = find_builtin_calls(&content, args);
+    for call in &calls {
+        writeln!(
+            output,
+            "{}:{}:{}: replace {}() with {}",
+            filename.display(),
+            call.line,
+            call.col,
+            call.name,
+            call.literal,
+        )?;
+    }
+
+    Ok((i32::from(!calls.is_empty()), output))
+}
+
+/// Find calls to builtin types without positional arguments.
+///
+/// `dict()` with only keyword arguments is allowed unless `--no-allow-dict-kwargs` is set.
+/// Attribute calls such as `collections.dict()` are never reported. Columns are byte offsets,
+/// as reported by Python's `ast`.
+fn find_builtin_calls(content: &[u8], args: &Args) -> Vec<BuiltinCall> {
+    // The flags override each other, so at most one of them is set.
+    let allow_dict_kwargs = args.allow_dict_kwargs || !args.no_allow_dict_kwargs;
+
+    // Mask the whole file first, since call arguments may span several lines.
+    let mut masker = LiteralMasker::default();
+    let mut line_starts = Vec::new();
+    let mut code = Vec::with_capacity(content.len());
+    for line in content.split(|&b| b == b'\n') {
+        line_starts.push(code.len());
+        code.extend(masker.mask_line(line));
+        code.push(b'\n');
+    }
+
+    let mut calls = Vec::new();
+    for captures in CALL_RE.captures_iter(&code) {
+        let name = captures.get(1).expect("builtin name group");
+        let (builtin, literal) = BUILTIN_TYPES
+            .iter()
+            .find(|(builtin, _)| builtin.as_bytes() == name.as_bytes())
+            .copied()
+            .expect("regex only matches builtin types");
+        if !args.checked(builtin) {
+            continue;
+        }
+
+        let line = line_starts.partition_point(|&start| start <= name.start()) - 1;
+        let before = &code[line_starts[line]..name.start()];
+        if before.trim_ascii_end().ends_with(b".") || is_definition(before) {
+            continue;
+        }
+
+        let arguments = call_arguments(&code[captures.get(0).expect("match").end()..]);
+        let has_positional = arguments
+            .iter()
+            .any(|argument| !KEYWORD_ARG_RE.is_match(argument));
+        let has_keywords = !arguments.is_empty() && !has_positional;
+        if has_positional || (builtin == "dict" && allow_dict_kwargs && has_keywords) {
+            continue;
+        }
+
+        calls.push(BuiltinCall {
+            line: line + 1,
+            col: name.start() - line_starts[line],
+            name: builtin,
+            literal,
+        });
+    }
+
+    calls
+}
+
+/// Split the arguments of a call at top-level commas, up to its closing parenthesis.
+///
+/// Blank arguments, such as after a trailing comma, are dropped.
+fn call_arguments(code: &[u8]) -> Vec<&[u8]> {
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, &b) in code.iter().enumerate() {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth > 0 => depth -= 1,
+            b')' | b',' if depth == 0 => {
+                arguments.push(&code[start..i]);
+                start = i + 1;
+                if b == b')' {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    arguments.retain(|argument| !argument.trim_ascii().is_empty());
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(content: &str, args: &[&str]) -> Vec<(usize, usize, String)> {
+        let args = Args::try_parse_from(
+            std::iter::once("check-builtin-literals").chain(args.iter().copied()),
+        )
+        .expect("valid args");
+        find_builtin_calls(content.as_bytes(), &args)
+            .into_iter()
+            .map(|call| {
+                (
+                    call.line,
+                    call.col,
+                    format!("{} {}", call.name, call.literal),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_empty_builtin_calls() {
+        let content = indoc::indoc! {r"
+            a = dict()
+            b = list ( )
+            c = tuple(
+            )
+            d = [str(), int(), float(), complex()]
+            e = list(dict())
+        "};
+        assert_eq!(
+            find(content, &[]),
+            vec![
+                (1, 4, "dict {}".to_string()),
+                (2, 4, "list []".to_string()),
+                (3, 4, "tuple ()".to_string()),
+                (5, 5, "str ''".to_string()),
+                (5, 12, "int 0".to_string()),
+                (5, 19, "float 0.0".to_string()),
+                (5, 28, "complex 0j".to_string()),
+                (6, 9, "dict {}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_calls_with_arguments_and_attributes() {
+        let content = indoc::indoc! {r#"
+            a = list(x)
+            b = tuple(
+                items,
+            )
+            c = collections.dict()
+            d = obj . list()
+            e = mylist()
+            def list(): pass
+            f = "dict()"  # list()
+            g = str(*parts)
+        "#};
+        assert_eq!(find(content, &[]), vec![]);
+    }
+
+    #[test]
+    fn dict_kwargs() {
+        let content = indoc::indoc! {r"
+            a = dict(a=1, b=2)
+            b = dict(**other)
+            c = dict(x == 1)
+            d = int(base=2)
+        "};
+        assert_eq!(find(content, &[]), vec![(4, 4, "int 0".to_string())]);
+        assert_eq!(
+            find(content, &["--no-allow-dict-kwargs"]),
+            vec![
+                (1, 4, "dict {}".to_string()),
+                (2, 4, "dict {}".to_string()),
+                (4, 4, "int 0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignore_builtins() {
+        let content = "a = dict()\nb = list()\nc = tuple()\n";
+        assert_eq!(
+            find(content, &["--ignore=dict,list"]),
+            vec![(3, 4, "tuple ()".to_string())]
+        );
+    }
+}
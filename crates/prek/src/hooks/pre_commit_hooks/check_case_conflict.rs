@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::io::Write;
 use std::path::Path;
@@ -5,6 +6,7 @@ use std::path::Path;
 use anyhow::Result;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
+use unicode_normalization::UnicodeNormalization;
 
 use crate::git;
 use crate::hook::Hook;
@@ -73,8 +75,14 @@ pub(crate) async fn check_case_conflict(
         }
     }
 
+    let normalization_conflicts = normalization_conflicts(
+        &repo_files_with_dirs,
+        &relevant_files_with_dirs,
+        &repo_lower,
+    );
+
     let mut output = Vec::new();
-    if conflicts.is_empty() {
+    if conflicts.is_empty() && normalization_conflicts.is_empty() {
         return Ok((0, output));
     }
 
@@ -94,9 +102,82 @@ pub(crate) async fn check_case_conflict(
         )?;
     }
 
+    let mut conflicting_files: Vec<_> = repo_files_with_dirs
+        .iter()
+        .chain(relevant_files_with_dirs.iter())
+        .filter(|path| normalization_conflicts.contains(normalized_key(&lower_key(path)).as_ref()))
+        .collect();
+    conflicting_files.sort();
+
+    for filename in conflicting_files {
+        writeln!(
+            output,
+            "Unicode normalization conflict found: {}",
+            filename.display()
+        )?;
+    }
+
     Ok((1, output))
 }
 
+/// Find names that only collide once Unicode normalization is applied, like `café` written
+/// precomposed (NFC) in one path and decomposed (NFD) in another, as macOS filesystems
+/// treat them as the same name.
+///
+/// Returns the normalized keys of the colliding names. Names that differ only in case are
+/// left to the case check.
+fn normalization_conflicts(
+    repo_files: &FxHashSet<&Path>,
+    relevant_files: &FxHashSet<&Path>,
+    repo_lower: &FxHashSet<String>,
+) -> FxHashSet<String> {
+    // Normalized key -> the distinct lowercase names, and whether a relevant file is among them.
+    let mut names: FxHashMap<String, (FxHashSet<String>, bool)> = FxHashMap::default();
+
+    // Normalization never changes ASCII, so a collision needs at least one non-ASCII name.
+    let mut insert = |lower: String, relevant: bool| {
+        let (lowers, has_relevant) = names
+            .entry(normalized_key(&lower).into_owned())
+            .or_default();
+        lowers.insert(lower);
+        *has_relevant |= relevant;
+    };
+    for path in repo_files {
+        let lower = lower_key(path);
+        if !lower.is_ascii() {
+            insert(lower, false);
+        }
+    }
+    let relevant_lower: Vec<String> = relevant_files.iter().map(|path| lower_key(path)).collect();
+    for lower in &relevant_lower {
+        if !lower.is_ascii() {
+            insert(lower.clone(), true);
+        }
+    }
+    if names.is_empty() {
+        return FxHashSet::default();
+    }
+
+    // ASCII names can still be the normalized form of a non-ASCII one, like `;` of `\u{37e}`.
+    for lower in repo_lower.iter().filter(|lower| lower.is_ascii()) {
+        if let Some((lowers, _)) = names.get_mut(lower) {
+            lowers.insert(lower.clone());
+        }
+    }
+    for lower in relevant_lower.into_iter().filter(|lower| lower.is_ascii()) {
+        if let Some((lowers, has_relevant)) = names.get_mut(&lower) {
+            lowers.insert(lower);
+            *has_relevant = true;
+        }
+    }
+
+    names
+        .into_iter()
+        .filter(|(_, (lowers, has_relevant))| *has_relevant && lowers.len() > 1)
+        .map(|(key, _)| key)
+        .collect()
+}
+
 fn insert_path_and_parents<'p>(set: &mut FxHashSet<&'p Path>, file: &'p Path) {
     set.insert(file);
 
@@ -114,6 +195,15 @@ fn lower_key(path: &Path) -> String {
     path.to_string_lossy().to_lowercase()
 }
 
+/// The NFC form of a lowercase key.
+fn normalized_key(lower: &str) -> Cow<'_, str> {
+    if lower.is_ascii() {
+        Cow::Borrowed(lower)
+    } else {
+        Cow::Owned(lower.nfc().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +252,42 @@ mod tests {
         assert_eq!(lower_key(Path::new("BAR.txt")), "bar.txt");
         assert_eq!(lower_key(Path::new("baz.TXT")), "baz.txt");
     }
+
+    #[test]
+    fn test_normalization_conflicts() {
+        let nfc = Path::new("caf\u{e9}.txt");
+        let nfd = Path::new("cafe\u{301}.txt");
+        let upper_nfd = Path::new("CAFE\u{301}.txt");
+        let repo_lower = |files: &FxHashSet<&Path>| files.iter().map(|p| lower_key(p)).collect();
+
+        let repo = FxHashSet::from_iter([nfc]);
+        let relevant = FxHashSet::from_iter([nfd]);
+        let conflicts = normalization_conflicts(&repo, &relevant, &repo_lower(&repo));
+        assert_eq!(
+            conflicts,
+            FxHashSet::from_iter(["caf\u{e9}.txt".to_string()])
+        );
+
+        // Differing in both case and normalization still collides.
+        let relevant = FxHashSet::from_iter([upper_nfd]);
+        let conflicts = normalization_conflicts(&repo, &relevant, &repo_lower(&repo));
+        assert_eq!(conflicts.len(), 1);
+
+        // A pure case difference is not a normalization conflict.
+        let relevant = FxHashSet::from_iter([Path::new("CAF\u{c9}.txt")]);
+        let conflicts = normalization_conflicts(&repo, &relevant, &repo_lower(&repo));
+        assert!(conflicts.is_empty());
+
+        // Conflicts only among existing files are not reported.
+        let repo = FxHashSet::from_iter([nfc, nfd]);
+        let relevant = FxHashSet::from_iter([Path::new("other.txt")]);
+        let conflicts = normalization_conflicts(&repo, &relevant, &repo_lower(&repo));
+        assert!(conflicts.is_empty());
+
+        // The Greek question mark normalizes to an ASCII `;`.
+        let repo = FxHashSet::from_iter([Path::new("a;.txt")]);
+        let relevant = FxHashSet::from_iter([Path::new("a\u{37e}.txt")]);
+        let conflicts = normalization_conflicts(&repo, &relevant, &repo_lower(&repo));
+        assert_eq!(conflicts, FxHashSet::from_iter(["a;.txt".to_string()]));
+    }
 }
@@ -1,4 +1,3 @@
-use std::io::Write;
 use std::path::Path;
 
 use anyhow::Result;
@@ -7,8 +6,8 @@ use tokio::io::AsyncBufReadExt;
 
 use crate::git::get_git_dir;
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
-use crate::run::CONCURRENCY;
+use crate::hooks::run_concurrent_finding_checks;
+use crate::run::{Finding, HookRunContext};
 
 const START_PATTERN: &[u8] = b"<<<<<<< ";
 const ANCESTOR_PATTERN: &[u8] = b"||||||| ";
@@ -27,6 +26,7 @@ struct Args {
 pub(crate) async fn check_merge_conflict(
     hook: &Hook,
     filenames: &[&Path],
+    context: &HookRunContext,
 ) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
 
@@ -35,7 +35,7 @@ pub(crate) async fn check_merge_conflict(
         return Ok((0, Vec::new()));
     }
 
-    run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
+    run_concurrent_finding_checks(hook, filenames.iter().copied(), context, |filename| {
         check_file(hook.project().relative_path(), filename)
     })
     .await
@@ -57,37 +57,39 @@ async fn is_in_merge() -> Result<bool> {
         || git_dir.join("rebase-merge").exists())
 }
 
-async fn check_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
+async fn check_file(file_base: &Path, filename: &Path) -> Result<Vec<Finding>> {
     let file_path = file_base.join(filename);
     let file = fs_err::tokio::File::open(&file_path).await?;
     let mut reader = tokio::io::BufReader::new(file);
 
-    let mut code = 0;
-    let mut output = Vec::new();
+    let mut findings = Vec::new();
     let mut line = Vec::new();
     let mut line_number = 1;
     let mut in_conflict = false;
 
-    let mut report_conflict = |line_number: usize, pattern: &str| -> Result<()> {
-        write_conflict_message(&mut output, filename, line_number, pattern)?;
-        code = 1;
-        Ok(())
+    let mut report_conflict = |line_number: usize, pattern: &str| {
+        findings.push(Finding {
+            path: filename.to_path_buf(),
+            line: line_number,
+            col: None,
+            message: format!("Merge conflict string {pattern:?} found"),
+        });
     };
 
     while reader.read_until(b'\n', &mut line).await? != 0 {
         if line.starts_with(START_PATTERN) {
-            report_conflict(line_number, "<<<<<<< ")?;
+            report_conflict(line_number, "<<<<<<< ");
             in_conflict = true;
         } else if in_conflict && line.starts_with(ANCESTOR_PATTERN) {
-            report_conflict(line_number, "||||||| ")?;
+            report_conflict(line_number, "||||||| ");
         } else if in_conflict
             && SEPARATOR_PATTERNS
                 .iter()
                 .any(|pattern| line.starts_with(pattern))
         {
-            report_conflict(line_number, "=======")?;
+            report_conflict(line_number, "=======");
         } else if line.starts_with(END_PATTERN) {
-            report_conflict(line_number, ">>>>>>> ")?;
+            report_conflict(line_number, ">>>>>>> ");
             in_conflict = false;
         }
 
@@ -95,20 +97,7 @@ async fn check_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)>
         line_number += 1;
     }
 
-    Ok((code, output))
-}
-
-fn write_conflict_message(
-    output: &mut Vec<u8>,
-    filename: &Path,
-    line_number: usize,
-    pattern: &str,
-) -> std::io::Result<()> {
-    writeln!(
-        output,
-        "{}:{line_number}: Merge conflict string {pattern:?} found",
-        filename.display(),
-    )
+    Ok(findings)
 }
 
 #[cfg(test)]
@@ -117,6 +106,16 @@ mod tests {
     use std::path::PathBuf;
     use tempfile::tempdir;
 
+    async fn check(file_path: &Path) -> Result<(i32, String)> {
+        let findings = check_file(Path::new(""), file_path).await?;
+        let output = findings
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok((i32::from(!findings.is_empty()), output))
+    }
+
     async fn create_test_file(
         dir: &tempfile::TempDir,
         name: &str,
@@ -132,7 +131,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"This is a normal file\nWith no conflict markers\n";
         let file_path = create_test_file(&dir, "clean.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 0);
         assert!(output.is_empty());
         Ok(())
@@ -143,12 +142,11 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Some content\n<<<<<<< HEAD\nConflicting line\n";
         let file_path = create_test_file(&dir, "conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
-        let output_str = String::from_utf8_lossy(&output);
-        assert!(output_str.contains("<<<<<<< "));
-        assert!(output_str.contains("conflict.txt:2"));
+        assert!(output.contains("<<<<<<< "));
+        assert!(output.contains("conflict.txt:2"));
         Ok(())
     }
 
@@ -157,11 +155,10 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Some content\n>>>>>>> branch\nMore content\n";
         let file_path = create_test_file(&dir, "conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
-        let output_str = String::from_utf8_lossy(&output);
-        assert!(output_str.contains(">>>>>>> "));
+        assert!(output.contains(">>>>>>> "));
         Ok(())
     }
 
@@ -170,14 +167,13 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Before conflict\n<<<<<<< HEAD\nOur changes\n=======\nTheir changes\n>>>>>>> branch\nAfter conflict\n";
         let file_path = create_test_file(&dir, "conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
-        let output_str = String::from_utf8_lossy(&output);
         // Should find all three markers
-        assert!(output_str.contains("<<<<<<< "));
-        assert!(output_str.contains("======="));
-        assert!(output_str.contains(">>>>>>> "));
+        assert!(output.contains("<<<<<<< "));
+        assert!(output.contains("======="));
+        assert!(output.contains(">>>>>>> "));
         Ok(())
     }
 
@@ -186,14 +182,13 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Before conflict\n<<<<<<< HEAD\nOur changes\n||||||| base\nCommon ancestor\n=======\nTheir changes\n>>>>>>> branch\nAfter conflict\n";
         let file_path = create_test_file(&dir, "conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
-        let output_str = String::from_utf8_lossy(&output);
-        assert!(output_str.contains("<<<<<<< "));
-        assert!(output_str.contains("||||||| "));
-        assert!(output_str.contains("======="));
-        assert!(output_str.contains(">>>>>>> "));
+        assert!(output.contains("<<<<<<< "));
+        assert!(output.contains("||||||| "));
+        assert!(output.contains("======="));
+        assert!(output.contains(">>>>>>> "));
         Ok(())
     }
 
@@ -202,7 +197,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Some content <<<<<<< HEAD\n";
         let file_path = create_test_file(&dir, "no_conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         // Should not detect conflict since marker is not at line start
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -214,7 +209,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Some content\r\n<<<<<<< HEAD\r\nConflicting line\r\n=======\r\nOther line\r\n>>>>>>> branch\r\n";
         let file_path = create_test_file(&dir, "conflict_crlf.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
         Ok(())
@@ -226,7 +221,7 @@ mod tests {
         let content =
             b"Some content\n<<<<<<< HEAD\nConflicting line\n=======\nOther line\n>>>>>>> branch\n";
         let file_path = create_test_file(&dir, "conflict_lf.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
         Ok(())
@@ -237,11 +232,10 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Before conflict\n<<<<<<< HEAD\nOur changes\n=======\n";
         let file_path = create_test_file(&dir, "partial_conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
-        let output_str = String::from_utf8_lossy(&output);
-        assert!(output_str.contains("<<<<<<< "));
-        assert!(output_str.contains("======="));
+        assert!(output.contains("<<<<<<< "));
+        assert!(output.contains("======="));
         Ok(())
     }
 
@@ -250,7 +244,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Before conflict\n||||||| base\n";
         let file_path = create_test_file(&dir, "partial_conflict.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 0);
         assert!(output.is_empty());
         Ok(())
@@ -261,7 +255,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"Depends\n=======\n";
         let file_path = create_test_file(&dir, "doc.rst", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 0);
         assert!(output.is_empty());
         Ok(())
@@ -272,7 +266,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"";
         let file_path = create_test_file(&dir, "empty.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 0);
         assert!(output.is_empty());
         Ok(())
@@ -283,11 +277,10 @@ mod tests {
         let dir = tempdir()?;
         let content = b"<<<<<<< HEAD\nFirst\n=======\nSecond\n>>>>>>> branch\nMiddle\n<<<<<<< HEAD\nThird\n=======\nFourth\n>>>>>>> other\n";
         let file_path = create_test_file(&dir, "multiple.txt", content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
-        let output_str = String::from_utf8_lossy(&output);
         // Should find all markers from both conflicts (one per line with marker)
-        let marker_count = output_str.matches("Merge conflict string").count();
+        let marker_count = output.matches("Merge conflict string").count();
         assert_eq!(marker_count, 6); // 3 markers per conflict * 2 conflicts
         Ok(())
     }
@@ -298,7 +291,7 @@ mod tests {
         let mut content = vec![0xFF, 0xFE, 0xFD];
         content.extend_from_slice(b"\n<<<<<<< HEAD\n");
         let file_path = create_test_file(&dir, "binary.bin", &content).await?;
-        let (code, output) = check_file(Path::new(""), &file_path).await?;
+        let (code, output) = check(&file_path).await?;
         assert_eq!(code, 1);
         assert!(!output.is_empty());
         Ok(())
@@ -0,0 +1,279 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use regex::bytes::Regex;
+
+use crate::hook::Hook;
+use crate::hooks::run_concurrent_finding_checks;
+use crate::run::{Finding, HookRunContext};
+
+/// Modules whose import is reported, matching upstream `debug-statements`.
+const DEBUG_MODULES: &[&str] = &[
+    "bpdb",
+    "ipdb",
+    "pdb",
+    "pdbr",
+    "pudb",
+    "pydevd_pycharm",
+    "q",
+    "rdb",
+    "rpdb",
+    "wdb",
+];
+
+// Statements may start a line, follow `;`, or follow the `:` of a one-line block.
+static IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[;:])\s*(import)\s+([^;]*)").expect("import regex must be valid")
+});
+static FROM_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[;:])\s*(from)\s+\.*([\w.]+)\s+import\b")
+        .expect("from-import regex must be valid")
+});
+static BREAKPOINT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w.])(breakpoint)\s*\(").expect("breakpoint regex must be valid")
+});
+
+#[derive(Debug, PartialEq, Eq)]
+struct DebugStatement {
+    line: usize,
+    col: usize,
+    name: String,
+    reason: &'static str,
+}
+
+pub(crate) async fn debug_statements(
+    hook: &Hook,
+    filenames: &[&Path],
+    context: &HookRunContext,
+) -> Result<(i32, Vec<u8>)> {
+    run_concurrent_finding_checks(hook, filenames.iter().copied(), context, |filename| {
+        check_file(hook.project().relative_path(), filename)
+    })
+    .await
+}
+
+async fn check_file(file_base: &Path, filename: &Path) -> Result<Vec<Finding>> {
+    let content = fs_err::tokio::read(file_base.join(filename)).await?;
+
+    Ok(find_debug_statements(&content)
+        .into_iter()
+        .map(|statement| Finding {
+            path: filename.to_path_buf(),
+            line: statement.line,
+            col: Some(statement.col),
+            message: format!("{} {}", statement.name, statement.reason),
+        })
+        .collect())
+}
+
+/// Find debugger imports and `breakpoint()` calls, line by line.
+///
+/// Columns are byte offsets, as reported by Python's `ast`.
+fn find_debug_statements(content: &[u8]) -> Vec<DebugStatement> {
+    let mut masker = LiteralMasker::default();
+    let mut statements = Vec::new();
+
+    for (index, line) in content.split(|&b| b == b'\n').enumerate() {
+        let code = masker.mask_line(line);
+        let mut found = Vec::new();
+
+        for captures in IMPORT_RE.captures_iter(&code) {
+            let keyword = captures.get(1).expect("import keyword group");
+            let names = String::from_utf8_lossy(&captures[2]);
+            for name in names.split(',') {
+                let name = name.trim_matches(|c: char| c.is_whitespace() || "()\\".contains(c));
+                let module = name.split_whitespace().next().unwrap_or_default();
+                if DEBUG_MODULES.contains(&module) {
+                    found.push((keyword.start(), module.to_string(), "imported"));
+                }
+            }
+        }
+        for captures in FROM_IMPORT_RE.captures_iter(&code) {
+            let keyword = captures.get(1).expect("from keyword group");
+            let module = String::from_utf8_lossy(&captures[2]);
+            if DEBUG_MODULES.contains(&module.as_ref()) {
+                found.push((keyword.start(), module.into_owned(), "imported"));
+            }
+        }
+        for captures in BREAKPOINT_RE.captures_iter(&code) {
+            let name = captures.get(1).expect("breakpoint group");
+            if !is_definition(&code[..name.start()]) {
+                found.push((name.start(), "breakpoint".to_string(), "called"));
+            }
+        }
+
+        found.sort_by_key(|(col, ..)| *col);
+        statements.extend(found.into_iter().map(|(col, name, reason)| DebugStatement {
+            line: index + 1,
+            col,
+            name,
+            reason,
+        }));
+    }
+
+    statements
+}
+
+/// Whether the code before a name makes it a `def` or `class` name rather than a call.
+pub(super) fn is_definition(before: &[u8]) -> bool {
+    let before = before.trim_ascii_end();
+    [b"def".as_slice(), b"class"].iter().any(|keyword| {
+        before.ends_with(keyword)
+            && before[..before.len() - keyword.len()]
+                .last()
+                .is_none_or(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+    })
+}
+
+/// Blanks out string literals and comments so their contents never match.
+///
+/// Masked bytes are replaced with spaces, which keeps byte offsets intact. Triple-quoted
+/// strings and backslash-continued strings carry over to the next line.
+#[derive(Default)]
+pub(super) struct LiteralMasker {
+    /// The quote byte and whether it is tripled, while inside a string.
+    string: Option<(u8, bool)>,
+}
+
+impl LiteralMasker {
+    pub(super) fn mask_line(&mut self, line: &[u8]) -> Vec<u8> {
+        let mut code = line.to_vec();
+        let mut continued = false;
+        let mut i = 0;
+
+        while i < line.len() {
+            let b = line[i];
+            match self.string {
+                Some(_) if b == b'\\' => {
+                    continued = line[i + 1..].iter().all(|&b| b == b'\r');
+                    let end = (i + 2).min(line.len());
+                    code[i..end].fill(b' ');
+                    i = end;
+                    continue;
+                }
+                Some((quote, true)) if line[i..].starts_with(&[quote; 3]) => {
+                    code[i..i + 3].fill(b' ');
+                    self.string = None;
+                    i += 3;
+                    continue;
+                }
+                Some((quote, false)) if b == quote => {
+                    self.string = None;
+                }
+                Some(_) => {}
+                None if b == b'#' => {
+                    code[i..].fill(b' ');
+                    break;
+                }
+                None if matches!(b, b'"' | b'\'') => {
+                    let triple = line[i..].starts_with(&[b; 3]);
+                    self.string = Some((b, triple));
+                    if triple {
+                        code[i..i + 3].fill(b' ');
+                        i += 3;
+                        continue;
+                    }
+                }
+                None => {
+                    i += 1;
+                    continue;
+                }
+            }
+            code[i] = b' ';
+            i += 1;
+        }
+
+        // An unterminated single-quoted string ends with the line unless it was
+        // continued with a backslash.
+        if matches!(self.string, Some((_, false))) && !continued {
+            self.string = None;
+        }
+
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(content: &str) -> Vec<(usize, usize, String)> {
+        find_debug_statements(content.as_bytes())
+            .into_iter()
+            .map(|s| (s.line, s.col, format!("{} {}", s.name, s.reason)))
+            .collect()
+    }
+
+    #[test]
+    fn finds_debugger_imports() {
+        let content = indoc::indoc! {r"
+            import os
+            import pdb
+            import os, ipdb as debugger
+            from pudb import set_trace
+            def f():
+                import pdb; pdb.set_trace()
+            if True: import q
+            import pdb.foo
+            import pdbx
+        "};
+        assert_eq!(
+            find(content),
+            vec![
+                (2, 0, "pdb imported".to_string()),
+                (3, 0, "ipdb imported".to_string()),
+                (4, 0, "pudb imported".to_string()),
+                (6, 4, "pdb imported".to_string()),
+                (7, 9, "q imported".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_breakpoint_calls() {
+        let content = indoc::indoc! {r"
+            breakpoint()
+            x = 1; breakpoint ()
+            obj.breakpoint()
+            def breakpoint(): pass
+            set_breakpoint()
+        "};
+        assert_eq!(
+            find(content),
+            vec![
+                (1, 0, "breakpoint called".to_string()),
+                (2, 7, "breakpoint called".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_strings_and_comments() {
+        let content = indoc::indoc! {r#"
+            # import pdb
+            x = "import pdb; breakpoint()"
+            y = 'it\'s; import pdb'
+            """
+            import pdb
+            breakpoint()
+            """
+            z = 1  # breakpoint()
+            s = "continued \
+            import pdb"
+            import ipdb
+        "#};
+        assert_eq!(find(content), vec![(11, 0, "ipdb imported".to_string())]);
+    }
+
+    #[test]
+    fn masking_keeps_offsets() {
+        let mut masker = LiteralMasker::default();
+        assert_eq!(
+            masker.mask_line(b"a = 'b' + \"c\" # d"),
+            b"a =     +        ".to_vec()
+        );
+        assert_eq!(masker.mask_line(b"x = '''open"), b"x =        ".to_vec());
+        assert_eq!(masker.mask_line(b"still ''' y"), b"          y".to_vec());
+    }
+}
@@ -5,7 +5,7 @@ use bstr::ByteSlice;
 use clap::Parser;
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
 use crate::run::CONCURRENCY;
 
 #[derive(Parser)]
@@ -17,44 +17,72 @@ struct Args {
     ignore_case: bool,
     #[arg(long, conflicts_with = "ignore_case")]
     unique: bool,
+    /// Keep `#` comment lines where they are and sort the lines around them.
+    #[arg(long)]
+    ignore_comments: bool,
 }
 
 pub(crate) async fn file_contents_sorter(
     hook: &Hook,
     filenames: &[&Path],
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
     let file_base = hook.project().relative_path();
 
     run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
-        sort_file(file_base, filename, args.ignore_case, args.unique)
+        sort_file(
+            file_base,
+            filename,
+            args.ignore_case,
+            args.unique,
+            args.ignore_comments,
+            show_diff,
+        )
     })
     .await
 }
 
+#[allow(clippy::fn_params_excessive_bools)]
 async fn sort_file(
     file_base: &Path,
     filename: &Path,
     ignore_case: bool,
     unique: bool,
+    ignore_comments: bool,
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     let file_path = file_base.join(filename);
     let before = fs_err::tokio::read(&file_path).await?;
-    let after = sorted_contents(&before, ignore_case, unique);
+    let after = sorted_contents(&before, ignore_case, unique, ignore_comments);
 
     if before == after {
         return Ok((0, Vec::new()));
     }
 
-    fs_err::tokio::write(&file_path, &after).await?;
+    if let Some(diff) = write_fixed(&file_path, filename, &before, &after, show_diff).await? {
+        return Ok((1, diff.into_bytes()));
+    }
     Ok((1, format!("Sorting {}\n", filename.display()).into_bytes()))
 }
 
-fn sorted_contents(before: &[u8], ignore_case: bool, unique: bool) -> Vec<u8> {
-    let mut lines = before
+fn sorted_contents(
+    before: &[u8],
+    ignore_case: bool,
+    unique: bool,
+    ignore_comments: bool,
+) -> Vec<u8> {
+    let all_lines = before
         .split_inclusive(|&byte| byte == b'\n')
         .filter_map(normalize_line)
         .collect::<Vec<_>>();
+    let is_comment = |line: &[u8]| ignore_comments && line.trim_ascii_start().starts_with(b"#");
+
+    let mut lines = all_lines
+        .iter()
+        .copied()
+        .filter(|line| !is_comment(line))
+        .collect::<Vec<_>>();
 
     if ignore_case {
         lines.sort_by_cached_key(|line| line.to_ascii_lowercase());
@@ -65,6 +93,22 @@ fn sorted_contents(before: &[u8], ignore_case: bool, unique: bool) -> Vec<u8> {
         }
     }
 
+    if ignore_comments {
+        // Put the sorted lines back into the slots between the comments. Slots left over
+        // after deduplication are dropped.
+        let mut sorted = lines.into_iter();
+        lines = all_lines
+            .into_iter()
+            .filter_map(|line| {
+                if is_comment(line) {
+                    Some(line)
+                } else {
+                    sorted.next()
+                }
+            })
+            .collect();
+    }
+
     if lines.is_empty() {
         return Vec::new();
     }
@@ -109,38 +153,68 @@ mod tests {
     #[test]
     fn test_sorted_contents_sorts_and_drops_blank_lines() {
         let before = b"beta\n\n  \nalpha\r\n";
-        let after = sorted_contents(before, false, false);
+        let after = sorted_contents(before, false, false, false);
         assert_eq!(after, b"alpha\nbeta\n");
     }
 
     #[test]
     fn test_sorted_contents_ignore_case() {
         let before = b"Banana\napple\nApricot\n";
-        let after = sorted_contents(before, true, false);
+        let after = sorted_contents(before, true, false, false);
         assert_eq!(after, b"apple\nApricot\nBanana\n");
     }
 
     #[test]
     fn test_sorted_contents_ignore_case_is_stable_for_equal_keys() {
         let before = b"Apple\napple\n";
-        let after = sorted_contents(before, true, false);
+        let after = sorted_contents(before, true, false, false);
         assert_eq!(after, b"Apple\napple\n");
     }
 
     #[test]
     fn test_sorted_contents_unique() {
         let before = b"beta\nalpha\nbeta\n";
-        let after = sorted_contents(before, false, true);
+        let after = sorted_contents(before, false, true, false);
         assert_eq!(after, b"alpha\nbeta\n");
     }
 
+    #[test]
+    fn test_sorted_contents_ignore_comments() {
+        let before = b"# header\ncherry\nbanana\n  # fruits starting with a\napple\nbanana\n";
+        let after = sorted_contents(before, false, true, true);
+        assert_eq!(
+            after,
+            b"# header\napple\nbanana\n  # fruits starting with a\ncherry\n"
+        );
+
+        let after = sorted_contents(before, false, false, false);
+        assert_eq!(
+            after,
+            b"  # fruits starting with a\n# header\napple\nbanana\nbanana\ncherry\n"
+        );
+    }
+
+    #[test]
+    fn test_sorted_contents_missing_trailing_newline_and_empty() {
+        assert_eq!(
+            sorted_contents(b"beta\nalpha", false, false, false),
+            b"alpha\nbeta\n"
+        );
+        assert_eq!(
+            sorted_contents(b"alpha\nbeta", false, false, false),
+            b"alpha\nbeta\n"
+        );
+        assert!(sorted_contents(b"", false, false, false).is_empty());
+        assert!(sorted_contents(b"\n\n", false, false, false).is_empty());
+    }
+
     #[tokio::test]
     async fn test_sort_file_modifies_unsorted_file() -> Result<()> {
         let dir = tempdir()?;
         let relative = PathBuf::from("allowlist.txt");
         let file_path = create_test_file(&dir, "allowlist.txt", b"beta\nalpha\n").await?;
 
-        let (code, output) = sort_file(dir.path(), &relative, false, false).await?;
+        let (code, output) = sort_file(dir.path(), &relative, false, false, false, false).await?;
 
         assert_eq!(code, 1);
         assert_eq!(String::from_utf8(output)?, "Sorting allowlist.txt\n");
@@ -155,7 +229,7 @@ mod tests {
         let relative = PathBuf::from("allowlist.txt");
         let file_path = create_test_file(&dir, "allowlist.txt", b"alpha\nbeta\n").await?;
 
-        let (code, output) = sort_file(dir.path(), &relative, false, false).await?;
+        let (code, output) = sort_file(dir.path(), &relative, false, false, false, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
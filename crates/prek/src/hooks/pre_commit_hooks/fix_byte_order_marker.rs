@@ -4,25 +4,35 @@ use anyhow::Result;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom};
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::pre_commit_hooks::UTF8_BOM;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
 use crate::run::CONCURRENCY;
 
-const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
 const BUFFER_SIZE: usize = 8192; // 8KB buffer for streaming
 
 pub(crate) async fn fix_byte_order_marker(
     hook: &Hook,
     filenames: &[&Path],
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
-        fix_file(hook.project().relative_path(), filename)
+        fix_file(hook.project().relative_path(), filename, show_diff)
     })
     .await
 }
 
-async fn fix_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
+async fn fix_file(file_base: &Path, filename: &Path, show_diff: bool) -> Result<(i32, Vec<u8>)> {
     let file_path = file_base.join(filename);
 
+    if show_diff {
+        let content = fs_err::tokio::read(&file_path).await?;
+        let Some(fixed) = content.strip_prefix(UTF8_BOM) else {
+            return Ok((0, Vec::new()));
+        };
+        let diff = write_fixed(&file_path, filename, &content, fixed, true).await?;
+        return Ok((1, diff.unwrap_or_default().into_bytes()));
+    }
+
     let mut file = fs_err::tokio::OpenOptions::new()
         .read(true)
         .write(true)
@@ -96,7 +106,7 @@ mod tests {
         let content = b"\xef\xbb\xbfHello, World!";
         let file_path = create_test_file(&dir, "with_bom.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 1);
         let output_str = String::from_utf8_lossy(&output);
@@ -114,7 +124,7 @@ mod tests {
         let content = b"Hello, World!";
         let file_path = create_test_file(&dir, "without_bom.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -131,7 +141,7 @@ mod tests {
         let content = b"";
         let file_path = create_test_file(&dir, "empty.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -148,7 +158,7 @@ mod tests {
         let content = b"Hi";
         let file_path = create_test_file(&dir, "short.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -165,7 +175,7 @@ mod tests {
         let content = b"\xef\xbbHello"; // Only first 2 bytes of BOM
         let file_path = create_test_file(&dir, "partial_bom.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -182,7 +192,7 @@ mod tests {
         let content = b"\xef\xbb\xbf";
         let file_path = create_test_file(&dir, "bom_only.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 1);
         let output_str = String::from_utf8_lossy(&output);
@@ -200,7 +210,7 @@ mod tests {
         let content = b"\xef\xbb\xbf\xe4\xb8\xad\xe6\x96\x87"; // BOM + Chinese characters "中文"
         let file_path = create_test_file(&dir, "utf8_with_bom.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 1);
         let output_str = String::from_utf8_lossy(&output);
@@ -227,7 +237,7 @@ mod tests {
 
         let file_path = create_test_file(&dir, "large_with_bom.txt", &content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, false).await?;
 
         assert_eq!(code, 1);
         let output_str = String::from_utf8_lossy(&output);
@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use anyhow::Result;
+use bstr::ByteSlice;
+use clap::Parser;
+
+use crate::hook::Hook;
+use crate::hooks::pre_commit_hooks::UTF8_BOM;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
+use crate::run::CONCURRENCY;
+
+const DEFAULT_PRAGMA: &str = "# -*- coding: utf-8 -*-";
+
+#[derive(Parser)]
+#[command(disable_help_subcommand = true)]
+#[command(disable_version_flag = true)]
+#[command(disable_help_flag = true)]
+struct Args {
+    /// The encoding pragma to use.
+    #[arg(long, default_value = DEFAULT_PRAGMA)]
+    pragma: String,
+    /// Remove the encoding pragma instead of adding it.
+    #[arg(long)]
+    remove: bool,
+}
+
+pub(crate) async fn fix_encoding_pragma(
+    hook: &Hook,
+    filenames: &[&Path],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
+    let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
+    let pragma = args.pragma.trim_end();
+
+    run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
+        fix_file(
+            hook.project().relative_path(),
+            filename,
+            pragma.as_bytes(),
+            args.remove,
+            show_diff,
+        )
+    })
+    .await
+}
+
+async fn fix_file(
+    file_base: &Path,
+    filename: &Path,
+    pragma: &[u8],
+    remove: bool,
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
+    let file_path = file_base.join(filename);
+    let content = fs_err::tokio::read(&file_path).await?;
+
+    let Some(fixed) = fixed_contents(&content, pragma, remove) else {
+        return Ok((0, Vec::new()));
+    };
+    if let Some(diff) = write_fixed(&file_path, filename, &content, &fixed, show_diff).await? {
+        return Ok((1, diff.into_bytes()));
+    }
+
+    let message = if remove {
+        format!("Removed encoding pragma from {}\n", filename.display())
+    } else {
+        format!(
+            "Added `{}` to {}\n",
+            String::from_utf8_lossy(pragma),
+            filename.display()
+        )
+    };
+    Ok((1, message.into_bytes()))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PragmaStatus {
+    /// The pragma line is exactly the expected pragma.
+    Expected,
+    /// The pragma line declares an encoding, but not the expected one.
+    Different,
+    /// There is no pragma line.
+    Missing,
+}
+
+/// Whether a line looks like an encoding declaration, using the same loose check as upstream.
+fn has_coding(line: &[u8]) -> bool {
+    let trimmed = line.trim_ascii_start();
+    trimmed.starts_with(b"#")
+        && [b"unicode".as_slice(), b"encoding", b"coding:", b"coding="]
+            .iter()
+            .any(|needle| line.contains_str(needle))
+}
+
+/// Split off the first line of `content`, including its line ending.
+fn split_line(content: &[u8]) -> (&[u8], &[u8]) {
+    let end = content
+        .find_byte(b'\n')
+        .map_or(content.len(), |pos| pos + 1);
+    content.split_at(end)
+}
+
+/// The file content with the pragma added or removed, or `None` if it is already as expected.
+///
+/// The pragma goes on the first line, or on the second one after a shebang. A leading BOM
+/// is kept in front of everything else.
+fn fixed_contents(content: &[u8], pragma: &[u8], remove: bool) -> Option<Vec<u8>> {
+    let (bom, content) = match content.strip_prefix(UTF8_BOM) {
+        Some(content) => (UTF8_BOM, content),
+        None => (b"".as_slice(), content),
+    };
+
+    let (first_line, after_first) = split_line(content);
+    let ending: &[u8] = if first_line.ends_with(b"\r\n") {
+        b"\r\n"
+    } else {
+        b"\n"
+    };
+
+    let (shebang, potential_coding, after_coding) = if first_line.starts_with(b"#!") {
+        let (second_line, rest) = split_line(after_first);
+        (first_line, second_line, rest)
+    } else {
+        (b"".as_slice(), first_line, after_first)
+    };
+
+    let status = if potential_coding.trim_ascii_end() == pragma {
+        PragmaStatus::Expected
+    } else if has_coding(potential_coding) {
+        PragmaStatus::Different
+    } else {
+        PragmaStatus::Missing
+    };
+    let rest = if status == PragmaStatus::Missing {
+        &content[shebang.len()..]
+    } else {
+        after_coding
+    };
+
+    // A file with nothing but a shebang or a pragma is emptied.
+    if rest.trim_ascii().is_empty() {
+        return if status != PragmaStatus::Missing || !shebang.is_empty() {
+            Some(bom.to_vec())
+        } else {
+            None
+        };
+    }
+
+    let expected = if remove {
+        PragmaStatus::Missing
+    } else {
+        PragmaStatus::Expected
+    };
+    if status == expected {
+        return None;
+    }
+
+    let mut fixed = Vec::with_capacity(bom.len() + content.len() + pragma.len() + ending.len());
+    fixed.extend_from_slice(bom);
+    fixed.extend_from_slice(shebang);
+    if !remove {
+        fixed.extend_from_slice(pragma);
+        fixed.extend_from_slice(ending);
+    }
+    fixed.extend_from_slice(rest);
+    Some(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRAGMA: &[u8] = DEFAULT_PRAGMA.as_bytes();
+
+    fn fix(content: &str, remove: bool) -> Option<String> {
+        fixed_contents(content.as_bytes(), PRAGMA, remove)
+            .map(|fixed| String::from_utf8(fixed).expect("valid utf-8"))
+    }
+
+    #[test]
+    fn adds_pragma() {
+        assert_eq!(
+            fix("x = 1\n", false).as_deref(),
+            Some("# -*- coding: utf-8 -*-\nx = 1\n")
+        );
+        assert_eq!(
+            fix("#!/usr/bin/env python\nx = 1\n", false).as_deref(),
+            Some("#!/usr/bin/env python\n# -*- coding: utf-8 -*-\nx = 1\n")
+        );
+        assert_eq!(
+            fix("x = 1\r\ny = 2\r\n", false).as_deref(),
+            Some("# -*- coding: utf-8 -*-\r\nx = 1\r\ny = 2\r\n")
+        );
+        assert_eq!(fix("# -*- coding: utf-8 -*-\nx = 1\n", false), None);
+        assert_eq!(fix("", false), None);
+    }
+
+    #[test]
+    fn replaces_different_pragma() {
+        assert_eq!(
+            fix("# coding=latin-1\nx = 1\n", false).as_deref(),
+            Some("# -*- coding: utf-8 -*-\nx = 1\n")
+        );
+    }
+
+    #[test]
+    fn removes_pragma() {
+        assert_eq!(
+            fix("# -*- coding: utf-8 -*-\nx = 1\n", true).as_deref(),
+            Some("x = 1\n")
+        );
+        assert_eq!(
+            fix("#!/usr/bin/env python\n# coding: utf-8\nx = 1\n", true).as_deref(),
+            Some("#!/usr/bin/env python\nx = 1\n")
+        );
+        assert_eq!(fix("x = 1\n", true), None);
+    }
+
+    #[test]
+    fn empties_files_without_code() {
+        assert_eq!(fix("# -*- coding: utf-8 -*-\n", false).as_deref(), Some(""));
+        assert_eq!(fix("#!/usr/bin/env python\n\n", true).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn keeps_bom_first() {
+        assert_eq!(
+            fix("\u{feff}#!/usr/bin/env python\nx = 1\n", false).as_deref(),
+            Some("\u{feff}#!/usr/bin/env python\n# -*- coding: utf-8 -*-\nx = 1\n")
+        );
+        assert_eq!(
+            fix("\u{feff}# -*- coding: utf-8 -*-\nx = 1\n", true).as_deref(),
+            Some("\u{feff}x = 1\n")
+        );
+    }
+
+    #[test]
+    fn custom_pragma() {
+        let fixed = fixed_contents(b"x = 1\n", b"# coding: utf-8", false);
+        assert_eq!(
+            fixed.as_deref(),
+            Some(b"# coding: utf-8\nx = 1\n".as_slice())
+        );
+        assert_eq!(
+            fixed_contents(b"# coding: utf-8\nx = 1\n", b"# coding: utf-8", false),
+            None
+        );
+    }
+}
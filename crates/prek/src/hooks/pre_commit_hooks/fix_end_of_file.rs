@@ -1,20 +1,51 @@
 use std::path::Path;
 
 use anyhow::Result;
+use clap::Parser;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWriteExt, SeekFrom};
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::pre_commit_hooks::UTF8_BOM;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
 use crate::run::CONCURRENCY;
 
-pub(crate) async fn fix_end_of_file(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+/// How much of a file is checked for NUL bytes, the same amount git checks.
+const BINARY_SNIFF_SIZE: u64 = 8000;
+
+#[derive(Parser)]
+#[command(disable_help_subcommand = true)]
+#[command(disable_version_flag = true)]
+#[command(disable_help_flag = true)]
+struct Args {
+    /// File extensions to leave untouched, such as `min.js`.
+    #[arg(long, value_delimiter = ',')]
+    exclude_ext: Vec<String>,
+}
+
+pub(crate) async fn fix_end_of_file(
+    hook: &Hook,
+    filenames: &[&Path],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
+    let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
+
     run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
-        fix_file(hook.project().relative_path(), filename)
+        fix_file(
+            hook.project().relative_path(),
+            filename,
+            &args.exclude_ext,
+            show_diff,
+        )
     })
     .await
 }
 
-async fn fix_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
+async fn fix_file(
+    file_base: &Path,
+    filename: &Path,
+    exclude_ext: &[String],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
     let file_path = file_base.join(filename);
     // If the file is empty, do nothing and avoid opening a write handle.
     let file_size = fs_err::tokio::metadata(&file_path).await?.len();
@@ -22,6 +53,35 @@ async fn fix_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
         return Ok((0, Vec::new()));
     }
 
+    let skip_reason = if has_extension(filename, exclude_ext) {
+        Some("excluded by `--exclude-ext`")
+    } else if is_binary(&file_path, file_size).await? {
+        Some("binary content")
+    } else {
+        None
+    };
+    if let Some(reason) = skip_reason {
+        // Passing hooks only show their output with `--verbose`.
+        let mut file = fs_err::tokio::File::open(&file_path).await?;
+        return if needs_fix(&mut file, file_size).await? {
+            Ok((
+                0,
+                format!("Skipping {} ({reason})\n", filename.display()).into_bytes(),
+            ))
+        } else {
+            Ok((0, Vec::new()))
+        };
+    }
+
+    if show_diff {
+        let content = fs_err::tokio::read(&file_path).await?;
+        let Some(fixed) = fixed_content(&content) else {
+            return Ok((0, Vec::new()));
+        };
+        let diff = write_fixed(&file_path, filename, &content, &fixed, true).await?;
+        return Ok((1, diff.unwrap_or_default().into_bytes()));
+    }
+
     let mut file = fs_err::tokio::OpenOptions::new()
         .read(true)
         .write(true)
@@ -57,6 +117,63 @@ async fn fix_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
     }
 }
 
+/// Whether the file name ends with one of the extensions, given with or without a leading dot.
+fn has_extension(filename: &Path, extensions: &[String]) -> bool {
+    let Some(name) = filename.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    extensions.iter().any(|ext| {
+        let ext = ext.trim_start_matches('.');
+        !ext.is_empty()
+            && name
+                .strip_suffix(ext)
+                .is_some_and(|stem| stem.ends_with('.'))
+    })
+}
+
+/// Whether a file tagged as text turns out to be binary: it has NUL bytes, or is only a BOM.
+async fn is_binary(file_path: &Path, file_size: u64) -> Result<bool> {
+    let file = fs_err::tokio::File::open(file_path).await?;
+    let mut head = Vec::new();
+    file.take(BINARY_SNIFF_SIZE).read_to_end(&mut head).await?;
+    Ok(head.contains(&0) || (file_size == UTF8_BOM.len() as u64 && head == UTF8_BOM))
+}
+
+/// Whether the in-place fix of `fix_file` would change the file.
+async fn needs_fix<T>(file: &mut T, file_size: u64) -> Result<bool>
+where
+    T: AsyncRead + AsyncSeek + Unpin,
+{
+    Ok(match find_last_non_ending(file).await? {
+        (None, _) | (Some(_), None) => true,
+        (Some(pos), Some(line_ending)) => file_size != pos + 1 + line_ending.len() as u64,
+    })
+}
+
+/// The content with exactly one trailing line ending, or `None` if it already has one.
+///
+/// This mirrors the in-place fix of `fix_file`, for showing it as a diff.
+fn fixed_content(content: &[u8]) -> Option<Vec<u8>> {
+    let Some(pos) = content.iter().rposition(|&b| b != b'\n' && b != b'\r') else {
+        // Only line endings, which are all removed.
+        return (!content.is_empty()).then(Vec::new);
+    };
+    let rest = &content[pos + 1..];
+    let ending = match (rest.first(), rest.get(1)) {
+        (Some(&first), second) => {
+            determine_line_ending(first, second.copied().unwrap_or_default()).unwrap_or("\n")
+        }
+        (None, _) => "\n",
+    };
+    if rest == ending.as_bytes() {
+        return None;
+    }
+
+    let mut fixed = content[..=pos].to_vec();
+    fixed.extend_from_slice(ending.as_bytes());
+    Some(fixed)
+}
+
 fn determine_line_ending(first: u8, second: u8) -> Option<&'static str> {
     if first == b'\r' && second == b'\n' {
         Some("\r\n")
@@ -146,7 +263,7 @@ mod tests {
 
         let content = b"line1\nline2\nline3";
         let file_path = create_test_file(&dir, "unix_no_eof.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -154,7 +271,7 @@ mod tests {
 
         let content = b"line1\r\nline2\nline3\r\nline4";
         let file_path = create_test_file(&dir, "mixed.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -162,7 +279,7 @@ mod tests {
 
         let content = b"line1\r\nline2\r\nline3";
         let file_path = create_test_file(&dir, "windows_no_eof.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -178,7 +295,7 @@ mod tests {
         let content = b"line1\r\nline2\r\nline3\r\n";
         let file_path = create_test_file(&dir, "windows_with_eof.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 0, "Should not change the file");
         assert!(output.is_empty());
@@ -196,7 +313,7 @@ mod tests {
         let content = b"line1\nline2\nline3\n";
         let file_path = create_test_file(&dir, "unix_with_eof.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 0, "Should not change the file");
         assert!(output.is_empty());
@@ -214,7 +331,7 @@ mod tests {
         let content = b"";
         let file_path = create_test_file(&dir, "empty.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 0, "Should not change empty file");
         assert!(output.is_empty());
@@ -232,7 +349,7 @@ mod tests {
         let content = b"line1\nline2\n\n\n\n";
         let file_path = create_test_file(&dir, "excess_newlines.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
@@ -250,7 +367,7 @@ mod tests {
         let content = b"line1\r\nline2\r\n\r\n\r\n";
         let file_path = create_test_file(&dir, "excess_crlf.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
@@ -268,7 +385,7 @@ mod tests {
         let content = b"\n\n\n\n";
         let file_path = create_test_file(&dir, "only_newlines.txt", content).await?;
 
-        let (code, output) = fix_file(Path::new(""), &file_path).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
 
         assert_eq!(code, 1, "Should fix the file");
         assert!(output.as_bytes().contains_str("Fixing"));
@@ -278,4 +395,71 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_fixed_content() {
+        assert_eq!(fixed_content(b"a\n"), None);
+        assert_eq!(fixed_content(b"a\r\n"), None);
+        assert_eq!(fixed_content(b"a"), Some(b"a\n".to_vec()));
+        assert_eq!(fixed_content(b"a\r\n\r\n"), Some(b"a\r\n".to_vec()));
+        assert_eq!(fixed_content(b"a\n\r\n"), Some(b"a\n".to_vec()));
+        assert_eq!(fixed_content(b"\n\n"), Some(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn test_excluded_extension_is_skipped() -> Result<()> {
+        let dir = tempdir()?;
+        let exclude_ext = vec!["min.js".to_string(), ".snap".to_string()];
+
+        for name in ["app.min.js", "output.snap"] {
+            let file_path = create_test_file(&dir, name, b"content").await?;
+            let (code, output) = fix_file(Path::new(""), &file_path, &exclude_ext, false).await?;
+            assert_eq!(code, 0);
+            assert!(
+                output
+                    .as_bytes()
+                    .contains_str("excluded by `--exclude-ext`")
+            );
+            assert_eq!(fs_err::tokio::read(&file_path).await?, b"content");
+        }
+
+        // Files that would not change are skipped silently.
+        let file_path = create_test_file(&dir, "done.min.js", b"content\n").await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, &exclude_ext, false).await?;
+        assert_eq!(code, 0);
+        assert!(output.is_empty());
+
+        // Only whole extensions match.
+        let file_path = create_test_file(&dir, "admin.js", b"content").await?;
+        let (code, _) = fix_file(Path::new(""), &file_path, &exclude_ext, false).await?;
+        assert_eq!(code, 1);
+        assert_eq!(fs_err::tokio::read(&file_path).await?, b"content\n");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_binary_content_is_skipped() -> Result<()> {
+        let dir = tempdir()?;
+
+        for content in [b"text\0more".as_slice(), UTF8_BOM] {
+            let file_path = create_test_file(&dir, "binary.txt", content).await?;
+            let (code, output) = fix_file(Path::new(""), &file_path, &[], false).await?;
+            assert_eq!(code, 0);
+            assert!(output.as_bytes().contains_str("binary content"));
+            assert_eq!(fs_err::tokio::read(&file_path).await?, content);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_extension() {
+        let extensions = vec!["min.js".to_string(), ".txt".to_string(), String::new()];
+        assert!(has_extension(Path::new("dist/app.min.js"), &extensions));
+        assert!(has_extension(Path::new("notes.txt"), &extensions));
+        assert!(!has_extension(Path::new("app.js"), &extensions));
+        assert!(!has_extension(Path::new("txt"), &extensions));
+        assert!(!has_extension(Path::new("dist.min.js/app.py"), &extensions));
+    }
 }
@@ -7,7 +7,7 @@ use bstr::ByteSlice;
 use clap::Parser;
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
 use crate::run::CONCURRENCY;
 
 const MARKDOWN_LINE_BREAK: &[u8] = b"  ";
@@ -71,6 +71,7 @@ impl Args {
 pub(crate) async fn fix_trailing_whitespace(
     hook: &Hook,
     filenames: &[&Path],
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
 
@@ -89,6 +90,7 @@ pub(crate) async fn fix_trailing_whitespace(
             &chars,
             force_markdown,
             &markdown_exts,
+            show_diff,
         )
     })
     .await
@@ -100,6 +102,7 @@ async fn fix_file(
     chars: &[char],
     force_markdown: bool,
     markdown_exts: &[String],
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     let is_markdown = force_markdown || {
         Path::new(filename)
@@ -140,7 +143,9 @@ async fn fix_file(
     }
 
     if modified {
-        fs_err::tokio::write(&file_path, &output).await?;
+        if let Some(diff) = write_fixed(&file_path, filename, &content, &output, show_diff).await? {
+            return Ok((1, diff.into_bytes()));
+        }
         Ok((1, format!("Fixing {}\n", filename.display()).into_bytes()))
     } else {
         Ok((0, Vec::new()))
@@ -187,7 +192,8 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![".md".to_string()];
 
-        let (code, msg) = fix_file(Path::new(""), &file_path, &chars, false, &md_exts).await?;
+        let (code, msg) =
+            fix_file(Path::new(""), &file_path, &chars, false, &md_exts, false).await?;
 
         // modified
         assert_eq!(code, 1);
@@ -215,7 +221,8 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![".md".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &file_path, &chars, false, &md_exts).await?;
+        let (code, _msg) =
+            fix_file(Path::new(""), &file_path, &chars, false, &md_exts, false).await?;
 
         // second line changed 3 -> 2 spaces, so modified
         assert_eq!(code, 1);
@@ -241,7 +248,8 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts: Vec<String> = vec![]; // irrelevant because force_markdown = true
 
-        let (code, _msg) = fix_file(Path::new(""), &file_path, &chars, true, &md_exts).await?;
+        let (code, _msg) =
+            fix_file(Path::new(""), &file_path, &chars, true, &md_exts, false).await?;
 
         // modified because one line had 3 spaces -> reduced to 2
         assert_eq!(code, 1);
@@ -261,7 +269,7 @@ mod tests {
         let md_exts = vec![".md".to_string()];
 
         // file already trimmed -> no changes
-        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 0);
         assert!(msg.is_empty());
 
@@ -278,7 +286,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![];
 
-        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 0);
         assert!(msg.is_empty());
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -295,7 +303,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![".md".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         // trimming whitespace-only lines will change them to empty lines -> modified true
         assert_eq!(code, 1);
 
@@ -314,7 +322,7 @@ mod tests {
         let chars = vec![]; // will hit trim_ascii_end()
         let md_exts = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -332,7 +340,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![".txt".to_string()]; // treat as markdown for this test
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         // read file and check logical lines presence (line endings may be normalized by lines())
@@ -351,7 +359,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -369,7 +377,7 @@ mod tests {
         let chars = vec!['。', '　'];
         let md_exts = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -386,7 +394,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![".md".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -403,7 +411,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let content = fs_err::tokio::read_to_string(&path).await?;
@@ -421,7 +429,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec![];
 
-        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 0);
         assert!(msg.is_empty());
 
@@ -439,7 +447,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts = vec!["*".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let expected = "foo  \nbar\nbaz  \n\n\n";
@@ -456,7 +464,7 @@ mod tests {
         let chars = vec![' '];
         let md_exts = vec!["*".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let expected = "\ta \t  \n";
@@ -473,7 +481,7 @@ mod tests {
         let chars = vec!['x'];
         let md_exts = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts, false).await?;
         assert_eq!(code, 0);
 
         let expected = "a\nb\r\r\r\n";
@@ -490,7 +498,7 @@ mod tests {
         let chars = vec!['x'];
         let md_exts = vec!["md".to_string()];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, true, &md_exts, false).await?;
         assert_eq!(code, 1);
 
         let expected = "a  \n";
@@ -509,7 +517,7 @@ mod tests {
         let chars = vec![' ', '\t'];
         let md_exts: Vec<String> = vec![];
 
-        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts).await?;
+        let (code, _msg) = fix_file(Path::new(""), &path, &chars, false, &md_exts, false).await?;
         assert_eq!(code, 0);
 
         let new_content = fs_err::tokio::read(&path).await?;
@@ -5,7 +5,7 @@ use bstr::ByteSlice;
 use clap::{Parser, ValueEnum};
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
 use crate::run::CONCURRENCY;
 
 const CRLF: &[u8] = b"\r\n";
@@ -58,17 +58,31 @@ impl LineEndingCounts {
     }
 }
 
-pub(crate) async fn mixed_line_ending(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+pub(crate) async fn mixed_line_ending(
+    hook: &Hook,
+    filenames: &[&Path],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
 
     run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
-        fix_file(hook.project().relative_path(), filename, args.fix)
+        fix_file(
+            hook.project().relative_path(),
+            filename,
+            args.fix,
+            show_diff,
+        )
     })
     .await
 }
 
 // Process a single file for mixed line endings
-async fn fix_file(file_base: &Path, filename: &Path, fix_mode: FixMode) -> Result<(i32, Vec<u8>)> {
+async fn fix_file(
+    file_base: &Path,
+    filename: &Path,
+    fix_mode: FixMode,
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
     let file_path = file_base.join(filename);
     let contents = fs_err::tokio::read(&file_path).await?;
 
@@ -97,8 +111,7 @@ async fn fix_file(file_base: &Path, filename: &Path, fix_mode: FixMode) -> Resul
             }
 
             let target_ending = find_most_common_ending(&counts);
-            apply_line_ending(&file_path, &contents, target_ending).await?;
-            Ok((1, format!("Fixing {}\n", filename.display()).into_bytes()))
+            apply_line_ending(&file_path, filename, &contents, target_ending, show_diff).await
         }
         _ => {
             let target_ending = match fix_mode {
@@ -110,8 +123,7 @@ async fn fix_file(file_base: &Path, filename: &Path, fix_mode: FixMode) -> Resul
             let needs_fixing = counts.has_any_except(target_ending);
 
             if needs_fixing {
-                apply_line_ending(&file_path, &contents, target_ending).await?;
-                Ok((1, format!("Fixing {}\n", filename.display()).into_bytes()))
+                apply_line_ending(&file_path, filename, &contents, target_ending, show_diff).await
             } else {
                 Ok((0, Vec::new()))
             }
@@ -155,7 +167,13 @@ fn find_most_common_ending(counts: &LineEndingCounts) -> &'static [u8] {
     }
 }
 
-async fn apply_line_ending(filename: &Path, contents: &[u8], ending: &[u8]) -> Result<()> {
+async fn apply_line_ending(
+    file_path: &Path,
+    filename: &Path,
+    contents: &[u8],
+    ending: &[u8],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
     let mut new_contents = Vec::with_capacity(contents.len());
     let mut line_start = 0;
     let mut index = 0;
@@ -183,8 +201,11 @@ async fn apply_line_ending(filename: &Path, contents: &[u8], ending: &[u8]) -> R
         new_contents.extend_from_slice(ending);
     }
 
-    fs_err::tokio::write(filename, &new_contents).await?;
-    Ok(())
+    if let Some(diff) = write_fixed(file_path, filename, contents, &new_contents, show_diff).await?
+    {
+        return Ok((1, diff.into_bytes()));
+    }
+    Ok((1, format!("Fixing {}\n", filename.display()).into_bytes()))
 }
 
 #[cfg(test)]
@@ -209,7 +230,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"line1\nline2\r\nline3\r\n"; // 1 LF, 2 CRLF
         let file_path = create_test_file(&dir, "mixed_crlf.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -223,7 +244,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"line1\nline2\nline3\r\n"; // 2 LF, 1 CRLF
         let file_path = create_test_file(&dir, "mixed_lf.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -237,7 +258,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"line1\nline2\r\n"; // 1 LF, 1 CRLF
         let file_path = create_test_file(&dir, "mixed_tie.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -251,7 +272,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"line1\nline2\r\n";
         let file_path = create_test_file(&dir, "mixed_no.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::No).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::No, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("mixed line endings"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -265,7 +286,7 @@ mod tests {
         let dir = tempdir()?;
         let content = b"some content";
         let file_path = create_test_file(&dir, "no_endings.txt", content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto, false).await?;
         assert_eq!(code, 0);
         assert!(output.is_empty());
 
@@ -280,7 +301,7 @@ mod tests {
         let file_path = create_test_file(&dir, "all_mixed.txt", content).await?;
 
         // Test auto fix (should prefer LF as it's a 3-way tie)
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::Auto, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -288,7 +309,7 @@ mod tests {
 
         // Restore content and test fix to CRLF
         fs_err::tokio::write(&file_path, content).await?;
-        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::CRLF).await?;
+        let (code, output) = fix_file(Path::new(""), &file_path, FixMode::CRLF, false).await?;
         assert_eq!(code, 1);
         assert!(output.as_bytes().contains_str("Fixing"));
         let new_content = fs_err::tokio::read(&file_path).await?;
@@ -296,4 +317,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_fix_consistent_target_untouched() -> Result<()> {
+        let dir = tempdir()?;
+
+        for (content, fix_mode) in [
+            (b"line1\nline2\n".as_slice(), FixMode::LF),
+            (b"line1\r\nline2\r\n".as_slice(), FixMode::CRLF),
+            (b"line1\rline2\r".as_slice(), FixMode::CR),
+        ] {
+            let file_path = create_test_file(&dir, "consistent.txt", content).await?;
+            let (code, output) = fix_file(Path::new(""), &file_path, fix_mode, false).await?;
+            assert_eq!(code, 0);
+            assert!(output.is_empty());
+            assert_eq!(fs_err::tokio::read(&file_path).await?, content);
+        }
+
+        // A consistent file with a different ending is still converted.
+        let file_path = create_test_file(&dir, "consistent.txt", b"line1\r\nline2").await?;
+        let (code, _) = fix_file(Path::new(""), &file_path, FixMode::LF, false).await?;
+        assert_eq!(code, 1);
+        assert_eq!(fs_err::tokio::read(&file_path).await?, b"line1\nline2\n");
+
+        Ok(())
+    }
 }
@@ -4,8 +4,10 @@ use anyhow::Result;
 use tracing::debug;
 
 use crate::hook::Hook;
+use crate::run::HookRunContext;
 
 mod check_added_large_files;
+mod check_builtin_literals;
 mod check_case_conflict;
 mod check_executables_have_shebangs;
 pub(crate) mod check_json;
@@ -16,19 +18,24 @@ mod check_toml;
 mod check_vcs_permalinks;
 mod check_xml;
 mod check_yaml;
+mod debug_statements;
 mod destroyed_symlinks;
 mod detect_private_key;
 mod file_contents_sorter;
 mod fix_byte_order_marker;
+mod fix_encoding_pragma;
 mod fix_end_of_file;
 mod fix_trailing_whitespace;
 mod forbid_new_submodules;
 mod mixed_line_ending;
+mod name_tests_test;
 mod no_commit_to_branch;
 mod pretty_format_json;
 mod shebangs;
+mod sort_simple_yaml;
 
 pub(crate) use check_added_large_files::check_added_large_files;
+pub(crate) use check_builtin_literals::check_builtin_literals;
 pub(crate) use check_case_conflict::check_case_conflict;
 pub(crate) use check_executables_have_shebangs::check_executables_have_shebangs;
 pub(crate) use check_json::check_json;
@@ -39,22 +46,30 @@ pub(crate) use check_toml::check_toml;
 pub(crate) use check_vcs_permalinks::check_vcs_permalinks;
 pub(crate) use check_xml::check_xml;
 pub(crate) use check_yaml::check_yaml;
+pub(crate) use debug_statements::debug_statements;
 pub(crate) use destroyed_symlinks::destroyed_symlinks;
 pub(crate) use detect_private_key::detect_private_key;
 pub(crate) use file_contents_sorter::file_contents_sorter;
 pub(crate) use fix_byte_order_marker::fix_byte_order_marker;
+pub(crate) use fix_encoding_pragma::fix_encoding_pragma;
 pub(crate) use fix_end_of_file::fix_end_of_file;
 pub(crate) use fix_trailing_whitespace::fix_trailing_whitespace;
 pub(crate) use forbid_new_submodules::forbid_new_submodules;
 pub(crate) use mixed_line_ending::mixed_line_ending;
+pub(crate) use name_tests_test::name_tests_test;
 pub(crate) use no_commit_to_branch::no_commit_to_branch;
 pub(crate) use pretty_format_json::pretty_format_json;
+pub(crate) use sort_simple_yaml::sort_simple_yaml;
+
+/// The UTF-8 byte order marker.
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
 
 /// Hooks from `https://github.com/pre-commit/pre-commit-hooks`.
 #[derive(strum::EnumString)]
 #[strum(serialize_all = "kebab-case")]
 pub(crate) enum PreCommitHooks {
     CheckAddedLargeFiles,
+    CheckBuiltinLiterals,
     CheckCaseConflict,
     CheckExecutablesHaveShebangs,
     CheckShebangScriptsAreExecutable,
@@ -62,6 +77,7 @@ pub(crate) enum PreCommitHooks {
     FileContentsSorter,
     EndOfFileFixer,
     FixByteOrderMarker,
+    FixEncodingPragma,
     ForbidNewSubmodules,
     CheckJson,
     CheckSymlinks,
@@ -69,14 +85,17 @@ pub(crate) enum PreCommitHooks {
     CheckToml,
     CheckXml,
     CheckYaml,
+    DebugStatements,
     DestroyedSymlinks,
     MixedLineEnding,
+    NameTestsTest,
     DetectPrivateKey,
     NoCommitToBranch,
     // `pretty-format-json` is intentionally builtin-only for now. Do not enable
     // automatic fast-path replacement until parity coverage against upstream
     // Python is broad enough to trust it as the default implementation.
     // PrettyFormatJson,
+    SortSimpleYaml,
     TrailingWhitespace,
 }
 
@@ -94,10 +113,13 @@ impl PreCommitHooks {
             Self::EndOfFileFixer
             | Self::FileContentsSorter
             | Self::FixByteOrderMarker
+            | Self::FixEncodingPragma
             | Self::MixedLineEnding
+            | Self::SortSimpleYaml
             | Self::TrailingWhitespace => true,
 
             Self::CheckAddedLargeFiles
+            | Self::CheckBuiltinLiterals
             | Self::CheckCaseConflict
             | Self::CheckExecutablesHaveShebangs
             | Self::CheckShebangScriptsAreExecutable
@@ -109,16 +131,25 @@ impl PreCommitHooks {
             | Self::CheckToml
             | Self::CheckXml
             | Self::CheckYaml
+            | Self::DebugStatements
             | Self::DestroyedSymlinks
             | Self::DetectPrivateKey
+            | Self::NameTestsTest
             | Self::NoCommitToBranch => false,
         }
     }
 
-    pub(crate) async fn run(self, hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+    pub(crate) async fn run(
+        self,
+        hook: &Hook,
+        filenames: &[&Path],
+        context: &HookRunContext,
+    ) -> Result<(i32, Vec<u8>)> {
         debug!("Running hook `{}` in fast path", hook.id);
+        let show_diff = context.show_diff;
         match self {
             Self::CheckAddedLargeFiles => check_added_large_files(hook, filenames).await,
+            Self::CheckBuiltinLiterals => check_builtin_literals(hook, filenames).await,
             Self::CheckCaseConflict => check_case_conflict(hook, filenames).await,
             Self::CheckExecutablesHaveShebangs => {
                 check_executables_have_shebangs(hook, filenames).await
@@ -127,21 +158,25 @@ impl PreCommitHooks {
                 check_shebang_scripts_are_executable(hook, filenames).await
             }
             Self::CheckVcsPermalinks => check_vcs_permalinks(hook, filenames).await,
-            Self::FileContentsSorter => file_contents_sorter(hook, filenames).await,
-            Self::EndOfFileFixer => fix_end_of_file(hook, filenames).await,
-            Self::FixByteOrderMarker => fix_byte_order_marker(hook, filenames).await,
+            Self::FileContentsSorter => file_contents_sorter(hook, filenames, show_diff).await,
+            Self::EndOfFileFixer => fix_end_of_file(hook, filenames, show_diff).await,
+            Self::FixByteOrderMarker => fix_byte_order_marker(hook, filenames, show_diff).await,
+            Self::FixEncodingPragma => fix_encoding_pragma(hook, filenames, show_diff).await,
             Self::ForbidNewSubmodules => forbid_new_submodules(hook, filenames).await,
             Self::CheckJson => check_json(hook, filenames).await,
             Self::CheckSymlinks => check_symlinks(hook, filenames).await,
-            Self::CheckMergeConflict => check_merge_conflict(hook, filenames).await,
+            Self::CheckMergeConflict => check_merge_conflict(hook, filenames, context).await,
             Self::CheckToml => check_toml(hook, filenames).await,
             Self::CheckYaml => check_yaml(hook, filenames).await,
             Self::CheckXml => check_xml(hook, filenames).await,
+            Self::DebugStatements => debug_statements(hook, filenames, context).await,
             Self::DestroyedSymlinks => destroyed_symlinks(hook, filenames).await,
-            Self::MixedLineEnding => mixed_line_ending(hook, filenames).await,
+            Self::MixedLineEnding => mixed_line_ending(hook, filenames, show_diff).await,
+            Self::NameTestsTest => name_tests_test(hook, filenames),
             Self::DetectPrivateKey => detect_private_key(hook, filenames).await,
             Self::NoCommitToBranch => no_commit_to_branch(hook).await,
-            Self::TrailingWhitespace => fix_trailing_whitespace(hook, filenames).await,
+            Self::SortSimpleYaml => sort_simple_yaml(hook, filenames, show_diff).await,
+            Self::TrailingWhitespace => fix_trailing_whitespace(hook, filenames, show_diff).await,
         }
     }
 }
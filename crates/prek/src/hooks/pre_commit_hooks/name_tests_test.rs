@@ -0,0 +1,132 @@
+use std::io::Write;
+use std::path::{Component, Path};
+
+use anyhow::Result;
+use clap::Parser;
+use regex::Regex;
+
+use crate::hook::Hook;
+
+const PYTEST_PATTERN: &str = r".*_test\.py";
+const PYTEST_TEST_FIRST_PATTERN: &str = r"test_.*\.py";
+const DJANGO_PATTERN: &str = r"test.*\.py";
+
+#[derive(Parser)]
+#[command(disable_help_subcommand = true)]
+#[command(disable_version_flag = true)]
+#[command(disable_help_flag = true)]
+struct Args {
+    /// Require `.*_test\.py`, the default.
+    #[arg(long, conflicts_with_all = ["pytest_test_first", "django"])]
+    pytest: bool,
+    /// Require `test_.*\.py`.
+    #[arg(long, conflicts_with = "django")]
+    pytest_test_first: bool,
+    /// Require `test.*\.py`.
+    #[arg(long, alias = "unittest")]
+    django: bool,
+}
+
+impl Args {
+    fn pattern(&self) -> &'static str {
+        if self.pytest_test_first {
+            PYTEST_TEST_FIRST_PATTERN
+        } else if self.django {
+            DJANGO_PATTERN
+        } else {
+            PYTEST_PATTERN
+        }
+    }
+}
+
+pub(crate) fn name_tests_test(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+    let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
+    let pattern = args.pattern();
+    let re = Regex::new(&format!("^(?:{pattern})$")).expect("test name pattern must be valid");
+
+    let mut code = 0;
+    let mut output = Vec::new();
+    for filename in filenames
+        .iter()
+        .filter(|filename| is_misnamed(filename, &re))
+    {
+        code = 1;
+        writeln!(
+            output,
+            "{} does not match pattern \"{pattern}\"",
+            filename.display()
+        )?;
+    }
+
+    Ok((code, output))
+}
+
+/// Whether a file under a test directory does not match the test naming pattern.
+///
+/// `__init__.py`, `conftest.py` and files inside a `fixtures` directory are never reported.
+fn is_misnamed(filename: &Path, re: &Regex) -> bool {
+    let Some(name) = filename.file_name() else {
+        return false;
+    };
+    let name = name.to_string_lossy();
+    if re.is_match(&name) || name == "__init__.py" || name == "conftest.py" {
+        return false;
+    }
+
+    let in_fixtures = filename.parent().is_some_and(|parent| {
+        parent
+            .components()
+            .any(|c| c == Component::Normal("fixtures".as_ref()))
+    });
+    !in_fixtures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn misnamed(args: &[&str], filename: &str) -> bool {
+        let args =
+            Args::try_parse_from(std::iter::once("name-tests-test").chain(args.iter().copied()))
+                .expect("valid args");
+        let re = Regex::new(&format!("^(?:{})$", args.pattern())).expect("valid pattern");
+        is_misnamed(Path::new(filename), &re)
+    }
+
+    #[test]
+    fn pytest_is_the_default() {
+        for args in [&[][..], &["--pytest"]] {
+            assert!(!misnamed(args, "tests/foo_test.py"));
+            assert!(misnamed(args, "tests/test_foo.py"));
+            assert!(misnamed(args, "tests/foo_test.pyc"));
+        }
+    }
+
+    #[test]
+    fn test_first_and_django() {
+        assert!(!misnamed(&["--pytest-test-first"], "tests/test_foo.py"));
+        assert!(misnamed(&["--pytest-test-first"], "tests/testfoo.py"));
+        assert!(misnamed(&["--pytest-test-first"], "tests/foo_test.py"));
+
+        for flag in ["--django", "--unittest"] {
+            assert!(!misnamed(&[flag], "tests/testfoo.py"));
+            assert!(!misnamed(&[flag], "tests/test_foo.py"));
+            assert!(misnamed(&[flag], "tests/foo_test.py"));
+        }
+    }
+
+    #[test]
+    fn skips_helpers_and_fixtures() {
+        assert!(!misnamed(&[], "tests/__init__.py"));
+        assert!(!misnamed(&[], "tests/conftest.py"));
+        assert!(!misnamed(&[], "tests/fixtures/data.py"));
+        assert!(!misnamed(&[], "tests/fixtures/nested/data.py"));
+        assert!(misnamed(&[], "tests/fixtures.py"));
+    }
+
+    #[test]
+    fn styles_are_exclusive() {
+        let result = Args::try_parse_from(["name-tests-test", "--pytest", "--django"]);
+        assert!(result.is_err());
+    }
+}
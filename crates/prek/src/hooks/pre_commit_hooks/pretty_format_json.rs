@@ -7,10 +7,9 @@ use clap::Parser;
 use serde::Serialize;
 use serde_json::Value;
 use serde_json::ser::{Formatter, PrettyFormatter};
-use similar::TextDiff;
 
 use crate::hook::Hook;
-use crate::hooks::run_concurrent_file_checks;
+use crate::hooks::{run_concurrent_file_checks, unified_diff, write_fixed};
 use crate::run::CONCURRENCY;
 
 #[derive(Parser, Debug)]
@@ -68,12 +67,21 @@ impl From<&Args> for PreparedArgs {
     }
 }
 
-pub(crate) async fn pretty_format_json(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+pub(crate) async fn pretty_format_json(
+    hook: &Hook,
+    filenames: &[&Path],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split()?.iter().chain(&hook.args))?;
     let prepared = PreparedArgs::from(&args);
 
     run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
-        check_file(hook.project().relative_path(), filename, &prepared)
+        check_file(
+            hook.project().relative_path(),
+            filename,
+            &prepared,
+            show_diff,
+        )
     })
     .await
 }
@@ -82,6 +90,7 @@ async fn check_file(
     file_base: &Path,
     filename: &Path,
     args: &PreparedArgs,
+    show_diff: bool,
 ) -> Result<(i32, Vec<u8>)> {
     let original_content = fs_err::tokio::read_to_string(file_base.join(filename)).await?;
 
@@ -96,12 +105,22 @@ async fn check_file(
                 // Rust writes bytes exactly as provided. Preserve the file's
                 // existing newline style instead of forcing serde_json's LF.
                 let output = with_original_line_ending(&prettified_json, &original_content);
-                fs_err::tokio::write(file_base.join(filename), output.as_bytes()).await?;
+                if let Some(diff) = write_fixed(
+                    &file_base.join(filename),
+                    filename,
+                    original_content.as_bytes(),
+                    output.as_bytes(),
+                    show_diff,
+                )
+                .await?
+                {
+                    return Ok((1, diff.into_bytes()));
+                }
                 let message = format!("Fixing file {}\n", filename.display());
                 Ok((1, message.into_bytes()))
             } else {
                 let normalized_content = normalize_newlines(&original_content);
-                let diff = generate_diff(normalized_content.as_ref(), &prettified_json, filename);
+                let diff = unified_diff(normalized_content.as_ref(), &prettified_json, filename);
                 let message = format!("{}: not pretty-formatted.\n{diff}", filename.display());
                 Ok((1, message.into_bytes()))
             }
@@ -436,17 +455,6 @@ fn reorder_keys(value: &mut Value, top_keys: &[String], sort_keys: bool) {
     }
 }
 
-fn generate_diff(original: &str, formatted: &str, filename: &Path) -> String {
-    TextDiff::from_lines(original, formatted)
-        .unified_diff()
-        .context_radius(3)
-        .header(
-            &filename.display().to_string(),
-            &filename.display().to_string(),
-        )
-        .to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -513,7 +521,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(dir.path(), Path::new("empty.json"), &args).await?;
+        let (code, output) = check_file(dir.path(), Path::new("empty.json"), &args, false).await?;
 
         assert_eq!(code, 1);
         let output = String::from_utf8(output)?;
@@ -537,7 +545,8 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(dir.path(), Path::new("invalid.json"), &args).await?;
+        let (code, output) =
+            check_file(dir.path(), Path::new("invalid.json"), &args, false).await?;
 
         assert_eq!(code, 1);
         let output = String::from_utf8(output)?;
@@ -561,7 +570,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -582,7 +591,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -603,7 +612,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 0);
         assert!(output.is_empty());
@@ -622,7 +631,8 @@ mod tests {
             ordered_top_keys: vec![],
             sort_keys: true,
         };
-        let (code, output) = check_file(dir.path(), Path::new("non_pretty.json"), &args).await?;
+        let (code, output) =
+            check_file(dir.path(), Path::new("non_pretty.json"), &args, false).await?;
 
         assert_eq!(code, 1);
         let output = String::from_utf8(output)?;
@@ -664,7 +674,7 @@ mod tests {
             sort_keys: false,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         // With sorting disabled, no changes needed
         assert_eq!(code, 0);
@@ -685,7 +695,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         let output_str = String::from_utf8_lossy(&output);
@@ -724,7 +734,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -749,7 +759,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -772,7 +782,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -796,7 +806,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -841,7 +851,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -864,7 +874,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -900,7 +910,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, _output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, _output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 0);
         let result = fs_err::tokio::read_to_string(&file_path).await?;
@@ -933,7 +943,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         assert!(String::from_utf8_lossy(&output).contains("Fixing file"));
@@ -991,7 +1001,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, _output) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, _output) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         let result = fs_err::tokio::read_to_string(&file_path).await?;
@@ -1022,7 +1032,7 @@ mod tests {
             sort_keys: true,
         };
 
-        let (code, _) = check_file(Path::new(""), &file_path, &args).await?;
+        let (code, _) = check_file(Path::new(""), &file_path, &args, false).await?;
 
         assert_eq!(code, 1);
         let result = fs_err::tokio::read_to_string(&file_path).await?;
@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use anyhow::Result;
+use bstr::ByteSlice;
+
+use crate::hook::Hook;
+use crate::hooks::{run_concurrent_file_checks, write_fixed};
+use crate::run::CONCURRENCY;
+
+pub(crate) async fn sort_simple_yaml(
+    hook: &Hook,
+    filenames: &[&Path],
+    show_diff: bool,
+) -> Result<(i32, Vec<u8>)> {
+    run_concurrent_file_checks(filenames.iter().copied(), *CONCURRENCY, |filename| {
+        sort_file(hook.project().relative_path(), filename, show_diff)
+    })
+    .await
+}
+
+async fn sort_file(file_base: &Path, filename: &Path, show_diff: bool) -> Result<(i32, Vec<u8>)> {
+    let file_path = file_base.join(filename);
+    let before = fs_err::tokio::read(&file_path).await?;
+
+    let lines = before
+        .lines()
+        .map(<[u8]>::trim_ascii_end)
+        .collect::<Vec<_>>();
+    let sorted = sort_lines(&lines);
+    if sorted == lines {
+        return Ok((0, Vec::new()));
+    }
+
+    let mut after = sorted.join(b"\n".as_slice());
+    after.push(b'\n');
+    if let Some(diff) = write_fixed(&file_path, filename, &before, &after, show_diff).await? {
+        return Ok((1, diff.into_bytes()));
+    }
+    Ok((
+        1,
+        format!("Fixing file `{}`\n", filename.display()).into_bytes(),
+    ))
+}
+
+/// Sort the blank-line separated blocks of a YAML file by their first key.
+///
+/// A leading block of comments is kept in place as a header. Comments inside a block
+/// move with it, and blocks are separated by a single blank line afterwards.
+fn sort_lines<'a>(lines: &[&'a [u8]]) -> Vec<&'a [u8]> {
+    let mut rest = lines;
+    let header_len = rest
+        .iter()
+        .take_while(|line| line.starts_with(b"#"))
+        .count();
+    let mut sorted = rest[..header_len].to_vec();
+    rest = &rest[header_len..];
+
+    let mut blocks = Vec::new();
+    while let Some((first, tail)) = rest.split_first() {
+        if first.is_empty() {
+            rest = tail;
+            continue;
+        }
+        let block_len = rest.iter().take_while(|line| !line.is_empty()).count();
+        blocks.push(&rest[..block_len]);
+        rest = &rest[block_len..];
+    }
+    blocks.sort_by_key(|block| first_key(block));
+
+    for block in blocks {
+        if !sorted.is_empty() {
+            sorted.push(b"");
+        }
+        sorted.extend_from_slice(block);
+    }
+    sorted
+}
+
+/// The sort key of a block: its first non-comment line, without a leading quote.
+fn first_key<'a>(block: &[&'a [u8]]) -> &'a [u8] {
+    block
+        .iter()
+        .find(|line| !line.starts_with(b"#"))
+        .map(|line| {
+            line.strip_prefix(b"'")
+                .or_else(|| line.strip_prefix(b"\""))
+                .unwrap_or(line)
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sort(content: &str) -> String {
+        let lines = content
+            .as_bytes()
+            .lines()
+            .map(<[u8]>::trim_ascii_end)
+            .collect::<Vec<_>>();
+        sort_lines(&lines)
+            .join(b"\n".as_slice())
+            .to_str_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn sorts_blocks_by_first_key() {
+        let content = indoc::indoc! {r"
+            b: 2
+
+            # about a
+            a: 1
+            a_more: 1
+
+            c: 3
+        "};
+        assert_eq!(sort(content), "# about a\na: 1\na_more: 1\n\nb: 2\n\nc: 3");
+    }
+
+    #[test]
+    fn keeps_header_comments() {
+        let content = indoc::indoc! {r"
+            # header
+            # more header
+
+            # about b
+            b: 2
+
+
+            a: 1
+        "};
+        assert_eq!(
+            sort(content),
+            "# header\n# more header\n\na: 1\n\n# about b\nb: 2"
+        );
+    }
+
+    #[test]
+    fn ignores_leading_quotes() {
+        let content = "'c': 3\n\n\"a\": 1\n\nb: 2\n";
+        assert_eq!(sort(content), "\"a\": 1\n\nb: 2\n\n'c': 3");
+    }
+
+    #[test]
+    fn sorted_and_empty_files_are_unchanged() {
+        let lines: Vec<&[u8]> = vec![b"a: 1", b"", b"b: 2"];
+        assert_eq!(sort_lines(&lines), lines);
+        assert!(sort_lines(&[]).is_empty());
+    }
+}
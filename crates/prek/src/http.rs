@@ -1,11 +1,13 @@
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use futures::TryStreamExt;
+use futures::StreamExt;
 use prek_consts::env_vars::EnvVars;
-use reqwest::Certificate;
-use tokio_util::compat::FuturesAsyncReadCompatExt;
+use reqwest::header;
+use reqwest::{Certificate, StatusCode};
+use tokio::io::AsyncWriteExt;
 use tracing::debug;
 
 use crate::archive::ArchiveExtension;
@@ -13,54 +15,67 @@ use crate::fs::Simplified;
 use crate::store::Store;
 use crate::{archive, warn_user};
 
+#[derive(Debug, thiserror::Error)]
+#[error("offline: `{0}` is not cached")]
+pub(crate) struct OfflineError(pub(crate) String);
+
+/// Download the archive at `url` and call `callback` with the extracted directory.
+///
+/// With `subdir`, the directory at that path inside the archive is selected (`.` for the
+/// top-level), and an archive without it is rejected. Otherwise, a single top-level
+/// directory is stripped if there is one.
 pub(crate) async fn download_and_extract(
     url: &str,
     filename: &str,
+    subdir: Option<&str>,
     store: &Store,
     callback: impl AsyncFn(&Path) -> Result<()>,
 ) -> Result<()> {
-    download_and_extract_with(url, filename, store, |req| req, callback).await
+    download_and_extract_with(url, filename, subdir, store, |req| req, callback).await
 }
 
 /// Like [`download_and_extract`], but accepts a `customize_request` closure
 /// that can modify the [`reqwest::RequestBuilder`] before it is sent (e.g. to
-/// add authentication headers).
+/// add authentication headers). The closure is called once per attempt.
 pub(crate) async fn download_and_extract_with(
     url: &str,
     filename: &str,
+    subdir: Option<&str>,
     store: &Store,
-    customize_request: impl FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    customize_request: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
     callback: impl AsyncFn(&Path) -> Result<()>,
 ) -> Result<()> {
-    let response = customize_request(REQWEST_CLIENT.get(url))
-        .send()
-        .await
-        .with_context(|| format!("Failed to download file from {url}"))?;
-    if !response.status().is_success() {
-        anyhow::bail!(
-            "Failed to download file from {}: {}",
-            url,
-            response.status()
-        );
-    }
-
-    let tarball = response
-        .bytes_stream()
-        .map_err(std::io::Error::other)
-        .into_async_read()
-        .compat();
+    store.ensure_online(url)?;
+    let ext = ArchiveExtension::from_path(filename)?;
 
     let scratch_dir = store.scratch_path();
+    let archive_file = tempfile::NamedTempFile::new_in(&scratch_dir)?;
     let temp_dir = tempfile::tempdir_in(&scratch_dir)?;
     debug!(url = %url, temp_dir = ?temp_dir.path(), "Downloading");
 
-    let ext = ArchiveExtension::from_path(filename)?;
-    archive::unpack(tarball, ext, temp_dir.path()).await?;
+    download_to_file(
+        url,
+        archive_file.path(),
+        &customize_request,
+        *DOWNLOAD_RETRIES,
+    )
+    .await?;
 
-    let extracted = match archive::strip_component(temp_dir.path()) {
-        Ok(top_level) => top_level,
-        Err(archive::Error::NonSingularArchive(_)) => temp_dir.path().to_path_buf(),
-        Err(err) => return Err(err.into()),
+    let reader = tokio::io::BufReader::new(fs_err::tokio::File::open(archive_file.path()).await?);
+    archive::unpack(reader, ext, temp_dir.path())
+        .await
+        .with_context(|| format!("Failed to extract `{filename}`"))?;
+    drop(archive_file);
+
+    let extracted = if let Some(subdir) = subdir {
+        archive::select_subdir(temp_dir.path(), subdir)
+            .with_context(|| format!("Unexpected layout of `{filename}`"))?
+    } else {
+        match archive::strip_component(temp_dir.path()) {
+            Ok(top_level) => top_level,
+            Err(archive::Error::NonSingularArchive(_)) => temp_dir.path().to_path_buf(),
+            Err(err) => return Err(err.into()),
+        }
     };
 
     callback(&extracted).await?;
@@ -70,6 +85,159 @@ pub(crate) async fn download_and_extract_with(
     Ok(())
 }
 
+const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+
+/// Number of times a failed download is retried, from `PREK_DOWNLOAD_RETRIES`.
+static DOWNLOAD_RETRIES: LazyLock<u32> = LazyLock::new(|| {
+    let Ok(value) = EnvVars::var(EnvVars::PREK_DOWNLOAD_RETRIES) else {
+        return DEFAULT_DOWNLOAD_RETRIES;
+    };
+    value.parse().unwrap_or_else(|_| {
+        warn_user!(
+            "Invalid value for {}: {value:?}, using default ({DEFAULT_DOWNLOAD_RETRIES})",
+            EnvVars::PREK_DOWNLOAD_RETRIES,
+        );
+        DEFAULT_DOWNLOAD_RETRIES
+    })
+});
+
+/// Delay before the given retry attempt (starting at 1): 500ms, 1s, 2s, ... capped at 10s.
+fn retry_delay(attempt: u32) -> Duration {
+    let delay = Duration::from_millis(500).saturating_mul(2u32.saturating_pow(attempt - 1));
+    delay.min(Duration::from_secs(10))
+}
+
+/// The outcome of a single failed download attempt.
+enum AttemptError {
+    /// The failure may go away on retry (connection reset, timeout, 5xx).
+    Transient(anyhow::Error),
+    /// Retrying would not help (e.g. 404).
+    Fatal(anyhow::Error),
+}
+
+impl AttemptError {
+    fn from_reqwest(err: reqwest::Error, url: &str) -> Self {
+        // A connection dropped mid-body surfaces as a decode error.
+        let transient = err.is_connect()
+            || err.is_timeout()
+            || err.is_request()
+            || err.is_body()
+            || err.is_decode();
+        let err = anyhow::Error::new(err).context(format!("Failed to download file from {url}"));
+        if transient {
+            Self::Transient(err)
+        } else {
+            Self::Fatal(err)
+        }
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Download `url` into `dest`, retrying transient failures with exponential backoff.
+///
+/// If the server advertised `Accept-Ranges: bytes`, a retry resumes from the bytes already
+/// written to `dest` instead of starting over.
+async fn download_to_file(
+    url: &str,
+    dest: &Path,
+    customize_request: &impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    retries: u32,
+) -> Result<()> {
+    let mut resumable = false;
+    let mut attempt = 0;
+
+    loop {
+        debug!(url = %url, attempt = attempt + 1, "Sending download request");
+        match download_attempt(url, dest, customize_request, &mut resumable).await {
+            Ok(()) => return Ok(()),
+            Err(AttemptError::Transient(err)) if attempt < retries => {
+                attempt += 1;
+                let delay = retry_delay(attempt);
+                debug!(
+                    url = %url,
+                    "Download failed, retrying in {}ms ({attempt}/{retries}): {err:#}",
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(AttemptError::Transient(err) | AttemptError::Fatal(err)) => return Err(err),
+        }
+    }
+}
+
+async fn download_attempt(
+    url: &str,
+    dest: &Path,
+    customize_request: &impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    resumable: &mut bool,
+) -> Result<(), AttemptError> {
+    let io_err = |err: std::io::Error| AttemptError::Fatal(err.into());
+
+    let offset = if *resumable {
+        fs_err::tokio::metadata(dest).await.map_err(io_err)?.len()
+    } else {
+        0
+    };
+
+    let mut request = customize_request(REQWEST_CLIENT.get(url));
+    if offset > 0 {
+        debug!(url = %url, offset, "Resuming download");
+        request = request.header(header::RANGE, format!("bytes={offset}-"));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|err| AttemptError::from_reqwest(err, url))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        // The partial file may be stale (e.g. the remote file changed), start over next time.
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            *resumable = false;
+        }
+        let err = anyhow::anyhow!("Failed to download file from {url}: {status}");
+        return Err(
+            if is_transient_status(status) || status == StatusCode::RANGE_NOT_SATISFIABLE {
+                AttemptError::Transient(err)
+            } else {
+                AttemptError::Fatal(err)
+            },
+        );
+    }
+
+    // Append only if the server actually honored the range request.
+    let append = offset > 0 && status == StatusCode::PARTIAL_CONTENT;
+    *resumable = response
+        .headers()
+        .get(header::ACCEPT_RANGES)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"bytes"))
+        || append;
+
+    let mut file = fs_err::tokio::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(dest)
+        .await
+        .map_err(io_err)?;
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|err| AttemptError::from_reqwest(err, url))?;
+        file.write_all(&chunk).await.map_err(io_err)?;
+    }
+    file.flush().await.map_err(io_err)?;
+
+    Ok(())
+}
+
 pub(crate) static REQWEST_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
     let native_tls = EnvVars::var_as_bool(EnvVars::PREK_NATIVE_TLS).unwrap_or(false);
 
@@ -179,6 +347,108 @@ fn create_reqwest_client(native_tls: bool, custom_certs: Vec<Certificate>) -> re
 mod tests {
     use anyhow::Result;
     use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned response per connection, recording each request head.
+    async fn serve(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/file.tar.gz", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut head = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    head.extend_from_slice(&buf[..n]);
+                }
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&head).to_lowercase());
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(super::retry_delay(1), Duration::from_millis(500));
+        assert_eq!(super::retry_delay(2), Duration::from_secs(1));
+        assert_eq!(super::retry_delay(3), Duration::from_secs(2));
+        assert_eq!(super::retry_delay(10), Duration::from_secs(10));
+        assert_eq!(super::retry_delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_download_retries_server_errors() -> Result<()> {
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+        ])
+        .await;
+        let temp_dir = tempfile::tempdir()?;
+        let dest = temp_dir.path().join("file");
+
+        super::download_to_file(&url, &dest, &|req| req, 2).await?;
+
+        assert_eq!(fs_err::read_to_string(&dest)?, "hello");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_with_range_request() -> Result<()> {
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 200 OK\r\nAccept-Ranges: bytes\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhello",
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 5-9/10\r\nContent-Length: 5\r\nConnection: close\r\n\r\nworld",
+        ])
+        .await;
+        let temp_dir = tempfile::tempdir()?;
+        let dest = temp_dir.path().join("file");
+
+        super::download_to_file(&url, &dest, &|req| req, 2).await?;
+
+        assert_eq!(fs_err::read_to_string(&dest)?, "helloworld");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=5-"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_does_not_retry_client_errors() -> Result<()> {
+        let (url, requests) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ])
+        .await;
+        let temp_dir = tempfile::tempdir()?;
+        let dest = temp_dir.path().join("file");
+
+        let err = super::download_to_file(&url, &dest, &|req| req, 2)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("404 Not Found"));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        Ok(())
+    }
 
     const TEST_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
 MIIBtjCCAVugAwIBAgITBmyf1XSXNmY/Owua2eiedgPySjAKBggqhkjOPQQDAjA5
@@ -8,7 +8,6 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
 use crate::languages::LanguageImpl;
@@ -16,7 +15,7 @@ use crate::languages::bun::BunRequest;
 use crate::languages::bun::installer::{BunInstaller, BunResult, bin_dir, lib_dir};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -100,7 +99,7 @@ impl LanguageImpl for Bun {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let bun = BunResult::from_executable(info.toolchain.clone())
             .fill_version()
             .await
@@ -122,9 +121,9 @@ impl LanguageImpl for Bun {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Bun must have env path");
         let bun_bin = hook.toolchain_dir().expect("Bun binary must have parent");
@@ -132,28 +131,33 @@ impl LanguageImpl for Bun {
             prepend_paths(&[&bin_dir(env_dir), bun_bin]).context("Failed to join PATH")?;
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::BUN_INSTALL, env_dir.as_os_str()),
+        ];
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "bun hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::BUN_INSTALL, env_dir)
-                .envs(&hook.env)
+                .envs(language_env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -164,7 +168,7 @@ impl LanguageImpl for Bun {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -215,12 +215,13 @@ impl BunInstaller {
             _ => anyhow::bail!("Unsupported OS"),
         };
 
-        let filename = format!("bun-{os}-{arch}.zip");
+        let dirname = format!("bun-{os}-{arch}");
+        let filename = format!("{dirname}.zip");
         let url =
             format!("https://github.com/oven-sh/bun/releases/download/bun-v{version}/{filename}");
         let target = self.root.join(version.to_string());
 
-        download_and_extract(&url, &filename, store, async |extracted| {
+        download_and_extract(&url, &filename, Some(&dirname), store, async |extracted| {
             if target.exists() {
                 debug!(target = %target.display(), "Removing existing bun");
                 fs_err::tokio::remove_dir_all(&target).await?;
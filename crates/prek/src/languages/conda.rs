@@ -8,16 +8,18 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Conda;
 
+/// Environment file a hook repository can ship to describe its Conda environment.
+const ENVIRONMENT_FILE: &str = "environment.yml";
+
 impl LanguageImpl for Conda {
     async fn install(
         &self,
@@ -36,14 +38,19 @@ impl LanguageImpl for Conda {
         debug!(%hook, target = %info.env_path.display(), "Installing Conda environment");
         let conda = conda_executable();
 
-        if let Some(repo_path) = hook.repo_path() {
+        if let Some(repo_path) = hook
+            .repo_path()
+            .filter(|repo_path| repo_path.join(ENVIRONMENT_FILE).is_file())
+        {
+            debug!(%hook, "Creating Conda environment from `{ENVIRONMENT_FILE}`");
             Cmd::new(conda, "create conda environment")
                 .current_dir(repo_path)
+                .arg("env")
                 .arg("create")
                 .arg("-p")
                 .arg(&info.env_path)
                 .arg("--file")
-                .arg("environment.yml")
+                .arg(ENVIRONMENT_FILE)
                 .check(true)
                 .output()
                 .await
@@ -86,7 +93,7 @@ impl LanguageImpl for Conda {
         })
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -95,38 +102,43 @@ impl LanguageImpl for Conda {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Conda must have env path");
         let new_path = conda_path(env_dir).context("Failed to join PATH")?;
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::CONDA_PREFIX, env_dir.as_os_str()),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "run conda hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::CONDA_PREFIX, env_dir)
+                .envs(language_env)
                 .env_remove(EnvVars::PYTHONHOME)
                 .env_remove(EnvVars::VIRTUAL_ENV)
-                .envs(&hook.env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -136,7 +148,7 @@ impl LanguageImpl for Conda {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -9,11 +9,10 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{CacheBucket, Store};
 
 const PRE_COMMIT_CHANNEL_DIR: &str = ".pre-commit-channel";
@@ -157,7 +156,7 @@ impl LanguageImpl for Coursier {
         })
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -166,9 +165,9 @@ impl LanguageImpl for Coursier {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_path = hook.env_path().expect("Coursier must have env path");
         let coursier_cache = store.cache_path(CacheBucket::Coursier);
@@ -179,24 +178,29 @@ impl LanguageImpl for Coursier {
             let mut output = Cmd::new(&entry[0], "run coursier hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .envs(&hook.env)
+                .envs(hook.env_vars(&[
+                    (EnvVars::PATH, path_env.as_os_str()),
+                    (EnvVars::COURSIER_CACHE, coursier_cache.as_os_str()),
+                ]))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
                 .env(EnvVars::PATH, &path_env)
                 .env(EnvVars::COURSIER_CACHE, &coursier_cache)
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -206,7 +210,7 @@ impl LanguageImpl for Coursier {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
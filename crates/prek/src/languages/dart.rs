@@ -23,11 +23,10 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -139,7 +138,7 @@ impl LanguageImpl for Dart {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let dart = find_dart_binary()?;
 
         if dart != info.toolchain {
@@ -158,14 +157,18 @@ impl LanguageImpl for Dart {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Dart must have env path");
         let bin_path = bin_path(env_dir);
         let new_path = prepend_paths(&[&bin_path]).context("Failed to join PATH")?;
         let packages_path = package_config_path(env_dir);
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::PUB_CACHE, env_dir.as_os_str()),
+        ];
 
         let mut entry = hook.entry.resolve(Some(&new_path), store)?;
         // `dart pub get` writes the hook env's dependency graph here. Dart's
@@ -183,24 +186,25 @@ impl LanguageImpl for Dart {
             let mut output = Cmd::new(&entry[0], "run dart command")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::PUB_CACHE, env_dir)
-                .envs(&hook.env)
+                .envs(language_env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -209,7 +213,7 @@ impl LanguageImpl for Dart {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
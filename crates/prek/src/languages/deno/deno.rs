@@ -8,14 +8,13 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::deno::DenoRequest;
 use crate::languages::deno::installer::{DenoInstaller, DenoResult, bin_dir};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 fn is_valid_install_name(name: &str) -> bool {
@@ -161,7 +160,7 @@ impl LanguageImpl for Deno {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let deno = DenoResult::from_executable(info.toolchain.clone())
             .fill_version()
             .await
@@ -183,9 +182,9 @@ impl LanguageImpl for Deno {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let deno_cache_dir = store.cache_path(CacheBucket::Deno);
         let info = hook.install_info().expect("Deno must be installed");
@@ -195,31 +194,36 @@ impl LanguageImpl for Deno {
             prepend_paths(&[&bin_dir(env_dir), deno_bin_dir]).context("Failed to join PATH")?;
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::DENO_DIR, deno_cache_dir.as_os_str()),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut cmd = Cmd::new(&entry[0], "deno hook");
             let mut output = cmd
                 .current_dir(hook.work_dir())
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::DENO_DIR, &deno_cache_dir)
+                .envs(language_env)
                 .env(EnvVars::DENO_NO_UPDATE_CHECK, "1")
-                .envs(&hook.env)
+                .envs(hook.env_vars(&language_env))
                 .args(&entry[1..])
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -230,7 +234,7 @@ impl LanguageImpl for Deno {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
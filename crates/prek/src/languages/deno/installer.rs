@@ -125,7 +125,7 @@ impl DenoInstaller {
             anyhow::bail!("No suitable system Deno version found and downloads are disabled");
         }
 
-        let resolved_version = self.resolve_version(request).await?;
+        let resolved_version = self.resolve_version(store, request).await?;
         trace!(version = %resolved_version, "Downloading deno");
 
         self.download(store, &resolved_version).await
@@ -164,10 +164,10 @@ impl DenoInstaller {
             .context("No installed deno version matches the request")
     }
 
-    async fn resolve_version(&self, req: &DenoRequest) -> Result<DenoVersion> {
+    async fn resolve_version(&self, store: &Store, req: &DenoRequest) -> Result<DenoVersion> {
         // Latest versions come first, so we can find the latest matching version.
         let versions = self
-            .list_remote_versions()
+            .list_remote_versions(store)
             .await
             .context("Failed to list remote versions")?;
         let version = versions
@@ -181,13 +181,14 @@ impl DenoInstaller {
     ///
     /// Uses <https://deno.com/versions.json> which is lightweight and doesn't
     /// have rate-limit issues like the GitHub API.
-    async fn list_remote_versions(&self) -> Result<Vec<DenoVersion>> {
+    async fn list_remote_versions(&self, store: &Store) -> Result<Vec<DenoVersion>> {
         #[derive(Deserialize)]
         struct VersionsResponse {
             cli: Vec<String>,
         }
 
         let url = "https://deno.com/versions.json";
+        store.ensure_online(url)?;
         let response: VersionsResponse = REQWEST_CLIENT.get(url).send().await?.json().await?;
 
         // Versions are already sorted in descending order (newest first)
@@ -223,20 +224,14 @@ impl DenoInstaller {
         let url = format!("https://dl.deno.land/release/v{version}/{filename}");
         let target = self.root.join(version.to_string());
 
-        download_and_extract(&url, &filename, store, async |extracted| {
+        download_and_extract(&url, &filename, Some("."), store, async |extracted| {
             if target.exists() {
                 debug!(target = %target.display(), "Removing existing deno");
                 fs_err::tokio::remove_dir_all(&target).await?;
             }
 
             // Deno ZIP contains just the binary at the root level.
-            // After strip_component, `extracted` may be the binary itself (if singular)
-            // or a directory containing the binary.
-            let extracted_binary = if extracted.is_file() {
-                extracted.to_path_buf()
-            } else {
-                extracted.join("deno").with_extension(EXE_EXTENSION)
-            };
+            let extracted_binary = extracted.join("deno").with_extension(EXE_EXTENSION);
 
             let target_bin_dir = bin_dir(&target);
             fs_err::tokio::create_dir_all(&target_bin_dir).await?;
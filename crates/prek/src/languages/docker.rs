@@ -13,11 +13,10 @@ use regex::Regex;
 use tracing::{trace, warn};
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
-use crate::languages::LanguageImpl;
+use crate::languages::{LanguageImpl, command_args};
 use crate::process::Cmd;
-use crate::run::{USE_COLOR, run_by_batch};
+use crate::run::{HookRunContext, USE_COLOR, run_by_batch};
 use crate::store::Store;
 
 static CGROUP_V2_CONTAINER_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -378,7 +377,11 @@ impl Docker {
         let mut command = Cmd::new(CONTAINER_RUNTIME.cmd(), "run container");
         command.arg("run").arg("--rm");
 
-        if *USE_COLOR {
+        // Keep stdin open for hooks that read it (`stdin_from`); a TTY cannot be allocated
+        // for it then, since the input is a file rather than a terminal.
+        if crate::run::HOOK_STDIN.try_with(|_| ()).is_ok() {
+            command.arg("--interactive");
+        } else if *USE_COLOR {
             command.arg("--tty");
         }
 
@@ -429,6 +432,66 @@ impl Docker {
 
         command
     }
+
+    /// Run a `system` or `script` hook inside `image`, for hooks that set `container_image`.
+    ///
+    /// `entry` is the split entry as seen from inside the container. Filenames are relative to
+    /// the hook's working directory, which is mounted as the container's working directory,
+    /// so they are passed through unchanged.
+    pub(crate) async fn run_in_container(
+        hook: &InstalledHook,
+        image: &str,
+        entry: &[String],
+        filenames: &[&Path],
+        context: &HookRunContext,
+    ) -> Result<(i32, Vec<u8>)> {
+        let progress = context.reporter.on_run_start(hook, filenames.len());
+
+        // Pass environment variables on the command line (they will appear in ps output).
+        let env_args: Vec<String> = hook
+            .env_vars(&[])
+            .iter()
+            .flat_map(|(key, value)| ["-e".to_owned(), format!("{key}={value}")])
+            .collect();
+
+        let run = async |batch: &[&Path]| {
+            let mut cmd = Docker::docker_run_cmd(hook.work_dir());
+            let mut output = cmd
+                .current_dir(hook.work_dir())
+                .args(&env_args)
+                .arg("--entrypoint")
+                .arg(&entry[0])
+                .arg(image)
+                .args(command_args(&entry[1..], &hook.args, batch))
+                .check(false)
+                .stdin(Stdio::null())
+                .output_with_sink(context.reporter.output_sink(progress))
+                .await?;
+
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
+
+            output.stdout.extend(output.stderr);
+            let code = output.status.code().unwrap_or(1);
+            anyhow::Ok((code, output.stdout))
+        };
+
+        let results = run_by_batch(hook, filenames, entry, context, run).await?;
+
+        // Collect results
+        let mut combined_status = 0;
+        let mut combined_output = Vec::new();
+
+        for (code, output) in results {
+            combined_status |= code;
+            combined_output.extend(output);
+        }
+
+        context.reporter.on_run_complete(progress);
+
+        Ok((combined_status, combined_output))
+    }
 }
 
 impl LanguageImpl for Docker {
@@ -460,7 +523,7 @@ impl LanguageImpl for Docker {
         })
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -469,13 +532,13 @@ impl LanguageImpl for Docker {
         hook: &InstalledHook,
         filenames: &[&Path],
         _store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         // Pass environment variables on the command line (they will appear in ps output).
         let env_args: Vec<String> = hook
-            .env
+            .env_vars(&[])
             .iter()
             .flat_map(|(key, value)| ["-e".to_owned(), format!("{key}={value}")])
             .collect();
@@ -503,17 +566,19 @@ impl LanguageImpl for Docker {
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .output_with_sink(reporter.output_sink(progress))
+                .output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, &entry, run).await?;
+        let results = run_by_batch(hook, filenames, &entry, context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -524,7 +589,7 @@ impl LanguageImpl for Docker {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -5,11 +5,10 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::docker::Docker;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -25,7 +24,7 @@ impl LanguageImpl for DockerImage {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -34,13 +33,13 @@ impl LanguageImpl for DockerImage {
         hook: &InstalledHook,
         filenames: &[&Path],
         _store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         // Pass environment variables on the command line (they will appear in ps output).
         let env_args: Vec<String> = hook
-            .env
+            .env_vars(&[])
             .iter()
             .flat_map(|(key, value)| ["-e".to_owned(), format!("{key}={value}")])
             .collect();
@@ -56,17 +55,19 @@ impl LanguageImpl for DockerImage {
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .output_with_sink(reporter.output_sink(progress))
+                .output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, &entry, run).await?;
+        let results = run_by_batch(hook, filenames, &entry, context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -77,7 +78,7 @@ impl LanguageImpl for DockerImage {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -8,14 +8,13 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::dotnet::DotnetRequest;
 use crate::languages::dotnet::installer::{DotnetInstaller, DotnetResult};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -54,7 +53,7 @@ impl LanguageImpl for Dotnet {
             _ => unreachable!(),
         };
         let dotnet = installer
-            .install(request, allows_download)
+            .install(store, request, allows_download)
             .await
             .context("Failed to install dotnet SDK")?;
 
@@ -89,7 +88,7 @@ impl LanguageImpl for Dotnet {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let current_version = DotnetResult::from_executable(info.toolchain.clone())
             .fill_version()
             .await
@@ -116,9 +115,9 @@ impl LanguageImpl for Dotnet {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("dotnet hook must have env path");
         let tools_dir = tools_dir(env_dir);
@@ -130,29 +129,34 @@ impl LanguageImpl for Dotnet {
 
         let new_path = prepend_paths(&[&tools_dir, &dotnet_root]).context("Failed to join PATH")?;
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::DOTNET_ROOT, dotnet_root.as_os_str()),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "run dotnet hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::DOTNET_ROOT, &dotnet_root)
-                .envs(&hook.env)
+                .envs(language_env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -162,7 +166,7 @@ impl LanguageImpl for Dotnet {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -14,6 +14,7 @@ use crate::fs::LockedFile;
 use crate::http::REQWEST_CLIENT;
 use crate::languages::dotnet::DotnetRequest;
 use crate::process::Cmd;
+use crate::store::Store;
 
 static DOTNET_BINARY_NAME: LazyLock<String> = LazyLock::new(|| {
     if let Ok(name) = EnvVars::var(EnvVars::PREK_INTERNAL__DOTNET_BINARY_NAME) {
@@ -107,6 +108,7 @@ impl DotnetInstaller {
     /// Install or find dotnet SDK based on the language request.
     pub(crate) async fn install(
         &self,
+        store: &Store,
         request: &DotnetRequest,
         allows_download: bool,
     ) -> Result<DotnetResult> {
@@ -127,7 +129,7 @@ impl DotnetInstaller {
             bail!("No suitable dotnet version found and downloads are disabled");
         }
 
-        self.install_managed(request).await
+        self.install_managed(store, request).await
     }
 
     /// Finds the newest managed SDK installation that satisfies the request.
@@ -192,7 +194,11 @@ impl DotnetInstaller {
     }
 
     /// Downloads an SDK into a temporary directory, then promotes it to its final location.
-    async fn install_managed(&self, request: &DotnetRequest) -> Result<DotnetResult> {
+    async fn install_managed(
+        &self,
+        store: &Store,
+        request: &DotnetRequest,
+    ) -> Result<DotnetResult> {
         let install_dir = tempfile::Builder::new()
             .prefix(".install-")
             .tempdir_in(&self.root)?;
@@ -203,7 +209,7 @@ impl DotnetInstaller {
             "Installing dotnet SDK"
         );
 
-        self.download(install_dir.path(), request).await?;
+        self.download(store, install_dir.path(), request).await?;
 
         let installed = DotnetResult::from_dir(install_dir.path())
             .fill_version()
@@ -237,7 +243,12 @@ impl DotnetInstaller {
     }
 
     /// Downloads the platform-specific install script and runs it for the request.
-    async fn download(&self, install_dir: &Path, request: &DotnetRequest) -> Result<()> {
+    async fn download(
+        &self,
+        store: &Store,
+        install_dir: &Path,
+        request: &DotnetRequest,
+    ) -> Result<()> {
         // https://learn.microsoft.com/en-us/dotnet/core/tools/dotnet-install-script
         let (script_url, script_name) = if cfg!(windows) {
             (
@@ -250,6 +261,7 @@ impl DotnetInstaller {
         let script_dir = tempfile::tempdir()?;
         let script_path = script_dir.path().join(script_name);
 
+        store.ensure_online(script_url)?;
         let response = REQWEST_CLIENT
             .get(script_url)
             .send()
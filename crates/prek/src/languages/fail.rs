@@ -5,9 +5,9 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
+use crate::run::HookRunContext;
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -23,7 +23,7 @@ impl LanguageImpl for Fail {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -32,7 +32,7 @@ impl LanguageImpl for Fail {
         hook: &InstalledHook,
         filenames: &[&Path],
         _store: &Store,
-        _reporter: &HookRunReporter,
+        _context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
         let mut out = Vec::new();
         writeln!(out, "{}\n", hook.entry.expect_direct().raw())?;
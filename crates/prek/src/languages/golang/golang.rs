@@ -1,3 +1,4 @@
+use std::ffi::{OsStr, OsString};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
@@ -8,14 +9,13 @@ use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::golang::GoRequest;
 use crate::languages::golang::installer::GoInstaller;
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -66,6 +66,7 @@ impl LanguageImpl for Golang {
             .and_then(|p| p.parent())
             .expect("Go root should exist");
         let go_cache = store.cache_path(CacheBucket::Go);
+        let go_flags = go_flags(EnvVars::var_os(EnvVars::GOFLAGS).as_deref());
 
         let go_install_cmd = || {
             if go.is_from_system() {
@@ -80,7 +81,7 @@ impl LanguageImpl for Golang {
                     .env(EnvVars::GOTOOLCHAIN, "local")
                     .env(EnvVars::GOROOT, go_root)
                     .env(EnvVars::GOBIN, bin_dir(&info.env_path))
-                    .env(EnvVars::GOFLAGS, "-modcacherw")
+                    .env(EnvVars::GOFLAGS, &go_flags)
                     .env(EnvVars::GOPATH, &go_cache);
                 cmd
             }
@@ -101,7 +102,12 @@ impl LanguageImpl for Golang {
             if let Some(repo) = hook.repo_path() {
                 cmd.current_dir(repo);
             }
-            cmd.arg(dep).remove_git_envs().check(true).output().await?;
+            cmd.arg(dep)
+                .remove_git_envs()
+                .check(true)
+                .output()
+                .await
+                .with_context(|| format!("Failed to install Go dependency `{dep}`"))?;
         }
 
         info.persist_env_path();
@@ -114,7 +120,7 @@ impl LanguageImpl for Golang {
         })
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> anyhow::Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> anyhow::Result<()> {
         Ok(())
     }
 
@@ -123,9 +129,9 @@ impl LanguageImpl for Golang {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> anyhow::Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Node hook must have env path");
 
@@ -136,39 +142,48 @@ impl LanguageImpl for Golang {
         let go_cache = store.cache_path(CacheBucket::Go);
 
         // Only set GOROOT and GOPATH if using the Go installed by prek
-        let go_envs = if go_root_bin.starts_with(go_tools) {
-            vec![(EnvVars::GOROOT, go_root), (EnvVars::GOPATH, &go_cache)]
+        let mut language_env = if go_root_bin.starts_with(go_tools) {
+            vec![
+                (EnvVars::GOROOT, go_root.as_os_str()),
+                (EnvVars::GOPATH, go_cache.as_os_str()),
+            ]
         } else {
             vec![]
         };
         let new_path = prepend_paths(&[&go_bin, go_root_bin]).context("Failed to join PATH")?;
+        language_env.extend([
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::GOBIN, go_bin.as_os_str()),
+        ]);
+
+        let go_flags = go_flags(EnvVars::var_os(EnvVars::GOFLAGS).as_deref());
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "go hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
+                .envs(language_env.iter().copied())
                 .env(EnvVars::GOTOOLCHAIN, "local")
-                .env(EnvVars::GOBIN, &go_bin)
-                .env(EnvVars::GOFLAGS, "-modcacherw")
-                .envs(go_envs.iter().copied())
-                .envs(&hook.env)
+                .env(EnvVars::GOFLAGS, &go_flags)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -178,7 +193,7 @@ impl LanguageImpl for Golang {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -187,3 +202,42 @@ impl LanguageImpl for Golang {
 pub(crate) fn bin_dir(env_path: &Path) -> PathBuf {
     env_path.join("bin")
 }
+
+/// Append `-modcacherw` to the user's `GOFLAGS` rather than replacing them, so flags such as
+/// `-mod=mod` or `-insecure` still apply. Module cache files are made writable so the
+/// prek-managed `GOPATH` can be cleaned up.
+///
+/// The other module settings (`GOPROXY`, `GOPRIVATE`, `GONOPROXY`, `GONOSUMDB` and
+/// `GOINSECURE`) are inherited from the environment as is.
+fn go_flags(ambient: Option<&OsStr>) -> OsString {
+    let mut flags = ambient.map(OsStr::to_os_string).unwrap_or_default();
+    let has_modcacherw = flags
+        .to_str()
+        .is_some_and(|flags| flags.split_whitespace().any(|flag| flag == "-modcacherw"));
+    if !has_modcacherw {
+        if !flags.is_empty() {
+            flags.push(" ");
+        }
+        flags.push("-modcacherw");
+    }
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn go_flags_keeps_ambient_flags() {
+        assert_eq!(go_flags(None), "-modcacherw");
+        assert_eq!(go_flags(Some(OsStr::new(""))), "-modcacherw");
+        assert_eq!(
+            go_flags(Some(OsStr::new("-mod=mod -insecure"))),
+            "-mod=mod -insecure -modcacherw"
+        );
+        assert_eq!(
+            go_flags(Some(OsStr::new("-modcacherw -mod=mod"))),
+            "-modcacherw -mod=mod"
+        );
+    }
+}
@@ -1,24 +1,65 @@
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
 use semver::Version;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Lua;
 
+/// An `additional_dependencies` item of a Lua hook, `name` or `name version`.
+#[derive(Debug, Eq, PartialEq)]
+struct LuaRock<'a> {
+    name: &'a str,
+    version: Option<&'a str>,
+}
+
+impl<'a> LuaRock<'a> {
+    fn parse(dependency: &'a str) -> Result<Self> {
+        let is_valid = |s: &str| {
+            s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+
+        let mut parts = dependency.split_whitespace();
+        let (Some(name), version, None) = (parts.next(), parts.next(), parts.next()) else {
+            bail!("`{dependency}` is not a valid rock, use `name` or `name version`");
+        };
+        if !is_valid(name) || !version.is_none_or(is_valid) {
+            bail!("`{dependency}` is not a valid rock, use `name` or `name version`");
+        }
+        Ok(Self { name, version })
+    }
+
+    /// Whether an installed `version` of the rock, like `1.8.0-1`, satisfies this one.
+    fn matches(&self, name: &str, version: &str) -> bool {
+        self.name == name
+            && self.version.is_none_or(|pinned| {
+                version == pinned
+                    || version
+                        .strip_prefix(pinned)
+                        .is_some_and(|revision| revision.starts_with('-'))
+            })
+    }
+}
+
+/// Check that an `additional_dependencies` item is a `name` or `name version` rock.
+pub(crate) fn check_dependency(dependency: &str) -> Result<()> {
+    LuaRock::parse(dependency).map(|_| ())
+}
+
 pub(crate) struct LuaInfo {
     pub(crate) version: Version,
     pub(crate) executable: std::path::PathBuf,
@@ -100,7 +141,7 @@ impl LanguageImpl for Lua {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let current_lua_info = query_lua_info()
             .await
             .context("Failed to query current Lua info")?;
@@ -121,6 +162,28 @@ impl LanguageImpl for Lua {
             );
         }
 
+        // `dependencies` also holds the repo URL of remote hooks, which is not a rock.
+        let rocks = info
+            .dependencies
+            .iter()
+            .filter_map(|dep| LuaRock::parse(dep).ok())
+            .collect::<Vec<_>>();
+        if !rocks.is_empty() {
+            let installed = Self::list_rocks(&info.env_path).await?;
+            for rock in rocks {
+                if !installed
+                    .iter()
+                    .any(|(name, version)| rock.matches(name, version))
+                {
+                    anyhow::bail!(
+                        "Lua rock `{}` is not installed in `{}`",
+                        rock.name,
+                        info.env_path.display()
+                    );
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -129,9 +192,9 @@ impl LanguageImpl for Lua {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Lua must have env path");
         let new_path = prepend_paths(&[&env_dir.join("bin")]).context("Failed to join PATH")?;
@@ -145,30 +208,35 @@ impl LanguageImpl for Lua {
         let version = format!("{}.{}", version.major, version.minor);
         let lua_path = Lua::get_lua_path(env_dir, &version);
         let lua_cpath = Lua::get_lua_cpath(env_dir, &version);
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::LUA_PATH, OsStr::new(&lua_path)),
+            (EnvVars::LUA_CPATH, OsStr::new(&lua_cpath)),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "run lua command")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::LUA_PATH, &lua_path)
-                .env(EnvVars::LUA_CPATH, &lua_cpath)
-                .envs(&hook.env)
+                .envs(language_env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -178,7 +246,7 @@ impl LanguageImpl for Lua {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -200,11 +268,13 @@ impl Lua {
     }
 
     async fn install_dependency(env_path: &Path, dependency: &str) -> Result<()> {
+        let rock = LuaRock::parse(dependency)?;
         Cmd::new("luarocks", "luarocks install dependency")
             .arg("--tree")
             .arg(env_path)
             .arg("install")
-            .arg(dependency)
+            .arg(rock.name)
+            .args(rock.version)
             .check(true)
             .output()
             .await
@@ -212,6 +282,29 @@ impl Lua {
         Ok(())
     }
 
+    /// List the `(name, version)` of rocks installed in the tree at `env_path`.
+    async fn list_rocks(env_path: &Path) -> Result<Vec<(String, String)>> {
+        let stdout = Cmd::new("luarocks", "luarocks list")
+            .arg("--tree")
+            .arg(env_path)
+            .arg("list")
+            .arg("--porcelain")
+            .check(true)
+            .output()
+            .await
+            .context("Failed to list installed Lua rocks")?
+            .stdout;
+
+        // Each line is `name\tversion\tstatus\ttree`.
+        Ok(String::from_utf8_lossy(&stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                Some((fields.next()?.to_string(), fields.next()?.to_string()))
+            })
+            .collect())
+    }
+
     fn get_rockspec_file(root_path: &Path) -> Option<PathBuf> {
         if let Ok(entries) = fs_err::read_dir(root_path) {
             for entry in entries.flatten() {
@@ -224,10 +317,14 @@ impl Lua {
         None
     }
 
+    /// `LUA_PATH` searching only the rock tree at `env_dir`.
+    ///
+    /// There's no trailing `;;`, so Lua does not fall back to its default path
+    /// and rocks installed elsewhere on the system are not picked up.
     fn get_lua_path(env_dir: &Path, version: &str) -> String {
         let share_dir = env_dir.join("share");
         format!(
-            "{};{};;",
+            "{};{}",
             share_dir.join("lua").join(version).join("?.lua").display(),
             share_dir
                 .join("lua")
@@ -238,16 +335,54 @@ impl Lua {
         )
     }
 
+    /// `LUA_CPATH` searching only the rock tree at `env_dir`.
     fn get_lua_cpath(env_dir: &Path, version: &str) -> String {
         let lib_dir = env_dir.join("lib");
         let so_ext = if cfg!(windows) { "dll" } else { "so" };
-        format!(
-            "{};;",
-            lib_dir
-                .join("lua")
-                .join(version)
-                .join(format!("?.{so_ext}"))
-                .display()
-        )
+        lib_dir
+            .join("lua")
+            .join(version)
+            .join(format!("?.{so_ext}"))
+            .display()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rock() {
+        assert_eq!(
+            LuaRock::parse("luafilesystem").unwrap(),
+            LuaRock {
+                name: "luafilesystem",
+                version: None,
+            }
+        );
+        assert_eq!(
+            LuaRock::parse("luafilesystem  1.8.0-1").unwrap(),
+            LuaRock {
+                name: "luafilesystem",
+                version: Some("1.8.0-1"),
+            }
+        );
+        assert!(LuaRock::parse("").is_err());
+        assert!(LuaRock::parse("luafilesystem 1.8.0 extra").is_err());
+        assert!(LuaRock::parse("luafilesystem>=1.8.0").is_err());
+        assert!(LuaRock::parse("https://github.com/lunarmodules/luafilesystem").is_err());
+    }
+
+    #[test]
+    fn rock_matches_installed_version() {
+        let rock = LuaRock::parse("luafilesystem 1.8.0").unwrap();
+        assert!(rock.matches("luafilesystem", "1.8.0-1"));
+        assert!(rock.matches("luafilesystem", "1.8.0"));
+        assert!(!rock.matches("luafilesystem", "1.8.01-1"));
+        assert!(!rock.matches("luasocket", "1.8.0-1"));
+
+        let rock = LuaRock::parse("luafilesystem").unwrap();
+        assert!(rock.matches("luafilesystem", "1.9.0-1"));
     }
 }
@@ -9,11 +9,11 @@ use prek_identify::parse_shebang;
 use tracing::{instrument, trace};
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::config::Language;
 use crate::fs::CWD;
 use crate::hook::{Hook, InstallInfo, InstalledHook, Repo};
 use crate::hooks;
+use crate::run::HookRunContext;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 mod bun;
@@ -73,14 +73,14 @@ trait LanguageImpl {
         reporter: &HookInstallReporter,
     ) -> Result<InstalledHook>;
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()>;
+    async fn check_health(&self, info: &InstallInfo, store: &Store) -> Result<()>;
 
     async fn run(
         &self,
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)>;
 }
 
@@ -290,6 +290,19 @@ impl Language {
         }
     }
 
+    /// Check the syntax of an `additional_dependencies` item, before anything is installed.
+    ///
+    /// Languages without a check accept any item and leave it to their package manager.
+    pub(crate) fn check_dependency(self, dependency: &str) -> Result<()> {
+        match self {
+            Self::Bun | Self::Node => node::check_dependency(dependency),
+            Self::Lua => lua::check_dependency(dependency),
+            Self::Python => python::check_dependency(dependency),
+            Self::Rust => rust::check_dependency(dependency),
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) async fn install(
         &self,
         hook: Arc<Hook>,
@@ -323,31 +336,31 @@ impl Language {
         }
     }
 
-    pub(crate) async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    pub(crate) async fn check_health(&self, info: &InstallInfo, store: &Store) -> Result<()> {
         match self {
-            Self::Dart => DART.check_health(info).await,
-            Self::Bun => BUN.check_health(info).await,
-            Self::Coursier => COURSIER.check_health(info).await,
-            Self::Deno => DENO.check_health(info).await,
-            Self::Docker => DOCKER.check_health(info).await,
-            Self::DockerImage => DOCKER_IMAGE.check_health(info).await,
-            Self::Dotnet => DOTNET.check_health(info).await,
-            Self::Fail => FAIL.check_health(info).await,
-            Self::Golang => GOLANG.check_health(info).await,
-            Self::Haskell => HASKELL.check_health(info).await,
-            Self::Julia => JULIA.check_health(info).await,
-            Self::Lua => LUA.check_health(info).await,
-            Self::Node => NODE.check_health(info).await,
-            Self::Perl => PERL.check_health(info).await,
-            Self::Pygrep => PYGREP.check_health(info).await,
-            Self::Python => PYTHON.check_health(info).await,
-            Self::R => R.check_health(info).await,
-            Self::Ruby => RUBY.check_health(info).await,
-            Self::Rust => RUST.check_health(info).await,
-            Self::Script => SCRIPT.check_health(info).await,
-            Self::Swift => SWIFT.check_health(info).await,
-            Self::System => SYSTEM.check_health(info).await,
-            Self::Conda => CONDA.check_health(info).await,
+            Self::Dart => DART.check_health(info, store).await,
+            Self::Bun => BUN.check_health(info, store).await,
+            Self::Coursier => COURSIER.check_health(info, store).await,
+            Self::Deno => DENO.check_health(info, store).await,
+            Self::Docker => DOCKER.check_health(info, store).await,
+            Self::DockerImage => DOCKER_IMAGE.check_health(info, store).await,
+            Self::Dotnet => DOTNET.check_health(info, store).await,
+            Self::Fail => FAIL.check_health(info, store).await,
+            Self::Golang => GOLANG.check_health(info, store).await,
+            Self::Haskell => HASKELL.check_health(info, store).await,
+            Self::Julia => JULIA.check_health(info, store).await,
+            Self::Lua => LUA.check_health(info, store).await,
+            Self::Node => NODE.check_health(info, store).await,
+            Self::Perl => PERL.check_health(info, store).await,
+            Self::Pygrep => PYGREP.check_health(info, store).await,
+            Self::Python => PYTHON.check_health(info, store).await,
+            Self::R => R.check_health(info, store).await,
+            Self::Ruby => RUBY.check_health(info, store).await,
+            Self::Rust => RUST.check_health(info, store).await,
+            Self::Script => SCRIPT.check_health(info, store).await,
+            Self::Swift => SWIFT.check_health(info, store).await,
+            Self::System => SYSTEM.check_health(info, store).await,
+            Self::Conda => CONDA.check_health(info, store).await,
         }
     }
 
@@ -357,54 +370,54 @@ impl Language {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
         match hook.repo() {
             Repo::Meta { .. } => {
                 return hooks::MetaHooks::from_str(&hook.id)
                     .unwrap()
-                    .run(store, hook, filenames, reporter)
+                    .run(store, hook, filenames, context)
                     .await;
             }
             Repo::Builtin { .. } => {
                 return hooks::BuiltinHooks::from_str(&hook.id)
                     .unwrap()
-                    .run(store, hook, filenames, reporter)
+                    .run(store, hook, filenames, context)
                     .await;
             }
             Repo::Remote { .. } => {
                 // Fast path for hooks implemented in Rust
                 if hooks::check_fast_path(hook) {
-                    return hooks::run_fast_path(store, hook, filenames, reporter).await;
+                    return hooks::run_fast_path(store, hook, filenames, context).await;
                 }
             }
             Repo::Local { .. } => {}
         }
 
         match self {
-            Self::Dart => DART.run(hook, filenames, store, reporter).await,
-            Self::Bun => BUN.run(hook, filenames, store, reporter).await,
-            Self::Coursier => COURSIER.run(hook, filenames, store, reporter).await,
-            Self::Deno => DENO.run(hook, filenames, store, reporter).await,
-            Self::Docker => DOCKER.run(hook, filenames, store, reporter).await,
-            Self::DockerImage => DOCKER_IMAGE.run(hook, filenames, store, reporter).await,
-            Self::Dotnet => DOTNET.run(hook, filenames, store, reporter).await,
-            Self::Fail => FAIL.run(hook, filenames, store, reporter).await,
-            Self::Golang => GOLANG.run(hook, filenames, store, reporter).await,
-            Self::Haskell => HASKELL.run(hook, filenames, store, reporter).await,
-            Self::Julia => JULIA.run(hook, filenames, store, reporter).await,
-            Self::Lua => LUA.run(hook, filenames, store, reporter).await,
-            Self::Node => NODE.run(hook, filenames, store, reporter).await,
-            Self::Perl => PERL.run(hook, filenames, store, reporter).await,
-            Self::Pygrep => PYGREP.run(hook, filenames, store, reporter).await,
-            Self::Python => PYTHON.run(hook, filenames, store, reporter).await,
-            Self::R => R.run(hook, filenames, store, reporter).await,
-            Self::Ruby => RUBY.run(hook, filenames, store, reporter).await,
-            Self::Rust => RUST.run(hook, filenames, store, reporter).await,
-            Self::Script => SCRIPT.run(hook, filenames, store, reporter).await,
-            Self::Swift => SWIFT.run(hook, filenames, store, reporter).await,
-            Self::System => SYSTEM.run(hook, filenames, store, reporter).await,
-            Self::Conda => CONDA.run(hook, filenames, store, reporter).await,
+            Self::Dart => DART.run(hook, filenames, store, context).await,
+            Self::Bun => BUN.run(hook, filenames, store, context).await,
+            Self::Coursier => COURSIER.run(hook, filenames, store, context).await,
+            Self::Deno => DENO.run(hook, filenames, store, context).await,
+            Self::Docker => DOCKER.run(hook, filenames, store, context).await,
+            Self::DockerImage => DOCKER_IMAGE.run(hook, filenames, store, context).await,
+            Self::Dotnet => DOTNET.run(hook, filenames, store, context).await,
+            Self::Fail => FAIL.run(hook, filenames, store, context).await,
+            Self::Golang => GOLANG.run(hook, filenames, store, context).await,
+            Self::Haskell => HASKELL.run(hook, filenames, store, context).await,
+            Self::Julia => JULIA.run(hook, filenames, store, context).await,
+            Self::Lua => LUA.run(hook, filenames, store, context).await,
+            Self::Node => NODE.run(hook, filenames, store, context).await,
+            Self::Perl => PERL.run(hook, filenames, store, context).await,
+            Self::Pygrep => PYGREP.run(hook, filenames, store, context).await,
+            Self::Python => PYTHON.run(hook, filenames, store, context).await,
+            Self::R => R.run(hook, filenames, store, context).await,
+            Self::Ruby => RUBY.run(hook, filenames, store, context).await,
+            Self::Rust => RUST.run(hook, filenames, store, context).await,
+            Self::Script => SCRIPT.run(hook, filenames, store, context).await,
+            Self::Swift => SWIFT.run(hook, filenames, store, context).await,
+            Self::System => SYSTEM.run(hook, filenames, store, context).await,
+            Self::Conda => CONDA.run(hook, filenames, store, context).await,
         }
     }
 }
@@ -488,6 +501,37 @@ pub(crate) fn resolve_command(mut cmds: Vec<String>, paths: Option<&OsStr>) -> V
     }
 }
 
+/// An `entry` or `args` word that is replaced by the filenames passed to the hook.
+pub(crate) const FILES_PLACEHOLDER: &str = "{files}";
+
+/// Build the arguments following the command: the rest of `entry`, then `args`, then `files`.
+///
+/// If any of those words is exactly [`FILES_PLACEHOLDER`], `files` are inserted in its place
+/// instead of being appended.
+pub(crate) fn command_args<'a>(
+    entry_args: &'a [String],
+    hook_args: &'a [String],
+    files: &[&'a Path],
+) -> Vec<&'a OsStr> {
+    let words = entry_args.iter().chain(hook_args);
+    if !words.clone().any(|word| word == FILES_PLACEHOLDER) {
+        return words
+            .map(OsStr::new)
+            .chain(files.iter().map(|file| file.as_os_str()))
+            .collect();
+    }
+
+    let mut args = Vec::new();
+    for word in words {
+        if word == FILES_PLACEHOLDER {
+            args.extend(files.iter().map(|file| file.as_os_str()));
+        } else {
+            args.push(OsStr::new(word));
+        }
+    }
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use std::ffi::OsString;
@@ -495,7 +539,7 @@ mod tests {
 
     use tempfile::tempdir;
 
-    use super::resolve_command;
+    use super::{command_args, resolve_command};
 
     fn write_file(path: &Path, contents: &str) {
         fs_err::write(path, contents).expect("write test file");
@@ -595,4 +639,66 @@ mod tests {
         assert_eq!(resolved_interp, interp_path.as_path());
         assert_eq!(resolved[1], script_path.to_string_lossy());
     }
+
+    #[cfg(windows)]
+    #[test]
+    fn resolve_command_windows_long_path() {
+        let dir = tempdir().expect("create temp dir");
+
+        // Nest directories until the path exceeds `MAX_PATH` (260 characters).
+        let mut long_dir = dir.path().to_path_buf();
+        while long_dir.as_os_str().len() <= 300 {
+            long_dir.push("a".repeat(50));
+        }
+        fs_err::create_dir_all(prek_identify::extended_length_path(&long_dir))
+            .expect("create long dir");
+        let script_path = long_dir.join("hook-script");
+        write_file(
+            &prek_identify::extended_length_path(&script_path),
+            "#!/usr/bin/env prek-test-interpreter\necho hi\n",
+        );
+
+        let interpreter_path = dir.path().join("prek-test-interpreter.exe");
+        write_file(&interpreter_path, "");
+
+        let paths = OsString::from(dir.path().as_os_str());
+        let resolved = resolve_command(
+            vec![script_path.to_string_lossy().into_owned()],
+            Some(paths.as_os_str()),
+        );
+        assert_eq!(resolved[0], interpreter_path.to_string_lossy());
+        // The extended-length form is only used to read the shebang.
+        assert_eq!(resolved[1], script_path.to_string_lossy());
+
+        // An entry that is already in the extended-length form is kept as is.
+        let verbatim = prek_identify::extended_length_path(&script_path).into_owned();
+        let resolved = resolve_command(
+            vec![verbatim.to_string_lossy().into_owned()],
+            Some(paths.as_os_str()),
+        );
+        assert_eq!(resolved[0], interpreter_path.to_string_lossy());
+        assert_eq!(resolved[1], verbatim.to_string_lossy());
+    }
+
+    #[test]
+    fn command_args_substitutes_files_placeholder() {
+        let entry = vec!["--check".to_string(), "{files}".to_string()];
+        let args = vec!["--verbose".to_string()];
+        let files = [Path::new("a.py"), Path::new("b.py")];
+
+        assert_eq!(
+            command_args(&entry, &args, &files),
+            ["--check", "a.py", "b.py", "--verbose"]
+        );
+        assert_eq!(
+            command_args(&entry[..1], &args, &files),
+            ["--check", "--verbose", "a.py", "b.py"]
+        );
+        // Only whole words are placeholders.
+        let entry = vec!["--files={files}".to_string()];
+        assert_eq!(
+            command_args(&entry, &[], &files),
+            ["--files={files}", "a.py", "b.py"]
+        );
+    }
 }
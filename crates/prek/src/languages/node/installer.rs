@@ -91,6 +91,75 @@ impl NodeResult {
     pub(crate) fn version(&self) -> &NodeVersion {
         &self.version
     }
+
+    /// The `corepack` shim shipped alongside the node binary, if present.
+    pub(crate) fn corepack(&self) -> Option<PathBuf> {
+        let dir = self.node.parent()?;
+        let corepack = dir
+            .join("corepack")
+            .with_extension(if cfg!(windows) { "cmd" } else { "" });
+        corepack.is_file().then_some(corepack)
+    }
+}
+
+/// Package manager used to install a hook repository's dependencies.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl Display for PackageManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+        })
+    }
+}
+
+impl PackageManager {
+    /// Detect the package manager a hook repository asks for.
+    ///
+    /// The `packageManager` field of `package.json` wins, then `pnpm-lock.yaml` or `yarn.lock`.
+    /// Yarn 2+ has no global installs, so it falls back to npm.
+    pub(crate) fn detect(repo: &Path) -> Self {
+        if let Some(pm) = fs_err::read_to_string(repo.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| {
+                json.get("packageManager")
+                    .and_then(serde_json::Value::as_str)
+                    .map(Self::from_package_manager_field)
+            })
+        {
+            return pm;
+        }
+
+        if repo.join("pnpm-lock.yaml").is_file() {
+            return Self::Pnpm;
+        }
+        if let Ok(lockfile) = fs_err::read_to_string(repo.join("yarn.lock")) {
+            // Yarn berry lockfiles carry a `__metadata` entry, classic ones do not.
+            if !lockfile.contains("__metadata:") {
+                return Self::Yarn;
+            }
+        }
+
+        Self::Npm
+    }
+
+    /// Parse a `packageManager` value like `pnpm@8.15.0` or `yarn@1.22.19+sha256.abc`.
+    fn from_package_manager_field(value: &str) -> Self {
+        let (name, version) = value.split_once('@').unwrap_or((value, ""));
+        match name.trim() {
+            "pnpm" => Self::Pnpm,
+            "yarn" if version.starts_with("1.") || version == "1" => Self::Yarn,
+            _ => Self::Npm,
+        }
+    }
 }
 
 pub(crate) struct NodeInstaller {
@@ -128,7 +197,7 @@ impl NodeInstaller {
             anyhow::bail!("No suitable system Node version found and downloads are disabled");
         }
 
-        let resolved_version = self.resolve_version(request).await?;
+        let resolved_version = self.resolve_version(store, request).await?;
         trace!(version = %resolved_version, "Downloading node");
 
         self.download(store, &resolved_version).await
@@ -167,10 +236,10 @@ impl NodeInstaller {
             .context("No installed node version matches the request")
     }
 
-    async fn resolve_version(&self, req: &NodeRequest) -> Result<NodeVersion> {
+    async fn resolve_version(&self, store: &Store, req: &NodeRequest) -> Result<NodeVersion> {
         // Latest versions come first, so we can find the latest matching version.
         let versions = self
-            .list_remote_versions()
+            .list_remote_versions(store)
             .await
             .context("Failed to list remote versions")?;
         let version = versions
@@ -181,8 +250,9 @@ impl NodeInstaller {
     }
 
     /// List all versions of Node.js available on the Node.js website.
-    async fn list_remote_versions(&self) -> Result<Vec<NodeVersion>> {
+    async fn list_remote_versions(&self, store: &Store) -> Result<Vec<NodeVersion>> {
         let url = "https://nodejs.org/dist/index.json";
+        store.ensure_online(url)?;
         let versions: Vec<NodeVersion> = REQWEST_CLIENT.get(url).send().await?.json().await?;
         Ok(versions)
     }
@@ -213,11 +283,12 @@ impl NodeInstaller {
         }
         let ext = if cfg!(windows) { "zip" } else { "tar.xz" };
 
-        let filename = format!("node-v{}-{os}-{arch}.{ext}", version.version());
+        let dirname = format!("node-v{}-{os}-{arch}", version.version());
+        let filename = format!("{dirname}.{ext}");
         let url = format!("https://nodejs.org/dist/v{}/{filename}", version.version());
         let target = self.root.join(version.to_string());
 
-        download_and_extract(&url, &filename, store, async |extracted| {
+        download_and_extract(&url, &filename, Some(&dirname), store, async |extracted| {
             if target.exists() {
                 debug!(target = %target.display(), "Removing existing node");
                 fs_err::tokio::remove_dir_all(&target).await?;
@@ -338,3 +409,50 @@ fn is_executable(path: &Path) -> bool {
         path.is_file() && fs_err::metadata(path).is_ok_and(|m| m.mode() & 0o111 != 0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_package_manager_field() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::write(
+            dir.path().join("package.json"),
+            r#"{"name": "hook", "packageManager": "pnpm@8"}"#,
+        )?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Pnpm);
+
+        fs_err::write(
+            dir.path().join("package.json"),
+            r#"{"name": "hook", "packageManager": "yarn@1.22.19+sha256.abc"}"#,
+        )?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Yarn);
+
+        fs_err::write(
+            dir.path().join("package.json"),
+            r#"{"name": "hook", "packageManager": "yarn@4.1.0"}"#,
+        )?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Npm);
+
+        Ok(())
+    }
+
+    #[test]
+    fn detect_package_manager_lockfiles() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::write(dir.path().join("package.json"), r#"{"name": "hook"}"#)?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Npm);
+
+        fs_err::write(dir.path().join("yarn.lock"), "# yarn lockfile v1\n")?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Yarn);
+
+        fs_err::write(
+            dir.path().join("pnpm-lock.yaml"),
+            "lockfileVersion: '6.0'\n",
+        )?;
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Pnpm);
+
+        Ok(())
+    }
+}
@@ -3,5 +3,5 @@ mod installer;
 mod node;
 mod version;
 
-pub(crate) use node::Node;
+pub(crate) use node::{Node, check_dependency};
 pub(crate) use version::NodeRequest;
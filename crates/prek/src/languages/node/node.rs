@@ -5,24 +5,71 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
-use tracing::debug;
+use rustc_hash::FxHashSet;
+use tracing::{debug, warn};
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
 use crate::languages::LanguageImpl;
 use crate::languages::node::NodeRequest;
-use crate::languages::node::installer::{NodeInstaller, NodeResult, bin_dir, lib_dir};
+use crate::languages::node::installer::{
+    NodeInstaller, NodeResult, PackageManager, bin_dir, lib_dir,
+};
 use crate::languages::node::version::EXTRA_KEY_LTS;
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Node;
 
+/// Check that an `additional_dependencies` item is an npm package spec: `name` or
+/// `name@version`, with an optional `@scope/` prefix.
+///
+/// Git, URL, alias and path specs such as `github:user/repo` or `./pkg` are left to npm.
+pub(crate) fn check_dependency(dependency: &str) -> Result<()> {
+    let (scope, spec) = match dependency.strip_prefix('@') {
+        Some(scoped) => {
+            let (scope, spec) = scoped
+                .split_once('/')
+                .with_context(|| format!("Scoped package `{dependency}` must be `@scope/name`"))?;
+            (Some(scope), spec)
+        }
+        None if dependency.contains([':', '/', '\\']) || dependency.starts_with(['.', '~']) => {
+            return Ok(());
+        }
+        None => (None, dependency),
+    };
+
+    let (name, version) = match spec.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (spec, None),
+    };
+    if let Some(scope) = scope
+        && !is_valid_package_name(scope)
+    {
+        anyhow::bail!("`@{scope}` is not a valid package scope");
+    }
+    if !is_valid_package_name(name) {
+        anyhow::bail!("`{name}` is not a valid package name, use `name@version` to pin a version");
+    }
+    if version.is_some_and(|version| version.trim().is_empty()) {
+        anyhow::bail!("Missing version after `@`");
+    }
+
+    Ok(())
+}
+
+fn is_valid_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(['.', '_'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~'))
+}
+
 const NPM_CONFIG_PREFIX_ENV: &str = "npm_config_prefix";
 const NPM_CONFIG_CACHE_ENV: &str = "npm_config_cache";
 // npm exports `global_prefix` and `local_prefix` as lowercase child-process
@@ -107,16 +154,45 @@ impl LanguageImpl for Node {
             let new_path = prepend_paths(&[&bin_dir, node_bin]).context("Failed to join PATH")?;
             let npm_cache = store.cache_path(CacheBucket::Npm);
 
-            let mut cmd = Cmd::new(node.npm(), "npm install");
-            cmd.arg("install")
-                .arg("-g")
-                .arg("--no-progress")
-                .arg("--no-save")
-                .arg("--no-fund")
-                .arg("--no-audit")
-                .arg("--install-links")
-                .args(&*deps)
-                .env(EnvVars::PATH, new_path)
+            let package_manager = hook
+                .repo_path()
+                .map_or(PackageManager::Npm, PackageManager::detect);
+            let corepack = if package_manager == PackageManager::Npm {
+                None
+            } else {
+                let corepack = node.corepack();
+                if corepack.is_none() {
+                    warn!(
+                        "Hook repo requests `{package_manager}` but `corepack` was not found next to `{}`, falling back to npm",
+                        node.node().display()
+                    );
+                }
+                corepack
+            };
+
+            let mut cmd = if let Some(corepack) = corepack {
+                debug!("Installing dependencies with `{package_manager}` via corepack");
+                corepack_install_cmd(
+                    &corepack,
+                    package_manager,
+                    &info.env_path,
+                    &npm_cache,
+                    &deps,
+                    hook.repo_path(),
+                )
+            } else {
+                let mut cmd = Cmd::new(node.npm(), "npm install");
+                cmd.arg("install")
+                    .arg("-g")
+                    .arg("--no-progress")
+                    .arg("--no-save")
+                    .arg("--no-fund")
+                    .arg("--no-audit")
+                    .arg("--install-links")
+                    .args(&*deps);
+                cmd
+            };
+            cmd.env(EnvVars::PATH, new_path)
                 .env(EnvVars::NODE_PATH, &lib_dir);
             apply_npm_config_env(&mut cmd, &info.env_path, &npm_cache);
             cmd.check(true).output().await?;
@@ -132,7 +208,7 @@ impl LanguageImpl for Node {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let node = NodeResult::from_executables(info.toolchain.clone(), PathBuf::new())
             .fill_version()
             .await
@@ -154,9 +230,9 @@ impl LanguageImpl for Node {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Node must have env path");
         let node_bin = hook.toolchain_dir().expect("Node binary must have parent");
@@ -165,31 +241,37 @@ impl LanguageImpl for Node {
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
         let npm_cache = store.cache_path(CacheBucket::Npm);
+        let node_path = lib_dir(env_dir);
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::NODE_PATH, node_path.as_os_str()),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut cmd = Cmd::new(&entry[0], "node hook");
             cmd.current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::NODE_PATH, lib_dir(env_dir))
-                .envs(&hook.env);
+                .envs(language_env)
+                .envs(hook.env_vars(&language_env));
             apply_npm_config_env(&mut cmd, env_dir, &npm_cache);
             let mut output = cmd
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -200,12 +282,70 @@ impl LanguageImpl for Node {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
 }
 
+/// Build a global install command for pnpm or yarn classic, run through corepack.
+///
+/// Binaries land in the env's `bin` directory so the hook `PATH` is the same as for npm.
+fn corepack_install_cmd(
+    corepack: &Path,
+    package_manager: PackageManager,
+    env_path: &Path,
+    cache: &Path,
+    deps: &FxHashSet<String>,
+    repo_path: Option<&Path>,
+) -> Cmd {
+    let bin_dir = bin_dir(env_path);
+    let mut cmd = Cmd::new(corepack, format!("{package_manager} install"));
+    cmd.arg(package_manager.to_string())
+        .env(EnvVars::COREPACK_HOME, cache.join("corepack"))
+        .env(EnvVars::COREPACK_ENABLE_DOWNLOAD_PROMPT, "0");
+
+    match package_manager {
+        PackageManager::Pnpm => {
+            cmd.arg("add")
+                .arg("--global")
+                .arg(format!(
+                    "--global-dir={}",
+                    env_path.join("pnpm-global").display()
+                ))
+                .arg(format!("--global-bin-dir={}", bin_dir.display()))
+                .arg(format!(
+                    "--store-dir={}",
+                    cache.join("pnpm-store").display()
+                ))
+                .args(deps)
+                .env(EnvVars::PNPM_HOME, &bin_dir);
+        }
+        PackageManager::Yarn => {
+            cmd.arg("global")
+                .arg("add")
+                .arg("--non-interactive")
+                .arg("--prefix")
+                .arg(env_path)
+                .arg("--global-folder")
+                .arg(env_path.join("yarn-global"))
+                .arg("--cache-folder")
+                .arg(cache.join("yarn"));
+            // Yarn classic treats bare paths as registry names; local folders need `file:`.
+            for dep in deps {
+                if repo_path.is_some_and(|repo| Path::new(dep) == repo) {
+                    cmd.arg(format!("file:{dep}"));
+                } else {
+                    cmd.arg(dep);
+                }
+            }
+        }
+        PackageManager::Npm => unreachable!("npm is installed without corepack"),
+    }
+
+    cmd
+}
+
 fn apply_npm_config_env(cmd: &mut Cmd, prefix: &Path, cache: &Path) {
     for key in NPM_CONFIG_ENVS_TO_REMOVE {
         cmd.env_remove(key);
@@ -213,3 +353,46 @@ fn apply_npm_config_env(cmd: &mut Cmd, prefix: &Path, cache: &Path) {
     cmd.env(NPM_CONFIG_PREFIX_ENV, prefix);
     cmd.env(NPM_CONFIG_CACHE_ENV, cache);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::check_dependency;
+
+    #[test]
+    fn check_dependency_accepts_package_specs() {
+        for dependency in [
+            "prettier",
+            "prettier@3.3.3",
+            "eslint@>=8 <9",
+            "@types/node",
+            "@prettier/plugin-xml@3",
+            "github:user/repo",
+            "user/repo#main",
+            "git+https://github.com/user/repo.git",
+            "./local-package",
+            "my-alias@npm:prettier@3",
+        ] {
+            assert!(check_dependency(dependency).is_ok(), "{dependency}");
+        }
+    }
+
+    #[test]
+    fn check_dependency_rejects_malformed_specs() {
+        for (dependency, error) in [
+            (
+                "prettier==3.3.3",
+                "`prettier==3.3.3` is not a valid package name, use `name@version` to pin a version",
+            ),
+            ("prettier@", "Missing version after `@`"),
+            ("@types", "Scoped package `@types` must be `@scope/name`"),
+            ("@/node", "`@` is not a valid package scope"),
+            (
+                "_private",
+                "`_private` is not a valid package name, use `name@version` to pin a version",
+            ),
+        ] {
+            let err = check_dependency(dependency).unwrap_err();
+            assert_eq!(err.to_string(), error, "{dependency}");
+        }
+    }
+}
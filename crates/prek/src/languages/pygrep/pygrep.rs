@@ -8,12 +8,12 @@ use tokio::io::AsyncWriteExt;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
+use crate::config::Language;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::python::{Uv, python_exec, query_python_info_cached};
 use crate::process::Cmd;
-use crate::run::CONCURRENCY;
+use crate::run::HookRunContext;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Default)]
@@ -172,8 +172,8 @@ impl LanguageImpl for Pygrep {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
-        query_python_info_cached(&info.toolchain)
+    async fn check_health(&self, info: &InstallInfo, store: &Store) -> Result<()> {
+        query_python_info_cached(&info.toolchain, store)
             .await
             .context("Failed to query Python info")?;
 
@@ -185,9 +185,9 @@ impl LanguageImpl for Pygrep {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let info = hook.install_info().expect("Pygrep hook must be installed");
 
@@ -202,12 +202,17 @@ impl LanguageImpl for Pygrep {
         let args = Args::parse(&hook.args).context("Failed to parse `args`")?;
         let mut cmd = Cmd::new(&info.toolchain, "python script")
             .current_dir(hook.work_dir())
-            .envs(&hook.env)
+            .envs(hook.env_vars(&[]))
             .arg("-I") // Isolate mode.
             .arg("-B") // Don't write bytecode.
             .arg(py_script.path())
             .args(args.to_args())
-            .arg(CONCURRENCY.to_string())
+            .arg(
+                context
+                    .language_limits
+                    .concurrency(Language::Pygrep)
+                    .to_string(),
+            )
             .arg(hook.entry.expect_direct().raw())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -250,10 +255,10 @@ impl LanguageImpl for Pygrep {
                 .and_then(serde_json::Value::as_i64)
                 .unwrap_or(0);
             let code = i32::try_from(code).unwrap_or(0);
-            reporter.on_run_complete(progress);
+            context.reporter.on_run_complete(progress);
             Ok((code, output.stdout))
         } else {
-            reporter.on_run_complete(progress);
+            context.reporter.on_run_complete(progress);
             // When there's an error, try to parse error JSON from stderr
             let stderr_str = String::from_utf8_lossy(&output.stderr);
 
@@ -8,15 +8,18 @@ mod pyproject;
 mod python;
 mod uv;
 mod version;
+mod version_file;
 
 /// Extract Python hook metadata with explicit precedence:
-/// PEP 723 > user-configured `language_version` > pyproject.toml > default.
+/// PEP 723 > user-configured `language_version` > pyproject.toml >
+/// `.python-version` / `.tool-versions` > default.
 pub(crate) async fn extract_metadata(hook: &mut Hook) -> Result<()> {
     pyproject::extract_pyproject_metadata(hook).await?;
+    version_file::extract_version_file_metadata(hook).await?;
     pep723::extract_pep723_metadata(hook).await
 }
 
-pub(crate) use python::Python;
+pub(crate) use python::{Python, check_dependency};
 pub(crate) use python::{python_exec, query_python_info_cached};
 pub(crate) use uv::Uv;
 pub(crate) use version::PythonRequest;
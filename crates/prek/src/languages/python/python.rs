@@ -1,18 +1,18 @@
 use std::env::consts::EXE_EXTENSION;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use mea::once::OnceMap;
 use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
-use rustc_hash::FxBuildHasher;
-use serde::Deserialize;
+use rustc_hash::{FxBuildHasher, FxHashMap};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, trace};
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
 use crate::languages::LanguageImpl;
@@ -21,12 +21,91 @@ use crate::languages::python::uv::Uv;
 use crate::languages::version::LanguageRequest;
 use crate::process;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
-use crate::store::{Store, ToolBucket};
+use crate::run::{HookRunContext, run_by_batch};
+use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Python;
 
+/// Version specifier operators of PEP 440, longest first so prefixes match last.
+const VERSION_OPERATORS: [&str; 8] = ["===", "==", "!=", "<=", ">=", "~=", "<", ">"];
+
+/// Check that an `additional_dependencies` item is a PEP 508 requirement, like
+/// `name[extra]>=1.0,<2; python_version < "3.12"`.
+///
+/// Items that look like pip options, paths or URLs are passed to `uv pip install` as is.
+pub(crate) fn check_dependency(dependency: &str) -> Result<()> {
+    let dependency = dependency.trim();
+    if dependency.starts_with(['-', '.', '~']) || dependency.contains(['/', '\\']) {
+        return Ok(());
+    }
+
+    let (requirement, _marker) = dependency.split_once(';').unwrap_or((dependency, ""));
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+        .unwrap_or(requirement.len());
+    let (name, mut rest) = requirement.split_at(name_end);
+    if !is_valid_requirement_name(name) {
+        anyhow::bail!("`{name}` is not a valid package name");
+    }
+
+    rest = rest.trim_start();
+    if let Some(extras) = rest.strip_prefix('[') {
+        let (extras, tail) = extras
+            .split_once(']')
+            .context("Missing `]` after the extras")?;
+        if let Some(extra) = extras
+            .split(',')
+            .map(str::trim)
+            .find(|extra| !is_valid_requirement_name(extra))
+        {
+            anyhow::bail!("`{extra}` is not a valid extra name");
+        }
+        rest = tail.trim_start();
+    }
+
+    // A direct reference such as `name @ file:///path` has no version specifiers.
+    if rest.starts_with('@') {
+        return Ok(());
+    }
+    let specifiers = rest
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or(rest)
+        .trim();
+    if specifiers.is_empty() {
+        return Ok(());
+    }
+    for specifier in specifiers.split(',').map(str::trim) {
+        let Some(version) = VERSION_OPERATORS
+            .iter()
+            .find_map(|operator| specifier.strip_prefix(operator))
+        else {
+            anyhow::bail!(
+                "`{specifier}` is not a version specifier, expected an operator like `==` or `>=`"
+            );
+        };
+        let version = version.trim();
+        if version.is_empty()
+            || !version.chars().all(|c| {
+                c.is_ascii_alphanumeric() || matches!(c, '.' | '*' | '+' | '!' | '-' | '_')
+            })
+        {
+            anyhow::bail!("`{specifier}` does not specify a valid version");
+        }
+    }
+
+    Ok(())
+}
+
+fn is_valid_requirement_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
 pub(crate) struct PythonInfo {
     pub(crate) version: semver::Version,
     pub(crate) python_exec: PathBuf,
@@ -45,13 +124,27 @@ pub(crate) enum PythonInfoError {
 static PYTHON_INFO_CACHE: LazyLock<OnceMap<PathBuf, Arc<PythonInfo>, FxBuildHasher>> =
     LazyLock::new(|| OnceMap::with_hasher(FxBuildHasher));
 
-async fn query_python_info(python: &Path) -> Result<PythonInfo, PythonInfoError> {
-    #[derive(Deserialize)]
-    struct QueryPythonInfo {
-        version: semver::Version,
-        base_exec_prefix: PathBuf,
+/// Interpreter metadata as reported by the interpreter itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct QueryPythonInfo {
+    version: semver::Version,
+    base_exec_prefix: PathBuf,
+}
+
+impl From<QueryPythonInfo> for PythonInfo {
+    fn from(info: QueryPythonInfo) -> Self {
+        Self {
+            version: info.version,
+            python_exec: python_exec(&info.base_exec_prefix),
+        }
     }
+}
+
+async fn query_python_info(python: &Path) -> Result<PythonInfo, PythonInfoError> {
+    Ok(query_python_info_raw(python).await?.into())
+}
 
+async fn query_python_info_raw(python: &Path) -> Result<QueryPythonInfo, PythonInfoError> {
     static QUERY_PYTHON_INFO: &str = indoc::indoc! {r#"
     import sys, json
     info = {
@@ -71,24 +164,115 @@ async fn query_python_info(python: &Path) -> Result<PythonInfo, PythonInfoError>
         .map_err(|err| PythonInfoError::Query(err.to_string()))?
         .stdout;
 
-    let info: QueryPythonInfo =
-        serde_json::from_slice(&stdout).map_err(|err| PythonInfoError::Parse(err.to_string()))?;
-    let python_exec = python_exec(&info.base_exec_prefix);
+    serde_json::from_slice(&stdout).map_err(|err| PythonInfoError::Parse(err.to_string()))
+}
+
+/// A [`QueryPythonInfo`] persisted across runs, valid while the interpreter's mtime is unchanged.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPythonInfo {
+    mtime: SystemTime,
+    #[serde(flatten)]
+    info: QueryPythonInfo,
+}
 
-    Ok(PythonInfo {
-        version: info.version,
-        python_exec,
-    })
+/// On-disk cache of interpreter metadata, keyed by the canonical interpreter path.
+///
+/// Probing an interpreter spawns Python, which adds up when many hooks share a few
+/// interpreters. The file is read once per process, see [`PythonInfoDiskCache::shared`], and
+/// the in-process [`PYTHON_INFO_CACHE`] still dedupes concurrent probes.
+struct PythonInfoDiskCache {
+    path: PathBuf,
+    entries: Mutex<FxHashMap<PathBuf, CachedPythonInfo>>,
+}
+
+impl PythonInfoDiskCache {
+    /// Load the cache of `store` from disk.
+    fn new(store: &Store) -> Self {
+        let path = store
+            .cache_path(CacheBucket::Python)
+            .join("interpreters.json");
+        let entries = match fs_err::read(&path) {
+            Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|err| {
+                debug!(%err, path = %path.display(), "Ignoring invalid Python info cache");
+                FxHashMap::default()
+            }),
+            Err(_) => FxHashMap::default(),
+        };
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// The cache of `store`, loaded on first use and shared by the rest of the process.
+    fn shared(store: &Store) -> Arc<Self> {
+        static CACHES: LazyLock<Mutex<FxHashMap<PathBuf, Arc<PythonInfoDiskCache>>>> =
+            LazyLock::new(Mutex::default);
+
+        let mut caches = CACHES
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let cache_path = store.cache_path(CacheBucket::Python);
+        Arc::clone(
+            caches
+                .entry(cache_path)
+                .or_insert_with(|| Arc::new(Self::new(store))),
+        )
+    }
+
+    fn get(&self, python: &Path, mtime: SystemTime) -> Option<QueryPythonInfo> {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = entries.get(python)?;
+        (entry.mtime == mtime).then(|| entry.info.clone())
+    }
+
+    fn insert(&self, python: PathBuf, mtime: SystemTime, info: QueryPythonInfo) -> Result<()> {
+        // Hold the lock while writing, so concurrent probes don't drop each other's entries.
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entries.insert(python, CachedPythonInfo { mtime, info });
+
+        // Write atomically, other prek processes may read the cache at the same time.
+        let parent = self.path.parent().expect("cache file must have a parent");
+        fs_err::create_dir_all(parent)?;
+        let mut file = tempfile::NamedTempFile::new_in(parent)?;
+        serde_json::to_writer(&mut file, &*entries)?;
+        file.persist(&self.path)?;
+        Ok(())
+    }
 }
 
 pub(crate) async fn query_python_info_cached(
     python: &Path,
+    store: &Store,
 ) -> Result<Arc<PythonInfo>, PythonInfoError> {
     let python = fs_err::canonicalize(python).unwrap_or_else(|_| python.to_path_buf());
     PYTHON_INFO_CACHE
         .try_compute(python.clone(), async move || {
-            let info = query_python_info(&python).await?;
-            Ok(Arc::new(info))
+            let disk_cache = PythonInfoDiskCache::shared(store);
+            let mtime = fs_err::metadata(&python)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+
+            if let Some(mtime) = mtime
+                && let Some(info) = disk_cache.get(&python, mtime)
+            {
+                trace!(python = %python.display(), "Using cached Python info");
+                return Ok(Arc::new(info.into()));
+            }
+
+            let info = query_python_info_raw(&python).await?;
+            if let Some(mtime) = mtime
+                && let Err(err) = disk_cache.insert(python.clone(), mtime, info.clone())
+            {
+                debug!(%err, "Failed to save Python info cache");
+            }
+            Ok(Arc::new(info.into()))
         })
         .await
 }
@@ -166,9 +350,9 @@ impl LanguageImpl for Python {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, store: &Store) -> Result<()> {
         let python = python_exec(&info.env_path);
-        let python_info = query_python_info_cached(&python)
+        let python_info = query_python_info_cached(&python, store)
             .await
             .context("Failed to query Python info")?;
 
@@ -188,37 +372,42 @@ impl LanguageImpl for Python {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Python must have env path");
         let new_path = prepend_paths(&[&bin_dir(env_dir)]).context("Failed to join PATH")?;
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::VIRTUAL_ENV, env_dir.as_os_str()),
+            (EnvVars::PATH, new_path.as_os_str()),
+        ];
 
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "python hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::VIRTUAL_ENV, env_dir)
-                .env(EnvVars::PATH, &new_path)
+                .envs(language_env)
                 .env_remove(EnvVars::PYTHONHOME)
-                .envs(&hook.env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -229,7 +418,7 @@ impl LanguageImpl for Python {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -399,7 +588,7 @@ mod tests {
     use prek_consts::env_vars::EnvVars;
     use rustc_hash::FxHashSet;
 
-    use super::Python;
+    use super::{Python, PythonInfoDiskCache, QueryPythonInfo, check_dependency};
     use crate::config::Language;
     use crate::hook::InstallInfo;
     use crate::languages::python::uv::Uv;
@@ -454,4 +643,74 @@ mod tests {
         assert_eq!(envs.get(EnvVars::UV_MANAGED_PYTHON), Some(&None));
         assert_eq!(envs.get(EnvVars::UV_NO_MANAGED_PYTHON), Some(&None));
     }
+
+    #[test]
+    fn python_info_disk_cache_invalidates_on_mtime_change() -> anyhow::Result<()> {
+        let (temp, _uv, store, _info) = setup_test_install();
+        let cache = PythonInfoDiskCache::new(&store);
+        let python = temp.path().join("python3");
+        let info = QueryPythonInfo {
+            version: semver::Version::new(3, 12, 1),
+            base_exec_prefix: PathBuf::from("/usr"),
+        };
+        let mtime = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+
+        assert_eq!(cache.get(&python, mtime), None);
+        cache.insert(python.clone(), mtime, info.clone())?;
+        assert_eq!(cache.get(&python, mtime), Some(info.clone()));
+
+        let touched = mtime + std::time::Duration::from_secs(1);
+        assert_eq!(cache.get(&python, touched), None);
+
+        // The entry was persisted for the next process.
+        let reloaded = PythonInfoDiskCache::new(&store);
+        assert_eq!(reloaded.get(&python, mtime), Some(info));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_dependency_accepts_requirements() {
+        for dependency in [
+            "ruff",
+            "ruff==0.6.9",
+            "black[jupyter]>=24,<25",
+            "types-requests (>=2.31)",
+            "tomli>=1.1.0; python_version < '3.11'",
+            "pkg @ https://example.com/pkg.whl",
+            "git+https://github.com/user/repo@main",
+            "./local-package",
+            "-e .",
+            "foo.bar_baz~=1.0.post1",
+            "pytest===8.0.0",
+        ] {
+            assert!(check_dependency(dependency).is_ok(), "{dependency}");
+        }
+    }
+
+    #[test]
+    fn check_dependency_rejects_malformed_requirements() {
+        for (dependency, error) in [
+            (
+                "ruff=0.6.9",
+                "`=0.6.9` is not a version specifier, expected an operator like `==` or `>=`",
+            ),
+            (
+                "ruff 0.6.9",
+                "`0.6.9` is not a version specifier, expected an operator like `==` or `>=`",
+            ),
+            ("ruff==", "`==` does not specify a valid version"),
+            (
+                "ruff>=0.6,",
+                "`` is not a version specifier, expected an operator like `==` or `>=`",
+            ),
+            ("black[jupyter", "Missing `]` after the extras"),
+            ("black[jupyter,]", "`` is not a valid extra name"),
+            ("==1.0", "`` is not a valid package name"),
+            ("_ruff", "`_ruff` is not a valid package name"),
+        ] {
+            let err = check_dependency(dependency).unwrap_err();
+            assert_eq!(err.to_string(), error, "{dependency}");
+        }
+    }
 }
@@ -191,21 +191,30 @@ impl InstallSource {
 
     async fn install_from_github(&self, store: &Store, target: &Path) -> Result<()> {
         let ext = if cfg!(windows) { "zip" } else { "tar.gz" };
-        let archive_name = format!("uv-{HOST}.{ext}");
+        let dirname = format!("uv-{HOST}");
+        let archive_name = format!("{dirname}.{ext}");
         let download_url = format!(
             "https://github.com/astral-sh/uv/releases/download/{CUR_UV_VERSION}/{archive_name}"
         );
-
-        download_and_extract(&download_url, &archive_name, store, async |extracted| {
-            let source = extracted.join("uv").with_extension(EXE_EXTENSION);
-            let target_path = target.join("uv").with_extension(EXE_EXTENSION);
-
-            debug!(?source, target = %target_path.display(), "Moving uv to target");
-            // TODO: retry on Windows
-            replace_uv_binary(&source, &target_path).await?;
-
-            anyhow::Ok(())
-        })
+        // The Windows ZIP contains the binaries at the root level.
+        let subdir = if cfg!(windows) { "." } else { dirname.as_str() };
+
+        download_and_extract(
+            &download_url,
+            &archive_name,
+            Some(subdir),
+            store,
+            async |extracted| {
+                let source = extracted.join("uv").with_extension(EXE_EXTENSION);
+                let target_path = target.join("uv").with_extension(EXE_EXTENSION);
+
+                debug!(?source, target = %target_path.display(), "Moving uv to target");
+                // TODO: retry on Windows
+                replace_uv_binary(&source, &target_path).await?;
+
+                anyhow::Ok(())
+            },
+        )
         .await
         .context("Failed to download and extract uv")?;
 
@@ -323,33 +332,36 @@ impl InstallSource {
         filename: &str,
         download_url: &str,
     ) -> Result<()> {
-        download_and_extract(download_url, filename, store, async |extracted| {
-            // Find the uv binary in the extracted contents
-            let data_dir = format!("uv-{CUR_UV_VERSION}.data");
-            let extracted_uv = extracted
-                .join(data_dir)
-                .join("scripts")
-                .join("uv")
-                .with_extension(EXE_EXTENSION);
-
-            // Copy the binary to the target location
-            let target_path = target.join("uv").with_extension(EXE_EXTENSION);
-
-            debug!(?extracted_uv, target = %target_path.display(), "Moving uv to target");
-            replace_uv_binary(&extracted_uv, &target_path).await?;
-
-            // Set executable permissions on Unix
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                let metadata = fs_err::tokio::metadata(&target_path).await?;
-                let mut perms = metadata.permissions();
-                perms.set_mode(0o755);
-                fs_err::tokio::set_permissions(&target_path, perms).await?;
-            }
+        // The wheel ships the uv binary in its data scripts directory.
+        let scripts_dir = format!("uv-{CUR_UV_VERSION}.data/scripts");
+
+        download_and_extract(
+            download_url,
+            filename,
+            Some(&scripts_dir),
+            store,
+            async |extracted| {
+                let extracted_uv = extracted.join("uv").with_extension(EXE_EXTENSION);
+
+                // Copy the binary to the target location
+                let target_path = target.join("uv").with_extension(EXE_EXTENSION);
+
+                debug!(?extracted_uv, target = %target_path.display(), "Moving uv to target");
+                replace_uv_binary(&extracted_uv, &target_path).await?;
+
+                // Set executable permissions on Unix
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let metadata = fs_err::tokio::metadata(&target_path).await?;
+                    let mut perms = metadata.permissions();
+                    perms.set_mode(0o755);
+                    fs_err::tokio::set_permissions(&target_path, perms).await?;
+                }
 
-            Ok(())
-        })
+                Ok(())
+            },
+        )
         .await
         .context("Failed to download and extract uv wheel")?;
 
@@ -407,6 +419,9 @@ impl Uv {
     pub(crate) fn cmd(&self, summary: &str, store: &Store) -> Cmd {
         let mut cmd = Cmd::new(&self.path, summary);
         cmd.env(EnvVars::UV_CACHE_DIR, store.cache_path(CacheBucket::Uv));
+        if store.is_offline() {
+            cmd.env(EnvVars::UV_OFFLINE, "1");
+        }
         cmd
     }
 
@@ -530,6 +545,7 @@ impl Uv {
             }
         }
 
+        store.ensure_online("uv")?;
         let source = if let Some(uv_source) = uv_source_from_env() {
             uv_source
         } else {
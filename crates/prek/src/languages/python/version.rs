@@ -2,6 +2,8 @@
 //! <https://github.com/pypa/virtualenv/blob/216dc9f3592aa1f3345290702f0e7ba3432af3ce/src/virtualenv/discovery/py_spec.py>
 use std::str::FromStr;
 
+use itertools::Itertools;
+
 use crate::hook::InstallInfo;
 use crate::languages::version::{Error, try_into_u64_slice};
 
@@ -26,6 +28,8 @@ pub(crate) enum PythonRequest {
 /// - `3.12.3`
 /// - `>=3.12`
 /// - `>=3.8, <3.12`
+/// - `~=3.11`
+/// - `==3.12.*`
 // TODO: support version like `3.8b1`, `3.8rc2`, `python3.8t`, `python3.8-64`, `pypy3.8`.
 impl FromStr for PythonRequest {
     type Err = Error;
@@ -44,8 +48,10 @@ impl FromStr for PythonRequest {
             Self::parse_version_numbers(version_part, request)
         } else {
             Self::parse_version_numbers(request, request).or_else(|_| {
-                // Try to parse as a VersionReq (like ">= 3.12" or ">=3.8, <3.12")
-                semver::VersionReq::parse(request)
+                // Try to parse as a VersionReq (like ">= 3.12" or ">=3.8, <3.12"),
+                // translating PEP 440 operators such as `~=` and `==3.12.*` first.
+                let semver_request = pep440_to_semver(request);
+                semver::VersionReq::parse(semver_request.as_deref().unwrap_or(request))
                     .map(|version_req| PythonRequest::Range(version_req, request.into()))
                     .map_err(|_| Error::InvalidVersion(request.to_string()))
             })
@@ -91,6 +97,57 @@ impl PythonRequest {
     }
 }
 
+/// Translate a PEP 440 version specifier set into an equivalent semver requirement.
+///
+/// semver treats partial versions as wildcards, so `==3.12` would match `3.12.4` and `>3.12`
+/// would skip the whole `3.12` series. PEP 440 pads them with zeros instead, which is what the
+/// translation does. Returns `None` if a clause is not a PEP 440 specifier semver can express
+/// (`!=` and `===` have no semver equivalent), leaving the request to be parsed as semver as is.
+fn pep440_to_semver(request: &str) -> Option<String> {
+    fn release(version: &str) -> Option<Vec<u64>> {
+        try_into_u64_slice(version.trim()).ok()
+    }
+
+    fn padded(parts: &[u64]) -> String {
+        let mut parts = parts.to_vec();
+        parts.resize(parts.len().max(3), 0);
+        parts.iter().join(".")
+    }
+
+    let mut clauses = Vec::new();
+    for clause in request.split(',').map(str::trim) {
+        if let Some(version) = clause.strip_prefix("~=") {
+            // `~=3.11` means `>=3.11, ==3.*`, and `~=3.11.2` means `>=3.11.2, ==3.11.*`.
+            let parts = release(version)?;
+            if parts.len() < 2 {
+                return None;
+            }
+            let mut upper = parts[..parts.len() - 1].to_vec();
+            *upper.last_mut()? += 1;
+            clauses.push(format!(">={}", padded(&parts)));
+            clauses.push(format!("<{}", padded(&upper)));
+        } else if let Some(version) = clause.strip_prefix("==") {
+            if let Some(prefix) = version.trim().strip_suffix(".*") {
+                clauses.push(format!("={}", release(prefix)?.iter().join(".")));
+            } else {
+                clauses.push(format!("={}", padded(&release(version)?)));
+            }
+        } else if let Some(version) = clause.strip_prefix(">=") {
+            clauses.push(format!(">={}", release(version)?.iter().join(".")));
+        } else if let Some(version) = clause.strip_prefix("<=") {
+            clauses.push(format!("<={}", padded(&release(version)?)));
+        } else if let Some(version) = clause.strip_prefix('>') {
+            clauses.push(format!(">{}", padded(&release(version)?)));
+        } else if let Some(version) = clause.strip_prefix('<') {
+            clauses.push(format!("<{}", release(version)?.iter().join(".")));
+        } else {
+            return None;
+        }
+    }
+
+    Some(clauses.join(", "))
+}
+
 /// Convert a wheel tag formatted version (e.g., `38`) to multiple components (e.g., `3.8`).
 ///
 /// The major version is always assumed to be a single digit 0-9. The minor version is all
@@ -182,8 +239,29 @@ mod tests {
             )
         );
 
+        // PEP 440 specifiers
+        let range = |request: &str| match PythonRequest::from_str(request).unwrap() {
+            PythonRequest::Range(req, raw) => {
+                assert_eq!(raw, request);
+                req.to_string()
+            }
+            other => panic!("expected a range for `{request}`, got {other:?}"),
+        };
+        assert_eq!(range(">=3.11,<3.13"), ">=3.11, <3.13");
+        assert_eq!(range("~=3.11"), ">=3.11.0, <4.0.0");
+        assert_eq!(range("~=3.11.2"), ">=3.11.2, <3.12.0");
+        assert_eq!(range("==3.12.*"), "=3.12");
+        assert_eq!(range("==3.12"), "=3.12.0");
+        assert_eq!(range(">3.12"), ">3.12.0");
+        assert_eq!(range("<=3.12"), "<=3.12.0");
+        // Plain semver requirements are still accepted.
+        assert_eq!(range("^3.11"), "^3.11");
+
         // Invalid versions
         assert!(PythonRequest::from_str("invalid").is_err());
+        assert!(PythonRequest::from_str("~=3").is_err());
+        assert!(PythonRequest::from_str(">=3.8, !=3.9.*").is_err());
+        assert!(PythonRequest::from_str("===3.12").is_err());
         assert!(PythonRequest::from_str("3.12.3.4").is_err());
         assert!(PythonRequest::from_str("3.12.a").is_err());
         assert!(PythonRequest::from_str("3.b.1").is_err());
@@ -221,6 +299,13 @@ mod tests {
         let range_req = semver::VersionReq::parse(">=4.0").unwrap();
         assert!(!PythonRequest::Range(range_req, ">=4.0".to_string()).satisfied_by(&install_info));
 
+        assert!(PythonRequest::from_str(">=3.11,<3.13")?.satisfied_by(&install_info));
+        assert!(PythonRequest::from_str("~=3.11")?.satisfied_by(&install_info));
+        assert!(PythonRequest::from_str("==3.12.*")?.satisfied_by(&install_info));
+        assert!(!PythonRequest::from_str("==3.12")?.satisfied_by(&install_info));
+        assert!(PythonRequest::from_str(">3.12")?.satisfied_by(&install_info));
+        assert!(!PythonRequest::from_str("<=3.12")?.satisfied_by(&install_info));
+
         Ok(())
     }
 }
@@ -0,0 +1,154 @@
+use std::io;
+use std::path::Path;
+
+use anyhow::Result;
+use tracing::trace;
+
+use crate::config::Language;
+use crate::hook::Hook;
+use crate::languages::version::LanguageRequest;
+
+/// Strip a `#` comment and surrounding whitespace from a version file line.
+fn strip_comment(line: &str) -> &str {
+    line.split_once('#')
+        .map_or(line, |(before, _)| before)
+        .trim()
+}
+
+/// The first version listed in a pyenv `.python-version` file.
+fn parse_python_version_file(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(strip_comment)
+        .find(|line| !line.is_empty())
+        .map(ToString::to_string)
+}
+
+/// The first version of the `python` line in an asdf `.tool-versions` file.
+fn parse_tool_versions_file(contents: &str) -> Option<String> {
+    contents.lines().map(strip_comment).find_map(|line| {
+        let mut tokens = line.split_whitespace();
+        if tokens.next()? != "python" {
+            return None;
+        }
+        tokens.next().map(ToString::to_string)
+    })
+}
+
+async fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs_err::tokio::read_to_string(path).await {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Read the pinned Python version from `.python-version`, falling back to `.tool-versions`.
+async fn extract_version_file_request(path: &Path) -> Result<Option<String>> {
+    if let Some(contents) = read_optional(&path.join(".python-version")).await? {
+        if let Some(version) = parse_python_version_file(&contents) {
+            return Ok(Some(version));
+        }
+    }
+    if let Some(contents) = read_optional(&path.join(".tool-versions")).await? {
+        return Ok(parse_tool_versions_file(&contents));
+    }
+    Ok(None)
+}
+
+/// Extract the Python version pinned by pyenv or asdf in the hook repo, or in the project for
+/// local hooks.
+///
+/// Only acts when `language_request` is still `Any` and downloads are allowed (i.e. neither
+/// `language_version` nor `requires-python` picked a version).
+pub(crate) async fn extract_version_file_metadata(hook: &mut Hook) -> Result<()> {
+    if !hook.language_request.is_any() || !hook.language_request.allows_download() {
+        trace!(
+            hook = %hook,
+            "Skipping Python version file extraction because language_version is already configured",
+        );
+        return Ok(());
+    }
+
+    let path = hook.repo_path().unwrap_or_else(|| hook.work_dir());
+    let Some(req_str) = extract_version_file_request(path).await? else {
+        trace!(hook = %hook, "No .python-version or .tool-versions found");
+        return Ok(());
+    };
+
+    let req = match LanguageRequest::parse(Language::Python, &req_str) {
+        Ok(req) => req,
+        Err(err) => {
+            trace!(%req_str, error = %err, "Ignoring invalid Python version file");
+            return Ok(());
+        }
+    };
+
+    trace!(hook = %hook, version = %req_str, "Using Python version file-derived language_version");
+    hook.language_request = req;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn python_version_uses_first_version() {
+        let contents = "# pinned by pyenv\n\n3.12.1\n3.11.4\n";
+        assert_eq!(
+            parse_python_version_file(contents).as_deref(),
+            Some("3.12.1")
+        );
+    }
+
+    #[test]
+    fn empty_python_version_is_none() {
+        assert_eq!(parse_python_version_file("\n# nothing\n"), None);
+    }
+
+    #[test]
+    fn tool_versions_uses_python_line() {
+        let contents = "nodejs 20.11.0\npython 3.11.7 3.10.13 # main first\nruby 3.3.0\n";
+        assert_eq!(
+            parse_tool_versions_file(contents).as_deref(),
+            Some("3.11.7")
+        );
+    }
+
+    #[test]
+    fn tool_versions_without_python_is_none() {
+        let contents = "nodejs 20.11.0\n# python 3.12.0\npython\n";
+        assert_eq!(parse_tool_versions_file(contents), None);
+    }
+
+    #[tokio::test]
+    async fn python_version_is_preferred_over_tool_versions() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::tokio::write(dir.path().join(".python-version"), "3.12\n").await?;
+        fs_err::tokio::write(dir.path().join(".tool-versions"), "python 3.11.7\n").await?;
+
+        let req = extract_version_file_request(dir.path()).await?;
+        assert_eq!(req.as_deref(), Some("3.12"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tool_versions_is_used_without_python_version() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs_err::tokio::write(dir.path().join(".tool-versions"), "python 3.11.7\n").await?;
+
+        let req = extract_version_file_request(dir.path()).await?;
+        assert_eq!(req.as_deref(), Some("3.11.7"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_version_files_is_none() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let req = extract_version_file_request(dir.path()).await?;
+        assert!(req.is_none());
+        Ok(())
+    }
+}
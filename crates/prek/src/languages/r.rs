@@ -10,11 +10,10 @@ use rustc_hash::FxHashSet;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -110,7 +109,7 @@ impl LanguageImpl for R {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         let current = query_r_version(&rscript_executable())
             .await
             .context("Failed to query R version")?;
@@ -131,9 +130,9 @@ impl LanguageImpl for R {
         hook: &InstalledHook,
         filenames: &[&Path],
         _store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_path = hook.env_path().expect("R must have env path");
         let activate = env_path.join("activate.R");
@@ -147,23 +146,25 @@ impl LanguageImpl for R {
                 .env(EnvVars::R_PROFILE_USER, &activate)
                 .stdin(Stdio::null());
 
-            cmd.envs(&hook.env)
+            cmd.envs(hook.env_vars(&[(EnvVars::R_PROFILE_USER, activate.as_os_str())]))
                 .args(&hook.args)
                 .args(batch)
                 .check(false);
 
             let mut output = cmd
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, &entry, run).await?;
+        let results = run_by_batch(hook, filenames, &entry, context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -173,7 +174,7 @@ impl LanguageImpl for R {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
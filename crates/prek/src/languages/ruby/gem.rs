@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
 use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
 use rand::RngExt;
@@ -21,9 +22,8 @@ use crate::run::CONCURRENCY;
 /// directory, but that directory is not on the parent process's PATH (e.g.
 /// in a Docker image with no system Ruby). Without prepending it, Ruby errors
 /// with `LoadError: No such file or directory -- gem`.
-fn ruby_path_env(ruby: &RubyResult) -> Result<OsString> {
-    let ruby_bin_dir = ruby
-        .ruby_bin()
+fn ruby_path_env(ruby_bin: &Path) -> Result<OsString> {
+    let ruby_bin_dir = ruby_bin
         .parent()
         .context("Ruby executable should have a parent directory")?;
     prepend_paths(&[ruby_bin_dir]).context("Failed to join PATH")
@@ -65,7 +65,7 @@ async fn build_gemspec(ruby: &RubyResult, gemspec_path: &Path) -> Result<PathBuf
         .arg("build")
         .arg(gemspec_path.file_name().unwrap())
         .current_dir(repo_dir)
-        .env(EnvVars::PATH, ruby_path_env(ruby)?)
+        .env(EnvVars::PATH, ruby_path_env(ruby.ruby_bin())?)
         .check(true)
         .output()
         .await?;
@@ -105,8 +105,8 @@ pub(crate) async fn build_gemspecs(ruby: &RubyResult, repo_dir: &Path) -> Result
 /// Also prepends the resolved Ruby's bin directory to `$PATH` so that
 /// `ruby -S gem` can find the bundled `gem` script even when no system Ruby
 /// is on PATH.
-fn gem_env<'a>(cmd: &'a mut Cmd, ruby: &RubyResult, gem_home: &Path) -> Result<&'a mut Cmd> {
-    cmd.env(EnvVars::PATH, ruby_path_env(ruby)?)
+fn gem_env<'a>(cmd: &'a mut Cmd, ruby_bin: &Path, gem_home: &Path) -> Result<&'a mut Cmd> {
+    cmd.env(EnvVars::PATH, ruby_path_env(ruby_bin)?)
         .env(EnvVars::GEM_HOME, gem_home)
         .env(EnvVars::BUNDLE_IGNORE_CONFIG, "1")
         .env_remove(EnvVars::GEM_PATH)
@@ -211,7 +211,7 @@ async fn resolve_gems(
         .arg(gem_home.join("bin"))
         .args(gem_files)
         .args(additional_dependencies);
-    gem_env(&mut cmd, ruby, gem_home)?;
+    gem_env(&mut cmd, ruby.ruby_bin(), gem_home)?;
 
     let output = cmd.check(true).output().await?;
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -248,7 +248,7 @@ async fn install_single_gem(
         }
     }
 
-    gem_env(&mut cmd, ruby, gem_home)?;
+    gem_env(&mut cmd, ruby.ruby_bin(), gem_home)?;
     cmd.check(true).output().await?;
     Ok(())
 }
@@ -273,7 +273,7 @@ async fn install_gems_sequential(
         .arg(gem_home.join("bin"))
         .args(gem_files)
         .args(additional_dependencies);
-    gem_env(&mut cmd, ruby, gem_home)?;
+    gem_env(&mut cmd, ruby.ruby_bin(), gem_home)?;
 
     debug!("Installing gems sequentially to {}", gem_home.display());
     cmd.check(true).output().await?;
@@ -382,6 +382,84 @@ pub(crate) async fn install_gems(
     }
 }
 
+/// Write a `Gemfile` to `env_path` that evaluates the hook repo's `Gemfile` and adds
+/// `additional_dependencies`, so they can be loaded under `bundle exec` too.
+///
+/// The repo's `Gemfile.lock` is copied next to it, to keep the versions it locks.
+pub(crate) async fn write_env_gemfile(
+    env_path: &Path,
+    repo_gemfile: &Path,
+    additional_dependencies: &FxHashSet<String>,
+) -> Result<PathBuf> {
+    let gemfile = env_path.join("Gemfile");
+    fs_err::tokio::write(
+        &gemfile,
+        env_gemfile_content(repo_gemfile, additional_dependencies),
+    )
+    .await?;
+
+    let repo_lockfile = repo_gemfile.with_file_name("Gemfile.lock");
+    if repo_lockfile.is_file() {
+        fs_err::tokio::copy(&repo_lockfile, env_path.join("Gemfile.lock")).await?;
+    }
+
+    Ok(gemfile)
+}
+
+/// `additional_dependencies` items are `name` or `name:version`, as accepted by `gem install`.
+fn env_gemfile_content(repo_gemfile: &Path, additional_dependencies: &FxHashSet<String>) -> String {
+    let gems = additional_dependencies.iter().sorted().map(|dependency| {
+        match dependency.split_once(':') {
+            Some((name, version)) => {
+                format!("gem {}, {}\n", ruby_string(name), ruby_string(version))
+            }
+            None => format!("gem {}\n", ruby_string(dependency)),
+        }
+    });
+    std::iter::once(format!(
+        "eval_gemfile {}\n",
+        ruby_string(&repo_gemfile.to_string_lossy())
+    ))
+    .chain(gems)
+    .collect()
+}
+
+/// Quote `value` as a single-quoted Ruby string.
+fn ruby_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Install the gems of `gemfile` to an isolated `GEM_HOME` with `bundle install`.
+pub(crate) async fn bundle_install(
+    ruby: &RubyResult,
+    gem_home: &Path,
+    gemfile: &Path,
+) -> Result<()> {
+    let mut cmd = Cmd::new(ruby.ruby_bin(), "bundle install");
+    cmd.arg("-S").arg("bundle").arg("install");
+    gem_env(&mut cmd, ruby.ruby_bin(), gem_home)?;
+    cmd.env(EnvVars::BUNDLE_GEMFILE, gemfile);
+
+    debug!(
+        "Installing bundle of {} to {}",
+        gemfile.display(),
+        gem_home.display()
+    );
+    cmd.check(true).output().await?;
+    Ok(())
+}
+
+/// Check that the gems of `gemfile` are installed in `GEM_HOME` with `bundle check`.
+pub(crate) async fn bundle_check(ruby_bin: &Path, gem_home: &Path, gemfile: &Path) -> Result<()> {
+    let mut cmd = Cmd::new(ruby_bin, "bundle check");
+    cmd.arg("-S").arg("bundle").arg("check");
+    gem_env(&mut cmd, ruby_bin, gem_home)?;
+    cmd.env(EnvVars::BUNDLE_GEMFILE, gemfile);
+
+    cmd.check(true).output().await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,4 +531,32 @@ Gems to install:
             "prism-1.9.0-x86_64-linux"
         );
     }
+
+    #[test]
+    fn test_env_gemfile_content() {
+        let repo_gemfile = Path::new("/store/repos/abc/Gemfile");
+        assert_eq!(
+            env_gemfile_content(repo_gemfile, &FxHashSet::default()),
+            "eval_gemfile '/store/repos/abc/Gemfile'\n"
+        );
+
+        let dependencies = ["rubocop-rails:2.27.0", "rubocop-rspec"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            env_gemfile_content(repo_gemfile, &dependencies),
+            indoc::indoc! {"
+                eval_gemfile '/store/repos/abc/Gemfile'
+                gem 'rubocop-rails', '2.27.0'
+                gem 'rubocop-rspec'
+            "}
+        );
+    }
+
+    #[test]
+    fn test_ruby_string() {
+        assert_eq!(ruby_string("rubocop"), "'rubocop'");
+        assert_eq!(ruby_string(r"C:\it's"), r"'C:\\it\'s'");
+    }
 }
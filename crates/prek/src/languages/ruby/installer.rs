@@ -210,7 +210,7 @@ impl RubyInstaller {
             ));
         };
 
-        let versions = match self.list_remote_versions(platform).await {
+        let versions = match self.list_remote_versions(store, platform).await {
             Ok(v) => v,
             Err(e) => {
                 anyhow::bail!(
@@ -264,10 +264,15 @@ impl RubyInstaller {
     }
 
     /// Fetch available Ruby versions from the rv-ruby GitHub release.
-    async fn list_remote_versions(&self, platform: &str) -> Result<Vec<semver::Version>> {
+    async fn list_remote_versions(
+        &self,
+        store: &Store,
+        platform: &str,
+    ) -> Result<Vec<semver::Version>> {
         let (api_url, is_github) = rv_ruby_api_url();
         let suffix = format!(".{platform}.tar.gz");
 
+        store.ensure_online(&api_url)?;
         let req = REQWEST_CLIENT
             .get(&api_url)
             .header("Accept", "application/vnd.github+json");
@@ -322,33 +327,22 @@ impl RubyInstaller {
 
         debug!(url = %url, target = %target.display(), "Downloading Ruby {version}");
 
+        // rv-ruby tarballs contain: rv-ruby@{version}/{version}/bin/ruby
+        let subdir = format!("rv-ruby@{version_str}/{version_str}");
+
         download_and_extract_with(
             &url,
             &filename,
+            Some(&subdir),
             store,
             |req| maybe_add_github_auth(req, is_github),
             async |extracted| {
-                // rv-ruby tarballs contain: rv-ruby@{version}/{version}/bin/ruby
-                // After strip_component, `extracted` is the rv-ruby@{version}/ directory.
-                // Move the inner {version}/ directory to our target.
-                let inner = extracted.join(&version_str);
-                if !inner.exists() {
-                    anyhow::bail!(
-                        "Expected directory '{}' inside rv-ruby archive, found: {:?}",
-                        version_str,
-                        fs_err::read_dir(extracted)?
-                            .flatten()
-                            .map(|e| e.file_name())
-                            .collect::<Vec<_>>()
-                    );
-                }
-
                 if target.exists() {
                     debug!(target = %target.display(), "Removing existing Ruby");
                     fs_err::tokio::remove_dir_all(&target).await?;
                 }
 
-                fs_err::tokio::rename(&inner, &target).await?;
+                fs_err::tokio::rename(extracted, &target).await?;
                 Ok(())
             },
         )
@@ -9,17 +9,21 @@ use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::ruby::RubyRequest;
-use crate::languages::ruby::gem::{build_gemspecs, install_gems};
+use crate::languages::ruby::gem::{
+    build_gemspecs, bundle_check, bundle_install, install_gems, write_env_gemfile,
+};
 use crate::languages::ruby::installer::{RubyInstaller, query_ruby_info};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{Store, ToolBucket};
 
+/// Extra key storing the `Gemfile` the hook's gems were installed from with bundler.
+const BUNDLE_GEMFILE_KEY: &str = "bundle_gemfile";
+
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Ruby;
 
@@ -66,29 +70,49 @@ impl LanguageImpl for Ruby {
         let gem_bin = gem_bin(&info.env_path);
         fs_err::tokio::create_dir_all(&gem_bin).await?;
 
-        // 4. Build gemspecs
-        if let Some(repo_path) = hook.repo_path() {
-            // Try to build gemspecs, but don't fail if there aren't any
-            match build_gemspecs(&ruby, repo_path).await {
-                Ok(gem_files) => {
-                    debug!("Built {} gem(s) from gemspecs", gem_files.len());
-                }
-                Err(e) if e.to_string().contains("No .gemspec files") => {
-                    debug!("No gemspecs found in repo, skipping gem build");
+        let repo_gemfile = hook
+            .repo_path()
+            .map(|repo_path| repo_path.join("Gemfile"))
+            .filter(|gemfile| gemfile.is_file());
+
+        if let Some(repo_gemfile) = repo_gemfile {
+            // 4. Install the repo's bundle, with `additional_dependencies` added to it. The
+            // bundle is resolved from a `Gemfile` in the environment, so bundler never writes a
+            // lockfile into the shared repo clone.
+            let gemfile =
+                write_env_gemfile(&info.env_path, &repo_gemfile, &hook.additional_dependencies)
+                    .await
+                    .context("Failed to write Gemfile")?;
+
+            bundle_install(&ruby, &gem_home, &gemfile)
+                .await
+                .context("Failed to install gems with bundler")?;
+            info.with_extra(BUNDLE_GEMFILE_KEY, &gemfile.to_string_lossy());
+        } else {
+            // 4. Build gemspecs
+            if let Some(repo_path) = hook.repo_path() {
+                // Try to build gemspecs, but don't fail if there aren't any
+                match build_gemspecs(&ruby, repo_path).await {
+                    Ok(gem_files) => {
+                        debug!("Built {} gem(s) from gemspecs", gem_files.len());
+                    }
+                    Err(e) if e.to_string().contains("No .gemspec files") => {
+                        debug!("No gemspecs found in repo, skipping gem build");
+                    }
+                    Err(e) => return Err(e).context("Failed to build gemspecs"),
                 }
-                Err(e) => return Err(e).context("Failed to build gemspecs"),
             }
-        }
 
-        // 5. Install gems (Note that pre-commit installs all *.gem files, not only those built from gemspecs)
-        install_gems(
-            &ruby,
-            &gem_home,
-            hook.repo_path(),
-            &hook.additional_dependencies,
-        )
-        .await
-        .context("Failed to install gems")?;
+            // 5. Install gems (Note that pre-commit installs all *.gem files, not only those built from gemspecs)
+            install_gems(
+                &ruby,
+                &gem_home,
+                hook.repo_path(),
+                &hook.additional_dependencies,
+            )
+            .await
+            .context("Failed to install gems")?;
+        }
 
         let gem_bin_ruby = gem_bin.join("ruby").with_extension(EXE_EXTENSION);
         crate::fs::symlink_or_copy(ruby.ruby_bin(), &gem_bin_ruby)
@@ -105,7 +129,7 @@ impl LanguageImpl for Ruby {
         })
     }
 
-    async fn check_health(&self, info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> Result<()> {
         // 1. Verify Ruby runs and reports correct version
         let (actual_version, _) = query_ruby_info(&info.toolchain)
             .await
@@ -129,6 +153,17 @@ impl LanguageImpl for Ruby {
             );
         }
 
+        // 3. Verify `bundle` resolves and the bundle is complete
+        if let Some(gemfile) = info.get_extra(BUNDLE_GEMFILE_KEY) {
+            bundle_check(
+                &info.toolchain,
+                &gem_home(&info.env_path),
+                Path::new(gemfile),
+            )
+            .await
+            .context("Failed to verify installed bundle")?;
+        }
+
         Ok(())
     }
 
@@ -137,9 +172,9 @@ impl LanguageImpl for Ruby {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Ruby hook must have env path");
 
@@ -152,35 +187,57 @@ impl LanguageImpl for Ruby {
 
         let new_path = prepend_paths(&[&gem_bin, ruby_bin]).context("Failed to join PATH")?;
 
-        // Resolve entry point
-        let entry = hook.entry.resolve(Some(&new_path), store)?;
+        // Resolve entry point, running it with `bundle exec` if gems were installed by bundler
+        let gemfile = hook
+            .install_info()
+            .and_then(|info| info.get_extra(BUNDLE_GEMFILE_KEY));
+        let mut entry = hook.entry.resolve(Some(&new_path), store)?;
+        if gemfile.is_some() {
+            let ruby = ruby_bin.join("ruby").with_extension(EXE_EXTENSION);
+            entry.argv_mut().splice(
+                0..0,
+                [ruby.to_string_lossy().into_owned()]
+                    .into_iter()
+                    .chain(["-S", "bundle", "exec"].map(String::from)),
+            );
+        }
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::GEM_HOME, gem_home.as_os_str()),
+        ];
 
         // Execute in batches
         let run = async |batch: &[&Path]| {
-            let mut output = Cmd::new(&entry[0], "ruby hook")
-                .current_dir(hook.work_dir())
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::GEM_HOME, &gem_home)
+            let mut cmd = Cmd::new(&entry[0], "ruby hook");
+            cmd.current_dir(hook.work_dir())
+                .envs(language_env)
                 .env(EnvVars::BUNDLE_IGNORE_CONFIG, "1")
-                .env_remove(EnvVars::GEM_PATH)
-                .env_remove(EnvVars::BUNDLE_GEMFILE)
-                .envs(&hook.env)
+                .env_remove(EnvVars::GEM_PATH);
+            if let Some(gemfile) = gemfile {
+                cmd.env(EnvVars::BUNDLE_GEMFILE, gemfile);
+            } else {
+                cmd.env_remove(EnvVars::BUNDLE_GEMFILE);
+            }
+            let mut output = cmd
+                .envs(hook.env_vars(&language_env))
                 .args(&entry[1..])
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Combine results
         let mut combined_status = 0;
@@ -191,7 +248,7 @@ impl LanguageImpl for Ruby {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -4,5 +4,5 @@ mod rust;
 mod rustup;
 mod version;
 
-pub(crate) use rust::Rust;
+pub(crate) use rust::{Rust, check_dependency};
 pub(crate) use version::RustRequest;
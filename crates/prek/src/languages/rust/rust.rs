@@ -7,13 +7,11 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use anyhow::{Context, bail};
-use itertools::{Either, Itertools};
 use prek_consts::env_vars::EnvVars;
 use prek_consts::prepend_paths;
 use tracing::debug;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageImpl;
 use crate::languages::rust::RustRequest;
@@ -22,7 +20,7 @@ use crate::languages::rust::rustup::Rustup;
 use crate::languages::rust::version::{Channel, EXTRA_KEY_CHANNEL};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 fn format_cargo_dependency(dep: &str) -> String {
@@ -34,6 +32,75 @@ fn format_cargo_dependency(dep: &str) -> String {
     }
 }
 
+/// An `additional_dependencies` item of a Rust hook.
+#[derive(Debug, Eq, PartialEq)]
+enum RustDependency<'a> {
+    /// A library crate added to the hook package, `name` or `name:version`.
+    Lib(&'a str),
+    /// A binary crate or git URL installed with `cargo install`, prefixed with `cli:`.
+    Cli(&'a str),
+    /// A rustup component of the hook toolchain, prefixed with `component:`.
+    Component(&'a str),
+    /// A rustup target of the hook toolchain, prefixed with `target:`.
+    Target(&'a str),
+}
+
+impl<'a> RustDependency<'a> {
+    fn parse(dependency: &'a str) -> Self {
+        if let Some(cli_dep) = dependency.strip_prefix("cli:") {
+            Self::Cli(cli_dep)
+        } else if let Some(component) = dependency.strip_prefix("component:") {
+            Self::Component(component)
+        } else if let Some(target) = dependency.strip_prefix("target:") {
+            Self::Target(target)
+        } else {
+            Self::Lib(dependency)
+        }
+    }
+}
+
+/// Check that an `additional_dependencies` item is a `name` or `name:version` library crate,
+/// a `cli:` prefixed crate or git URL, or a `component:` or `target:` of the toolchain.
+pub(crate) fn check_dependency(dependency: &str) -> anyhow::Result<()> {
+    let (name, version) = match RustDependency::parse(dependency) {
+        RustDependency::Cli(cli_dep) => match CargoCliDependency::from_str(cli_dep)? {
+            CargoCliDependency::Crate { name, version } => (name, version),
+            CargoCliDependency::Git { .. } => return Ok(()),
+        },
+        RustDependency::Component(name) | RustDependency::Target(name) => {
+            let is_valid = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+            if !is_valid {
+                bail!("`{name}` is not a valid rustup component or target name");
+            }
+            return Ok(());
+        }
+        RustDependency::Lib(lib_dep) => {
+            let (name, version) = lib_dep.split_once(':').unwrap_or((lib_dep, ""));
+            (
+                name.to_string(),
+                Some(version.to_string()).filter(|version| !version.is_empty()),
+            )
+        }
+    };
+
+    let is_valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if !is_valid_name {
+        bail!("`{name}` is not a valid crate name, use `name:version` to pin a version");
+    }
+    if let Some(version) = version {
+        semver::VersionReq::parse(&version)
+            .with_context(|| format!("`{version}` is not a valid version requirement"))?;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum CargoCliDependency {
     Crate {
@@ -205,6 +272,24 @@ fn package_produces_binary(package: &cargo_metadata::Package, binary_name: &str)
         .any(|t| names_match(&t.name, binary_name))
 }
 
+/// Whether `component` is listed in the `components` file of a toolchain's `rustlib` directory.
+///
+/// Entries of target-specific components end with the target triple, and some keep the
+/// `-preview` suffix of their old name, like `clippy-preview-x86_64-unknown-linux-gnu`.
+fn has_component(rustlib: &Path, installed: &str, component: &str) -> bool {
+    let component = component.strip_suffix("-preview").unwrap_or(component);
+    installed.lines().any(|line| {
+        let Some(rest) = line.trim().strip_prefix(component) else {
+            return false;
+        };
+        let rest = rest.strip_prefix("-preview").unwrap_or(rest);
+        rest.is_empty()
+            || rest
+                .strip_prefix('-')
+                .is_some_and(|target| rustlib.join(target).is_dir())
+    })
+}
+
 /// Copy executable binaries from a release directory to a destination bin directory.
 async fn copy_binaries(release_dir: &Path, dest_bin_dir: &Path) -> anyhow::Result<()> {
     let mut entries = fs_err::tokio::read_dir(release_dir).await?;
@@ -421,7 +506,7 @@ impl LanguageImpl for Rust {
         let cargo_home = store.cache_path(CacheBucket::Cargo);
         let rustup_dir = store.tools_path(ToolBucket::Rustup);
         let rustup = Rustup::install(store, &rustup_dir).await?;
-        let installer = RustInstaller::new(rustup);
+        let installer = RustInstaller::new(rustup.clone());
 
         let (version, allows_download) = match &hook.language_request {
             LanguageRequest::Any { system_only } => (&RustRequest::Any, !system_only),
@@ -462,15 +547,25 @@ impl LanguageImpl for Rust {
         fs_err::tokio::create_dir_all(bin_dir(&info.env_path)).await?;
 
         // 3. Install dependencies
-        // Split dependencies by cli: prefix
-        let (cli_deps, lib_deps): (Vec<_>, Vec<_>) =
-            hook.additional_dependencies.iter().partition_map(|dep| {
-                if let Some(stripped) = dep.strip_prefix("cli:") {
-                    Either::Left(stripped)
-                } else {
-                    Either::Right(dep)
-                }
-            });
+        let mut cli_deps = Vec::new();
+        let mut lib_deps = Vec::new();
+        let mut components = Vec::new();
+        let mut targets = Vec::new();
+        for dep in &hook.additional_dependencies {
+            match RustDependency::parse(dep) {
+                RustDependency::Lib(_) => lib_deps.push(dep),
+                RustDependency::Cli(cli_dep) => cli_deps.push(cli_dep),
+                RustDependency::Component(component) => components.push(component),
+                RustDependency::Target(target) => targets.push(target),
+            }
+        }
+
+        // Add components and targets first, `cargo` may need them to build dependencies
+        if !components.is_empty() || !targets.is_empty() {
+            rustup
+                .add_to_toolchain(rust.toolchain(), &components, &targets)
+                .await?;
+        }
 
         // Use the hook entry as the binary name to find the package, this could be improved by allowing an explicit binary name in the hook config.
         let hook_entry = hook.entry.expect_direct().split()?;
@@ -505,7 +600,37 @@ impl LanguageImpl for Rust {
         })
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> anyhow::Result<()> {
+    async fn check_health(&self, info: &InstallInfo, _store: &Store) -> anyhow::Result<()> {
+        let rustlib = info.toolchain.join("lib").join("rustlib");
+        let mut installed_components = None;
+        for dep in &info.dependencies {
+            match RustDependency::parse(dep) {
+                RustDependency::Component(component) => {
+                    let installed = match &installed_components {
+                        Some(installed) => installed,
+                        None => installed_components.insert(
+                            fs_err::tokio::read_to_string(rustlib.join("components")).await?,
+                        ),
+                    };
+                    if !has_component(&rustlib, installed, component) {
+                        bail!(
+                            "Rust component `{component}` is not installed in toolchain `{}`",
+                            info.toolchain.display()
+                        );
+                    }
+                }
+                RustDependency::Target(target) => {
+                    if !rustlib.join(target).join("lib").is_dir() {
+                        bail!(
+                            "Rust target `{target}` is not installed in toolchain `{}`",
+                            info.toolchain.display()
+                        );
+                    }
+                }
+                RustDependency::Lib(_) | RustDependency::Cli(_) => {}
+            }
+        }
+
         Ok(())
     }
 
@@ -514,9 +639,9 @@ impl LanguageImpl for Rust {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> anyhow::Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let env_dir = hook.env_path().expect("Rust hook must have env path");
         let info = hook.install_info().expect("Rust hook must be installed");
@@ -528,29 +653,34 @@ impl LanguageImpl for Rust {
         let new_path = prepend_paths(&[&rust_bin, &rustc_bin]).context("Failed to join PATH")?;
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
+        let language_env = [
+            (EnvVars::PATH, new_path.as_os_str()),
+            (EnvVars::CARGO_HOME, cargo_home.as_os_str()),
+        ];
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "rust hook")
                 .current_dir(hook.work_dir())
                 .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::CARGO_HOME, &cargo_home)
+                .envs(language_env)
                 .env(EnvVars::RUSTUP_AUTO_INSTALL, "0")
-                .envs(&hook.env)
+                .envs(hook.env_vars(&language_env))
                 .args(&hook.args)
                 .args(batch)
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         let mut combined_status = 0;
         let mut combined_output = Vec::new();
@@ -560,7 +690,7 @@ impl LanguageImpl for Rust {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
@@ -988,4 +1118,101 @@ edition = "2021"
             assert_eq!(dep.to_cargo_args(), expected, "input: {input}");
         }
     }
+
+    #[test]
+    fn check_dependency_accepts_crates() {
+        for dependency in [
+            "serde",
+            "serde:1.0",
+            "tokio_util:>=0.7, <0.8",
+            "cli:ripgrep",
+            "cli:cargo-deny:0.16.1",
+            "cli:https://github.com/user/repo:v1.0:tool",
+        ] {
+            assert!(check_dependency(dependency).is_ok(), "{dependency}");
+        }
+    }
+
+    #[test]
+    fn check_dependency_rejects_malformed_crates() {
+        for (dependency, error) in [
+            (
+                "serde@1.0",
+                "`serde@1.0` is not a valid crate name, use `name:version` to pin a version",
+            ),
+            ("serde:one", "`one` is not a valid version requirement"),
+            (
+                "cli:ripgrep:14..1",
+                "`14..1` is not a valid version requirement",
+            ),
+            (
+                "cli:1tool",
+                "`1tool` is not a valid crate name, use `name:version` to pin a version",
+            ),
+        ] {
+            let err = check_dependency(dependency).unwrap_err();
+            assert_eq!(err.to_string(), error, "{dependency}");
+        }
+    }
+
+    #[test]
+    fn parse_rust_dependency() {
+        assert_eq!(
+            RustDependency::parse("serde:1.0"),
+            RustDependency::Lib("serde:1.0")
+        );
+        assert_eq!(
+            RustDependency::parse("cli:ripgrep"),
+            RustDependency::Cli("ripgrep")
+        );
+        assert_eq!(
+            RustDependency::parse("component:clippy"),
+            RustDependency::Component("clippy")
+        );
+        assert_eq!(
+            RustDependency::parse("target:wasm32-unknown-unknown"),
+            RustDependency::Target("wasm32-unknown-unknown")
+        );
+    }
+
+    #[test]
+    fn check_dependency_components_and_targets() {
+        assert!(check_dependency("component:clippy").is_ok());
+        assert!(check_dependency("component:llvm-tools-preview").is_ok());
+        assert!(check_dependency("target:wasm32-unknown-unknown").is_ok());
+
+        let err = check_dependency("component:").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`` is not a valid rustup component or target name"
+        );
+        let err = check_dependency("target:wasm32 unknown").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`wasm32 unknown` is not a valid rustup component or target name"
+        );
+    }
+
+    #[test]
+    fn has_component_matches_rustlib_entries() {
+        let temp = TempDir::new().unwrap();
+        let rustlib = temp.path();
+        fs_err::create_dir_all(rustlib.join("x86_64-unknown-linux-gnu")).unwrap();
+        let installed = indoc::indoc! {"
+            cargo-x86_64-unknown-linux-gnu
+            clippy-preview-x86_64-unknown-linux-gnu
+            rust-docs-x86_64-unknown-linux-gnu
+            rust-src
+            llvm-tools-x86_64-unknown-linux-gnu
+        "};
+
+        assert!(has_component(rustlib, installed, "clippy"));
+        assert!(has_component(rustlib, installed, "clippy-preview"));
+        assert!(has_component(rustlib, installed, "rust-src"));
+        assert!(has_component(rustlib, installed, "llvm-tools-preview"));
+        assert!(has_component(rustlib, installed, "rust-docs"));
+        assert!(!has_component(rustlib, installed, "rustfmt"));
+        assert!(!has_component(rustlib, installed, "rust"));
+        assert!(!has_component(rustlib, installed, "rust-std"));
+    }
 }
@@ -118,6 +118,7 @@ impl Rustup {
         debug!(url = %url, temp_dir = ?temp_dir.path(), "Downloading");
 
         let tmp_target = temp_dir.path().join(filename);
+        store.ensure_online(&url)?;
         let response = REQWEST_CLIENT
             .get(&url)
             .send()
@@ -188,6 +189,56 @@ impl Rustup {
         Ok(self.rustup_home.join("toolchains").join(installed_name))
     }
 
+    /// Add rustup `components` and `targets` to the toolchain installed at `toolchain_dir`.
+    ///
+    /// The toolchain may belong to prek's rustup home or to the system one, so rustup runs
+    /// against the home containing `toolchain_dir`.
+    pub(crate) async fn add_to_toolchain(
+        &self,
+        toolchain_dir: &Path,
+        components: &[&str],
+        targets: &[&str],
+    ) -> Result<()> {
+        let (Some(name), Some(rustup_home)) = (
+            toolchain_dir.file_name(),
+            toolchain_dir.parent().and_then(Path::parent),
+        ) else {
+            anyhow::bail!(
+                "Toolchain `{}` is not managed by rustup",
+                toolchain_dir.display()
+            );
+        };
+
+        fs_err::tokio::create_dir_all(&self.rustup_home).await?;
+        let _lock = LockedFile::acquire(self.rustup_home.join(".lock"), "rustup").await?;
+
+        for (kind, items) in [("component", components), ("target", targets)] {
+            if items.is_empty() {
+                continue;
+            }
+            Cmd::new(&self.bin, format!("rustup {kind} add"))
+                .env(EnvVars::RUSTUP_HOME, rustup_home)
+                .env(EnvVars::RUSTUP_AUTO_INSTALL, "0")
+                .arg(kind)
+                .arg("add")
+                .arg("--toolchain")
+                .arg(name)
+                .args(items)
+                .check(true)
+                .output()
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to add rust {kind} `{}` to toolchain `{}`",
+                        items.join(", "),
+                        name.display()
+                    )
+                })?;
+        }
+
+        Ok(())
+    }
+
     /// List installed toolchains managed by prek.
     pub(crate) async fn list_installed_toolchains(&self) -> Result<Vec<ToolchainInfo>> {
         let output = Cmd::new(&self.bin, "rustup list toolchains")
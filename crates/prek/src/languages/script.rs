@@ -5,12 +5,12 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::cli::reporter::HookInstallReporter;
-use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
-use crate::languages::LanguageImpl;
+use crate::languages::docker::Docker;
+use crate::languages::{LanguageImpl, command_args};
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::{HookRunContext, run_by_batch};
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -26,7 +26,7 @@ impl LanguageImpl for Script {
         Ok(InstalledHook::NoNeedInstall(hook))
     }
 
-    async fn check_health(&self, _info: &InstallInfo) -> Result<()> {
+    async fn check_health(&self, _info: &InstallInfo, _store: &Store) -> Result<()> {
         Ok(())
     }
 
@@ -35,13 +35,21 @@ impl LanguageImpl for Script {
         hook: &InstalledHook,
         filenames: &[&Path],
         store: &Store,
-        reporter: &HookRunReporter,
+        context: &HookRunContext,
     ) -> Result<(i32, Vec<u8>)> {
         // For `language: script`, the `entry[0]` is a script path.
         // For remote hooks, the path is relative to the repo root.
         // For local hooks, the path is relative to the current working directory.
 
-        let progress = reporter.on_run_start(hook, filenames.len());
+        if let Some(image) = &hook.container_image {
+            // Only local script hooks can run in a container, so the script is relative to
+            // the hook's working directory, which is mounted at `/src`.
+            let mut entry = hook.entry.expect_direct().split()?;
+            entry[0] = format!("/src/{}", entry[0].trim_start_matches("./"));
+            return Docker::run_in_container(hook, image, &entry, filenames, context).await;
+        }
+
+        let progress = context.reporter.on_run_start(hook, filenames.len());
 
         let repo_path = hook.repo_path().unwrap_or(hook.work_dir());
         let entry = hook.entry.resolve_script(repo_path, None, store)?;
@@ -49,23 +57,23 @@ impl LanguageImpl for Script {
         let run = async |batch: &[&Path]| {
             let mut output = Cmd::new(&entry[0], "run script command")
                 .current_dir(hook.work_dir())
-                .envs(&hook.env)
-                .args(&entry[1..])
-                .args(&hook.args)
-                .args(batch)
+                .envs(hook.env_vars(&[]))
+                .args(command_args(&entry[1..], &hook.args, batch))
                 .check(false)
                 .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
+                .pty_output_with_sink(context.reporter.output_sink(progress))
                 .await?;
 
-            reporter.on_run_progress(progress, batch.len() as u64);
+            context
+                .reporter
+                .on_run_progress(progress, batch.len() as u64);
 
             output.stdout.extend(output.stderr);
             let code = output.status.code().unwrap_or(1);
             anyhow::Ok((code, output.stdout))
         };
 
-        let results = run_by_batch(hook, filenames, entry.argv(), run).await?;
+        let results = run_by_batch(hook, filenames, entry.argv(), context, run).await?;
 
         // Collect results
         let mut combined_status = 0;
@@ -76,7 +84,7 @@ impl LanguageImpl for Script {
             combined_output.extend(output);
         }
 
-        reporter.on_run_complete(progress);
+        context.reporter.on_run_complete(progress);
 
         Ok((combined_status, combined_output))
     }
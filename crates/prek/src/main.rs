@@ -33,6 +33,7 @@ mod archive;
 mod cleanup;
 mod cli;
 mod config;
+mod env_expand;
 mod fs;
 mod git;
 mod hook;
@@ -52,6 +53,7 @@ mod run;
 mod schema;
 mod settings;
 mod store;
+mod suggest;
 mod version;
 mod warnings;
 mod workspace;
@@ -149,13 +151,36 @@ fn setup_logging(level: Level, log_file: LogFile, store: &Store) -> Result<()> {
     Ok(())
 }
 
+/// Whether the command operates on the git repository containing the current directory.
+///
+/// `None` is the implicit `prek run`.
+fn requires_git_repo(command: Option<&Command>) -> bool {
+    matches!(
+        command,
+        None | Some(
+            Command::Install(_)
+                | Command::PrepareHooks(_)
+                | Command::Warmup(_)
+                | Command::Doctor(_)
+                | Command::Run(_)
+                | Command::List(_)
+                | Command::Uninstall(_)
+                | Command::AutoUpdate(_)
+                | Command::TryRepo(_)
+                | Command::HookImpl(_)
+        )
+    )
+}
+
 async fn run(cli: Cli) -> Result<ExitStatus> {
     // Enabled ANSI colors on Windows.
     let _ = anstyle_query::windows::enable_ansi_colors();
 
     ColorChoice::write_global(cli.globals.color.into());
 
-    let store = Store::from_settings()?;
+    let store = Store::from_settings()?.with_offline(
+        cli.globals.offline || EnvVars::var_as_bool(EnvVars::PREK_OFFLINE).unwrap_or(false),
+    );
     let log_file = LogFile::from_args(cli.globals.log_file.clone(), cli.globals.no_log_file);
     setup_logging(
         match cli.globals.verbose {
@@ -214,8 +239,24 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
     }
 
     if let Some(dir) = cli.globals.cd.as_ref() {
+        if !dir.is_dir() {
+            anyhow::bail!("--cd target is not a directory: {}", dir.display());
+        }
         debug!("Changing current directory to: `{}`", dir.display());
         std::env::set_current_dir(dir)?;
+
+        // Fail early with a clear message instead of letting workspace discovery
+        // report a confusing git error later. `--show-settings` only prints the arguments.
+        if !cli.globals.show_settings
+            && requires_git_repo(cli.command.as_ref())
+            && let Err(err) = git::GIT_ROOT.as_ref()
+        {
+            debug!("Failed to find git root after `--cd`: {err}");
+            anyhow::bail!(
+                "--cd target is not inside a git repository: {}",
+                dir.display()
+            );
+        }
     }
 
     debug!("Args: {:?}", std::env::args().collect::<Vec<_>>());
@@ -248,21 +289,55 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.includes,
                 args.skips,
                 args.hook_types,
+                args.auto_hook_types,
                 args.prepare_hooks,
                 args.overwrite,
                 args.allow_missing_config,
+                args.legacy_last,
                 cli.globals.refresh,
                 printer,
                 args.git_dir.as_deref(),
+                args.no_hooks_path,
             )
             .await
         }
         Command::PrepareHooks(args) => {
             cli::prepare_hooks(
+                &store,
+                cli.globals.config,
+                args.hooks.includes,
+                args.hooks.skips,
+                args.hooks.frozen,
+                args.dry_run,
+                cli.globals.refresh,
+                printer,
+            )
+            .await
+        }
+        Command::Warmup(args) => {
+            show_settings!(args);
+
+            cli::warmup(
                 &store,
                 cli.globals.config,
                 args.includes,
                 args.skips,
+                args.frozen,
+                cli.globals.refresh,
+                printer,
+            )
+            .await
+        }
+        Command::Doctor(args) => {
+            show_settings!(args);
+
+            cli::doctor(
+                &store,
+                cli.globals.config,
+                args.hooks.includes,
+                args.hooks.skips,
+                args.hooks.frozen,
+                args.fix,
                 cli.globals.refresh,
                 printer,
             )
@@ -287,24 +362,47 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
 
             cli::run(
                 &store,
-                cli.globals.config,
-                options.includes,
-                options.skips,
-                args.groups,
-                args.no_groups,
-                args.stage,
-                options.from_ref,
-                options.to_ref,
-                options.all_files,
-                options.files,
-                options.directory,
-                options.last_commit,
-                options.show_diff_on_failure,
-                flag(options.fail_fast, options.no_fail_fast),
-                options.dry_run,
-                cli.globals.refresh,
-                options.extra,
-                cli.globals.verbose > 0,
+                cli::RunSettings {
+                    config: cli.globals.config,
+                    includes: options.includes,
+                    skips: options.skips,
+                    groups: args.groups,
+                    no_groups: args.no_groups,
+                    hook_stage: args.stage,
+                    from_ref: options.from_ref,
+                    to_ref: options.to_ref,
+                    all_files: options.all_files,
+                    files: options.files,
+                    files_from_stdin: args.files_from_stdin,
+                    directories: options.directory,
+                    last_commit: options.last_commit,
+                    since: options.since,
+                    include_deleted: options.include_deleted,
+                    include_untracked: options.include_untracked,
+                    show_diff_on_failure: options.show_diff_on_failure,
+                    show_diff: options.show_diff,
+                    fail_fast: flag(options.fail_fast, options.no_fail_fast),
+                    keep_going: options.keep_going,
+                    dry_run: options.dry_run,
+                    explain: options.explain,
+                    allow_missing_config: args.allow_missing_config,
+                    relativize_paths: args.relativize_paths,
+                    fail_on_warnings: args.fail_on_warnings,
+                    output_format: args.output_format,
+                    notify: args.notify,
+                    retries: args.retries,
+                    timeout: args.timeout,
+                    language_concurrency: args.language_concurrency,
+                    parallel_projects: args.parallel_projects,
+                    report_junit: args.report_junit,
+                    modified_files: args.modified_files,
+                    timings: args.timings,
+                    timings_file: args.timings_file,
+                    no_cache: args.no_cache,
+                    refresh: cli.globals.refresh,
+                    extra_args: options.extra,
+                    verbose: cli.globals.verbose > 0,
+                },
                 printer,
             )
             .await
@@ -339,6 +437,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.hook_type,
                 args.hook_dir,
                 args.skip_on_missing_config,
+                args.legacy_last,
                 args.script_version,
                 args.args,
                 printer,
@@ -369,14 +468,32 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         Command::ValidateConfig(args) => {
             show_settings!(args);
 
-            cli::validate_configs(args.configs, printer)
+            cli::validate_configs(args.configs, args.strict, printer)
         }
         Command::ValidateManifest(args) => {
             show_settings!(args);
 
             cli::validate_manifest(args.manifests, printer)
         }
+        Command::MigrateConfig(args) => {
+            show_settings!(args);
+
+            cli::migrate_config(
+                args.configs,
+                cli.globals.config.as_deref(),
+                args.dry_run,
+                printer,
+            )
+        }
         Command::SampleConfig(args) => cli::sample_config(args.file.into(), args.format, printer),
+        #[cfg(feature = "schemars")]
+        Command::ConfigSchema => cli::config_schema(printer),
+        #[cfg(not(feature = "schemars"))]
+        Command::ConfigSchema => {
+            anyhow::bail!(
+                "This build of prek does not include the configuration schema, use https://www.schemastore.org/prek.json instead"
+            );
+        }
         Command::AutoUpdate(args) => {
             let filesystem = FilesystemOptions::user()?;
             show_settings!(args);
@@ -406,6 +523,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             cli::try_repo(
+                &store,
                 cli.globals.config,
                 args.repo,
                 args.rev,
@@ -508,6 +626,9 @@ fn main() -> ExitCode {
     CompleteEnv::with_factory(Cli::command).complete();
 
     ctrlc::set_handler(move || {
+        // Hooks with a timeout run in their own process group, out of reach of the terminal's
+        // Ctrl-C.
+        process::kill_hook_process_groups();
         cleanup();
 
         #[allow(clippy::exit, clippy::cast_possible_wrap)]
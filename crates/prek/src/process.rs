@@ -31,14 +31,19 @@ use std::path::Path;
 use std::process::Output;
 use std::process::{CommandArgs, CommandEnvs, ExitStatus, Stdio};
 use std::sync::LazyLock;
+#[cfg(unix)]
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
 
 use owo_colors::OwoColorize;
 use prek_consts::env_vars::EnvVars;
 use thiserror::Error;
 use tokio::io::AsyncReadExt;
+use tokio::process::Child;
 use tracing::trace;
 
 use crate::git::GIT;
+use crate::run::HookDeadline;
 
 static LOG_TRUNCATE_LIMIT: LazyLock<usize> = LazyLock::new(|| {
     EnvVars::var(EnvVars::PREK_LOG_TRUNCATE_LIMIT)
@@ -48,6 +53,24 @@ static LOG_TRUNCATE_LIMIT: LazyLock<usize> = LazyLock::new(|| {
         .unwrap_or(120)
 });
 
+/// Color environment passed to hook processes, following prek's own color choice for stdout,
+/// where hook output ends up.
+///
+/// A `NO_COLOR` set by the user is passed through untouched and never overridden.
+static HOOK_COLOR_ENV: LazyLock<&'static [(&'static str, &'static str)]> = LazyLock::new(|| {
+    if EnvVars::var_os(EnvVars::NO_COLOR).is_some_and(|value| !value.is_empty()) {
+        return &[];
+    }
+    match anstream::Stdout::choice(&std::io::stdout()) {
+        anstream::ColorChoice::Always | anstream::ColorChoice::AlwaysAnsi => {
+            &[(EnvVars::FORCE_COLOR, "1"), (EnvVars::CLICOLOR_FORCE, "1")]
+        }
+        anstream::ColorChoice::Never => &[(EnvVars::NO_COLOR, "1")],
+        // We just asked anstream for a choice, that can't be auto
+        anstream::ColorChoice::Auto => unreachable!(),
+    }
+});
+
 /// An error from executing a Command
 #[derive(Debug, Error)]
 pub enum Error {
@@ -62,6 +85,14 @@ pub enum Error {
     },
     #[error("Command `{summary}` exited with an error:\n{error}")]
     Status { summary: String, error: StatusError },
+    /// The command was killed for running past the deadline of its hook
+    #[error("Command `{summary}` timed out after {}s", timeout.as_secs())]
+    TimedOut {
+        summary: String,
+        timeout: Duration,
+        /// Output captured before the command was killed
+        output: Vec<u8>,
+    },
     #[cfg(not(windows))]
     #[error("Failed to open pty")]
     Pty(#[from] prek_pty::Error),
@@ -130,6 +161,92 @@ fn write_output_chunk(output: &mut Vec<u8>, sink: &mut impl OutputSink, chunk: &
     sink.write_chunk(chunk);
 }
 
+/// Await `future`, giving up with the elapsed timeout once `deadline` has passed.
+async fn until_deadline<T>(
+    deadline: Option<HookDeadline>,
+    future: impl Future<Output = T>,
+) -> Result<T, Duration> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.at, future)
+            .await
+            .map_err(|_| deadline.timeout),
+        None => Ok(future.await),
+    }
+}
+
+/// Whether hook processes with a deadline are started in their own process group.
+///
+/// A process outside the terminal's foreground process group is stopped by `SIGTTIN` or
+/// `SIGTTOU` when it reads from the controlling terminal or changes its settings, so a hook
+/// prompting on `/dev/tty` would hang until its timeout. When prek has a controlling terminal,
+/// such hooks stay in prek's process group instead, and a timeout only kills the hook process,
+/// not the processes it started.
+#[cfg(unix)]
+static OWN_PROCESS_GROUPS: LazyLock<bool> =
+    LazyLock::new(|| fs_err::File::open("/dev/tty").is_err());
+
+/// Process groups of running hook processes with a deadline.
+///
+/// They are not in prek's process group, so they don't receive the terminal's Ctrl-C.
+#[cfg(unix)]
+static HOOK_PROCESS_GROUPS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// Keeps a process group in [`HOOK_PROCESS_GROUPS`] while the process runs.
+#[cfg(unix)]
+struct ProcessGroupGuard(Option<u32>);
+
+#[cfg(unix)]
+impl ProcessGroupGuard {
+    fn register(child: &Child) -> Self {
+        let pgid = child.id();
+        if let Some(pgid) = pgid {
+            HOOK_PROCESS_GROUPS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(pgid);
+        }
+        Self(pgid)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for ProcessGroupGuard {
+    fn drop(&mut self) {
+        if let Some(pgid) = self.0 {
+            HOOK_PROCESS_GROUPS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retain(|group| *group != pgid);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pgid: u32) {
+    let Some(pid) = i32::try_from(pgid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+    else {
+        return;
+    };
+    if let Err(err) = rustix::process::kill_process_group(pid, rustix::process::Signal::KILL) {
+        trace!("Failed to kill process group {pgid}: {err}");
+    }
+}
+
+/// Kill the hook processes running in their own process group, so they don't outlive an
+/// interrupted prek.
+pub(crate) fn kill_hook_process_groups() {
+    #[cfg(unix)]
+    for pgid in HOOK_PROCESS_GROUPS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+    {
+        kill_process_group(*pgid);
+    }
+}
+
 /// Constructors
 impl Cmd {
     /// Create a new Command with an additional "summary" of what this is trying to do
@@ -203,16 +320,90 @@ impl Cmd {
     }
 
     /// Like [`Cmd::output`], but streams stdout and stderr chunks into `sink` as
-    /// they are read. The sink receives both pipes in arrival order; the returned
-    /// output keeps stdout and stderr separated.
+    /// they are read.
+    ///
+    /// Both streams are captured together in the order the command wrote them, as a
+    /// terminal would show them: the returned output holds them in `stdout`, and `stderr`
+    /// is empty.
     pub(crate) async fn output_with_sink<S: OutputSink>(
         &mut self,
         mut sink: S,
     ) -> Result<Output, Error> {
         self.log_command();
-        self.inner.stdin(Stdio::null());
+        self.inner.stdin(self.hook_stdin()?);
+        self.apply_color_env();
+
+        let (status, output) = self.capture_interleaved(&mut sink).await?;
+        let output = Output {
+            status,
+            stdout: output,
+            stderr: Vec::new(),
+        };
+
+        self.maybe_check_output(&output)?;
+        Ok(output)
+    }
+
+    /// Run the command with stdout and stderr sharing a single pipe, which preserves the
+    /// order of writes across both streams.
+    #[cfg(unix)]
+    async fn capture_interleaved<S: OutputSink>(
+        &mut self,
+        sink: &mut S,
+    ) -> Result<(ExitStatus, Vec<u8>), Error> {
+        let deadline = self.hook_deadline();
+        let exec_error = |cause| Error::Exec {
+            summary: self.summary.clone(),
+            cause,
+        };
+
+        let (reader, writer) = std::io::pipe().map_err(exec_error)?;
+        let stderr_writer = writer.try_clone().map_err(exec_error)?;
+        self.inner.stdout(writer);
+        self.inner.stderr(stderr_writer);
+
+        let child = self.inner.spawn();
+        // Drop our copies of the write end, so EOF means the child side has closed.
+        self.inner.stdout(Stdio::null());
+        self.inner.stderr(Stdio::null());
+        let mut child = child.map_err(exec_error)?;
+        let _group = deadline
+            .filter(|_| *OWN_PROCESS_GROUPS)
+            .map(|_| ProcessGroupGuard::register(&child));
+
+        let mut reader =
+            tokio::net::unix::pipe::Receiver::from_owned_fd(std::os::fd::OwnedFd::from(reader))
+                .map_err(exec_error)?;
+        let mut buffer = [0u8; 4096];
+        let mut output = Vec::new();
+        let capture = async {
+            loop {
+                match reader.read(&mut buffer).await {
+                    Ok(0) => break,
+                    Ok(n) => write_output_chunk(&mut output, sink, &buffer[..n]),
+                    Err(cause) => return Err(exec_error(cause)),
+                }
+            }
+
+            // EOF on the pipe is the point where output capture is complete.
+            // Waiting earlier must not make us return before trailing pipe bytes are read.
+            child.wait().await.map_err(exec_error)
+        };
+        match until_deadline(deadline, capture).await {
+            Ok(status) => Ok((status?, output)),
+            Err(timeout) => Err(self.kill_timed_out(&mut child, timeout, output).await),
+        }
+    }
+
+    /// Run the command with separate stdout and stderr pipes, merging chunks as they arrive.
+    #[cfg(not(unix))]
+    async fn capture_interleaved<S: OutputSink>(
+        &mut self,
+        sink: &mut S,
+    ) -> Result<(ExitStatus, Vec<u8>), Error> {
         self.inner.stdout(Stdio::piped());
         self.inner.stderr(Stdio::piped());
+        let deadline = self.hook_deadline();
 
         let mut child = self.inner.spawn().map_err(|cause| Error::Exec {
             summary: self.summary.clone(),
@@ -231,52 +422,50 @@ impl Cmd {
         let mut stderr_done = false;
         let mut stdout_buffer = [0u8; 4096];
         let mut stderr_buffer = [0u8; 4096];
-        let mut stdout_output = Vec::new();
-        let mut stderr_output = Vec::new();
-
-        while !stdout_done || !stderr_done {
-            tokio::select! {
-                result = stdout.read(&mut stdout_buffer), if !stdout_done => {
-                    match result {
-                        Ok(0) => stdout_done = true,
-                        Ok(n) => write_output_chunk(&mut stdout_output, &mut sink, &stdout_buffer[..n]),
-                        Err(cause) => {
-                            return Err(Error::Exec {
-                                summary: self.summary.clone(),
-                                cause,
-                            });
+        let mut output = Vec::new();
+
+        let capture = async {
+            while !stdout_done || !stderr_done {
+                tokio::select! {
+                    biased;
+                    result = stdout.read(&mut stdout_buffer), if !stdout_done => {
+                        match result {
+                            Ok(0) => stdout_done = true,
+                            Ok(n) => write_output_chunk(&mut output, sink, &stdout_buffer[..n]),
+                            Err(cause) => {
+                                return Err(Error::Exec {
+                                    summary: self.summary.clone(),
+                                    cause,
+                                });
+                            }
                         }
                     }
-                }
-                result = stderr.read(&mut stderr_buffer), if !stderr_done => {
-                    match result {
-                        Ok(0) => stderr_done = true,
-                        Ok(n) => write_output_chunk(&mut stderr_output, &mut sink, &stderr_buffer[..n]),
-                        Err(cause) => {
-                            return Err(Error::Exec {
-                                summary: self.summary.clone(),
-                                cause,
-                            });
+                    result = stderr.read(&mut stderr_buffer), if !stderr_done => {
+                        match result {
+                            Ok(0) => stderr_done = true,
+                            Ok(n) => write_output_chunk(&mut output, sink, &stderr_buffer[..n]),
+                            Err(cause) => {
+                                return Err(Error::Exec {
+                                    summary: self.summary.clone(),
+                                    cause,
+                                });
+                            }
                         }
                     }
                 }
             }
-        }
 
-        // For regular pipes, EOF on both streams is the point where output capture is complete.
-        // Waiting earlier must not make us return before trailing pipe bytes are read.
-        let status = child.wait().await.map_err(|cause| Error::Exec {
-            summary: self.summary.clone(),
-            cause,
-        })?;
-        let output = Output {
-            status,
-            stdout: stdout_output,
-            stderr: stderr_output,
+            // For regular pipes, EOF on both streams is the point where output capture is complete.
+            // Waiting earlier must not make us return before trailing pipe bytes are read.
+            child.wait().await.map_err(|cause| Error::Exec {
+                summary: self.summary.clone(),
+                cause,
+            })
         };
-
-        self.maybe_check_output(&output)?;
-        Ok(output)
+        match until_deadline(deadline, capture).await {
+            Ok(status) => Ok((status?, output)),
+            Err(timeout) => Err(self.kill_timed_out(&mut child, timeout, output).await),
+        }
     }
 
     #[cfg(windows)]
@@ -305,7 +494,8 @@ impl Cmd {
         let (mut pty, pts) = prek_pty::open()?;
         let (_, stdout, stderr) = pts.setup_subprocess()?;
 
-        self.inner.stdin(Stdio::null());
+        self.inner.stdin(self.hook_stdin()?);
+        self.apply_color_env();
         self.inner.stdout(stdout);
         self.inner.stderr(stderr);
 
@@ -323,8 +513,14 @@ impl Cmd {
         //
         // We intentionally do not make the child a session leader/foreground process group here.
         // When we did, termenv detected it as foreground and ran OSC probes, which then hung.
+        // A hook with a timeout may get its own process group, but it is never the foreground one.
 
+        let deadline = self.hook_deadline();
         let mut child = self.spawn()?;
+        #[cfg(unix)]
+        let _group = deadline
+            .filter(|_| *OWN_PROCESS_GROUPS)
+            .map(|_| ProcessGroupGuard::register(&child));
         // The parent must not keep the slave side open; otherwise EOF no longer
         // represents only the child-side descriptors closing.
         drop(pts);
@@ -332,45 +528,51 @@ impl Cmd {
         let mut buffer = [0u8; 4096];
         let mut output = Vec::new();
 
-        let status = loop {
-            tokio::select! {
-                read_result = pty.read(&mut buffer) => {
-                    match read_result {
-                        Ok(0) => break child.wait().await.map_err(|cause| Error::Exec {
-                            summary: self.summary.clone(),
-                            cause,
-                        })?,
-                        Ok(n) => write_output_chunk(&mut output, &mut sink, &buffer[..n]),
-                        // Linux reports PTY master EOF as EIO after all slave handles close.
-                        Err(err) if err.raw_os_error() == Some(libc::EIO) => {
-                            break child.wait().await.map_err(|cause| Error::Exec {
+        let capture = async {
+            Ok(loop {
+                tokio::select! {
+                    read_result = pty.read(&mut buffer) => {
+                        match read_result {
+                            Ok(0) => break child.wait().await.map_err(|cause| Error::Exec {
                                 summary: self.summary.clone(),
                                 cause,
-                            })?;
-                        }
-                        Err(err) => return Err(Error::PtySetup(err)),
-                    }
-                }
-                status = child.wait() => {
-                    let status = status.map_err(|cause| Error::Exec {
-                        summary: self.summary.clone(),
-                        cause,
-                    })?;
-                    // Child exit can be observed before the PTY read future is woken. Drain any
-                    // bytes already available so fast commands do not lose their final output.
-                    loop {
-                        match pty.try_read(&mut buffer) {
-                            Ok(0) => break,
+                            })?,
                             Ok(n) => write_output_chunk(&mut output, &mut sink, &buffer[..n]),
-                            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
                             // Linux reports PTY master EOF as EIO after all slave handles close.
-                            Err(err) if err.raw_os_error() == Some(libc::EIO) => break,
+                            Err(err) if err.raw_os_error() == Some(libc::EIO) => {
+                                break child.wait().await.map_err(|cause| Error::Exec {
+                                    summary: self.summary.clone(),
+                                    cause,
+                                })?;
+                            }
                             Err(err) => return Err(Error::PtySetup(err)),
                         }
                     }
-                    break status;
+                    status = child.wait() => {
+                        let status = status.map_err(|cause| Error::Exec {
+                            summary: self.summary.clone(),
+                            cause,
+                        })?;
+                        // Child exit can be observed before the PTY read future is woken. Drain any
+                        // bytes already available so fast commands do not lose their final output.
+                        loop {
+                            match pty.try_read(&mut buffer) {
+                                Ok(0) => break,
+                                Ok(n) => write_output_chunk(&mut output, &mut sink, &buffer[..n]),
+                                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
+                                // Linux reports PTY master EOF as EIO after all slave handles close.
+                                Err(err) if err.raw_os_error() == Some(libc::EIO) => break,
+                                Err(err) => return Err(Error::PtySetup(err)),
+                            }
+                        }
+                        break status;
+                    }
                 }
-            }
+            })
+        };
+        let status = match until_deadline(deadline, capture).await {
+            Ok(status) => status?,
+            Err(timeout) => return Err(self.kill_timed_out(&mut child, timeout, output).await),
         };
 
         child.stdin.take();
@@ -553,6 +755,65 @@ impl Cmd {
         Ok(())
     }
 
+    /// Stdin for a captured hook process: the file from [`crate::run::HOOK_STDIN`] if set,
+    /// otherwise null.
+    fn hook_stdin(&self) -> Result<Stdio, Error> {
+        let Ok(path) = crate::run::HOOK_STDIN.try_with(Clone::clone) else {
+            return Ok(Stdio::null());
+        };
+        let file = std::fs::File::open(&path).map_err(|cause| Error::Exec {
+            summary: format!("{} (reading stdin from `{}`)", self.summary, path.display()),
+            cause,
+        })?;
+        Ok(Stdio::from(file))
+    }
+
+    /// The deadline of the hook this process runs for, from [`crate::run::HOOK_DEADLINE`].
+    ///
+    /// With a deadline, the process is started in its own process group on Unix, so that
+    /// everything it spawns can be killed along with it, unless prek has a controlling terminal
+    /// (see [`OWN_PROCESS_GROUPS`]).
+    fn hook_deadline(&mut self) -> Option<HookDeadline> {
+        let deadline = crate::run::HOOK_DEADLINE
+            .try_with(|deadline| *deadline)
+            .ok()?;
+        #[cfg(unix)]
+        if *OWN_PROCESS_GROUPS {
+            self.inner.process_group(0);
+        }
+        Some(deadline)
+    }
+
+    /// Kill a hook process that ran past its deadline, with its process group on Unix.
+    async fn kill_timed_out(&self, child: &mut Child, timeout: Duration, output: Vec<u8>) -> Error {
+        #[cfg(unix)]
+        if *OWN_PROCESS_GROUPS && let Some(pgid) = child.id() {
+            kill_process_group(pgid);
+        }
+        if let Err(err) = child.kill().await {
+            trace!("Failed to kill `{}`: {err}", self.summary);
+        }
+        Error::TimedOut {
+            summary: self.summary.clone(),
+            timeout,
+            output,
+        }
+    }
+
+    /// Pass prek's color choice on to the process, unless its environment already sets
+    /// one of the variables.
+    fn apply_color_env(&mut self) {
+        let configured = self.inner.as_std().get_envs().any(|(key, _)| {
+            key == EnvVars::NO_COLOR
+                || key == EnvVars::FORCE_COLOR
+                || key == EnvVars::CLICOLOR_FORCE
+        });
+        if configured {
+            return;
+        }
+        self.inner.envs(HOOK_COLOR_ENV.iter().copied());
+    }
+
     /// Log the current Command using the method specified by [`Cmd::log`][]
     /// (defaults to [`tracing::info!`][]).
     pub fn log_command(&self) {
@@ -619,6 +880,7 @@ impl Display for Cmd {
 
 #[cfg(all(test, not(windows)))]
 mod tests {
+    use std::fmt::Write;
     use std::sync::{Arc, Mutex};
 
     use super::{Cmd, OutputSink};
@@ -648,13 +910,31 @@ mod tests {
             .expect("piped command should succeed");
 
         assert!(output.status.success());
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        assert!(stdout.contains("OUT\n"));
-        assert!(stderr.contains("ERR\n"));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "OUT\nERR\n");
+        assert!(output.stderr.is_empty());
         assert_ne!(*chunks.lock().unwrap(), 0);
     }
 
+    #[tokio::test]
+    async fn output_with_sink_preserves_stdout_and_stderr_order() {
+        let mut script = String::new();
+        let mut expected = String::new();
+        for i in 1..=50 {
+            write!(script, "echo out{i}; echo err{i} >&2;").unwrap();
+            write!(expected, "out{i}\nerr{i}\n").unwrap();
+        }
+        let output = Cmd::new("/bin/sh", "interleaved output test")
+            .arg("-c")
+            .arg(script)
+            .check(false)
+            .output_with_sink(RecordingSink::default())
+            .await
+            .expect("piped command should succeed");
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    }
+
     #[tokio::test]
     async fn pty_output_captures_trailing_output_after_fast_exit() {
         for _ in 0..20 {
@@ -1,15 +1,18 @@
 use std::cmp::max;
 use std::ffi::OsStr;
-use std::path::Path;
-use std::sync::LazyLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
 
 use anstream::ColorChoice;
 use futures::{StreamExt, TryStreamExt};
+use mea::semaphore::Semaphore;
 use prek_consts::env_vars::EnvVars;
 use rustc_hash::FxHashMap;
 use tracing::trace;
 
-use crate::config::PassFilenames;
+use crate::cli::run::HookRunReporter;
+use crate::config::{Language, PassFilenames};
 use crate::hook::Hook;
 use crate::warn_user;
 
@@ -21,6 +24,36 @@ pub(crate) static USE_COLOR: LazyLock<bool> =
         ColorChoice::Auto => unreachable!(),
     });
 
+tokio::task_local! {
+    /// File whose content is fed on stdin to hook processes spawned in this scope.
+    ///
+    /// Set per invocation for hooks with `stdin_from: files`; hook processes get a null
+    /// stdin outside of it.
+    pub(crate) static HOOK_STDIN: PathBuf;
+
+    /// Deadline for hook processes spawned in this scope, after which they are killed.
+    ///
+    /// Set around the whole run of a hook with a `timeout`, so all its invocations share it.
+    pub(crate) static HOOK_DEADLINE: HookDeadline;
+}
+
+/// When a hook that has been given a `timeout` must have finished.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HookDeadline {
+    pub(crate) timeout: Duration,
+    pub(crate) at: tokio::time::Instant,
+}
+
+impl HookDeadline {
+    /// A deadline `timeout` from now.
+    pub(crate) fn after(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            at: tokio::time::Instant::now() + timeout,
+        }
+    }
+}
+
 fn resolve_concurrency(no_concurrency: bool, max_concurrency: Option<&str>, cpu: usize) -> usize {
     if no_concurrency {
         return 1;
@@ -55,6 +88,128 @@ fn target_concurrency(serial: bool) -> usize {
     if serial { 1 } else { *CONCURRENCY }
 }
 
+/// The built-in cap for languages that start a heavyweight runtime in every hook process.
+fn default_language_concurrency(language: Language, cpu: usize) -> Option<usize> {
+    match language {
+        Language::Bun
+        | Language::Conda
+        | Language::Coursier
+        | Language::Deno
+        | Language::Docker
+        | Language::DockerImage
+        | Language::Dotnet
+        | Language::Julia
+        | Language::Node
+        | Language::Python
+        | Language::R
+        | Language::Ruby => Some((cpu / 2).max(1)),
+        _ => None,
+    }
+}
+
+/// Per-language caps on concurrently running hook processes for a run.
+#[derive(Default)]
+pub(crate) struct LanguageLimits {
+    /// Overrides of the built-in caps, from `default_language_concurrency` in the config and
+    /// `prek run --language-concurrency`.
+    limits: FxHashMap<Language, usize>,
+    /// Semaphores enforcing the caps, shared by all hooks of a language.
+    semaphores: Mutex<FxHashMap<Language, Arc<Semaphore>>>,
+}
+
+impl LanguageLimits {
+    pub(crate) fn new(limits: FxHashMap<Language, usize>) -> Self {
+        Self {
+            limits,
+            semaphores: Mutex::default(),
+        }
+    }
+
+    /// The maximum number of processes hooks of `language` may run at the same time.
+    pub(crate) fn concurrency(&self, language: Language) -> usize {
+        self.limits
+            .get(&language)
+            .copied()
+            .or_else(|| default_language_concurrency(language, *CONCURRENCY))
+            .map_or(*CONCURRENCY, |limit| limit.clamp(1, *CONCURRENCY))
+    }
+
+    /// The semaphore limiting processes of `language`, or `None` if only `CONCURRENCY` applies.
+    fn semaphore(&self, language: Language) -> Option<Arc<Semaphore>> {
+        let limit = self.concurrency(language);
+        if limit >= *CONCURRENCY {
+            return None;
+        }
+        let mut semaphores = self.semaphores.lock().unwrap();
+        Some(Arc::clone(
+            semaphores
+                .entry(language)
+                .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+        ))
+    }
+}
+
+/// The settings of the current run that hooks need while running.
+pub(crate) struct HookRunContext {
+    pub(crate) reporter: HookRunReporter,
+    /// Builtin fixers print a diff instead of modifying files, see `prek run --show-diff`.
+    pub(crate) show_diff: bool,
+    /// Whether `cacheable` hooks may skip files that passed before, see `prek run --no-cache`.
+    pub(crate) result_cache: bool,
+    pub(crate) language_limits: LanguageLimits,
+    pub(crate) findings: Findings,
+}
+
+/// A problem a builtin hook found at a known line of a file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Finding {
+    /// The file, as passed to the hook.
+    pub(crate) path: PathBuf,
+    pub(crate) line: usize,
+    /// The byte offset in the line, printed as is like Python's `ast` does.
+    pub(crate) col: Option<usize>,
+    pub(crate) message: String,
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)?;
+        if let Some(col) = self.col {
+            write!(f, ":{col}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Findings of builtin hooks, collected per hook for `prek run --output-format=github`.
+#[derive(Default)]
+pub(crate) struct Findings {
+    findings: Mutex<FxHashMap<(usize, usize), Vec<Finding>>>,
+}
+
+impl Findings {
+    fn key(hook: &Hook) -> (usize, usize) {
+        (hook.project().idx(), hook.idx)
+    }
+
+    pub(crate) fn report(&self, hook: &Hook, findings: impl IntoIterator<Item = Finding>) {
+        let mut all = self.findings.lock().unwrap();
+        all.entry(Self::key(hook)).or_default().extend(findings);
+    }
+
+    /// Take the findings `hook` reported since the last call, ordered by file and line.
+    pub(crate) fn take(&self, hook: &Hook) -> Vec<Finding> {
+        let mut findings = self
+            .findings
+            .lock()
+            .unwrap()
+            .remove(&Self::key(hook))
+            .unwrap_or_default();
+        findings.sort();
+        findings
+    }
+}
+
 /// Iterator that yields partitions of filenames that fit within the maximum command line length.
 struct Partitions<'a> {
     filenames: &'a [&'a Path],
@@ -183,7 +338,7 @@ impl<'a> Partitions<'a> {
             arg_max = 8192 - 1024;
         } else if cfg!(unix) {
             // We have to share space with the environment variables
-            arg_max -= env_size(&hook.env);
+            arg_max -= env_size(&hook.env_vars(&[]));
             // Account for the terminating NULL entry
             arg_max -= POINTER_SIZE_CONSERVATIVE;
         }
@@ -260,13 +415,18 @@ pub(crate) async fn run_by_batch<T, F>(
     hook: &Hook,
     filenames: &[&Path],
     entry: &[String],
+    context: &HookRunContext,
     run: F,
 ) -> anyhow::Result<Vec<T>>
 where
     F: for<'a> AsyncFn(&'a [&'a Path]) -> anyhow::Result<T>,
     T: Send + 'static,
 {
-    let concurrency = target_concurrency(hook.require_serial);
+    let limits = &context.language_limits;
+    let concurrency =
+        target_concurrency(hook.require_serial).min(limits.concurrency(hook.language));
+    // Batches of other hooks of the same language share the language's cap.
+    let semaphore = limits.semaphore(hook.language);
 
     // Split files into batches
     let partitions = Partitions::split(hook, entry, filenames, concurrency)?;
@@ -277,9 +437,16 @@ where
         hook.id,
     );
 
-    #[allow(clippy::redundant_closure)]
+    let run = &run;
+    let semaphore = semaphore.as_deref();
     let results: Vec<_> = futures::stream::iter(partitions)
-        .map(|batch| run(batch))
+        .map(|batch| async move {
+            let _permit = match semaphore {
+                Some(semaphore) => Some(semaphore.acquire(1).await),
+                None => None,
+            };
+            run(batch).await
+        })
         .buffered(concurrency)
         .try_collect()
         .await?;
@@ -1,6 +1,6 @@
 use crate::config::{
-    BuiltinHook, BuiltinRepo, FilePattern, LocalHook, LocalRepo, MetaHook, MetaRepo, PassFilenames,
-    RemoteHook, RemoteRepo, Repo, Stage, Stages,
+    BuiltinHook, BuiltinRepo, Config, FilePattern, LocalHook, LocalRepo, MetaHook, MetaRepo,
+    PassFilenames, RemoteHook, RemoteRepo, Repo, Stage, Stages,
 };
 use std::borrow::Cow;
 
@@ -166,7 +166,7 @@ impl schemars::JsonSchema for FilePattern {
                                     "items": {
                                         "type": "string",
                                     },
-                                    "description": "A list of glob patterns.",
+                                    "description": "A list of glob patterns. Patterns starting with `!` exclude paths matched by the others.",
                                 }
                             ]
                         }
@@ -399,10 +399,19 @@ impl schemars::JsonSchema for Repo {
     }
 }
 
+/// The JSON schema of the configuration file, as published in `prek.schema.json`.
+pub(crate) fn config_json_schema() -> String {
+    let settings = schemars::generate::SchemaSettings::draft07()
+        .with_transform(schemars::transform::RestrictFormats::default())
+        .with_transform(RemoveNullTypes);
+    let generator = schemars::SchemaGenerator::new(settings);
+    let schema = generator.into_root_schema_for::<Config>();
+    serde_json::to_string_pretty(&schema).unwrap() + "\n"
+}
+
 #[cfg(unix)]
 #[cfg(all(test, feature = "schemars"))]
 mod _gen {
-    use crate::config::Config;
     use anyhow::bail;
     use prek_consts::env_vars::EnvVars;
     use pretty_assertions::StrComparison;
@@ -421,15 +430,6 @@ mod _gen {
         DryRun,
     }
 
-    fn generate() -> String {
-        let settings = schemars::generate::SchemaSettings::draft07()
-            .with_transform(schemars::transform::RestrictFormats::default())
-            .with_transform(super::RemoveNullTypes);
-        let generator = schemars::SchemaGenerator::new(settings);
-        let schema = generator.into_root_schema_for::<Config>();
-        serde_json::to_string_pretty(&schema).unwrap() + "\n"
-    }
-
     #[test]
     fn generate_json_schema() -> anyhow::Result<()> {
         let mode = if EnvVars::is_set(EnvVars::PREK_GENERATE) {
@@ -438,7 +438,7 @@ mod _gen {
             Mode::Check
         };
 
-        let schema_string = generate();
+        let schema_string = super::config_json_schema();
         let filename = "prek.schema.json";
         let schema_path = PathBuf::from(ROOT_DIR).join(filename);
 
@@ -11,9 +11,10 @@ use seahash::SeaHasher;
 use thiserror::Error;
 use tracing::{debug, warn};
 
-use crate::config::{RemoteRepo, RemoteRepoKey};
+use crate::config::{RemoteRepo, RemoteRepoKey, is_mutable_rev};
 use crate::fs::{LockedFile, expand_tilde};
 use crate::git::{self, TerminalPrompt};
+use crate::http;
 use crate::run::CONCURRENCY;
 use crate::warn_user;
 use crate::workspace::{HookInitReporter, WorkspaceCache};
@@ -49,19 +50,56 @@ pub enum Error {
     },
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Offline(#[from] http::OfflineError),
 }
 
 pub(crate) const REPO_MARKER: &str = ".prek-repo.json";
 
+/// The contents of [`REPO_MARKER`], written once a remote repo is cloned into the store.
+#[derive(serde::Serialize)]
+struct RepoMarker<'a> {
+    #[serde(flatten)]
+    repo: &'a RemoteRepo,
+    /// The commit a mutable `rev` (a branch or moving tag) resolved to at clone time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+}
+
 /// A store for managing repos.
 #[derive(Debug)]
 pub struct Store {
     path: PathBuf,
+    /// Forbid network access, see `--offline`.
+    offline: bool,
 }
 
 impl Store {
     pub(crate) fn from_path(path: impl Into<PathBuf>) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            offline: false,
+        }
+    }
+
+    /// Fail anything that needs the network instead of fetching it.
+    #[must_use]
+    pub(crate) fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub(crate) fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Fail immediately in offline mode, instead of trying to fetch `resource` and timing out.
+    pub(crate) fn ensure_online(&self, resource: &str) -> Result<(), http::OfflineError> {
+        if self.offline {
+            Err(http::OfflineError(resource.to_string()))
+        } else {
+            Ok(())
+        }
     }
 
     /// Create a store from environment variables or default paths.
@@ -127,11 +165,27 @@ impl Store {
     ) -> Result<PathBuf, Error> {
         let target = self.repo_path(repo);
 
+        // Record the commit a mutable `rev` resolved to, so it is known which code this clone
+        // keeps running for as long as it lives in the store.
+        let commit = if is_mutable_rev(&repo.rev) {
+            let commit =
+                git::get_head_commit(temp.path())
+                    .await
+                    .map_err(|error| Error::CloneRepo {
+                        repo: repo.repo.clone(),
+                        error,
+                    })?;
+            debug!(%repo, %commit, "Resolved mutable repo rev to commit");
+            Some(commit)
+        } else {
+            None
+        };
+
         // TODO: add windows retry
         fs_err::tokio::remove_dir_all(&target).await.ok();
         fs_err::tokio::rename(temp, &target).await?;
 
-        let content = serde_json::to_string_pretty(&repo)?;
+        let content = serde_json::to_string_pretty(&RepoMarker { repo, commit })?;
         fs_err::tokio::write(target.join(REPO_MARKER), content).await?;
 
         Ok(target)
@@ -151,12 +205,12 @@ impl Store {
         let mut pending = Vec::new();
 
         for repo in repos {
-            let target = self.repo_path(repo);
-            if target.join(REPO_MARKER).try_exists()? {
+            if let Some(target) = self.cloned_repo_path(repo)? {
                 cloned.insert(repo.key(), target);
                 continue;
             }
 
+            self.ensure_online(&repo.repo)?;
             pending.push(PendingClone { repo });
         }
 
@@ -284,6 +338,12 @@ impl Store {
         self.repos_dir().join(Self::repo_key(repo))
     }
 
+    /// Returns the path to a remote repo if it has already been cloned into the store.
+    pub(crate) fn cloned_repo_path(&self, repo: &RemoteRepo) -> Result<Option<PathBuf>, Error> {
+        let target = self.repo_path(repo);
+        Ok(target.join(REPO_MARKER).try_exists()?.then_some(target))
+    }
+
     /// Returns the store key (directory name) for a remote repo.
     pub(crate) fn repo_key(repo: &RemoteRepo) -> String {
         let mut hasher = SeaHasher::new();
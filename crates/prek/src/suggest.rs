@@ -0,0 +1,116 @@
+//! "Did you mean" suggestions for mistyped names.
+
+use std::fmt::Write as _;
+
+use owo_colors::OwoColorize;
+
+/// Return the candidate closest to `name` by edit distance, if any is close enough to be a
+/// likely typo.
+pub(crate) fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    // Allow roughly one edit per three characters, and at least one.
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (levenshtein_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Build a hint for an unknown hook id: the closest known id, and the full list when there
+/// are only a few.
+///
+/// Returns an empty string when there is nothing useful to suggest, otherwise a string
+/// starting with a blank line, to be appended to an error message.
+pub(crate) fn hook_id_hint(id: &str, known: &[&str]) -> String {
+    const MAX_LISTED: usize = 5;
+
+    let mut hint = String::new();
+    if let Some(closest) = closest_match(id, known.iter().copied()) {
+        let _ = write!(
+            hint,
+            "\n\n{} did you mean `{}`?",
+            "hint:".yellow().bold(),
+            closest.cyan()
+        );
+    }
+    if !known.is_empty() && known.len() <= MAX_LISTED {
+        let listed = known
+            .iter()
+            .map(|id| format!("`{}`", id.cyan()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = write!(
+            hint,
+            "{}{} available hooks: {listed}",
+            if hint.is_empty() { "\n\n" } else { "\n" },
+            "hint:".yellow().bold(),
+        );
+    }
+    hint
+}
+
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    if a == b {
+        return 0;
+    }
+
+    let a_len = a.chars().count();
+    let b_len = b.chars().count();
+
+    if a_len == 0 {
+        return b_len;
+    }
+
+    if b_len == 0 {
+        return a_len;
+    }
+
+    let mut row = (0..=a_len).collect::<Vec<_>>();
+
+    for (b_index, b_char) in b.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = b_index + 1;
+
+        for (a_index, a_char) in a.chars().enumerate() {
+            let deletion = row[a_index + 1] + 1;
+            let insertion = row[a_index] + 1;
+            let substitution = previous + usize::from(a_char != b_char);
+
+            previous = row[a_index + 1];
+            row[a_index + 1] = deletion.min(insertion).min(substitution);
+        }
+    }
+
+    row[a_len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_match, levenshtein_distance};
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("", "v1.0.0"), 6);
+        assert_eq!(levenshtein_distance("v1.0.0", "v1.1.0"), 1);
+        assert_eq!(levenshtein_distance("v1.0.0", "foo-v1.1.0"), 5);
+        assert_eq!(levenshtein_distance("v1.0.0", "v1.0.0"), 0);
+        assert_eq!(levenshtein_distance("mañana", "manana"), 1);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["trailing-whitespace", "end-of-file-fixer", "check-yaml"];
+        assert_eq!(
+            closest_match("trailing-whitespac", candidates),
+            Some("trailing-whitespace")
+        );
+        assert_eq!(closest_match("check-yml", candidates), Some("check-yaml"));
+        assert_eq!(closest_match("ruff", candidates), None);
+    }
+}
@@ -21,7 +21,7 @@
 // SOFTWARE.
 
 use std::collections::HashSet;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{LazyLock, Mutex};
 
 // macro hygiene: The user might not have direct dependencies on those crates
@@ -33,14 +33,22 @@ pub use owo_colors;
 /// Whether user-facing warnings are enabled.
 pub static ENABLED: AtomicBool = AtomicBool::new(false);
 
+/// Number of user-facing warnings raised so far, including suppressed ones.
+pub static COUNT: AtomicUsize = AtomicUsize::new(0);
+
 /// Enable user-facing warnings.
 pub fn enable() {
-    ENABLED.store(true, std::sync::atomic::Ordering::SeqCst);
+    ENABLED.store(true, Ordering::SeqCst);
 }
 
 /// Disable user-facing warnings.
 pub fn disable() {
-    ENABLED.store(false, std::sync::atomic::Ordering::SeqCst);
+    ENABLED.store(false, Ordering::SeqCst);
+}
+
+/// Return how many warnings have been raised, whether or not they were printed.
+pub fn count() -> usize {
+    COUNT.load(Ordering::SeqCst)
 }
 
 /// Warn a user, if warnings are enabled.
@@ -50,6 +58,7 @@ macro_rules! warn_user {
         use $crate::warnings::anstream::eprintln;
         use $crate::warnings::owo_colors::OwoColorize;
 
+        $crate::warnings::COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         if $crate::warnings::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
             let message = format!("{}", format_args!($($arg)*));
             let formatted = message.bold();
@@ -68,10 +77,11 @@ macro_rules! warn_user_once {
         use $crate::warnings::anstream::eprintln;
         use $crate::warnings::owo_colors::OwoColorize;
 
-        if $crate::warnings::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
-            if let Ok(mut states) = $crate::warnings::WARNINGS.lock() {
-                let message = format!("{}", format_args!($($arg)*));
-                if states.insert(message.clone()) {
+        if let Ok(mut states) = $crate::warnings::WARNINGS.lock() {
+            let message = format!("{}", format_args!($($arg)*));
+            if states.insert(message.clone()) {
+                $crate::warnings::COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if $crate::warnings::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
                     eprintln!("{}{} {}", "warning".yellow().bold(), ":".bold(), message.bold());
                 }
             }
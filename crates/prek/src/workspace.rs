@@ -9,20 +9,20 @@ use anyhow::Result;
 use ignore::WalkState;
 use itertools::zip_eq;
 use owo_colors::OwoColorize;
-use prek_consts::CONFIG_FILENAMES;
+use prek_consts::{CONFIG_FILENAMES, PREKIGNORE};
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, error, instrument, trace};
 
 use crate::cli::run::{ConfiguredHook, GroupFilters, Selectors};
-use crate::config::{self, Config, read_config};
+use crate::config::{self, Config, Stage, read_config};
 use crate::fs::Simplified;
 use crate::git::GIT_ROOT;
 use crate::hook::HookSpec;
 use crate::hook::{self, Hook, HookBuilder, Repo};
 use crate::store::{CacheBucket, Store};
-use crate::{git, store, warn_user};
+use crate::{git, store, suggest, warn_user};
 
 #[derive(Error, Debug)]
 pub(crate) enum Error {
@@ -41,8 +41,12 @@ pub(crate) enum Error {
     )]
     MissingConfigFile,
 
-    #[error("Hook `{hook}` not present in repo `{repo}`")]
-    HookNotFound { hook: String, repo: String },
+    #[error("Hook `{hook}` not present in repo `{repo}`{hint}")]
+    HookNotFound {
+        hook: String,
+        repo: String,
+        hint: String,
+    },
 
     #[error(transparent)]
     Store(#[from] store::Error),
@@ -58,6 +62,7 @@ pub(crate) trait HookInitReporter {
 pub(crate) struct HookInitFilters<'a> {
     selectors: Option<&'a Selectors>,
     group_filters: Option<&'a GroupFilters>,
+    stage: Option<Stage>,
 }
 
 impl<'a> HookInitFilters<'a> {
@@ -68,6 +73,7 @@ impl<'a> HookInitFilters<'a> {
         Self {
             selectors,
             group_filters,
+            stage: None,
         }
     }
 
@@ -75,6 +81,13 @@ impl<'a> HookInitFilters<'a> {
         Self::default()
     }
 
+    /// Build hooks for the given stage, which selects stage-specific language versions.
+    #[must_use]
+    pub(crate) fn with_stage(mut self, stage: Stage) -> Self {
+        self.stage = Some(stage);
+        self
+    }
+
     fn keeps_remote_repo(self, project: &Project, repo: &config::RemoteRepo) -> bool {
         repo.hooks.iter().any(|hook| {
             let hook = ConfiguredHook::new(
@@ -269,23 +282,7 @@ impl Project {
             .parent()
             .expect("config file must have a parent");
 
-        // Resolve relative repo paths against the config file's directory.
-        // This ensures paths like `../hook-repo` are resolved from where the
-        // config file lives, not from the process's current working directory.
-        for repo in &mut config.repos {
-            if let config::Repo::Remote(remote) = repo {
-                let repo_path = Path::new(&remote.repo);
-                if !remote.repo.starts_with("http://")
-                    && !remote.repo.starts_with("https://")
-                    && repo_path.is_relative()
-                {
-                    let resolved = config_dir.join(repo_path);
-                    if resolved.is_dir() {
-                        remote.repo = resolved.to_string_lossy().into_owned();
-                    }
-                }
-            }
-        }
+        config.resolve_relative_repos(config_dir);
 
         let root = root.unwrap_or_else(|| config_dir.to_path_buf());
 
@@ -298,7 +295,7 @@ impl Project {
         })
     }
 
-    fn find_config(path: &Path) -> Option<PathBuf> {
+    pub(crate) fn find_config(path: &Path) -> Option<PathBuf> {
         for name in CONFIG_FILENAMES {
             let file = path.join(name);
             if file.is_file() {
@@ -418,7 +415,7 @@ impl Project {
         let repos = self.init_repos(store, filters, reporter).await?;
 
         let project = Arc::new(self);
-        let hooks = Project::build_hooks(project, repos).await?;
+        let hooks = Project::build_hooks(project, repos, filters.stage).await?;
 
         Ok(hooks)
     }
@@ -452,6 +449,7 @@ impl Project {
     async fn build_hooks(
         project: Arc<Project>,
         repos: ProjectRepoSlots,
+        stage: Option<Stage>,
     ) -> Result<Vec<Hook>, Error> {
         let mut hooks = Vec::new();
         let mut push_hook = async |repo: &Arc<Repo>, hook_spec: HookSpec| {
@@ -460,7 +458,8 @@ impl Project {
                 Arc::clone(repo),
                 hook_spec,
                 hooks.len(),
-            );
+            )
+            .with_stage(stage);
             let hook = builder.build().await?;
             hooks.push(hook);
             Ok::<_, Error>(())
@@ -475,7 +474,9 @@ impl Project {
                     for hook_config in &repo_config.hooks {
                         // Check hook id is valid.
                         let Some(manifest_hook) = repo.get_hook(&hook_config.id) else {
+                            let known = repo.hook_ids().collect::<Vec<_>>();
                             return Err(Error::HookNotFound {
+                                hint: suggest::hook_id_hint(&hook_config.id, &known),
                                 hook: hook_config.id.clone(),
                                 repo: repo.to_string(),
                             });
@@ -871,10 +872,12 @@ impl Workspace {
             error!("Failed to list git submodules: {e}");
             Vec::new()
         });
+        // Task-locals don't reach the walker threads, so carry the `--frozen` scope over.
+        let frozen_revs = config::FROZEN_REVS.try_with(|()| ()).is_ok();
 
         ignore::WalkBuilder::new(root)
             .follow_links(false)
-            .add_custom_ignore_filename(".prekignore")
+            .add_custom_ignore_filename(PREKIGNORE)
             .build_parallel()
             .run(|| {
                 Box::new(|result| {
@@ -913,7 +916,12 @@ impl Workspace {
                         return WalkState::Skip;
                     }
 
-                    match Project::from_directory(entry.path()) {
+                    let project = if frozen_revs {
+                        config::FROZEN_REVS.sync_scope((), || Project::from_directory(entry.path()))
+                    } else {
+                        Project::from_directory(entry.path())
+                    };
+                    match project {
                         Ok(mut project) => {
                             let relative_path = entry
                                 .into_path()
@@ -1035,7 +1043,8 @@ impl Workspace {
 
         let mut hooks = Vec::new();
         for (project, repos) in zip_eq(&self.projects, project_repos) {
-            let project_hooks = Project::build_hooks(Arc::clone(project), repos).await?;
+            let project_hooks =
+                Project::build_hooks(Arc::clone(project), repos, filters.stage).await?;
             hooks.extend(project_hooks);
         }
 
@@ -1044,6 +1053,58 @@ impl Workspace {
         Ok(hooks)
     }
 
+    /// Load hooks for all projects from the repos already in the store, without cloning.
+    ///
+    /// Also returns the remote repos that are not cloned yet, whose hooks are unknown.
+    pub(crate) async fn init_cached_hooks(
+        &self,
+        store: &Store,
+        filters: HookInitFilters<'_>,
+    ) -> Result<(Vec<Hook>, Vec<&config::RemoteRepo>), Error> {
+        let mut remote_keys = FxHashSet::default();
+        let mut remote_configs = Vec::new();
+        let mut project_repo_entries_to_init = self
+            .projects
+            .iter()
+            .map(|project| {
+                plan_project_repo_init(project, filters, &mut remote_keys, &mut remote_configs)
+            })
+            .collect::<Vec<_>>();
+
+        let mut remote_repos = FxHashMap::default();
+        let mut uncloned = Vec::new();
+        for repo in remote_configs {
+            if let Some(path) = store.cloned_repo_path(repo)? {
+                let cloned = Repo::remote(repo.repo.clone(), repo.rev.clone(), path)?;
+                remote_repos.insert(repo.key(), Arc::new(cloned));
+            } else {
+                uncloned.push(repo);
+            }
+        }
+
+        // Skip the entries of repos that are not cloned, there are no hooks to build for them.
+        for (project, entries) in zip_eq(&self.projects, &mut project_repo_entries_to_init) {
+            for (repo, keep) in zip_eq(&project.config.repos, entries.iter_mut()) {
+                if let config::Repo::Remote(repo) = repo
+                    && !remote_repos.contains_key(&repo.key())
+                {
+                    *keep = false;
+                }
+            }
+        }
+
+        let mut hooks = Vec::new();
+        for (project, repo_entries_to_init) in zip_eq(&self.projects, project_repo_entries_to_init)
+        {
+            let repos = build_project_repo_slots(project, repo_entries_to_init, &remote_repos);
+            let project_hooks =
+                Project::build_hooks(Arc::clone(project), repos, filters.stage).await?;
+            hooks.extend(project_hooks);
+        }
+
+        Ok((hooks, uncloned))
+    }
+
     /// Check if all configuration files are staged in git.
     pub(crate) async fn check_configs_staged(&self) -> Result<()> {
         let config_files = self
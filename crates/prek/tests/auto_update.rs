@@ -187,6 +187,71 @@ fn auto_update_basic() -> Result<()> {
     Ok(())
 }
 
+/// Repos inherited through `extends` are left alone; only the project's own file is updated.
+#[test]
+fn auto_update_skips_repos_from_extends() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_path = create_local_git_repo(&context, "test-repo", &["v1.0.0", "v2.0.0"])?;
+    let base_repo_path = create_local_git_repo(&context, "base-repo", &["v1.0.0", "v2.0.0"])?;
+
+    context
+        .work_dir()
+        .child("base.yaml")
+        .write_str(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+    ", base_repo_path})?;
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        extends: base.yaml
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+    ", repo_path});
+    context.git_add(".");
+
+    let filters = context.filters();
+
+    cmd_snapshot!(filters.clone(), context.auto_update().arg("--cooldown-days").arg("0"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [HOME]/test-repos/test-repo
+      updating rev `v1.0.0` -> `v2.0.0`
+
+    ----- stderr -----
+    ");
+
+    insta::with_settings!(
+        { filters => filters.clone() },
+        {
+            assert_snapshot!(context.read(PRE_COMMIT_CONFIG_YAML), @"
+            extends: base.yaml
+            repos:
+              - repo: [HOME]/test-repos/test-repo
+                rev: v2.0.0
+                hooks:
+                  - id: test-hook
+            ");
+            assert_snapshot!(context.read("base.yaml"), @"
+            repos:
+              - repo: [HOME]/test-repos/base-repo
+                rev: v1.0.0
+                hooks:
+                  - id: test-hook
+            ");
+        }
+    );
+
+    Ok(())
+}
+
 #[test]
 fn auto_update_already_up_to_date() -> Result<()> {
     let context = TestContext::new();
@@ -606,6 +671,63 @@ fn auto_update_specific_repos() -> Result<()> {
     Ok(())
 }
 
+/// `--repo` accepts glob and `re:` regex patterns matching several repositories.
+#[test]
+fn auto_update_repo_patterns() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo1_path = create_local_git_repo(&context, "repo1", &["v1.0.0", "v1.1.0"])?;
+    let repo2_path = create_local_git_repo(&context, "repo2", &["v2.0.0", "v2.1.0"])?;
+    let other_path = create_local_git_repo(&context, "other", &["v3.0.0", "v3.1.0"])?;
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+          - repo: {}
+            rev: v2.0.0
+            hooks:
+              - id: another-hook
+          - repo: {}
+            rev: v3.0.0
+            hooks:
+              - id: test-hook
+    ", repo1_path, repo2_path, other_path});
+
+    context.git_add(".");
+
+    let filters = context.filters();
+
+    cmd_snapshot!(filters.clone(), context.auto_update().arg("--repo").arg("*/repo[0-9]").arg("--repo").arg("*/missing").arg("--dry-run").arg("--cooldown-days").arg("0"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [HOME]/test-repos/repo1
+      would update rev `v1.0.0` -> `v1.1.0`
+
+    [HOME]/test-repos/repo2
+      would update rev `v2.0.0` -> `v2.1.0`
+
+    ----- stderr -----
+    warning: `--repo */missing` does not match any repository in the config
+    ");
+
+    cmd_snapshot!(filters.clone(), context.auto_update().arg("--repo").arg("re:/other$").arg("--cooldown-days").arg("0"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [HOME]/test-repos/other
+      updating rev `v3.0.0` -> `v3.1.0`
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn auto_update_exclude_repo_skips_fetching_repo() -> Result<()> {
     let context = TestContext::new();
@@ -150,6 +150,63 @@ fn end_of_file_fixer_hook() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn end_of_file_fixer_exclude_ext() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+                args: [--exclude-ext=min.js]
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("app.min.js").write_str("minified")?;
+    cwd.child("bom.txt").write_binary(b"\xef\xbb\xbf")?;
+    cwd.child("app.js").write_str("source")?;
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing app.js
+      Skipping bom.txt (binary content)
+      Skipping app.min.js (excluded by `--exclude-ext`)
+
+    ----- stderr -----
+    ");
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--verbose"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    fix end of files.........................................................Passed
+    - hook id: end-of-file-fixer
+    - duration: [TIME]
+
+      Skipping bom.txt (binary content)
+      Skipping app.min.js (excluded by `--exclude-ext`)
+
+    ----- stderr -----
+    ");
+
+    assert_snapshot!(context.read("app.min.js"), @"minified");
+
+    Ok(())
+}
+
 #[test]
 fn file_contents_sorter_hook() -> Result<()> {
     let context = TestContext::new();
@@ -209,6 +266,133 @@ fn file_contents_sorter_hook() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sort_simple_yaml_hook() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: sort-simple-yaml
+                files: ^versions\.yaml$
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("versions.yaml").write_str(indoc::indoc! {r"
+        # Pinned versions.
+
+        zlib: 1.3
+        # Keep in sync with the CI image.
+        python: 3.12
+
+        openssl: 3.0"})?;
+    cwd.child("other.yaml").write_str("b: 1\n\na: 2\n")?;
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    sort simple yaml files...................................................Failed
+    - hook id: sort-simple-yaml
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing file `versions.yaml`
+
+    ----- stderr -----
+    ");
+
+    assert_snapshot!(context.read("versions.yaml"), @r"
+    # Pinned versions.
+
+    openssl: 3.0
+
+    zlib: 1.3
+    # Keep in sync with the CI image.
+    python: 3.12
+    ");
+    assert_snapshot!(context.read("other.yaml"), @r"
+    b: 1
+
+    a: 2
+    ");
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    sort simple yaml files...................................................Passed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn show_diff_previews_fixer_changes() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: file-contents-sorter
+                files: ^a\.txt$
+              - id: end-of-file-fixer
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("a.txt").write_str("b\na\n")?;
+    cwd.child("b.txt").write_str("baz")?;
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--show-diff"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    file contents sorter.....................................................Failed
+    - hook id: file-contents-sorter
+    - exit code: 1
+
+      --- a.txt
+      +++ a.txt
+      @@ -1,2 +1,2 @@
+      +a
+       b
+      -a
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+
+      --- b.txt
+      +++ b.txt
+      @@ -1 +1 @@
+      -baz
+      \ No newline at end of file
+      +baz
+
+    ----- stderr -----
+    ");
+
+    // Files are left untouched.
+    assert_snapshot!(context.read("a.txt"), @r"
+    b
+    a
+    ");
+    assert_snapshot!(context.read("b.txt"), @"baz");
+
+    Ok(())
+}
+
 #[test]
 fn forbid_new_submodules_hook_in_workspace_project() -> Result<()> {
     let context = TestContext::new();
@@ -755,6 +939,29 @@ fn check_added_large_files_hook() -> Result<()> {
     ----- stderr -----
     ");
 
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: check-added-large-files
+                args: ['--maxkb=1', '--enforce-all']
+    "});
+    context.git_add(".");
+
+    // Fourth run: `--enforce-all` checks git-lfs files too
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check for added large files..............................................Failed
+    - hook id: check-added-large-files
+    - exit code: 1
+
+      lfs_file.dat (2 KB) exceeds 1 KB
+
+    ----- stderr -----
+    ");
+
     Ok(())
 }
 
@@ -770,15 +977,13 @@ fn check_added_large_files_workspace_mode_respects_project_relative_lfs_paths()
     // nested project root, not the workspace root.
     let app = context.work_dir().child("app");
     app.create_dir_all()?;
-    // Use `--enforce-all` so this regression isolates git-lfs attribute lookup in workspace
-    // mode instead of depending on the separate staged-file path filtering behavior.
     app.child(PRE_COMMIT_CONFIG_YAML)
         .write_str(indoc::indoc! {r"
         repos:
           - repo: builtin
             hooks:
               - id: check-added-large-files
-                args: ['--maxkb', '1', '--enforce-all']
+                args: ['--maxkb', '1']
     "})?;
     app.child(".gitattributes")
         .write_str("*.dat filter=lfs diff=lfs merge=lfs -text")?;
@@ -1151,6 +1356,84 @@ fn fix_byte_order_marker_hook() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn fix_encoding_pragma_hook() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: fix-encoding-pragma
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("plain.py").write_str("x = 1\n")?;
+    cwd.child("script.py")
+        .write_str("#!/usr/bin/env python\nprint('hi')\n")?;
+    cwd.child("done.py")
+        .write_str("# -*- coding: utf-8 -*-\nx = 1\n")?;
+    // Not a Python file.
+    cwd.child("notes.txt").write_str("x = 1\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    fix python encoding pragma...............................................Failed
+    - hook id: fix-encoding-pragma
+    - exit code: 1
+    - files were modified by this hook
+
+      Added `# -*- coding: utf-8 -*-` to plain.py
+      Added `# -*- coding: utf-8 -*-` to script.py
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(context.read("plain.py"), "# -*- coding: utf-8 -*-\nx = 1\n");
+    assert_eq!(
+        context.read("script.py"),
+        "#!/usr/bin/env python\n# -*- coding: utf-8 -*-\nprint('hi')\n"
+    );
+    assert_eq!(context.read("notes.txt"), "x = 1\n");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: fix-encoding-pragma
+                args: [--remove]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    fix python encoding pragma...............................................Failed
+    - hook id: fix-encoding-pragma
+    - exit code: 1
+    - files were modified by this hook
+
+      Removed encoding pragma from plain.py
+      Removed encoding pragma from done.py
+      Removed encoding pragma from script.py
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(context.read("plain.py"), "x = 1\n");
+    assert_eq!(
+        context.read("script.py"),
+        "#!/usr/bin/env python\nprint('hi')\n"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn pretty_format_json_hook() -> Result<()> {
     let context = TestContext::new();
@@ -1870,6 +2153,132 @@ fn detect_private_key_hook() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn debug_statements_hook() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: debug-statements
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("debug.py").write_str(indoc::indoc! {r#"
+        import os
+        import pdb
+
+        def main():
+            message = "import ipdb"  # breakpoint()
+            breakpoint()
+    "#})?;
+    cwd.child("clean.py").write_str(indoc::indoc! {r#"
+        """Call `breakpoint()` to debug, or `import pdb`."""
+        print("hello")
+    "#})?;
+    // Not a Python file.
+    cwd.child("notes.txt").write_str("import pdb\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    debug statements (python)................................................Failed
+    - hook id: debug-statements
+    - exit code: 1
+
+      debug.py:2:0: pdb imported
+      debug.py:6:4: breakpoint called
+
+    ----- stderr -----
+    ");
+
+    cwd.child("debug.py").write_str("import os\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    debug statements (python)................................................Passed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn check_builtin_literals_hook() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: check-builtin-literals
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("literals.py").write_str(indoc::indoc! {r#"
+        import collections
+
+        a = dict()
+        b = list(range(3))
+        c = collections.OrderedDict()
+        d = dict(key="value")
+        e = "tuple()"  # str()
+        f = tuple(
+        )
+    "#})?;
+    // Not a Python file.
+    cwd.child("notes.txt").write_str("dict()\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check builtin type constructor use.......................................Failed
+    - hook id: check-builtin-literals
+    - exit code: 1
+
+      literals.py:3:4: replace dict() with {}
+      literals.py:8:4: replace tuple() with ()
+
+    ----- stderr -----
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: check-builtin-literals
+                args: [--ignore=tuple, --no-allow-dict-kwargs]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check builtin type constructor use.......................................Failed
+    - hook id: check-builtin-literals
+    - exit code: 1
+
+      literals.py:3:4: replace dict() with {}
+      literals.py:6:4: replace dict() with {}
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn check_merge_conflict_hook() -> Result<()> {
     let context = TestContext::new();
@@ -2237,6 +2646,45 @@ fn check_xml_with_features() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn name_tests_test_hook() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: name-tests-test
+                args: [--pytest-test-first]
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("tests/test_good.py").touch()?;
+    cwd.child("tests/helpers.py").touch()?;
+    cwd.child("tests/__init__.py").touch()?;
+    cwd.child("tests/conftest.py").touch()?;
+    cwd.child("tests/fixtures/sample.py").touch()?;
+    // Not under a `tests` directory.
+    cwd.child("src/helpers.py").touch()?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    python tests naming......................................................Failed
+    - hook id: name-tests-test
+    - exit code: 1
+
+      tests/helpers.py does not match pattern "test_.*/.py"
+
+    ----- stderr -----
+    "#);
+
+    Ok(())
+}
+
 #[test]
 fn no_commit_to_branch_hook() -> Result<()> {
     let context = TestContext::new();
@@ -3049,6 +3497,53 @@ fn check_case_conflict_among_new_files() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn check_case_conflict_unicode_normalization() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    let nfc = cwd.child("caf\u{e9}.txt");
+    let nfd = cwd.child("cafe\u{301}.txt");
+    nfc.write_str("precomposed")?;
+    if nfd.exists() {
+        // Skipping test on a normalization-insensitive filesystem
+        return Ok(());
+    }
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: check-case-conflict
+    "});
+
+    // Add the same name in decomposed form
+    nfd.write_str("decomposed")?;
+    context.git_add(".");
+
+    let mut filters = context.filters();
+    filters.push(("cafe\u{301}", "cafe[U+0301]"));
+
+    cmd_snapshot!(filters, context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check for case conflicts.................................................Failed
+    - hook id: check-case-conflict
+    - exit code: 1
+
+      Unicode normalization conflict found: cafe[U+0301].txt
+      Unicode normalization conflict found: café.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn check_case_conflict_workspace_mode_includes_added_files() -> Result<()> {
     let context = TestContext::new();
@@ -562,6 +562,56 @@ fn cache_gc_keeps_local_hook_env() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn cache_gc_keeps_hook_env_in_use() -> anyhow::Result<()> {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config("repos: []\n");
+    let home = context.home_dir();
+    let config_path = context.work_dir().child(PRE_COMMIT_CONFIG_YAML);
+    write_config_tracking_file(home, &[config_path.path()])?;
+
+    // An env no config references, but that a concurrent run still holds.
+    let env = home.child("hooks/python-in-use");
+    env.create_dir_all()?;
+    let marker = json!({
+        "language": "python",
+        "language_version": "3.12.0",
+        "dependencies": [],
+        "env_path": env.path(),
+        "toolchain": home.child("tools/python/3.12.0/bin/python").path(),
+        "extra": {},
+    });
+    env.child(".prek-hook.json")
+        .write_str(&serde_json::to_string_pretty(&marker)?)?;
+    let lock = fs_err::File::create(env.child(".prek-in-use.lock").path())?;
+    lock.file().lock_shared()?;
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "gc"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Nothing to clean
+
+    ----- stderr -----
+    ");
+    env.assert(predicates::path::is_dir());
+
+    // Once the run is done, the env is swept.
+    drop(lock);
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "gc"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Removed 1 hook env ([SIZE])
+
+    ----- stderr -----
+    ");
+    env.assert(predicates::path::missing());
+
+    Ok(())
+}
+
 #[test]
 fn cache_gc_removes_stale_patch_files() -> anyhow::Result<()> {
     let context = TestContext::new();
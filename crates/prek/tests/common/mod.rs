@@ -182,6 +182,9 @@ impl TestContext {
             .env("GIT_CONFIG_KEY_0", "core.autocrlf")
             .env("GIT_CONFIG_VALUE_0", "false");
 
+        // Keep the output stable when the tests themselves run on GitHub Actions.
+        cmd.env_remove(EnvVars::GITHUB_ACTIONS);
+
         cmd
     }
 
@@ -220,6 +223,12 @@ impl TestContext {
         command
     }
 
+    pub fn migrate_config(&self) -> Command {
+        let mut command = self.command();
+        command.arg("migrate-config");
+        command
+    }
+
     pub fn install(&self) -> Command {
         let mut command = self.command();
         command.arg("install");
@@ -232,6 +241,18 @@ impl TestContext {
         command
     }
 
+    pub fn warmup(&self) -> Command {
+        let mut command = self.command();
+        command.arg("warmup");
+        command
+    }
+
+    pub fn doctor(&self) -> Command {
+        let mut command = self.command();
+        command.arg("doctor");
+        command
+    }
+
     pub fn uninstall(&self) -> Command {
         let mut command = self.command();
         command.arg("uninstall");
@@ -0,0 +1,106 @@
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::PathChild;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// `prek doctor` reports broken environments and `--fix` reinstalls them.
+#[test]
+fn doctor_reports_and_fixes_broken_environments() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: node-hook
+                name: Node Hook
+                language: node
+                entry: node -e 'console.log("node")'
+              - id: system-hook
+                name: System Hook
+                language: system
+                entry: echo system
+        "#});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        // The exact message is host language dependent.
+        .chain([(
+            r"failed: .* \(os error 2\)",
+            "failed: No such file or directory (os error 2)",
+        )])
+        .collect::<Vec<_>>();
+
+    // Nothing is installed yet, and `doctor` does not install anything.
+    cmd_snapshot!(filters.clone(), context.doctor(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:node-hook  node  not installed
+
+    ----- stderr -----
+    ");
+
+    context.prepare_hooks().assert().success();
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:node-hook  node  ok
+
+    ----- stderr -----
+    ");
+
+    // Point the environment at a missing node binary to break it.
+    let env = context
+        .home_dir()
+        .child("hooks")
+        .read_dir()?
+        .next()
+        .expect("node environment")?
+        .path();
+    let marker = env.join(".prek-hook.json");
+    let content = fs_err::read_to_string(&marker)?;
+    let mut info: serde_json::Value = serde_json::from_str(&content)?;
+    info["toolchain"] =
+        serde_json::Value::String(env.join("missing-node").to_string_lossy().into_owned());
+    fs_err::write(&marker, serde_json::to_string(&info)?)?;
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .:node-hook  node  FAIL
+      Failed to query node version: Run command `node -p` failed: No such file or directory (os error 2)
+
+    ----- stderr -----
+    error: 1 hook environment failed the health check, run `prek doctor --fix` to reinstall
+    ");
+
+    cmd_snapshot!(filters.clone(), context.doctor().arg("--fix"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:node-hook  node  FAIL
+      Failed to query node version: Run command `node -p` failed: No such file or directory (os error 2)
+
+    ----- stderr -----
+    success: Reinstalled environments for 1 hook
+    ");
+
+    cmd_snapshot!(filters.clone(), context.doctor(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:node-hook  node  ok
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -499,6 +499,113 @@ fn hook_impl_pre_push_runs_legacy_and_prek() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// The legacy hook runs before prek's hooks by default, or after them with `--legacy-last`.
+/// A failure in either one fails the Git hook.
+#[cfg(unix)]
+#[test]
+fn hook_impl_legacy_hook_order() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: prek-hook
+                name: prek-hook
+                language: system
+                entry: sh -c 'echo prek hook ran; exit ${PREK_HOOK_EXIT:-0}'
+                pass_filenames: false
+                always_run: true
+    "});
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    let legacy_hook = context.work_dir().child(".git/hooks/pre-commit");
+    legacy_hook.write_str(indoc::indoc! {r#"
+        #!/bin/sh
+        echo "legacy hook ran"
+        exit ${LEGACY_EXIT:-0}
+    "#})?;
+    set_executable(legacy_hook.path())?;
+    context.install().assert().success();
+
+    let commit = |legacy_exit: &str, prek_exit: &str| {
+        let mut commit = git_cmd(context.work_dir());
+        commit
+            .env(EnvVars::PREK_HOME, &**context.home_dir())
+            .env("LEGACY_EXIT", legacy_exit)
+            .env("PREK_HOOK_EXIT", prek_exit)
+            .arg("commit")
+            .arg("--allow-empty")
+            .arg("-m")
+            .arg("Test commit");
+        commit
+    };
+
+    cmd_snapshot!(context.filters(), commit("1", "0"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    legacy hook ran
+    prek-hook................................................................Passed
+    ");
+    cmd_snapshot!(context.filters(), commit("0", "1"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    legacy hook ran
+    prek-hook................................................................Failed
+    - hook id: prek-hook
+    - exit code: 1
+
+      prek hook ran
+    ");
+
+    cmd_snapshot!(context.filters(), context.install().arg("--legacy-last"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Migration mode: prek will also run legacy hook `.git/hooks/pre-commit.legacy` after its hooks. Use `--overwrite` to remove legacy hooks.
+    prek installed at `.git/hooks/pre-commit`
+
+    ----- stderr -----
+    ");
+    assert!(
+        context
+            .read(".git/hooks/pre-commit")
+            .contains("--legacy-last")
+    );
+
+    cmd_snapshot!(context.filters(), commit("1", "0"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    prek-hook................................................................Passed
+    legacy hook ran
+    ");
+    cmd_snapshot!(context.filters(), commit("0", "1"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    prek-hook................................................................Failed
+    - hook id: prek-hook
+    - exit code: 1
+
+      prek hook ran
+    legacy hook ran
+    ");
+
+    Ok(())
+}
+
 #[cfg(unix)]
 fn set_executable(path: &Path) -> anyhow::Result<()> {
     let mut perms = fs_err::metadata(path)?.permissions();
@@ -306,6 +306,43 @@ fn install_with_git_dir_allows_external_hooks_path_set() {
     "#);
 }
 
+/// `--no-hooks-path` installs into `.git/hooks` even with a global `core.hooksPath`.
+#[test]
+fn install_no_hooks_path_ignores_hooks_path() {
+    let context = TestContext::new();
+    context.init_project();
+
+    let global_gitconfig = context.work_dir().join("global.gitconfig");
+    git_cmd(context.work_dir())
+        .env("GIT_CONFIG_GLOBAL", &global_gitconfig)
+        .args(["config", "--global", "core.hooksPath", "custom-hooks"])
+        .assert()
+        .success();
+
+    let mut install = context.install();
+    install
+        .arg("--no-hooks-path")
+        .env("GIT_CONFIG_GLOBAL", &global_gitconfig);
+    cmd_snapshot!(context.filters(), install, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prek installed at `.git/hooks/pre-commit`
+
+    ----- stderr -----
+    warning: `core.hooksPath` is set, so Git will not run the shims in `.git/hooks` unless the configured hooks call them
+    ");
+
+    context
+        .work_dir()
+        .child(".git/hooks/pre-commit")
+        .assert(predicates::path::exists());
+    context
+        .work_dir()
+        .child("custom-hooks")
+        .assert(predicates::path::missing());
+}
+
 #[test]
 fn install_refuses_empty_external_hooks_path_set() {
     let context = TestContext::new();
@@ -679,6 +716,54 @@ fn install_with_existing_legacy_hook() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A foreign hook is not moved over a foreign `.legacy` script kept from an earlier install.
+#[test]
+fn install_refuses_to_replace_foreign_legacy_hook() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hooks = context.work_dir().child(".git/hooks");
+    hooks
+        .child("pre-commit.legacy")
+        .write_str("#!/bin/sh\necho 'legacy'\n")?;
+    hooks
+        .child("pre-commit")
+        .write_str("#!/bin/sh\necho 'custom'\n")?;
+
+    cmd_snapshot!(context.filters(), context.install(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to move hook `.git/hooks/pre-commit` to `.git/hooks/pre-commit.legacy`, which already exists. Neither of them is managed by prek.
+
+    hint: Merge `.git/hooks/pre-commit` into `.git/hooks/pre-commit.legacy` and remove it, or use `--overwrite` to remove both.
+    ");
+    hooks
+        .child("pre-commit.legacy")
+        .assert("#!/bin/sh\necho 'legacy'\n");
+    hooks
+        .child("pre-commit")
+        .assert("#!/bin/sh\necho 'custom'\n");
+
+    // `--force` replaces both.
+    cmd_snapshot!(context.filters(), context.install().arg("--force"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Overwriting existing hook at `.git/hooks/pre-commit`
+    prek installed at `.git/hooks/pre-commit`
+
+    ----- stderr -----
+    ");
+    hooks
+        .child("pre-commit.legacy")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
 /// Run `prek prepare-hooks` to prepare prek hook environments without installing the git hook.
 #[test]
 fn install_hooks_only() -> anyhow::Result<()> {
@@ -726,6 +811,220 @@ fn install_hooks_only() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `prek prepare-hooks --frozen` refuses mutable revs before cloning anything.
+#[test]
+fn install_auto_hook_types() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        default_install_hook_types: [commit-msg]
+        repos:
+          - repo: local
+            hooks:
+              - id: format
+                name: format
+                language: system
+                entry: echo
+              - id: test
+                name: test
+                language: system
+                entry: echo
+                stages: [pre-push, post-checkout]
+              - id: lint
+                name: lint
+                language: system
+                entry: echo
+                stages: [pre-push]
+              - id: release
+                name: release
+                language: system
+                entry: echo
+                stages: [manual]
+    "});
+
+    cmd_snapshot!(context.filters(), context.install().arg("--auto-hook-types"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hook type `commit-msg` is needed by `default_install_hook_types`
+    Hook type `post-checkout` is needed by hook `test`
+    Hook type `pre-commit` is needed by hook `format`
+    Hook type `pre-push` is needed by hooks `test`, `lint`
+    prek installed at `.git/hooks/commit-msg`
+    prek installed at `.git/hooks/post-checkout`
+    prek installed at `.git/hooks/pre-commit`
+    prek installed at `.git/hooks/pre-push`
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.install().arg("--auto-hook-types").arg("--hook-type").arg("pre-push"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--auto-hook-types' cannot be used with '--hook-type <HOOK_TYPE>'
+
+    Usage: prek install --auto-hook-types [HOOK|PROJECT]...
+
+    For more information, try '--help'.
+    ");
+}
+
+#[test]
+fn prepare_hooks_frozen_rejects_mutable_rev() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: main
+            hooks:
+              - id: trailing-whitespace
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            hooks:
+              - id: end-of-file-fixer
+    "});
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--frozen"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The following repos have mutable `rev` fields (moving tag / branch), which are not allowed with `--frozen`:
+      https://github.com/pre-commit/pre-commit-hooks: main
+
+    hint: Pin them to a tag or commit SHA, for example with `prek auto-update`.
+    ");
+
+    // Nothing was cloned.
+    assert_eq!(context.home_dir().child("repos").read_dir()?.count(), 0);
+
+    Ok(())
+}
+
+/// Without `--frozen`, the commit a mutable rev resolved to is recorded with the clone.
+#[test]
+fn prepare_hooks_records_commit_of_mutable_rev() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo = context.home_dir().child("hook-repo");
+    repo.create_dir_all()?;
+    git_cmd(&repo)
+        .args(["-c", "init.defaultBranch=master", "init"])
+        .assert()
+        .success();
+    repo.child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+            - id: hello
+              name: hello
+              entry: echo hello
+              language: system
+        "})?;
+    git_cmd(&repo).arg("add").arg(".").assert().success();
+    git_cmd(&repo)
+        .args(["commit", "-m", "Initial commit"])
+        .assert()
+        .success();
+    let output = git_cmd(&repo).args(["rev-parse", "HEAD"]).output()?;
+    let head = String::from_utf8(output.stdout)?.trim().to_string();
+
+    let repo_path = repo.path().to_string_lossy().replace('\\', "/");
+    context.write_pre_commit_config(&format!(
+        "repos:\n  - repo: {repo_path}\n    rev: master\n    hooks:\n      - id: hello\n"
+    ));
+    context.prepare_hooks().assert().success();
+
+    let clones = context
+        .home_dir()
+        .child("repos")
+        .read_dir()?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(clones.len(), 1);
+    let marker: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        clones[0].path().join(".prek-repo.json"),
+    )?)?;
+    assert_eq!(marker["rev"], "master");
+    assert_eq!(marker["commit"], head.as_str());
+
+    Ok(())
+}
+
+/// `prek prepare-hooks --dry-run` lists what would be cloned and installed without doing it.
+#[test]
+fn prepare_hooks_dry_run() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            hooks:
+              - id: trailing-whitespace
+          - repo: https://github.com/pre-commit/mirrors-mypy
+            rev: main
+            hooks:
+              - id: mypy
+          - repo: local
+            hooks:
+              - id: lint
+                name: lint
+                language: python
+                entry: python -c 'print("lint")'
+                additional_dependencies: [ruff, black]
+              - id: format
+                name: format
+                language: python
+                entry: python -c 'print("format")'
+                additional_dependencies: [black, ruff]
+              - id: check
+                name: check
+                language: node
+                language_version: "20"
+                entry: node -e 'console.log(1)'
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+    "#});
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--dry-run"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Repos to clone:
+      https://github.com/pre-commit/pre-commit-hooks@v5.0.0
+      https://github.com/pre-commit/mirrors-mypy@main (moving ref, resolved when cloned)
+
+    Environments to create:
+      python (default) for `.:lint`, `.:format`
+        additional dependencies: black, ruff
+        toolchain: found on the system or in the store, downloaded otherwise
+      node (20) for `.:check`
+        toolchain: found on the system or in the store, downloaded otherwise
+    Environments for hooks of repos to clone are not listed; they are known after cloning.
+
+    ----- stderr -----
+    warning: The following repos have mutable `rev` fields (moving tag / branch):
+    https://github.com/pre-commit/mirrors-mypy: main
+    Mutable references are never updated after first install and are not supported.
+    See https://pre-commit.com/#using-the-latest-version-for-a-repository for more details.
+    hint: `prek auto-update` often fixes this",
+    "#);
+
+    // Nothing was cloned or installed.
+    for dir in ["repos", "hooks"] {
+        let entries = std::fs::read_dir(context.home_dir().child(dir))
+            .map(Iterator::count)
+            .unwrap_or_default();
+        assert_eq!(entries, 0, "`{dir}` should be empty");
+    }
+}
+
 #[test]
 fn install_with_legacy_install_hooks_subcommand_alias() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -1582,7 +1881,7 @@ fn workspace_install_hooks() -> anyhow::Result<()> {
     context.git_add(".");
 
     // Install by selectors
-    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("project3").arg("--skip").arg("project3/project5/"), @r"
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("project3/").arg("--skip").arg("project3/project5/"), @r"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -135,3 +135,68 @@ fn remote_repo_install() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Dependencies listed in the repo's `environment.yml` are installed into the hook env.
+#[test]
+fn remote_repo_environment_file() -> anyhow::Result<()> {
+    if cfg!(windows) || which::which("conda").is_err() {
+        return Ok(());
+    }
+
+    let hook_repo = TestContext::new();
+    hook_repo.init_project();
+
+    hook_repo
+        .work_dir()
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r#"
+            - id: conda-env-file
+              name: conda-env-file
+              language: conda
+              entry: sh -c 'test -x "$CONDA_PREFIX/bin/jq" && echo "jq from environment.yml"'
+        "#})?;
+
+    hook_repo
+        .work_dir()
+        .child("environment.yml")
+        .write_str(indoc::indoc! {r"
+            channels:
+              - conda-forge
+            dependencies:
+              - jq
+        "})?;
+
+    hook_repo.git_add(".");
+    hook_repo.git_commit("Add conda hook");
+    hook_repo.git_tag("v1.0.0");
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: conda-env-file
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    ", hook_repo.work_dir().display()});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    conda-env-file...........................................................Passed
+    - hook id: conda-env-file
+    - duration: [TIME]
+
+      jq from environment.yml
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -139,3 +139,115 @@ fn docker_image_does_not_resolve_entry() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `stdin_from: files` feeds each file to the container's stdin.
+#[test]
+fn docker_image_stdin_from_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("a.txt").write_str("first file\n")?;
+    cwd.child("b.txt").write_str("second file\n")?;
+
+    Command::new("docker")
+        .args(["pull", "docker.io/library/alpine:latest"])
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: alpine-upper
+                name: Alpine upper
+                language: docker_image
+                entry: docker.io/library/alpine:latest tr a-z A-Z
+                files: \.txt$
+                stdin_from: files
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Alpine upper.............................................................Passed
+    - hook id: alpine-upper
+    - duration: [TIME]
+
+      FIRST FILE
+      SECOND FILE
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Test that `container_image` runs `system` and `script` hooks inside the image.
+#[test]
+fn container_image_option() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("hello.txt").write_str("hello from file\n")?;
+    cwd.child("scripts").create_dir_all()?;
+    let script = cwd.child("scripts/check.sh");
+    script.write_str("#!/bin/sh\ngrep -H hello \"$@\"\n")?;
+
+    let mut perms = fs_err::metadata(script.path())?.permissions();
+    perms.set_mode(0o755);
+    fs_err::set_permissions(script.path(), perms)?;
+
+    Command::new("docker")
+        .args(["pull", "docker.io/library/alpine:latest"])
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: system-in-container
+                name: system in container
+                language: system
+                entry: sh -c 'echo "$GREETING from $(cat /etc/alpine-release | cut -c1)"; cat "$@"' sh
+                container_image: docker.io/library/alpine:latest
+                env:
+                  GREETING: hi
+                files: \.txt$
+                verbose: true
+              - id: script-in-container
+                name: script in container
+                language: script
+                entry: scripts/check.sh
+                container_image: docker.io/library/alpine:latest
+                files: \.txt$
+                verbose: true
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    system in container......................................................Passed
+    - hook id: system-in-container
+    - duration: [TIME]
+
+      hi from 3
+      hello from file
+    script in container......................................................Passed
+    - hook id: script-in-container
+    - duration: [TIME]
+
+      hello.txt:hello from file
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
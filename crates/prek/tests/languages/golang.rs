@@ -320,6 +320,81 @@ fn local_additional_deps() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Hook `env` values can reference the Go environment prek sets up for the hook.
+#[test]
+fn env_references_go_env() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: go-env
+                name: go-env
+                language: golang
+                entry: go run ./check.go
+                language_version: '1.23.11' # will auto download
+                env:
+                  TOOLS_DIR: "$GOBIN"
+                  MODULE_ROOT: "$GOPATH"
+                  PATH: "$PATH:/extra"
+                pass_filenames: false
+                verbose: true
+    "#});
+
+    context
+        .work_dir()
+        .child("check.go")
+        .write_str(indoc::indoc! {r#"
+        package main
+
+        import (
+            "fmt"
+            "os"
+            "path/filepath"
+            "strings"
+        )
+
+        func main() {
+            gobin := os.Getenv("GOBIN")
+            if filepath.Base(gobin) != "bin" || os.Getenv("TOOLS_DIR") != gobin {
+                fmt.Println("GOBIN is not the hook env:", gobin, os.Getenv("TOOLS_DIR"))
+                os.Exit(1)
+            }
+            gopath := os.Getenv("EXPECTED_GOPATH")
+            if os.Getenv("GOPATH") != gopath || os.Getenv("MODULE_ROOT") != gopath {
+                fmt.Println("GOPATH mismatch:", gopath, os.Getenv("GOPATH"), os.Getenv("MODULE_ROOT"))
+                os.Exit(1)
+            }
+            if !strings.HasPrefix(os.Getenv("PATH"), gobin) {
+                fmt.Println("GOBIN is not on PATH:", os.Getenv("PATH"))
+                os.Exit(1)
+            }
+            fmt.Println("go env is exposed")
+        }
+    "#})?;
+
+    context.git_add(".");
+
+    // prek-managed Go keeps its module cache in the store.
+    let gopath = context.home_dir().child("cache").child("go");
+    cmd_snapshot!(context.filters(), context.run().env("EXPECTED_GOPATH", &*gopath), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    go-env...................................................................Passed
+    - hook id: go-env
+    - duration: [TIME]
+
+      go env is exposed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Ensure `go.mod` metadata (go/toolchain directives) is used to constrain
 /// the Go version for remote hooks.
 #[test]
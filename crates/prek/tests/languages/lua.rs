@@ -215,8 +215,8 @@ fn lua_environment() {
     - hook id: lua
     - duration: [TIME]
 
-      LUA_PATH:	[HOME]/hooks/lua-[HASH]/share/lua/5.4/?.lua;[HOME]/hooks/lua-[HASH]/share/lua/5.4/?/init.lua;;
-      LUA_CPATH:	[HOME]/hooks/lua-[HASH]/lib/lua/5.4/?.so;;
+      LUA_PATH:	[HOME]/hooks/lua-[HASH]/share/lua/5.4/?.lua;[HOME]/hooks/lua-[HASH]/share/lua/5.4/?/init.lua
+      LUA_CPATH:	[HOME]/hooks/lua-[HASH]/lib/lua/5.4/?.so
 
     ----- stderr -----
     ");
@@ -230,8 +230,8 @@ fn lua_environment() {
     - hook id: lua
     - duration: [TIME]
 
-      LUA_PATH:	[HOME]/hooks/lua-[HASH]/share/lua/5.4\?.lua;[HOME]/hooks/lua-[HASH]/share/lua/5.4\?/init.lua;;
-      LUA_CPATH:	[HOME]/hooks/lua-[HASH]/lib/lua/5.4\?.dll;;
+      LUA_PATH:	[HOME]/hooks/lua-[HASH]/share/lua/5.4\?.lua;[HOME]/hooks/lua-[HASH]/share/lua/5.4\?/init.lua
+      LUA_CPATH:	[HOME]/hooks/lua-[HASH]/lib/lua/5.4\?.dll
 
     ----- stderr -----
     "#);
@@ -273,6 +273,89 @@ fn additional_dependencies() {
     ");
 }
 
+/// Test pinning the version of an additional dependency rock.
+#[test]
+fn additional_dependencies_pinned_version() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: lua
+                name: lua
+                language: lua
+                entry: lua -e 'print(require("lfs")._VERSION)'
+                additional_dependencies: ["luafilesystem 1.8.0"]
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "#});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    lua......................................................................Passed
+    - hook id: lua
+    - duration: [TIME]
+
+      LuaFileSystem 1.8.0
+
+    ----- stderr -----
+    ");
+
+    // Run again to check `check_health` finds the pinned rock in the hook tree.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    lua......................................................................Passed
+    - hook id: lua
+    - duration: [TIME]
+
+      LuaFileSystem 1.8.0
+
+    ----- stderr -----
+    ");
+}
+
+/// Test that malformed additional dependency rocks are rejected.
+#[test]
+fn invalid_additional_dependencies() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: lua
+                name: lua
+                language: lua
+                entry: lua -v
+                additional_dependencies: ["luafilesystem >= 1.8.0"]
+                pass_filenames: false
+    "#});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `lua`
+      caused by: Invalid `additional_dependencies` entry `luafilesystem >= 1.8.0` for language `lua`
+      caused by: `luafilesystem >= 1.8.0` is not a valid rock, use `name` or `name version`
+    ");
+}
+
 /// Test remote Lua hook from GitHub repository.
 #[test]
 fn remote_hook() {
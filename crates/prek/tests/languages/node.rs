@@ -2,7 +2,7 @@ use assert_fs::assert::PathAssert;
 use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
 use prek_consts::env_vars::EnvVars;
 
-use crate::common::{TestContext, cmd_snapshot, remove_bin_from_path};
+use crate::common::{TestContext, cmd_snapshot, git_cmd, remove_bin_from_path};
 
 /// Test `language_version` parsing and auto downloading works correctly.
 /// We use `setup-node` action to install node 20 in CI, so node 19 should be downloaded by prek.
@@ -358,3 +358,104 @@ fn npm_version() {
     ----- stderr -----
     ");
 }
+
+/// Test that a hook repo declaring `packageManager: pnpm` is installed with pnpm via corepack.
+#[test]
+fn pnpm_package_manager() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook_repo = context.work_dir().child("pnpm-hook-repo");
+    hook_repo.create_dir_all()?;
+    hook_repo
+        .child("package.json")
+        .write_str(indoc::indoc! {r#"
+        {
+          "name": "pnpm-hook",
+          "version": "1.0.0",
+          "packageManager": "pnpm@8.15.9",
+          "bin": { "pnpm-hook": "index.js" }
+        }
+    "#})?;
+    hook_repo.child("index.js").write_str(indoc::indoc! {r#"
+        #!/usr/bin/env node
+        console.log("Hello from pnpm hook");
+    "#})?;
+    hook_repo
+        .child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+            - id: pnpm-hook
+              name: pnpm-hook
+              entry: pnpm-hook
+              language: node
+              pass_filenames: false
+              always_run: true
+        "})?;
+
+    for args in [
+        &["init"][..],
+        &["add", "."],
+        &["commit", "-m", "Initial commit"],
+    ] {
+        let output = git_cmd(&hook_repo).args(args).output()?;
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+    }
+    let rev = git_cmd(&hook_repo)
+        .args(["rev-parse", "HEAD"])
+        .output()?
+        .stdout;
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: {}
+            hooks:
+              - id: pnpm-hook
+                verbose: true
+        ",
+        hook_repo.display(),
+        String::from_utf8_lossy(&rev).trim(),
+    });
+    context.git_add(".pre-commit-config.yaml");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pnpm-hook................................................................Passed
+    - hook id: pnpm-hook
+    - duration: [TIME]
+
+      Hello from pnpm hook
+
+    ----- stderr -----
+    ");
+
+    // pnpm installs into its global dir with a lockfile and a `.pnpm` virtual store,
+    // while npm would have installed the hook package into `lib/node_modules`.
+    let env_dir = fs_err::read_dir(context.home_dir().join("hooks"))?
+        .filter_map(Result::ok)
+        .find(|e| e.file_name().to_string_lossy().starts_with("node-"))
+        .map(|e| e.path())
+        .expect("No node hook directory found");
+    let pnpm_dirs = fs_err::read_dir(env_dir.join("pnpm-global"))?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .collect::<Vec<_>>();
+    assert!(
+        pnpm_dirs
+            .iter()
+            .any(|dir| dir.join("pnpm-lock.yaml").is_file()
+                && dir.join("node_modules").join(".pnpm").is_dir()),
+        "Expected a pnpm global install in {pnpm_dirs:?}"
+    );
+    assert!(
+        !env_dir
+            .join("lib")
+            .join("node_modules")
+            .join("pnpm-hook")
+            .exists()
+    );
+
+    Ok(())
+}
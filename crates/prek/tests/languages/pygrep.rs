@@ -224,6 +224,49 @@ fn negate_multiline_mode() -> Result<()> {
     Ok(())
 }
 
+/// Enforce a required multiline license header with `--multiline --negate`.
+#[test]
+fn negate_multiline_license_header() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("licensed.py").write_str(
+        "# Copyright (c) Example Corp.\n# SPDX-License-Identifier: MIT\n\nprint('ok')\n",
+    )?;
+    // The header must be at the top of the file and complete.
+    cwd.child("partial.py")
+        .write_str("# Copyright (c) Example Corp.\n\nprint('missing SPDX line')\n")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: license-header
+                name: license-header
+                language: pygrep
+                entry: '\A# Copyright \(c\) Example Corp\.\n# SPDX-License-Identifier: MIT\n'
+                args: ["--multiline", "--negate"]
+                files: "\\.py$"
+        "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    license-header...........................................................Failed
+    - hook id: license-header
+    - exit code: 1
+
+      partial.py
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test invalid regex pattern
 #[test]
 fn invalid_regex() {
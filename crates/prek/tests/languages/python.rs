@@ -364,6 +364,60 @@ fn hook_stderr() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Hook `env` values can reference the virtualenv prek created for the hook.
+#[test]
+fn env_references_virtual_env() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: local
+                name: local
+                language: python
+                entry: python ./hook.py
+                env:
+                  STUBS_DIR: "$VIRTUAL_ENV/stubs"
+                  PATH: "$PATH:/extra"
+                pass_filenames: false
+                verbose: true
+    "#});
+
+    context
+        .work_dir()
+        .child("hook.py")
+        .write_str(indoc::indoc! {r#"
+        import os
+        import sys
+
+        venv = os.environ["VIRTUAL_ENV"]
+        assert os.path.samefile(venv, sys.prefix), (venv, sys.prefix)
+        assert os.environ["STUBS_DIR"] == os.path.join(venv, "stubs")
+        bin_dir = os.path.dirname(sys.executable)
+        assert os.environ["PATH"].startswith(bin_dir), os.environ["PATH"]
+        print("virtualenv is exposed")
+    "#})?;
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    local....................................................................Passed
+    - hook id: local
+    - duration: [TIME]
+
+      virtualenv is exposed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test that pep723 script for local hook is installed correctly.
 /// Only if no additional dependencies are specified.
 #[test]
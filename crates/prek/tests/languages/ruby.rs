@@ -2,6 +2,8 @@ use std::env::consts::EXE_EXTENSION;
 
 use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
 
+use prek_consts::PRE_COMMIT_HOOKS_YAML;
+
 use crate::common::{TestContext, cmd_snapshot, git_cmd};
 
 /// Test basic Ruby hook with system Ruby
@@ -41,17 +43,15 @@ fn system_ruby() {
     success: true
     exit_code: 0
     ----- stdout -----
+    [ruby-version] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-version-unspecified] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version.............................................................Passed
     - hook id: ruby-version
     - duration: [TIME]
-
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version-unspecified.................................................Passed
     - hook id: ruby-version-unspecified
     - duration: [TIME]
 
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
-
     ----- stderr -----
     ");
 }
@@ -88,12 +88,11 @@ fn language_version_default() {
     success: true
     exit_code: 0
     ----- stdout -----
+      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-default.............................................................Passed
     - hook id: ruby-default
     - duration: [TIME]
 
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
-
     ----- stderr -----
     ");
 }
@@ -157,32 +156,27 @@ fn specific_ruby_available() {
     success: true
     exit_code: 0
     ----- stdout -----
+    [ruby-version-prefixed] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-version] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-version-range-min] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-version-range-max] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-version-constrained-range] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version-prefixed....................................................Passed
     - hook id: ruby-version-prefixed
     - duration: [TIME]
-
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version.............................................................Passed
     - hook id: ruby-version
     - duration: [TIME]
-
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version-range-min...................................................Passed
     - hook id: ruby-version-range-min
     - duration: [TIME]
-
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version-range-max...................................................Passed
     - hook id: ruby-version-range-max
     - duration: [TIME]
-
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-version-constrained-range...........................................Passed
     - hook id: ruby-version-constrained-range
     - duration: [TIME]
 
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
-
     ----- stderr -----
     ");
 }
@@ -292,8 +286,8 @@ fn additional_gem_dependencies() -> anyhow::Result<()> {
     let filters = [
         // Normalize unpinned rspec version (only for test-gem-require, not test-gem-require-versioned)
         (
-            r"(- hook id: test-gem-require\n- duration: .*?\n\n)  \d+\.\d+\.\d+",
-            "$1  X.Y.Z",
+            r"\[test-gem-require\] \d+\.\d+\.\d+",
+            "[test-gem-require] X.Y.Z",
         ),
         // Normalize Ruby internal paths
         (r"<internal:[^>]+>:\d+:in", "<internal:[RUBY_LIB]>:[X]:in"),
@@ -306,25 +300,22 @@ fn additional_gem_dependencies() -> anyhow::Result<()> {
     success: false
     exit_code: 1
     ----- stdout -----
+    [test-gem-require] X.Y.Z
+    [test-gem-require-versioned] 3.12.0
+    [test-gem-require-missing] <internal:[RUBY_LIB]>:[X]:in 'Kernel#require': cannot load such file -- rspec (LoadError)
+    [test-gem-require-missing] 	from <internal:[RUBY_LIB]>:[X]:in 'Kernel#require'
+    [test-gem-require-missing] 	from test_script.rb:1:in '<main>'
     test-gem-require.........................................................Passed
     - hook id: test-gem-require
     - duration: [TIME]
-
-      X.Y.Z
     test-gem-require-versioned...............................................Passed
     - hook id: test-gem-require-versioned
     - duration: [TIME]
-
-      3.12.0
     test-gem-require-missing.................................................Failed
     - hook id: test-gem-require-missing
     - duration: [TIME]
     - exit code: 1
 
-      <internal:[RUBY_LIB]>:[X]:in 'Kernel#require': cannot load such file -- rspec (LoadError)
-      	from <internal:[RUBY_LIB]>:[X]:in 'Kernel#require'
-      	from test_script.rb:1:in '<main>'
-
     ----- stderr -----
     ");
 
@@ -393,12 +384,11 @@ fn gemspec_workflow() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hello from TestGem
     test-gemspec.............................................................Passed
     - hook id: test-gemspec
     - duration: [TIME]
 
-      Hello from TestGem
-
     ----- stderr -----
     ");
 
@@ -675,15 +665,80 @@ fn local_hook_with_gemspec() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hook executed from gem!
     my-hook..................................................................Passed
     - hook id: my-hook
     - duration: [TIME]
 
-      Hook executed from gem!
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Test remote Ruby hook repository with a `Gemfile`, installed with bundler
+#[test]
+fn remote_hook_with_gemfile() -> anyhow::Result<()> {
+    let hook_repo = TestContext::new();
+    hook_repo.init_project();
+
+    hook_repo
+        .work_dir()
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r#"
+            - id: bundled-hook
+              name: bundled-hook
+              language: ruby
+              entry: ruby -e 'require "rspec"; puts RSpec::Version::STRING'
+              pass_filenames: false
+        "#})?;
+    hook_repo
+        .work_dir()
+        .child("Gemfile")
+        .write_str(indoc::indoc! {r#"
+            source "https://rubygems.org"
+
+            gem "rspec", "3.12.0"
+        "#})?;
+
+    hook_repo.git_add(".");
+    hook_repo.git_commit("Add bundled hook");
+    hook_repo.git_tag("v1.0.0");
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: bundled-hook
+                always_run: true
+                verbose: true
+    ", hook_repo.work_dir().display()});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    bundled-hook.............................................................Passed
+    - hook id: bundled-hook
+    - duration: [TIME]
+
+      3.12.0
 
     ----- stderr -----
     ");
 
+    // The bundle is resolved in the hook environment, leaving the repo clone untouched.
+    let clone = std::fs::read_dir(context.home_dir().child("repos"))?
+        .next()
+        .expect("repo was cloned")?
+        .path();
+    assert!(clone.join("Gemfile").is_file());
+    assert!(!clone.join("Gemfile.lock").exists());
+
     Ok(())
 }
 
@@ -728,13 +783,12 @@ fn native_gem_dependency() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      MessagePack native extension working!
+      Packed size: 21 bytes
     test-native-gem..........................................................Passed
     - hook id: test-native-gem
     - duration: [TIME]
 
-      MessagePack native extension working!
-      Packed size: 21 bytes
-
     ----- stderr -----
     ");
 
@@ -852,13 +906,12 @@ fn multiple_gemspecs() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      rainbow=X.Y.Z
+      udw=X.Y.Z
     Multi Gem................................................................Passed
     - hook id: multi-gem
     - duration: [TIME]
 
-      rainbow=X.Y.Z
-      udw=X.Y.Z
-
     ----- stderr -----
     ");
 
@@ -912,12 +965,11 @@ fn prebuilt_vs_compiled_gems() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      sqlite3=X.Y.Z msgpack=X.Y.Z
     test-native-gems.........................................................Passed
     - hook id: test-native-gems
     - duration: [TIME]
 
-      sqlite3=X.Y.Z msgpack=X.Y.Z
-
     ----- stderr -----
     ");
 
@@ -1078,17 +1130,15 @@ fn auto_download() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+    [ruby-downloaded] ruby 3.2.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    [ruby-system] ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-downloaded..........................................................Passed
     - hook id: ruby-downloaded
     - duration: [TIME]
-
-      ruby 3.2.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-system..............................................................Passed
     - hook id: ruby-system
     - duration: [TIME]
 
-      ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
-
     ----- stderr -----
     ");
 
@@ -1145,12 +1195,11 @@ fn auto_download() -> anyhow::Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      ruby 3.2.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
     ruby-reused..............................................................Passed
     - hook id: ruby-reused
     - duration: [TIME]
 
-      ruby 3.2.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
-
     ----- stderr -----
     ");
 
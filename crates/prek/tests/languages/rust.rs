@@ -124,18 +124,17 @@ fn rustup_installer() {
         .chain(context.filters())
         .collect::<Vec<_>>();
 
-    cmd_snapshot!(filters, context.run().arg("-v").env(EnvVars::PREK_INTERNAL__RUSTUP_BINARY_NAME, "non-exist-rustup"), @r#"
+    cmd_snapshot!(filters, context.run().arg("-v").env(EnvVars::PREK_INTERNAL__RUSTUP_BINARY_NAME, "non-exist-rustup"), @"
     success: true
     exit_code: 0
     ----- stdout -----
+      rustc 1.X.X
     rustup-test..............................................................Passed
     - hook id: rustup-test
     - duration: [TIME]
 
-      rustc 1.X.X
-
     ----- stderr -----
-    "#);
+    ");
 }
 
 /// Test that `additional_dependencies` with cli: prefix are installed correctly.
@@ -174,6 +173,64 @@ fn additional_dependencies_cli() {
     ");
 }
 
+/// Test that `component:` and `target:` dependencies are added to the hook toolchain.
+#[test]
+fn additional_dependencies_components() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: rust-clippy
+                name: rust-clippy
+                language: rust
+                entry: cargo-clippy --version
+                additional_dependencies: ["component:clippy", "component:rustfmt"]
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "#});
+
+    context.git_add(".");
+
+    let filters = [(r"clippy 0\.1\.\d+ .+", "clippy 0.1.X")]
+        .into_iter()
+        .chain(context.filters())
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters.clone(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rust-clippy..............................................................Passed
+    - hook id: rust-clippy
+    - duration: [TIME]
+
+      clippy 0.1.X
+
+    ----- stderr -----
+    ");
+
+    // The installed components pass the health check, so the environment is reused.
+    cmd_snapshot!(filters, context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rust-clippy..............................................................Passed
+    - hook id: rust-clippy
+    - duration: [TIME]
+
+      clippy 0.1.X
+
+    ----- stderr -----
+    ");
+    assert_eq!(context.home_dir().child("hooks").read_dir()?.count(), 1);
+
+    Ok(())
+}
+
 /// Test that remote Rust hooks are installed and run correctly.
 #[test]
 fn remote_hooks() {
@@ -114,3 +114,62 @@ fn shell_entry_receives_hook_args_before_filenames() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[test]
+fn files_placeholder_positions_filenames() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+    repos:
+      - repo: local
+        hooks:
+          - id: placeholder
+            name: placeholder
+            language: system
+            files: \.txt$
+            entry: echo before {files}
+            args: [after]
+            verbose: true
+          - id: placeholder-in-args
+            name: placeholder-in-args
+            language: system
+            files: \.txt$
+            entry: echo before
+            args: ['{files}', after]
+            verbose: true
+          - id: placeholder-no-filenames
+            name: placeholder-no-filenames
+            language: system
+            entry: echo before {files} after
+            pass_filenames: false
+            verbose: true
+    "});
+    context.work_dir().child("a.txt").write_str("a")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    placeholder..............................................................Passed
+    - hook id: placeholder
+    - duration: [TIME]
+
+      before a.txt after
+    placeholder-in-args......................................................Passed
+    - hook id: placeholder-in-args
+    - duration: [TIME]
+
+      before a.txt after
+    placeholder-no-filenames.................................................Passed
+    - hook id: placeholder-no-filenames
+    - duration: [TIME]
+
+      before after
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -523,7 +523,14 @@ fn list_json_output() {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "yaml"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "."
       },
       {
         "id": "check-json",
@@ -544,7 +551,14 @@ fn list_json_output() {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "json"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "."
       }
     ]
 
@@ -576,7 +590,14 @@ fn list_json_output() {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "json"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "."
       }
     ]
 
@@ -584,6 +605,57 @@ fn list_json_output() {
     "#);
 }
 
+#[test]
+fn list_jsonl_output() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        default_stages: [pre-commit, pre-push]
+        repos:
+          - repo: local
+            hooks:
+              - id: check-yaml
+                name: Check YAML
+                entry: check-yaml
+                language: system
+                types: [yaml]
+              - id: check-json
+                name: Check JSON
+                entry: check-json
+                language: system
+                types_or: [json, yaml]
+                always_run: true
+                stages: [manual]
+              - id: lint-python
+                name: Lint Python
+                entry: ruff
+                language: python
+    "});
+
+    // Effective options are serialized: `default_stages` is applied to hooks without `stages`.
+    cmd_snapshot!(context.filters(), context.list().arg("--output-format=jsonl"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"id":"check-yaml","full_id":".:check-yaml","name":"Check YAML","alias":"","language":"system","description":null,"stages":["pre-commit","pre-push"],"types":["yaml"],"types_or":[],"exclude_types":[],"always_run":false,"project":"."}
+    {"id":"check-json","full_id":".:check-json","name":"Check JSON","alias":"","language":"system","description":null,"stages":["manual"],"types":["file"],"types_or":["json","yaml"],"exclude_types":[],"always_run":true,"project":"."}
+    {"id":"lint-python","full_id":".:lint-python","name":"Lint Python","alias":"","language":"python","description":null,"stages":["pre-commit","pre-push"],"types":["file"],"types_or":[],"exclude_types":[],"always_run":false,"project":"."}
+
+    ----- stderr -----
+    "#);
+
+    // Filters apply before serialization.
+    cmd_snapshot!(context.filters(), context.list().arg("--language=system").arg("--hook-stage=pre-push").arg("--output-format=jsonl"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"id":"check-yaml","full_id":".:check-yaml","name":"Check YAML","alias":"","language":"system","description":null,"stages":["pre-commit","pre-push"],"types":["yaml"],"types_or":[],"exclude_types":[],"always_run":false,"project":"."}
+
+    ----- stderr -----
+    "#);
+}
+
 #[test]
 fn workspace_list() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -651,7 +723,14 @@ fn workspace_list() -> anyhow::Result<()> {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "file"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "nested/project4"
       },
       {
         "id": "show-cwd",
@@ -672,7 +751,14 @@ fn workspace_list() -> anyhow::Result<()> {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "file"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "project3/project5"
       },
       {
         "id": "show-cwd",
@@ -693,7 +779,14 @@ fn workspace_list() -> anyhow::Result<()> {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "file"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "project2"
       },
       {
         "id": "show-cwd",
@@ -714,7 +807,14 @@ fn workspace_list() -> anyhow::Result<()> {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "file"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "project3"
       },
       {
         "id": "show-cwd",
@@ -735,7 +835,14 @@ fn workspace_list() -> anyhow::Result<()> {
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "types": [
+          "file"
+        ],
+        "types_or": [],
+        "exclude_types": [],
+        "always_run": false,
+        "project": "."
       }
     ]
 
@@ -11,6 +11,7 @@ fn list_builtins_basic() {
     exit_code: 0
     ----- stdout -----
     check-added-large-files
+    check-builtin-literals
     check-case-conflict
     check-executables-have-shebangs
     check-illegal-windows-names
@@ -23,15 +24,19 @@ fn list_builtins_basic() {
     check-vcs-permalinks
     check-xml
     check-yaml
+    debug-statements
     destroyed-symlinks
     detect-private-key
     end-of-file-fixer
     file-contents-sorter
     fix-byte-order-marker
+    fix-encoding-pragma
     forbid-new-submodules
     mixed-line-ending
+    name-tests-test
     no-commit-to-branch
     pretty-format-json
+    sort-simple-yaml
     trailing-whitespace
 
     ----- stderr -----
@@ -49,6 +54,9 @@ fn list_builtins_verbose() {
     check-added-large-files
       prevents giant files from being committed.
 
+    check-builtin-literals
+      requires literal syntax when initializing empty or zero python builtin types.
+
     check-case-conflict
       checks for files that would conflict in case-insensitive filesystems
 
@@ -85,6 +93,9 @@ fn list_builtins_verbose() {
     check-yaml
       checks yaml files for parseable syntax.
 
+    debug-statements
+      checks for debugger imports and py37+ `breakpoint()` calls in python source.
+
     destroyed-symlinks
       detects symlinks that were replaced with regular files whose contents are the original symlink target path.
 
@@ -100,17 +111,26 @@ fn list_builtins_verbose() {
     fix-byte-order-marker
       removes utf-8 byte order marker.
 
+    fix-encoding-pragma
+      adds # -*- coding: utf-8 -*- to the top of python files.
+
     forbid-new-submodules
       Prevent addition of new git submodules.
 
     mixed-line-ending
       replaces or checks mixed line ending.
 
+    name-tests-test
+      verifies that test files are named correctly.
+
     no-commit-to-branch
 
     pretty-format-json
       checks that JSON files are pretty-formatted.
 
+    sort-simple-yaml
+      sorts simple yaml files which consist only of top-level keys, preserving comments and blocks.
+
     trailing-whitespace
       trims trailing whitespace.
 
@@ -133,6 +153,11 @@ fn list_builtins_json() {
         "name": "check for added large files",
         "description": "prevents giant files from being committed."
       },
+      {
+        "id": "check-builtin-literals",
+        "name": "check builtin type constructor use",
+        "description": "requires literal syntax when initializing empty or zero python builtin types."
+      },
       {
         "id": "check-case-conflict",
         "name": "check for case conflicts",
@@ -193,6 +218,11 @@ fn list_builtins_json() {
         "name": "check yaml",
         "description": "checks yaml files for parseable syntax."
       },
+      {
+        "id": "debug-statements",
+        "name": "debug statements (python)",
+        "description": "checks for debugger imports and py37+ `breakpoint()` calls in python source."
+      },
       {
         "id": "destroyed-symlinks",
         "name": "detect destroyed symlinks",
@@ -218,6 +248,11 @@ fn list_builtins_json() {
         "name": "fix utf-8 byte order marker",
         "description": "removes utf-8 byte order marker."
       },
+      {
+        "id": "fix-encoding-pragma",
+        "name": "fix python encoding pragma",
+        "description": "adds # -*- coding: utf-8 -*- to the top of python files."
+      },
       {
         "id": "forbid-new-submodules",
         "name": "forbid new submodules",
@@ -228,6 +263,11 @@ fn list_builtins_json() {
         "name": "mixed line ending",
         "description": "replaces or checks mixed line ending."
       },
+      {
+        "id": "name-tests-test",
+        "name": "python tests naming",
+        "description": "verifies that test files are named correctly."
+      },
       {
         "id": "no-commit-to-branch",
         "name": "don't commit to branch",
@@ -238,6 +278,11 @@ fn list_builtins_json() {
         "name": "pretty format json",
         "description": "checks that JSON files are pretty-formatted."
       },
+      {
+        "id": "sort-simple-yaml",
+        "name": "sort simple yaml files",
+        "description": "sorts simple yaml files which consist only of top-level keys, preserving comments and blocks."
+      },
       {
         "id": "trailing-whitespace",
         "name": "trim trailing whitespace",
@@ -248,3 +293,44 @@ fn list_builtins_json() {
     ----- stderr -----
     "#);
 }
+
+#[test]
+fn list_builtins_jsonl() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.command().arg("util").arg("list-builtins").arg("--output-format=jsonl"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"id":"check-added-large-files","name":"check for added large files","description":"prevents giant files from being committed."}
+    {"id":"check-builtin-literals","name":"check builtin type constructor use","description":"requires literal syntax when initializing empty or zero python builtin types."}
+    {"id":"check-case-conflict","name":"check for case conflicts","description":"checks for files that would conflict in case-insensitive filesystems"}
+    {"id":"check-executables-have-shebangs","name":"check that executables have shebangs","description":"ensures that (non-binary) executables have a shebang."}
+    {"id":"check-illegal-windows-names","name":"check illegal windows names","description":"checks for filenames which cannot be created on Windows."}
+    {"id":"check-json","name":"check json","description":"checks json files for parseable syntax."}
+    {"id":"check-json5","name":"check json5","description":"checks json5 files for parseable syntax."}
+    {"id":"check-merge-conflict","name":"check for merge conflicts","description":"checks for files that contain merge conflict strings."}
+    {"id":"check-shebang-scripts-are-executable","name":"check that scripts with shebangs are executable","description":"ensures that (non-binary) files with a shebang are executable."}
+    {"id":"check-symlinks","name":"check for broken symlinks","description":"checks for symlinks which do not point to anything."}
+    {"id":"check-toml","name":"check toml","description":"checks toml files for parseable syntax."}
+    {"id":"check-vcs-permalinks","name":"check vcs permalinks","description":"ensures that links to vcs websites are permalinks."}
+    {"id":"check-xml","name":"check xml","description":"checks xml files for parseable syntax."}
+    {"id":"check-yaml","name":"check yaml","description":"checks yaml files for parseable syntax."}
+    {"id":"debug-statements","name":"debug statements (python)","description":"checks for debugger imports and py37+ `breakpoint()` calls in python source."}
+    {"id":"destroyed-symlinks","name":"detect destroyed symlinks","description":"detects symlinks that were replaced with regular files whose contents are the original symlink target path."}
+    {"id":"detect-private-key","name":"detect private key","description":"detects the presence of private keys."}
+    {"id":"end-of-file-fixer","name":"fix end of files","description":"ensures that a file is either empty, or ends with one newline."}
+    {"id":"file-contents-sorter","name":"file contents sorter","description":"sorts the lines in specified files (defaults to alphabetical)."}
+    {"id":"fix-byte-order-marker","name":"fix utf-8 byte order marker","description":"removes utf-8 byte order marker."}
+    {"id":"fix-encoding-pragma","name":"fix python encoding pragma","description":"adds # -*- coding: utf-8 -*- to the top of python files."}
+    {"id":"forbid-new-submodules","name":"forbid new submodules","description":"Prevent addition of new git submodules."}
+    {"id":"mixed-line-ending","name":"mixed line ending","description":"replaces or checks mixed line ending."}
+    {"id":"name-tests-test","name":"python tests naming","description":"verifies that test files are named correctly."}
+    {"id":"no-commit-to-branch","name":"don't commit to branch","description":null}
+    {"id":"pretty-format-json","name":"pretty format json","description":"checks that JSON files are pretty-formatted."}
+    {"id":"sort-simple-yaml","name":"sort simple yaml files","description":"sorts simple yaml files which consist only of top-level keys, preserving comments and blocks."}
+    {"id":"trailing-whitespace","name":"trim trailing whitespace","description":"trims trailing whitespace."}
+
+    ----- stderr -----
+    "#);
+}
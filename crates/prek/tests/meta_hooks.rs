@@ -51,6 +51,7 @@ fn meta_hooks() -> anyhow::Result<()> {
     - hook id: check-useless-excludes
     - exit code: 1
 
+      The files pattern `regex: ^nonexistent$` for `match-no-files` does not match any files
       The exclude pattern `regex: $nonexistent^` for `useless-exclude` does not match any files
     identity.................................................................Passed
     - hook id: identity
@@ -203,6 +204,7 @@ fn meta_hooks_workspace() -> anyhow::Result<()> {
       - hook id: check-useless-excludes
       - exit code: 1
 
+        The files pattern `regex: ^nonexistent$` for `match-no-files` does not match any files
         The exclude pattern `regex: $nonexistent^` for `useless-exclude` does not match any files
       identity...............................................................Passed
       - hook id: identity
@@ -271,3 +273,157 @@ fn check_useless_excludes_workspace_paths_are_project_relative() -> anyhow::Resu
 
     Ok(())
 }
+
+#[test]
+fn check_useless_excludes_reports_unmatched_files_patterns() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        exclude: ^build/
+        repos:
+          - repo: meta
+            hooks:
+              - id: check-useless-excludes
+          - repo: local
+            hooks:
+              - id: rust-only
+                name: rust only
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                files: '[.]rs$'
+                exclude: ^vendor/
+              - id: python-only
+                name: python only
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                files: '[.]py$'
+    "});
+    context.work_dir().child("main.py").write_str("")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("check-useless-excludes"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Check useless excludes...................................................Failed
+    - hook id: check-useless-excludes
+    - exit code: 1
+
+      The global exclude pattern `regex: ^build/` does not match any files
+      The files pattern `regex: [.]rs$` for `rust-only` does not match any files
+      The exclude pattern `regex: ^vendor/` for `rust-only` does not match any files
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn check_hooks_apply_types_and_patterns() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("main.py").write_str("print('hello')\n")?;
+    cwd.child("vendor").create_dir_all()?;
+    cwd.child("vendor/lib.js").write_str("")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: meta
+            hooks:
+              - id: check-hooks-apply
+          - repo: local
+            hooks:
+              - id: python
+                name: python
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types: [python]
+              - id: renamed-tag
+                name: renamed tag
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types: [rust]
+              - id: types-or
+                name: types or
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types_or: [rust, javascript]
+              - id: excluded-types
+                name: excluded types
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types_or: [python, javascript]
+                exclude_types: [text]
+              - id: excluded-files
+                name: excluded files
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types: [javascript]
+                exclude: ^vendor/
+              - id: always-run
+                name: always run
+                language: system
+                entry: python3 -c 'import sys; sys.exit(0)'
+                types: [rust]
+                always_run: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("check-hooks-apply"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Check hooks apply........................................................Failed
+    - hook id: check-hooks-apply
+    - exit code: 1
+
+      renamed-tag does not apply to this repository
+      excluded-types does not apply to this repository
+      excluded-files does not apply to this repository
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn identity_prints_args_and_filenames() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("main.py").write_str("")?;
+    cwd.child("README.md").write_str("")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: meta
+            hooks:
+              - id: identity
+                args: [--flag, value]
+                types: [python]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    identity.................................................................Passed
+    - hook id: identity
+    - duration: [TIME]
+
+      --flag
+      value
+      main.py
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
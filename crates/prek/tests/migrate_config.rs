@@ -0,0 +1,89 @@
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use prek_consts::PRE_COMMIT_CONFIG_YAML;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn migrate_config_rewrites_legacy_syntax() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc::indoc! {r"
+        minimum_pre_commit_version: '2.9.0'
+        default_stages: [commit, push]
+        repos:
+          # Pinned on purpose.
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            sha: v5.0.0  # keep me
+            hooks:
+              - id: trailing-whitespace
+                stages:
+                  - merge-commit
+                  - manual
+    "};
+    context
+        .work_dir()
+        .child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(config)?;
+
+    cmd_snapshot!(context.filters(), context.migrate_config().arg("--dry-run"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Would migrate `.pre-commit-config.yaml`:
+      line 1: removed `minimum_pre_commit_version`, which prek ignores
+      line 2: stage `commit` → `pre-commit`
+      line 2: stage `push` → `pre-push`
+      line 6: `sha` → `rev`
+      line 10: stage `merge-commit` → `pre-merge-commit`
+
+    ----- stderr -----
+    ");
+    assert_eq!(context.read(PRE_COMMIT_CONFIG_YAML), config);
+
+    cmd_snapshot!(context.filters(), context.migrate_config(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Migrated `.pre-commit-config.yaml`:
+      line 1: removed `minimum_pre_commit_version`, which prek ignores
+      line 2: stage `commit` → `pre-commit`
+      line 2: stage `push` → `pre-push`
+      line 6: `sha` → `rev`
+      line 10: stage `merge-commit` → `pre-merge-commit`
+
+    ----- stderr -----
+    ");
+    insta::assert_snapshot!(context.read(PRE_COMMIT_CONFIG_YAML), @"
+    default_stages: [pre-commit, pre-push]
+    repos:
+      # Pinned on purpose.
+      - repo: https://github.com/pre-commit/pre-commit-hooks
+        rev: v5.0.0  # keep me
+        hooks:
+          - id: trailing-whitespace
+            stages:
+              - pre-merge-commit
+              - manual
+    ");
+
+    // The migrated config is valid and has nothing left to migrate.
+    context
+        .validate_config()
+        .arg(PRE_COMMIT_CONFIG_YAML)
+        .assert()
+        .success();
+    cmd_snapshot!(context.filters(), context.migrate_config(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    `.pre-commit-config.yaml` is already up to date
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use anyhow::Result;
 use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::ChildPath;
 use assert_fs::prelude::*;
 use insta::assert_snapshot;
 use predicates::prelude::predicate;
@@ -154,6 +156,88 @@ fn run_in_non_git_repo() {
     ");
 }
 
+/// `--fail-on-warnings` turns prek's own warnings into a failing exit status.
+#[test]
+fn run_fail_on_warnings() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        unexpected_top_level_key: true
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.....................................................................Passed
+
+    ----- stderr -----
+    warning: Ignored unexpected keys in `.pre-commit-config.yaml`: `unexpected_top_level_key`
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--fail-on-warnings"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    echo.....................................................................Passed
+
+    ----- stderr -----
+    warning: Ignored unexpected keys in `.pre-commit-config.yaml`: `unexpected_top_level_key`
+    error: prek emitted 1 warning and `--fail-on-warnings` is set
+    ");
+}
+
+/// `--allow-missing-config` and `PREK_ALLOW_NO_CONFIG` turn a missing config into a no-op.
+#[test]
+fn run_allow_missing_config() {
+    let context = TestContext::new();
+    context.init_project();
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No `prek.toml` or `.pre-commit-config.yaml` found in the current directory or parent directories.
+
+    hint: If you just added one, rerun your command with the `--refresh` flag to rescan the workspace.
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--allow-missing-config"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_ALLOW_NO_CONFIG, "1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--config").arg("missing.yaml").arg("--allow-missing-config"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
 #[test]
 fn invalid_config() {
     let context = TestContext::new();
@@ -245,6 +329,30 @@ fn invalid_config() {
       caused by: Hook specified `additional_dependencies: swift-format@5.0.0` but the language `swift` does not support installing dependencies for now
     ");
 
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: lint
+                name: lint
+                language: python
+                additional_dependencies: ["ruff=0.6.9"]
+                entry: ruff check
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `lint`
+      caused by: Invalid `additional_dependencies` entry `ruff=0.6.9` for language `python`
+      caused by: `=0.6.9` is not a version specifier, expected an operator like `==` or `>=`
+    ");
+
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
@@ -834,6 +942,47 @@ fn priority_fail_fast_stops_later_groups() {
     "#);
 }
 
+/// A `require_serial` hook does not run alongside other hooks of its priority group.
+#[test]
+fn priority_group_runs_require_serial_hooks_alone() {
+    let context = TestContext::new();
+    context.init_project();
+
+    let marker = context.home_dir().child("running");
+    let marker = marker.path().display().to_string().replace('\\', "/");
+    context.write_pre_commit_config(&indoc::formatdoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: serial
+                name: Serial Hook
+                language: system
+                entry: python3 -c "import os, sys, time; time.sleep(0.2); sys.exit(os.path.exists('{marker}'))"
+                always_run: true
+                pass_filenames: false
+                require_serial: true
+                priority: 0
+              - id: slow
+                name: Slow Hook
+                language: system
+                entry: python3 -c "import os, time; open('{marker}', 'w').close(); time.sleep(0.5); os.remove('{marker}')"
+                always_run: true
+                pass_filenames: false
+                priority: 0
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_MAX_CONCURRENCY, "4"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Serial Hook..............................................................Passed
+    Slow Hook................................................................Passed
+
+    ----- stderr -----
+    ");
+}
+
 #[test]
 fn priority_group_modified_files_is_group_failure_and_output_is_indented() -> Result<()> {
     let context = TestContext::new();
@@ -1054,6 +1203,70 @@ fn skips() {
     ");
 }
 
+/// Skips hooks listed in a `.prek-skip` file, in addition to the `SKIP` environment variable.
+#[test]
+fn skip_file() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: python3 -c "exit(1)"
+              - id: end-of-file-fixer
+                name: fix end of files
+                language: system
+                entry: python3 -c "exit(1)"
+              - id: check-json
+                name: check json
+                language: system
+                entry: python3 -c "exit(1)"
+    "#});
+    context
+        .work_dir()
+        .child(".prek-skip")
+        .write_str(indoc::indoc! {"
+        # Hooks that don't work on this machine
+        end-of-file-fixer
+
+    "})?;
+    context.work_dir().child("sub").create_dir_all()?;
+    context.work_dir().child("sub/file.txt").write_str("")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().env("SKIP", "check-json"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trailing-whitespace......................................................Failed
+    - hook id: trailing-whitespace
+    - exit code: 1
+
+    ----- stderr -----
+    ");
+
+    // The file is found from a subdirectory.
+    cmd_snapshot!(context.filters(), context.run().current_dir(context.work_dir().child("sub")), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trailing-whitespace......................................................Failed
+    - hook id: trailing-whitespace
+    - exit code: 1
+    check json...............................................................Failed
+    - hook id: check-json
+    - exit code: 1
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Run hooks with matched `stage`.
 #[test]
 fn stage() {
@@ -1164,11 +1377,12 @@ fn fallback_to_manual_stage() {
     ----- stderr -----
     ");
 
-    // Selecting manual + pre-commit hooks still runs only the pre-commit ones.
+    // Hooks named by id run regardless of their stages.
     cmd_snapshot!(context.filters(), context.run().arg("manual-only").arg("default-stage"), @r"
     success: true
     exit_code: 0
     ----- stdout -----
+    manual-only..............................................................Passed
     default-stage............................................................Passed
 
     ----- stderr -----
@@ -1185,107 +1399,235 @@ fn fallback_to_manual_stage() {
     ----- stderr -----
     ");
 
-    // Mixing `pre-push` and manual selectors still runs the manual hook via fallback.
+    // Mixing `pre-push` and manual selectors runs both named hooks.
     cmd_snapshot!(context.filters(), context.run().arg("pre-push").arg("manual-only"), @r"
     success: true
     exit_code: 0
     ----- stdout -----
     manual-only..............................................................Passed
+    pre-push.................................................................Passed
 
     ----- stderr -----
     ");
 }
 
-/// Test global `files`, `exclude`, and hook level `files`, `exclude`.
+/// Hooks named by id run whatever their stages, unless they need a commit message file.
 #[test]
-fn files_and_exclude() -> Result<()> {
+fn named_hooks_ignore_stages() {
     let context = TestContext::new();
-
     context.init_project();
-
-    let cwd = context.work_dir();
-    cwd.child("file.txt").write_str("Hello, world!  \n")?;
-    cwd.child("valid.json").write_str("{}\n  ")?;
-    cwd.child("invalid.json").write_str("{}")?;
-    cwd.child("main.py").write_str(r#"print "abc"  "#)?;
-
-    // Global files and exclude.
     context.write_pre_commit_config(indoc::indoc! {r"
-        files: file.txt
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing whitespace
+              - id: manual-check
+                name: manual-check
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
-                types: [text]
-              - id: end-of-file-fixer
-                name: fix end of files
+                entry: echo manual-check
+                stages: [manual]
+              - id: commit-msg-check
+                name: commit-msg-check
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
-                types: [text]
-              - id: check-json
-                name: check json
+                entry: echo commit-msg-check
+                stages: [commit-msg]
+              - id: default-stage
+                name: default-stage
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
-                types: [json]
+                entry: echo default-stage
     "});
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r"
-    success: false
-    exit_code: 1
+    cmd_snapshot!(context.filters(), context.run().arg("manual-check").arg("commit-msg-check").arg("default-stage"), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
-    trailing whitespace......................................................Failed
-    - hook id: trailing-whitespace
-    - exit code: 1
+    manual-check.............................................................Passed
+    default-stage............................................................Passed
 
-      ['file.txt']
-    fix end of files.........................................................Failed
-    - hook id: end-of-file-fixer
-    - exit code: 1
+    ----- stderr -----
+    ");
 
-      ['file.txt']
-    check json...........................................(no files to check)Skipped
+    // Hooks selected implicitly still respect their stages.
+    cmd_snapshot!(context.filters(), context.run().arg("./"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    default-stage............................................................Passed
 
     ----- stderr -----
     ");
+}
 
-    // Override hook level files and exclude.
+/// `--commit-msg-file` runs the commit message hooks against a file, relative to the current
+/// directory.
+#[test]
+fn commit_msg_file() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
     context.write_pre_commit_config(indoc::indoc! {r"
-        files: file.txt
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing whitespace
+              - id: commit-msg-check
+                name: commit-msg-check
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
-                files: valid.json
-              - id: end-of-file-fixer
-                name: fix end of files
+                entry: cat
+                stages: [commit-msg]
+                verbose: true
+              - id: prepare-commit-msg-check
+                name: prepare-commit-msg-check
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
-                exclude: (valid.json|main.py)
-              - id: check-json
-                name: check json
+                entry: echo prepare
+                stages: [prepare-commit-msg]
+                verbose: true
+              - id: default-stage
+                name: default-stage
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                entry: echo default-stage
     "});
+    let drafts = context.work_dir().child("drafts");
+    drafts.child("msg.txt").write_str("feat: add a feature\n")?;
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r"
-    success: false
-    exit_code: 1
+    cmd_snapshot!(context.filters(), context.run().current_dir(&drafts).arg("--commit-msg-file").arg("msg.txt"), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
-    trailing whitespace..................................(no files to check)Skipped
-    fix end of files.........................................................Failed
-    - hook id: end-of-file-fixer
-    - exit code: 1
+    commit-msg-check.........................................................Passed
+    - hook id: commit-msg-check
+    - duration: [TIME]
 
-      ['file.txt']
-    check json...............................................................Failed
+      feat: add a feature
+    default-stage............................................................Passed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--stage").arg("prepare-commit-msg").arg("--commit-msg-file").arg("drafts/msg.txt"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prepare-commit-msg-check.................................................Passed
+    - hook id: prepare-commit-msg-check
+    - duration: [TIME]
+
+      prepare drafts/msg.txt
+    default-stage............................................................Passed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--commit-msg-file").arg("missing.txt"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Commit message file `missing.txt` does not exist
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--stage").arg("pre-push").arg("--commit-msg-file").arg("drafts/msg.txt"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--commit-msg-file` can only be used with `--stage commit-msg` or `--stage prepare-commit-msg`, not `--stage pre-push`
+    ");
+
+    Ok(())
+}
+
+/// Test global `files`, `exclude`, and hook level `files`, `exclude`.
+#[test]
+fn files_and_exclude() -> Result<()> {
+    let context = TestContext::new();
+
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("file.txt").write_str("Hello, world!  \n")?;
+    cwd.child("valid.json").write_str("{}\n  ")?;
+    cwd.child("invalid.json").write_str("{}")?;
+    cwd.child("main.py").write_str(r#"print "abc"  "#)?;
+
+    // Global files and exclude.
+    context.write_pre_commit_config(indoc::indoc! {r"
+        files: file.txt
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing whitespace
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                types: [text]
+              - id: end-of-file-fixer
+                name: fix end of files
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                types: [text]
+              - id: check-json
+                name: check json
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                types: [json]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trailing whitespace......................................................Failed
+    - hook id: trailing-whitespace
+    - exit code: 1
+
+      ['file.txt']
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+
+      ['file.txt']
+    check json...........................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    // Override hook level files and exclude.
+    context.write_pre_commit_config(indoc::indoc! {r"
+        files: file.txt
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing whitespace
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                files: valid.json
+              - id: end-of-file-fixer
+                name: fix end of files
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+                exclude: (valid.json|main.py)
+              - id: check-json
+                name: check json
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1:]); exit(1)'
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trailing whitespace..................................(no files to check)Skipped
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+
+      ['file.txt']
+    check json...............................................................Failed
     - hook id: check-json
     - exit code: 1
 
@@ -1527,6 +1869,84 @@ fn no_fail_fast_cli_flag() {
     ");
 }
 
+/// Test --keep-going overrides config-level and hook-level `fail_fast` and prints a summary.
+#[test]
+fn keep_going_cli_flag() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        fail_fast: true
+        repos:
+          - repo: local
+            hooks:
+              - id: failing-hook
+                name: failing-hook
+                language: system
+                entry: python3 -c 'print("Failed"); exit(1)'
+                always_run: true
+                fail_fast: true
+              - id: passing-hook
+                name: passing-hook
+                language: system
+                entry: python3 -c 'print("Passed")'
+                always_run: true
+              - id: no-files-hook
+                name: no-files-hook
+                language: system
+                entry: python3 -c 'print("Unreachable")'
+                files: \.rs$
+              - id: another-failing-hook
+                name: another-failing-hook
+                language: system
+                entry: python3 -c 'print("Failed again"); exit(1)'
+                always_run: true
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--keep-going"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    failing-hook.............................................................Failed
+    - hook id: failing-hook
+    - exit code: 1
+
+      Failed
+    passing-hook.............................................................Passed
+    no-files-hook........................................(no files to check)Skipped
+    another-failing-hook.....................................................Failed
+    - hook id: another-failing-hook
+    - exit code: 1
+
+      Failed again
+
+    Summary:
+      Passed   1
+      Failed   2
+      Skipped  1
+
+    Failed hooks:
+      failing-hook
+      another-failing-hook
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--keep-going").arg("--fail-fast"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--keep-going' cannot be used with '--fail-fast'
+
+    Usage: prek run --keep-going [HOOK|PROJECT]...
+
+    For more information, try '--help'.
+    ");
+}
+
 /// Run from a subdirectory. File arguments should be fixed to be relative to the root.
 #[test]
 fn subdirectory() -> Result<()> {
@@ -1580,6 +2000,35 @@ fn subdirectory() -> Result<()> {
     Ok(())
 }
 
+/// `--cd` into a directory outside any git repository fails with a clear error.
+#[test]
+fn cd_outside_git_repo() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    let outside = context.home_dir().child("outside");
+    outside.create_dir_all()?;
+
+    cmd_snapshot!(context.filters(), context.run().arg("--cd").arg(&*outside), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: --cd target is not inside a git repository: [HOME]/outside
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--cd").arg(context.home_dir().child("missing").path()), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: --cd target is not a directory: [HOME]/missing
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn global_path_options_expand_tilde() -> Result<()> {
     let context = TestContext::new();
@@ -1605,6 +2054,7 @@ fn global_path_options_expand_tilde() -> Result<()> {
         ),
         color: Auto,
         refresh: false,
+        offline: false,
         help: (),
         no_progress: false,
         quiet: 0,
@@ -1624,10 +2074,16 @@ fn global_path_options_expand_tilde() -> Result<()> {
             from_ref: None,
             to_ref: None,
             last_commit: false,
+            since: None,
+            include_deleted: false,
+            include_untracked: false,
             show_diff_on_failure: false,
+            show_diff: false,
             fail_fast: false,
             no_fail_fast: false,
-            dry_run: false,
+            keep_going: false,
+            dry_run: None,
+            explain: None,
             extra: RunExtraArgs {
                 remote_branch: None,
                 local_branch: None,
@@ -1646,6 +2102,20 @@ fn global_path_options_expand_tilde() -> Result<()> {
         stage: None,
         groups: [],
         no_groups: [],
+        files_from_stdin: false,
+        relativize_paths: false,
+        fail_on_warnings: false,
+        output_format: None,
+        notify: false,
+        retries: None,
+        timeout: None,
+        language_concurrency: [],
+        parallel_projects: false,
+        report_junit: None,
+        modified_files: None,
+        timings: None,
+        no_cache: false,
+        allow_missing_config: false,
     }
 
     ----- stderr -----
@@ -1720,247 +2190,289 @@ fn pass_env_vars() {
     ");
 }
 
+/// `$VAR`, `${VAR:-default}` and `$$` are expanded in `entry` and `env` values; quoted
+/// text in `entry` is left as written.
 #[test]
-fn staged_files_only() -> Result<()> {
+fn expand_env_vars_in_entry_and_env() {
     let context = TestContext::new();
+
     context.init_project();
+
     context.write_pre_commit_config(indoc::indoc! {r#"
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing-whitespace
+              - id: expand-entry
+                name: Expand entry
                 language: system
-                entry: python3 -c 'print(open("file.txt", "rt").read())'
+                entry: echo $GREETING ${PREK_TEST_MISSING:-world} $PREK_TEST_VALUE $$HOME '$HOME' "$HOME"
+                env:
+                  GREETING: hello
+                pass_filenames: false
+                always_run: true
                 verbose: true
-                types: [text]
-   "#});
-
-    context
-        .work_dir()
-        .child("file.txt")
-        .write_str("Hello, world!")?;
-    context.git_add(".");
-
-    // Non-staged files should be stashed and restored.
-    context
-        .work_dir()
-        .child("file.txt")
-        .write_str("Hello world again!")?;
-
-    let filters: Vec<_> = context
-        .filters()
-        .into_iter()
-        .chain([(r"/\d+-\d+.patch", "/[TIME]-[PID].patch")])
-        .collect();
+              - id: expand-env
+                name: Expand env
+                language: system
+                entry: python3 -c "import os; print(os.environ['MESSAGE'])"
+                env:
+                  GREETING: hi
+                  MESSAGE: "$GREETING from ${PREK_TEST_VALUE}, ${PREK_TEST_MISSING}done"
+                pass_filenames: false
+                always_run: true
+                verbose: true
+    "#});
 
-    cmd_snapshot!(filters, context.run(), @r"
+    cmd_snapshot!(context.filters(), context.run().env("PREK_TEST_VALUE", "a b"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    trailing-whitespace......................................................Passed
-    - hook id: trailing-whitespace
+    Expand entry.............................................................Passed
+    - hook id: expand-entry
     - duration: [TIME]
 
-      Hello, world!
+      hello world a b $HOME $HOME $HOME
+    Expand env...............................................................Passed
+    - hook id: expand-env
+    - duration: [TIME]
+
+      hi from a b, done
 
     ----- stderr -----
-    Unstaged changes detected, stashing unstaged changes to `[HOME]/patches/[TIME]-[PID].patch`
-    Restored working tree changes from `[HOME]/patches/[TIME]-[PID].patch`
     ");
-
-    let content = context.read("file.txt");
-    assert_snapshot!(content, @"Hello world again!");
-
-    Ok(())
 }
 
+/// `stdin_from: files` runs the hook once per matched file with the content on stdin.
 #[test]
-fn intent_to_add_file_survives_conflicted_stash_restore() -> Result<()> {
+fn stdin_from_files() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
-    context.write_pre_commit_config(indoc::indoc! {r#"
+
+    context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: rewrite-python
-                name: rewrite-python
+              - id: upper
+                name: upper
                 language: system
-                entry: python3 -c 'open("test.py", "w").write("a = 1\n")'
-                files: ^test\.py$
-   "#});
-
-    let cwd = context.work_dir();
-    context.git_add(PRE_COMMIT_CONFIG_YAML);
-
-    cwd.child("intent.txt").write_str("preserve me\n")?;
-    git_cmd(cwd)
-        .arg("add")
-        .arg("--intent-to-add")
-        .arg("intent.txt")
-        .assert()
-        .success();
-
-    cwd.child("test.py").write_str("a=1\n")?;
-    context.git_add("test.py");
-    cwd.child("test.py").write_str("a=1\nb = 2\n")?;
-
-    let filters: Vec<_> = context
-        .filters()
-        .into_iter()
-        .chain([(r"/\d+-\d+.patch", "/[TIME]-[PID].patch")])
-        .collect();
+                entry: tr a-z A-Z
+                files: \.txt$
+                stdin_from: files
+                verbose: true
+    "});
+    context
+        .work_dir()
+        .child("a.txt")
+        .write_str("first file\n")?;
+    context
+        .work_dir()
+        .child("b.txt")
+        .write_str("second file\n")?;
+    context
+        .work_dir()
+        .child("c.md")
+        .write_str("not matched\n")?;
+    context.git_add(".");
 
-    cmd_snapshot!(filters, context.run(), @r"
-    success: false
-    exit_code: 1
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
-    rewrite-python...........................................................Failed
-    - hook id: rewrite-python
-    - files were modified by this hook
+    upper....................................................................Passed
+    - hook id: upper
+    - duration: [TIME]
+
+      FIRST FILE
+      SECOND FILE
 
     ----- stderr -----
-    Unstaged changes detected, stashing unstaged changes to `[HOME]/patches/[TIME]-[PID].patch`
-    Stashed changes conflicted with changes made by hook, rolling back the hook changes
-    Restored working tree changes from `[HOME]/patches/[TIME]-[PID].patch`
     ");
 
-    assert_eq!(context.read("intent.txt"), "preserve me\n");
-    assert_eq!(context.read("test.py"), "a=1\nb = 2\n");
-
-    let output = git_cmd(cwd)
-        .arg("diff")
-        .arg("--diff-filter=A")
-        .arg("--name-only")
-        .arg("--")
-        .arg("intent.txt")
-        .output()?;
-    assert!(output.status.success(), "{output:?}");
-    assert_eq!(String::from_utf8(output.stdout)?, "intent.txt\n");
-
     Ok(())
 }
 
-#[cfg(unix)]
+/// A `cacheable` hook skips files that passed before and have not changed since.
 #[test]
-fn restore_on_interrupt() -> Result<()> {
+fn cacheable_hook_skips_unchanged_files() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
-    // The hook will sleep for 3 seconds.
-    context.write_pre_commit_config(indoc::indoc! {r#"
+
+    context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing-whitespace
+              - id: check
+                name: check
                 language: system
-                entry: python3 -c 'import time; open("out.txt", "wt").write(open("file.txt", "rt").read()); time.sleep(10)'
+                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                files: \.txt$
+                cacheable: true
                 verbose: true
-                types: [text]
-   "#});
+    "});
+    context.work_dir().child("a.txt").write_str("a\n")?;
+    context.work_dir().child("b.txt").write_str("b\n")?;
+    context.git_add(".");
 
-    context
-        .work_dir()
-        .child("file.txt")
-        .write_str("Hello, world!")?;
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    // Nothing changed, so the hook does not run at all.
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check.........................................................(unchanged)Passed
+
+    ----- stderr -----
+    ");
+
+    // Only the changed file is checked again.
+    context.work_dir().child("b.txt").write_str("changed\n")?;
     context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
 
-    // Non-staged files should be stashed and restored.
-    context
-        .work_dir()
-        .child("file.txt")
-        .write_str("Hello world again!")?;
+      ['b.txt']
 
-    let mut child = context.run().spawn()?;
-    let child_id = child.id();
+    ----- stderr -----
+    ");
 
-    // Send an interrupt signal to the process.
-    let handle = std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        #[allow(clippy::cast_possible_wrap)]
-        unsafe {
-            libc::kill(child_id as i32, libc::SIGINT)
-        };
-    });
+    // `--no-cache` checks every file again.
+    cmd_snapshot!(context.filters(), context.run().arg("--no-cache"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
 
-    handle.join().unwrap();
-    child.wait()?;
+      ['a.txt', 'b.txt']
 
-    let content = context.read("out.txt");
-    assert_snapshot!(content, @"Hello, world!");
+    ----- stderr -----
+    ");
 
-    let content = context.read("file.txt");
-    assert_snapshot!(content, @"Hello world again!");
+    // So does `PREK_NO_RESULT_CACHE`.
+    cmd_snapshot!(context.filters(), context.run().env("PREK_NO_RESULT_CACHE", "1"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
 
-    Ok(())
-}
+      ['a.txt', 'b.txt']
 
-/// When in merge conflict, runs on files that have conflicts fixed.
-#[test]
-fn merge_conflicts() -> Result<()> {
-    let context = TestContext::new();
-    context.init_project();
+    ----- stderr -----
+    ");
 
-    // Create a merge conflict.
-    let cwd = context.work_dir();
-    cwd.child("file.txt").write_str("Hello, world!")?;
+    // A file rewritten by an earlier fixer is checked again.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: fix
+                name: fix
+                language: system
+                entry: python3 -c "open('a.txt', 'w').write('fixed\n')"
+                files: ^a\.txt$
+              - id: check
+                name: check
+                language: system
+                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                files: \.txt$
+                cacheable: true
+                verbose: true
+    "#});
     context.git_add(".");
-    context.git_commit("Initial commit");
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    fix......................................................................Failed
+    - hook id: fix
+    - files were modified by this hook
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
 
-    git_cmd(cwd)
-        .arg("checkout")
-        .arg("-b")
-        .arg("feature")
-        .assert()
-        .success();
-    cwd.child("file.txt").write_str("Hello, world again!")?;
-    context.git_add(".");
-    context.git_commit("Feature commit");
+      ['a.txt']
 
-    git_cmd(cwd)
-        .arg("checkout")
-        .arg("master")
-        .assert()
-        .success();
-    cwd.child("file.txt")
-        .write_str("Hello, world from master!")?;
-    context.git_add(".");
-    context.git_commit("Master commit");
+    ----- stderr -----
+    ");
 
-    git_cmd(cwd).arg("merge").arg("feature").assert().code(1);
+    Ok(())
+}
+
+/// Editing the local script of a `cacheable` hook checks every file again.
+#[test]
+fn cacheable_hook_reruns_after_script_change() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
 
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing-whitespace
+              - id: check
+                name: check
                 language: system
-                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                entry: sh check.sh
+                files: \.txt$
+                cacheable: true
                 verbose: true
     "});
+    let script = context.work_dir().child("check.sh");
+    script.write_str("echo old \"$@\"\n")?;
+    context.work_dir().child("a.txt").write_str("a\n")?;
+    context.git_add(".");
 
-    // Abort on merge conflicts.
-    cmd_snapshot!(context.filters(), context.run(), @r#"
-    success: false
-    exit_code: 2
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
+    check....................................................................Passed
+    - hook id: check
+    - duration: [TIME]
+
+      old a.txt
 
     ----- stderr -----
-    error: You have unmerged paths. Resolve them before running prek
-    "#);
+    ");
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check.........................................................(unchanged)Passed
 
-    // Fix the conflict and run again.
+    ----- stderr -----
+    ");
+
+    script.write_str("echo new \"$@\"\n")?;
     context.git_add(".");
-    cmd_snapshot!(context.filters(), context.run(), @r"
+    cmd_snapshot!(context.filters(), context.run(), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    trailing-whitespace......................................................Passed
-    - hook id: trailing-whitespace
+    check....................................................................Passed
+    - hook id: check
     - duration: [TIME]
 
-      ['.pre-commit-config.yaml', 'file.txt']
+      new a.txt
 
     ----- stderr -----
     ");
@@ -1968,427 +2480,1837 @@ fn merge_conflicts() -> Result<()> {
     Ok(())
 }
 
-/// Local python hook with no additional dependencies.
+/// With `--verbose`, hook output is streamed as it is written, labelled with the hook id when
+/// several hooks run; output of builtin hooks is still printed when they finish.
 #[test]
-fn local_python_hook() {
+fn verbose_streams_hook_output() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
 
-    context.write_pre_commit_config(indoc::indoc! {r#"
+    context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: local-python-hook
-                name: local-python-hook
-                language: python
-                entry: python3 -c 'import sys; print("Hello, world!"); sys.exit(1)'
-    "#});
-
+              - id: first
+                name: first
+                language: system
+                entry: sh -c 'echo one; echo two'
+                pass_filenames: false
+                always_run: true
+              - id: second
+                name: second
+                language: system
+                entry: sh -c 'printf unterminated; exit 1'
+                pass_filenames: false
+                always_run: true
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+    "});
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("no newline")?;
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r"
+    cmd_snapshot!(context.filters(), context.run().arg("--verbose"), @"
     success: false
     exit_code: 1
     ----- stdout -----
-    local-python-hook........................................................Failed
-    - hook id: local-python-hook
+    [first] one
+    [first] two
+    [second] unterminated
+    first....................................................................Passed
+    - hook id: first
+    - duration: [TIME]
+    second...................................................................Failed
+    - hook id: second
+    - duration: [TIME]
+    - exit code: 1
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - duration: [TIME]
     - exit code: 1
+    - files were modified by this hook
 
-      Hello, world!
+      Fixing file.txt
+
+    ----- stderr -----
+    ");
+
+    // A single hook is not labelled.
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run().arg("--verbose").arg("first"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+      one
+      two
+    first....................................................................Passed
+    - hook id: first
+    - duration: [TIME]
 
     ----- stderr -----
     ");
+
+    Ok(())
 }
 
-/// Invalid `entry`
+/// A failing hook is retried up to `retries` times before being reported as failed.
 #[test]
-fn invalid_entry() {
+fn retries_flaky_hook() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
 
+    // Fails until it has been invoked three times.
     context.write_pre_commit_config(indoc::indoc! {r#"
         repos:
           - repo: local
             hooks:
-              - id: entry
-                name: entry
-                language: python
-                entry: '"'
+              - id: flaky
+                name: flaky
+                language: system
+                entry: python3 -c "import os, sys; n = len(open('.attempts').read()) + 1 if os.path.exists('.attempts') else 1; open('.attempts', 'w').write('x' * n); print(f'attempt {n}'); sys.exit(n < 3)"
+                pass_filenames: false
+                always_run: true
+                retries: 2
     "#});
-
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run(), @r#"
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flaky....................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    // `--retries` overrides the per-hook option.
+    context.work_dir().child(".attempts").write_str("")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--retries").arg("1"), @"
     success: false
-    exit_code: 2
+    exit_code: 1
     ----- stdout -----
+    flaky....................................................................Failed
+    - hook id: flaky
+    - exit code: 1
+
+      attempt 2
 
     ----- stderr -----
-    error: Failed to run hook `entry`
-      caused by: Invalid hook `entry`
-      caused by: Failed to parse entry `"` as commands
-    "#);
+    ");
+
+    // With `--verbose`, the output of every attempt is shown.
+    context.work_dir().child(".attempts").write_str("")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--verbose"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+      attempt 1
+      prek: attempt 1 failed with exit code 1, retrying
+      attempt 2
+      prek: attempt 2 failed with exit code 1, retrying
+      attempt 3
+    flaky....................................................................Passed
+    - hook id: flaky
+    - duration: [TIME]
+
+    ----- stderr -----
+    ");
+
+    Ok(())
 }
 
-/// Initialize a repo that does not exist.
+/// With a controlling terminal, a hook with a `timeout` can still use it instead of being
+/// stopped for accessing it from a background process group.
+#[cfg(unix)]
 #[test]
-fn init_nonexistent_repo() {
+fn hook_timeout_keeps_terminal_access() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
+
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
-          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
-            rev: v1.0.0
+          - repo: local
             hooks:
-              - id: nonexistent
-                name: nonexistent
-        "});
+              - id: tty
+                name: tty
+                language: system
+                entry: sh -c 'stty -echo < /dev/tty && stty echo < /dev/tty'
+                pass_filenames: false
+                always_run: true
+                timeout: 10
+    "});
     context.git_add(".");
 
-    let filters = context
-        .filters()
-        .into_iter()
-        .chain([
-            (r"exit code: ", "exit status: "),
-            // Normalize Git error message to handle environment-specific variations
-            (
-                r"fatal: unable to access 'https://notexistentatallnevergonnahappen\.com/nonexistent/repo/':.*",
-                r"fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]",
-            ),
-        ])
-        .collect::<Vec<_>>();
+    // `script` runs prek with a pseudo-terminal as its controlling terminal.
+    let run = context.run();
+    let command = format!("'{}' run", run.get_program().to_string_lossy());
+    let mut cmd = Command::new("script");
+    cmd.args(["-qec", &command, "/dev/null"])
+        .current_dir(context.work_dir())
+        .envs(
+            run.get_envs()
+                .filter_map(|(key, value)| Some((key, value?))),
+        );
 
-    cmd_snapshot!(filters, context.run(), @"
+    let start = std::time::Instant::now();
+    let output = cmd.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Passed"), "{stdout}");
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    Ok(())
+}
+
+/// A hook running past its `timeout` is killed, along with the processes it started.
+#[cfg(unix)]
+#[test]
+fn hook_timeout_kills_hung_hook() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: hung
+                name: hung
+                language: system
+                entry: sh -c 'echo started; sleep 60 & wait'
+                pass_filenames: false
+                always_run: true
+                timeout: 1
+              - id: slow
+                name: slow
+                language: system
+                entry: sh -c 'sleep 2; echo done'
+                pass_filenames: false
+                always_run: true
+    "});
+    context.git_add(".");
+
+    let start = std::time::Instant::now();
+    cmd_snapshot!(context.filters(), context.run(), @"
     success: false
-    exit_code: 2
+    exit_code: 1
     ----- stdout -----
+    hung.....................................................................Failed
+    - hook id: hung
+    - exit code: 1
+
+      started
+      prek: hook timed out after [TIME]
+    slow.....................................................................Passed
 
     ----- stderr -----
-    error: Failed to init hooks
-      caused by: Failed to clone repo `https://notexistentatallnevergonnahappen.com/nonexistent/repo`
-      caused by: Command `git full clone` exited with an error:
+    ");
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
 
-    [status]
-    exit status: 128
+    // `--timeout` applies to hooks without a `timeout` of their own.
+    cmd_snapshot!(context.filters(), context.run().arg("slow").arg("--timeout").arg("1"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    slow.....................................................................Failed
+    - hook id: slow
+    - exit code: 1
 
-    [stderr]
-    fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]
+      prek: hook timed out after [TIME]
+
+    ----- stderr -----
     ");
 }
 
+/// `default_language_concurrency` caps how many processes of a language run at once, and
+/// `--language-concurrency` overrides it.
+#[cfg(unix)]
 #[test]
-fn skipped_remote_repo_is_not_cloned() {
+fn language_concurrency_limits_hook_processes() {
     let context = TestContext::new();
     context.init_project();
+
+    // Each hook fails if another one holds the lock directory at the same time.
     context.write_pre_commit_config(indoc::indoc! {r"
+        default_language_concurrency:
+          system: 1
         repos:
-          - repo: builtin
-            hooks:
-              - id: end-of-file-fixer
-
-          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
-            rev: v1.0.0
+          - repo: local
             hooks:
-              - id: ruff-check
-        "});
+              - id: first
+                name: first
+                language: system
+                entry: sh -c 'mkdir lock || exit 1; sleep 1; rmdir lock'
+                pass_filenames: false
+                always_run: true
+                priority: 0
+              - id: second
+                name: second
+                language: system
+                entry: sh -c 'mkdir lock || exit 1; sleep 1; rmdir lock'
+                pass_filenames: false
+                always_run: true
+                priority: 0
+    "});
     context.git_add(".");
 
-    cmd_snapshot!(
-        context.filters(),
-        context.run().arg("--all-files").arg("--skip").arg("ruff-check"),
-        @r"
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_MAX_CONCURRENCY, "4"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    fix end of files.........................................................Passed
+    first....................................................................Passed
+    second...................................................................Passed
 
     ----- stderr -----
-    "
-    );
-}
+    ");
 
-#[test]
-fn skipped_same_key_remote_repo_entry_is_not_initialized() -> Result<()> {
-    let context = TestContext::new();
-    context.init_project();
+    // Raising the limit lets the hooks overlap, so one of them fails.
+    context
+        .run()
+        .env(EnvVars::PREK_MAX_CONCURRENCY, "4")
+        .arg("--language-concurrency")
+        .arg("system=2")
+        .assert()
+        .failure();
 
-    let hook_repo = context.home_dir().child("duplicate-key-hook-repo");
-    hook_repo.create_dir_all()?;
+    cmd_snapshot!(context.filters(), context.run().arg("--language-concurrency").arg("system=0"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
 
-    git_cmd(&hook_repo)
-        .arg("-c")
-        .arg("init.defaultBranch=master")
-        .arg("init")
-        .assert()
-        .success();
+    ----- stderr -----
+    error: invalid value 'system=0' for '--language-concurrency <LANGUAGE=N>': expected a positive number of processes, got `0`
 
-    hook_repo
-        .child(PRE_COMMIT_HOOKS_YAML)
-        .write_str(indoc::indoc! {r"
-        - id: test-hook
-          name: Test Hook
-          entry: echo ok
-          language: system
-          always_run: true
-    "})?;
+    For more information, try '--help'.
+    ");
+}
 
-    git_cmd(&hook_repo).arg("add").arg(".").assert().success();
-    git_cmd(&hook_repo)
-        .arg("commit")
-        .arg("-m")
-        .arg("Initial commit")
-        .assert()
-        .success();
-    git_cmd(&hook_repo)
-        .arg("tag")
-        .arg("v1.0.0")
-        .assert()
-        .success();
+#[test]
+fn report_junit() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
 
-    context.write_pre_commit_config(&indoc::formatdoc! {r"
+    context.write_pre_commit_config(indoc::indoc! {r#"
         repos:
-          - repo: {repo}
-            rev: v1.0.0
-            hooks:
-              - id: missing-hook
-
-          - repo: {repo}
-            rev: v1.0.0
+          - repo: local
             hooks:
-              - id: test-hook
-    ", repo = hook_repo.display()});
+              - id: passing
+                name: passing
+                language: system
+                entry: python3 -c "print('ok')"
+                pass_filenames: false
+                always_run: true
+              - id: failing
+                name: failing
+                language: system
+                entry: python3 -c "import sys; print('a.py:1 error <bad> & worse'); sys.exit(1)"
+                pass_filenames: false
+                always_run: true
+              - id: no-files
+                name: no-files
+                language: system
+                entry: python3 -c "print('unreachable')"
+                files: \.rs$
+    "#});
     context.git_add(".");
 
-    context
-        .run()
-        .arg("--all-files")
-        .arg("--skip")
-        .arg("missing-hook")
-        .assert()
-        .success();
+    cmd_snapshot!(context.filters(), context.run().arg("--report-junit").arg("report.xml"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    passing..................................................................Passed
+    failing..................................................................Failed
+    - hook id: failing
+    - exit code: 1
+
+      a.py:1 error <bad> & worse
+    no-files.............................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    let report = context.read("report.xml");
+    let report = regex::Regex::new(r#"time="[0-9.]+""#)?.replace_all(&report, r#"time="[TIME]""#);
+    insta::assert_snapshot!(report, @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <testsuites name="prek" tests="3" failures="1" errors="0" skipped="1" time="[TIME]">
+      <testsuite name="." tests="3" failures="1" errors="0" skipped="1" time="[TIME]">
+        <testcase name="passing" classname="." time="[TIME]"/>
+        <testcase name="failing" classname="." time="[TIME]">
+          <failure message="exit code: 1">a.py:1 error &lt;bad&gt; &amp; worse
+    </failure>
+        </testcase>
+        <testcase name="no-files" classname="." time="[TIME]">
+          <skipped message="no files to check"/>
+        </testcase>
+      </testsuite>
+    </testsuites>
+    "#);
 
     Ok(())
 }
 
 #[test]
-fn group_excluded_remote_repo_is_not_cloned() {
+fn timings() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
-    context.write_pre_commit_config(indoc::indoc! {r"
-        repos:
-          - repo: builtin
-            hooks:
-              - id: end-of-file-fixer
-                groups: [ci]
 
-          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
-            rev: v1.0.0
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
             hooks:
-              - id: ruff-check
-                groups: [local]
-        "});
+              - id: slow
+                name: slow
+                language: system
+                entry: python3 -c "import time; time.sleep(0.3)"
+              - id: fast
+                name: fast
+                language: system
+                entry: python3 -c "pass"
+                pass_filenames: false
+                always_run: true
+              - id: no-files
+                name: no-files
+                language: system
+                entry: python3 -c "pass"
+                files: \.rs$
+    "#});
+    context.work_dir().child("a.txt").write_str("a")?;
+    context.work_dir().child("b.txt").write_str("b")?;
     context.git_add(".");
 
-    cmd_snapshot!(
-        context.filters(),
-        context.run().arg("--all-files").arg("--group").arg("ci"),
-        @r"
+    // Hooks are sorted slowest first, and skipped hooks are left out.
+    cmd_snapshot!(context.filters(), context.run().arg("--timings"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    fix end of files.........................................................Passed
+    slow.....................................................................Passed
+    fast.....................................................................Passed
+    no-files.............................................(no files to check)Skipped
+
+    Timings:
+      Repository setup        [TIME]
+      Environment install     [TIME]
+
+      Hook                 Duration   Files
+      slow                    [TIME]       3
+      fast                    [TIME]       0
 
     ----- stderr -----
-    "
-    );
-}
+    ");
 
-#[test]
-fn unmatched_skip_does_not_suppress_remote_clone() {
+    let check_report = |report: &[u8]| -> Result<()> {
+        let timings: serde_json::Value = serde_json::from_slice(report)?;
+        assert!(timings["setup"].is_f64());
+        assert!(timings["install"].is_f64());
+        let hooks = timings["hooks"]
+            .as_array()
+            .expect("hooks should be an array");
+        let hooks = hooks
+            .iter()
+            .map(|hook| {
+                (
+                    hook["id"].as_str().unwrap(),
+                    hook["files"].as_u64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hooks, [("slow", 3), ("fast", 0)]);
+        Ok(())
+    };
+
+    // With `--quiet`, the JSON report is all that is printed for a passing run.
+    let output = context
+        .run()
+        .arg("--timings=json")
+        .arg("--quiet")
+        .output()?;
+    check_report(&output.stdout)?;
+
+    // `--timings-file` writes the report apart from the run output.
+    let output = context
+        .run()
+        .arg("--timings-file")
+        .arg("timings.json")
+        .output()?;
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("\"hooks\""));
+    check_report(context.read("timings.json").as_bytes())?;
+
+    Ok(())
+}
+
+#[test]
+fn working_directory() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: show-cwd
+                name: show-cwd
+                language: system
+                entry: python3 -c "import os, sys; print(os.path.basename(os.getcwd())); print(sorted(sys.argv[1:]))"
+                files: \.txt$
+                working_directory: crate
+                verbose: true
+    "#});
+    context.work_dir().child("crate/src").create_dir_all()?;
+    context
+        .work_dir()
+        .child("crate/src/lib.txt")
+        .write_str("lib")?;
+    context.work_dir().child("top.txt").write_str("top")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    show-cwd.................................................................Passed
+    - hook id: show-cwd
+    - duration: [TIME]
+
+      crate
+      ['../top.txt', 'src/lib.txt']
+
+    ----- stderr -----
+    ");
+
+    // A missing working directory is reported when the hook runs.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: show-cwd
+                name: show-cwd
+                language: system
+                entry: python3 -c "print('unreachable')"
+                working_directory: missing
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to run hook `show-cwd`
+      caused by: Working directory `missing` does not exist
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn staged_files_only() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: python3 -c 'print(open("file.txt", "rt").read())'
+                verbose: true
+                types: [text]
+   "#});
+
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("Hello, world!")?;
+    context.git_add(".");
+
+    // Non-staged files should be stashed and restored.
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("Hello world again!")?;
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"/\d+-\d+.patch", "/[TIME]-[PID].patch")])
+        .collect();
+
+    cmd_snapshot!(filters, context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    trailing-whitespace......................................................Passed
+    - hook id: trailing-whitespace
+    - duration: [TIME]
+
+      Hello, world!
+
+    ----- stderr -----
+    Unstaged changes detected, stashing unstaged changes to `[HOME]/patches/[TIME]-[PID].patch`
+    Restored working tree changes from `[HOME]/patches/[TIME]-[PID].patch`
+    ");
+
+    let content = context.read("file.txt");
+    assert_snapshot!(content, @"Hello world again!");
+
+    Ok(())
+}
+
+#[test]
+fn intent_to_add_file_survives_conflicted_stash_restore() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: rewrite-python
+                name: rewrite-python
+                language: system
+                entry: python3 -c 'open("test.py", "w").write("a = 1\n")'
+                files: ^test\.py$
+   "#});
+
+    let cwd = context.work_dir();
+    context.git_add(PRE_COMMIT_CONFIG_YAML);
+
+    cwd.child("intent.txt").write_str("preserve me\n")?;
+    git_cmd(cwd)
+        .arg("add")
+        .arg("--intent-to-add")
+        .arg("intent.txt")
+        .assert()
+        .success();
+
+    cwd.child("test.py").write_str("a=1\n")?;
+    context.git_add("test.py");
+    cwd.child("test.py").write_str("a=1\nb = 2\n")?;
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"/\d+-\d+.patch", "/[TIME]-[PID].patch")])
+        .collect();
+
+    cmd_snapshot!(filters, context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    rewrite-python...........................................................Failed
+    - hook id: rewrite-python
+    - files were modified by this hook
+
+    ----- stderr -----
+    Unstaged changes detected, stashing unstaged changes to `[HOME]/patches/[TIME]-[PID].patch`
+    Stashed changes conflicted with changes made by hook, rolling back the hook changes
+    Restored working tree changes from `[HOME]/patches/[TIME]-[PID].patch`
+    ");
+
+    assert_eq!(context.read("intent.txt"), "preserve me\n");
+    assert_eq!(context.read("test.py"), "a=1\nb = 2\n");
+
+    let output = git_cmd(cwd)
+        .arg("diff")
+        .arg("--diff-filter=A")
+        .arg("--name-only")
+        .arg("--")
+        .arg("intent.txt")
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8(output.stdout)?, "intent.txt\n");
+
+    Ok(())
+}
+
+/// Hook output keeps stdout and stderr in the order they were written.
+#[cfg(unix)]
+#[test]
+fn interleaved_stdout_and_stderr() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: interleave
+                name: interleave
+                language: system
+                entry: sh -c 'echo out1; echo err1 >&2; echo out2; echo err2 >&2'
+                pass_filenames: false
+                always_run: true
+                verbose: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    interleave...............................................................Passed
+    - hook id: interleave
+    - duration: [TIME]
+
+      out1
+      err1
+      out2
+      err2
+
+    ----- stderr -----
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn restore_on_interrupt() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    // The hook will sleep for 3 seconds.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: python3 -c 'import time; open("out.txt", "wt").write(open("file.txt", "rt").read()); time.sleep(10)'
+                verbose: true
+                types: [text]
+   "#});
+
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("Hello, world!")?;
+    context.git_add(".");
+
+    // Non-staged files should be stashed and restored.
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("Hello world again!")?;
+
+    let mut child = context.run().spawn()?;
+    let child_id = child.id();
+
+    // Send an interrupt signal to the process.
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        #[allow(clippy::cast_possible_wrap)]
+        unsafe {
+            libc::kill(child_id as i32, libc::SIGINT)
+        };
+    });
+
+    handle.join().unwrap();
+    child.wait()?;
+
+    let content = context.read("out.txt");
+    assert_snapshot!(content, @"Hello, world!");
+
+    let content = context.read("file.txt");
+    assert_snapshot!(content, @"Hello world again!");
+
+    Ok(())
+}
+
+/// When in merge conflict, runs on files that have conflicts fixed.
+#[test]
+fn merge_conflicts() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    // Create a merge conflict.
+    let cwd = context.work_dir();
+    cwd.child("file.txt").write_str("Hello, world!")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    git_cmd(cwd)
+        .arg("checkout")
+        .arg("-b")
+        .arg("feature")
+        .assert()
+        .success();
+    cwd.child("file.txt").write_str("Hello, world again!")?;
+    context.git_add(".");
+    context.git_commit("Feature commit");
+
+    git_cmd(cwd)
+        .arg("checkout")
+        .arg("master")
+        .assert()
+        .success();
+    cwd.child("file.txt")
+        .write_str("Hello, world from master!")?;
+    context.git_add(".");
+    context.git_commit("Master commit");
+
+    git_cmd(cwd).arg("merge").arg("feature").assert().code(1);
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+                verbose: true
+    "});
+
+    // Abort on merge conflicts.
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: You have unmerged paths. Resolve them before running prek
+    "#);
+
+    // Fix the conflict and run again.
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    trailing-whitespace......................................................Passed
+    - hook id: trailing-whitespace
+    - duration: [TIME]
+
+      ['.pre-commit-config.yaml', 'file.txt']
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Local python hook with no additional dependencies.
+#[test]
+fn local_python_hook() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: local-python-hook
+                name: local-python-hook
+                language: python
+                entry: python3 -c 'import sys; print("Hello, world!"); sys.exit(1)'
+    "#});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    local-python-hook........................................................Failed
+    - hook id: local-python-hook
+    - exit code: 1
+
+      Hello, world!
+
+    ----- stderr -----
+    ");
+}
+
+/// Invalid `entry`
+#[test]
+fn invalid_entry() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: entry
+                name: entry
+                language: python
+                entry: '"'
+    "#});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to run hook `entry`
+      caused by: Invalid hook `entry`
+      caused by: Failed to parse entry `"` as commands
+    "#);
+}
+
+/// Initialize a repo that does not exist.
+#[test]
+fn init_nonexistent_repo() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
+            rev: v1.0.0
+            hooks:
+              - id: nonexistent
+                name: nonexistent
+        "});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([
+            (r"exit code: ", "exit status: "),
+            // Normalize Git error message to handle environment-specific variations
+            (
+                r"fatal: unable to access 'https://notexistentatallnevergonnahappen\.com/nonexistent/repo/':.*",
+                r"fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]",
+            ),
+        ])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters, context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Failed to clone repo `https://notexistentatallnevergonnahappen.com/nonexistent/repo`
+      caused by: Command `git full clone` exited with an error:
+
+    [status]
+    exit status: 128
+
+    [stderr]
+    fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]
+    ");
+}
+
+#[test]
+fn skipped_remote_repo_is_not_cloned() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+
+          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
+            rev: v1.0.0
+            hooks:
+              - id: ruff-check
+        "});
+    context.git_add(".");
+
+    cmd_snapshot!(
+        context.filters(),
+        context.run().arg("--all-files").arg("--skip").arg("ruff-check"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    fix end of files.........................................................Passed
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn skipped_same_key_remote_repo_entry_is_not_initialized() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook_repo = context.home_dir().child("duplicate-key-hook-repo");
+    hook_repo.create_dir_all()?;
+
+    git_cmd(&hook_repo)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+
+    hook_repo
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        - id: test-hook
+          name: Test Hook
+          entry: echo ok
+          language: system
+          always_run: true
+    "})?;
+
+    git_cmd(&hook_repo).arg("add").arg(".").assert().success();
+    git_cmd(&hook_repo)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&hook_repo)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo}
+            rev: v1.0.0
+            hooks:
+              - id: missing-hook
+
+          - repo: {repo}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+    ", repo = hook_repo.display()});
+    context.git_add(".");
+
+    context
+        .run()
+        .arg("--all-files")
+        .arg("--skip")
+        .arg("missing-hook")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Unknown hook ids, in the config or as selectors, suggest the closest known id.
+#[test]
+fn unknown_hook_id_suggestions() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook_repo = context.home_dir().child("suggest-hook-repo");
+    hook_repo.create_dir_all()?;
+
+    git_cmd(&hook_repo)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+
+    hook_repo
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        - id: trailing-whitespace
+          name: Trailing Whitespace
+          entry: echo ok
+          language: system
+        - id: end-of-file-fixer
+          name: End Of File Fixer
+          entry: echo ok
+          language: system
+    "})?;
+
+    git_cmd(&hook_repo).arg("add").arg(".").assert().success();
+    git_cmd(&hook_repo)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&hook_repo)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo}
+            rev: v1.0.0
+            hooks:
+              - id: trailing-whitespac
+    ", repo = hook_repo.display()});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Hook `trailing-whitespac` not present in repo `[HOME]/suggest-hook-repo@v1.0.0`
+
+    hint: did you mean `trailing-whitespace`?
+    hint: available hooks: `trailing-whitespace`, `end-of-file-fixer`
+    ");
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo}
+            rev: v1.0.0
+            hooks:
+              - id: trailing-whitespace
+              - id: end-of-file-fixer
+    ", repo = hook_repo.display()});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("end-of-file-fixr"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: selector `end-of-file-fixr` did not match any hooks, did you mean `end-of-file-fixer`?
+    error: No hooks found after filtering with the given selectors
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn group_excluded_remote_repo_is_not_cloned() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+                groups: [ci]
+
+          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
+            rev: v1.0.0
+            hooks:
+              - id: ruff-check
+                groups: [local]
+        "});
+    context.git_add(".");
+
+    cmd_snapshot!(
+        context.filters(),
+        context.run().arg("--all-files").arg("--group").arg("ci"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    fix end of files.........................................................Passed
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn unmatched_skip_does_not_suppress_remote_clone() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+
+          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
+            rev: v1.0.0
+            hooks:
+              - id: ruff-check
+        "});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"exit code: ", "exit status: "),
+            // Normalize Git error message to handle environment-specific variations
+            (
+                r"fatal: unable to access 'https://notexistentatallnevergonnahappen\.com/nonexistent/repo/':.*",
+                r"fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]"
+            ),
+        ])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(
+        filters,
+        context.run().arg("--all-files").arg("--skip").arg("other-hook"),
+        @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Failed to clone repo `https://notexistentatallnevergonnahappen.com/nonexistent/repo`
+      caused by: Command `git full clone` exited with an error:
+
+    [status]
+    exit status: 128
+
+    [stderr]
+    fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]
+    "
+    );
+}
+
+/// Test hooks that specifies `types: [directory]`.
+#[test]
+fn types_directory() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: directory
+                name: directory
+                language: system
+                entry: echo
+                types: [directory]
+        "});
+    context.work_dir().child("dir").create_dir_all()?;
+    context
+        .work_dir()
+        .child("dir/file.txt")
+        .write_str("Hello, world!")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    directory............................................(no files to check)Skipped
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("dir"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    directory................................................................Passed
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    directory............................................(no files to check)Skipped
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("non-exist-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    directory............................................(no files to check)Skipped
+
+    ----- stderr -----
+    warning: This file does not exist and will be ignored: `non-exist-files`
+    ");
+    Ok(())
+}
+
+/// Test `--files-from-stdin` reads NUL-separated paths.
+#[test]
+fn run_files_from_stdin() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                files: ^src/
+                verbose: true
+        "});
+    context.work_dir().child("src").create_dir_all()?;
+    context.work_dir().child("src/a file.txt").write_str("a")?;
+    context.work_dir().child("src/b.txt").write_str("b")?;
+    context.work_dir().child("other.txt").write_str("other")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from-stdin").pass_stdin("src/a file.txt\0other.txt\0./src/b.txt\0"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      src/b.txt src/a file.txt
+
+    ----- stderr -----
+    ");
+
+    // An empty list runs on no files instead of falling back to staged files.
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from-stdin").pass_stdin(""), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.................................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from-stdin").arg("--all-files"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--files-from-stdin' cannot be used with '--all-files'
+
+    Usage: prek run --files-from-stdin [HOOK|PROJECT]...
+
+    For more information, try '--help'.
+    ");
+
+    Ok(())
+}
+
+/// Test `--relativize-paths` replaces the store path and git root in hook output.
+#[test]
+fn run_relativize_paths() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: leak-paths
+                name: leak-paths
+                language: system
+                entry: sh -c 'echo "$PREK_HOME/hooks/env/bin/python"; echo "$(pwd)/src/main.py"; exit 1'
+                pass_filenames: false
+                always_run: true
+        "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--relativize-paths"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    leak-paths...............................................................Failed
+    - hook id: leak-paths
+    - exit code: 1
+
+      <store>/hooks/env/bin/python
+      <root>/src/main.py
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn run_last_commit() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
+
+    let cwd = context.work_dir();
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
-          - repo: builtin
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
             hooks:
+              - id: trailing-whitespace
               - id: end-of-file-fixer
+    "});
+
+    // Create initial files and make first commit
+    cwd.child("file1.txt").write_str("Hello, world!\n")?;
+    cwd.child("file2.txt")
+        .write_str("Initial content with trailing spaces   \n")?; // This has issues but won't be in last commit
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    // Modify files and make second commit with trailing whitespace
+    cwd.child("file1.txt").write_str("Hello, world!   \n")?; // trailing whitespace
+    cwd.child("file3.txt").write_str("New file")?; // missing newline
+    // Note: file2.txt is NOT modified in this commit, so it should be filtered out by --last-commit
+    context.git_add(".");
+    context.git_commit("Second commit with issues");
+
+    // Run with --last-commit should only check files from the last commit
+    // This should only process file1.txt and file3.txt, NOT file2.txt
+    cmd_snapshot!(context.filters(), context.run().arg("--last-commit"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trim trailing whitespace.................................................Failed
+    - hook id: trailing-whitespace
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing file1.txt
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing file3.txt
+
+    ----- stderr -----
+    ");
+
+    // Now reset the files to their problematic state for comparison
+    cwd.child("file1.txt").write_str("Hello, world!   \n")?; // trailing whitespace
+    cwd.child("file3.txt").write_str("New file")?; // missing newline
+
+    // Run with --all-files should check ALL files including file2.txt
+    // This demonstrates that file2.txt was indeed filtered out in the previous test
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    trim trailing whitespace.................................................Failed
+    - hook id: trailing-whitespace
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing file1.txt
+      Fixing file2.txt
+    fix end of files.........................................................Failed
+    - hook id: end-of-file-fixer
+    - exit code: 1
+    - files were modified by this hook
+
+      Fixing file3.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `--output-format github` emits annotations for `path:line: message` hook output, and for the
+/// findings of builtin hooks.
+#[test]
+fn run_github_annotations() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: lint
+                name: lint
+                language: system
+                entry: sh lint.sh
+                files: ^src/
+              - id: crash
+                name: crash
+                language: system
+                entry: sh -c 'echo something went wrong; exit 1'
+                pass_filenames: false
+          - repo: builtin
+            hooks:
+              - id: debug-statements
+    "});
+    cwd.child("lint.sh").write_str(indoc::indoc! {r#"
+        for f in "$@"; do
+            echo "$f:3:7: E001 bad style"
+        done
+        exit 1
+    "#})?;
+    cwd.child("src/main.py")
+        .write_str("print(1)\nimport pdb\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--output-format").arg("github"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    lint.....................................................................Failed
+    - hook id: lint
+    - exit code: 1
+
+      src/main.py:3:7: E001 bad style
+    ::error file=src/main.py,line=3,col=7,title=lint::E001 bad style
+    crash....................................................................Failed
+    - hook id: crash
+    - exit code: 1
+
+      something went wrong
+    ::error title=crash::something went wrong
+    debug statements (python)................................................Failed
+    - hook id: debug-statements
+    - exit code: 1
+
+      src/main.py:2:0: pdb imported
+    ::error file=src/main.py,line=2,col=1,title=debug statements (python)::pdb imported
+
+    ----- stderr -----
+    ");
+
+    // `GITHUB_ACTIONS=true` turns annotations on by default.
+    cmd_snapshot!(context.filters(), context.run().arg("lint").env(EnvVars::GITHUB_ACTIONS, "true"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    lint.....................................................................Failed
+    - hook id: lint
+    - exit code: 1
+
+      src/main.py:3:7: E001 bad style
+    ::error file=src/main.py,line=3,col=7,title=lint::E001 bad style
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `--since` runs on files changed since the merge-base of `HEAD` and the given ref.
+#[test]
+fn run_since() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                verbose: true
+    "});
+    cwd.child("base.txt").write_str("base")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    context.git_branch("feature");
+    // Commits on `master` after the branch point are not part of the feature branch.
+    cwd.child("master.txt").write_str("master")?;
+    context.git_add(".");
+    context.git_commit("Master commit");
+
+    context.git_checkout("feature");
+    cwd.child("feature.txt").write_str("feature")?;
+    context.git_add(".");
+    context.git_commit("Feature commit");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--since").arg("master"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      feature.txt
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--since").arg("master").arg("--from-ref").arg("HEAD~1"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--since <REF>' cannot be used with '--from-ref <FROM_REF>'
+
+    Usage: prek run --since <REF> [HOOK|PROJECT]...
+
+    For more information, try '--help'.
+    ");
+
+    // A branch with unrelated history has no merge-base with `master`.
+    git_cmd(cwd)
+        .arg("checkout")
+        .arg("--orphan")
+        .arg("unrelated")
+        .assert()
+        .success();
+    context.git_commit("Unrelated commit");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--since").arg("master"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to resolve `--since master`
+      caused by: No merge base between `HEAD` and `master`, the histories are unrelated
+    ");
+
+    Ok(())
+}
+
+/// Ref-range runs report renamed files under their new path and skip deleted files unless
+/// `--include-deleted` is given.
+#[test]
+fn run_ref_range_renames_and_deletions() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                verbose: true
+              - id: echo-untyped
+                name: echo-untyped
+                language: system
+                entry: echo
+                types: []
+                verbose: true
+    "});
+    cwd.child("old.txt")
+        .write_str("a file that will be renamed\n")?;
+    cwd.child("deleted.txt")
+        .write_str("a file that will be deleted\n")?;
+    cwd.child("modified.txt").write_str("before\n")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    git_cmd(cwd)
+        .arg("mv")
+        .arg("old.txt")
+        .arg("new.txt")
+        .assert()
+        .success();
+    git_cmd(cwd)
+        .arg("rm")
+        .arg("-q")
+        .arg("deleted.txt")
+        .assert()
+        .success();
+    cwd.child("modified.txt").write_str("after\n")?;
+    context.git_add(".");
+    context.git_commit("Rename, delete and modify");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--from-ref").arg("HEAD~1"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      new.txt modified.txt
+    echo-untyped.............................................................Passed
+    - hook id: echo-untyped
+    - duration: [TIME]
 
-          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
-            rev: v1.0.0
-            hooks:
-              - id: ruff-check
-        "});
-    context.git_add(".");
+      new.txt modified.txt
 
-    let filters = context
-        .filters()
-        .into_iter()
-        .chain([(r"exit code: ", "exit status: "),
-            // Normalize Git error message to handle environment-specific variations
-            (
-                r"fatal: unable to access 'https://notexistentatallnevergonnahappen\.com/nonexistent/repo/':.*",
-                r"fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]"
-            ),
-        ])
-        .collect::<Vec<_>>();
+    ----- stderr -----
+    ");
 
-    cmd_snapshot!(
-        filters,
-        context.run().arg("--all-files").arg("--skip").arg("other-hook"),
-        @"
-    success: false
-    exit_code: 2
+    // Deleted files have no type tags, so only the untyped hook receives them.
+    cmd_snapshot!(context.filters(), context.run().arg("--last-commit").arg("--include-deleted"), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
 
-    ----- stderr -----
-    error: Failed to init hooks
-      caused by: Failed to clone repo `https://notexistentatallnevergonnahappen.com/nonexistent/repo`
-      caused by: Command `git full clone` exited with an error:
+      new.txt modified.txt
+    echo-untyped.............................................................Passed
+    - hook id: echo-untyped
+    - duration: [TIME]
 
-    [status]
-    exit status: 128
+      modified.txt deleted.txt new.txt
 
-    [stderr]
-    fatal: unable to access 'https://notexistentatallnevergonnahappen.com/nonexistent/repo/': [error]
-    "
-    );
+    ----- stderr -----
+    ");
+
+    Ok(())
 }
 
-/// Test hooks that specifies `types: [directory]`.
+/// `--include-untracked` adds untracked files to the collected files, but never ignored ones.
 #[test]
-fn types_directory() -> Result<()> {
+fn run_include_untracked() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
+
+    let cwd = context.work_dir();
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: directory
-                name: directory
+              - id: echo
+                name: echo
                 language: system
                 entry: echo
-                types: [directory]
-        "});
-    context.work_dir().child("dir").create_dir_all()?;
-    context
-        .work_dir()
-        .child("dir/file.txt")
-        .write_str("Hello, world!")?;
+                exclude: ^\.
+                verbose: true
+    "});
+    cwd.child(".gitignore").write_str("ignored.txt\n")?;
+    cwd.child("tracked.txt").write_str("tracked\n")?;
+    cwd.child("removed.txt")
+        .write_str("removed from the index\n")?;
     context.git_add(".");
+    context.git_commit("Initial commit");
 
-    cmd_snapshot!(context.filters(), context.run(), @r#"
-    success: true
-    exit_code: 0
-    ----- stdout -----
-    directory............................................(no files to check)Skipped
-
-    ----- stderr -----
-    "#);
+    // Deleted from the index but kept on disk, so it is untracked now.
+    git_cmd(cwd)
+        .arg("rm")
+        .arg("-q")
+        .arg("--cached")
+        .arg("removed.txt")
+        .assert()
+        .success();
+    cwd.child("staged.txt").write_str("staged\n")?;
+    context.git_add("staged.txt");
+    cwd.child("src/untracked.txt").write_str("untracked\n")?;
+    cwd.child("ignored.txt").write_str("ignored\n")?;
 
-    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("dir"), @r#"
+    cmd_snapshot!(context.filters(), context.run().arg("--include-untracked"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    directory................................................................Passed
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      src/untracked.txt removed.txt staged.txt
 
     ----- stderr -----
-    "#);
+    ");
 
-    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r#"
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--include-untracked"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    directory............................................(no files to check)Skipped
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      src/untracked.txt removed.txt tracked.txt staged.txt
 
     ----- stderr -----
-    "#);
+    ");
 
-    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("non-exist-files"), @r"
+    cmd_snapshot!(context.filters(), context.run().arg("--directory").arg("src").arg("--include-untracked"), @"
     success: true
     exit_code: 0
     ----- stdout -----
-    directory............................................(no files to check)Skipped
+    echo.....................................................................Passed
+    - hook id: echo
+    - duration: [TIME]
+
+      src/untracked.txt
 
     ----- stderr -----
-    warning: This file does not exist and will be ignored: `non-exist-files`
     ");
+
     Ok(())
 }
 
+/// `--dry-run` reports how many files each hook would receive, and lists them with `--verbose`.
 #[test]
-fn run_last_commit() -> Result<()> {
+fn run_dry_run_reports_files() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
 
     let cwd = context.work_dir();
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
-          - repo: https://github.com/pre-commit/pre-commit-hooks
-            rev: v5.0.0
+          - repo: local
             hooks:
-              - id: trailing-whitespace
-              - id: end-of-file-fixer
+              - id: python
+                name: python
+                language: system
+                entry: false
+                types: [python]
+              - id: not-docs
+                name: not-docs
+                language: system
+                entry: false
+                exclude: ^docs/
+              - id: markdown
+                name: markdown
+                language: system
+                entry: false
+                files: \.md$
+                exclude_types: [python]
+              - id: rust
+                name: rust
+                language: system
+                entry: false
+                types: [rust]
+              - id: no-files
+                name: no-files
+                language: system
+                entry: false
+                pass_filenames: false
     "});
-
-    // Create initial files and make first commit
-    cwd.child("file1.txt").write_str("Hello, world!\n")?;
-    cwd.child("file2.txt")
-        .write_str("Initial content with trailing spaces   \n")?; // This has issues but won't be in last commit
-    context.git_add(".");
-    context.git_commit("Initial commit");
-
-    // Modify files and make second commit with trailing whitespace
-    cwd.child("file1.txt").write_str("Hello, world!   \n")?; // trailing whitespace
-    cwd.child("file3.txt").write_str("New file")?; // missing newline
-    // Note: file2.txt is NOT modified in this commit, so it should be filtered out by --last-commit
+    cwd.child("main.py").write_str("print(1)\n")?;
+    cwd.child("tests/test_main.py").write_str("print(2)\n")?;
+    cwd.child("docs/index.md").write_str("# Docs\n")?;
+    cwd.child("README.md").write_str("# Readme\n")?;
     context.git_add(".");
-    context.git_commit("Second commit with issues");
 
-    // Run with --last-commit should only check files from the last commit
-    // This should only process file1.txt and file3.txt, NOT file2.txt
-    cmd_snapshot!(context.filters(), context.run().arg("--last-commit"), @r"
-    success: false
-    exit_code: 1
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run"), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
-    trim trailing whitespace.................................................Failed
-    - hook id: trailing-whitespace
-    - exit code: 1
-    - files were modified by this hook
+    python..................................................................Dry Run
+    - hook id: python
 
-      Fixing file1.txt
-    fix end of files.........................................................Failed
-    - hook id: end-of-file-fixer
-    - exit code: 1
-    - files were modified by this hook
+      `python` would be run on 2 files
+    not-docs................................................................Dry Run
+    - hook id: not-docs
 
-      Fixing file3.txt
+      `not-docs` would be run on 4 files
+    markdown................................................................Dry Run
+    - hook id: markdown
+
+      `markdown` would be run on 2 files
+    rust.................................................(no files to check)Skipped
+    no-files................................................................Dry Run
+    - hook id: no-files
+
+      `no-files` would be run without filenames
 
     ----- stderr -----
     ");
 
-    // Now reset the files to their problematic state for comparison
-    cwd.child("file1.txt").write_str("Hello, world!   \n")?; // trailing whitespace
-    cwd.child("file3.txt").write_str("New file")?; // missing newline
-
-    // Run with --all-files should check ALL files including file2.txt
-    // This demonstrates that file2.txt was indeed filtered out in the previous test
-    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
-    success: false
-    exit_code: 1
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run").arg("--verbose").arg("not-docs"), @"
+    success: true
+    exit_code: 0
     ----- stdout -----
-    trim trailing whitespace.................................................Failed
-    - hook id: trailing-whitespace
-    - exit code: 1
-    - files were modified by this hook
-
-      Fixing file1.txt
-      Fixing file2.txt
-    fix end of files.........................................................Failed
-    - hook id: end-of-file-fixer
-    - exit code: 1
-    - files were modified by this hook
+    not-docs................................................................Dry Run
+    - hook id: not-docs
+    - duration: [TIME]
 
-      Fixing file3.txt
+      `not-docs` would be run on 4 files:
+      - .pre-commit-config.yaml
+      - README.md
+      - main.py
+      - tests/test_main.py
 
     ----- stderr -----
     ");
@@ -2396,6 +4318,87 @@ fn run_last_commit() -> Result<()> {
     Ok(())
 }
 
+/// `--dry-run=json` prints the plan, including hooks skipped for having no files or for the stage.
+#[test]
+fn run_dry_run_json() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: python
+                name: python
+                language: system
+                entry: false
+                types: [python]
+              - id: rust
+                name: rust
+                language: system
+                entry: false
+                types: [rust]
+              - id: always
+                name: always
+                language: system
+                entry: false
+                types: [rust]
+                always_run: true
+              - id: manual
+                name: manual
+                language: system
+                entry: false
+                stages: [manual]
+    "});
+    cwd.child("main.py").write_str("print(1)\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run=json"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "hooks": [
+        {
+          "id": "python",
+          "project": ".",
+          "run": true,
+          "files": 1,
+          "always_run": false
+        },
+        {
+          "id": "rust",
+          "project": ".",
+          "run": false,
+          "skip_reason": "no-files",
+          "files": 0,
+          "always_run": false
+        },
+        {
+          "id": "always",
+          "project": ".",
+          "run": true,
+          "files": 0,
+          "always_run": true
+        },
+        {
+          "id": "manual",
+          "project": ".",
+          "run": false,
+          "skip_reason": "stage-mismatch",
+          "files": 0,
+          "always_run": false
+        }
+      ]
+    }
+
+    ----- stderr -----
+    "#);
+
+    Ok(())
+}
+
 /// Test `prek run --files` with multiple files.
 #[test]
 fn run_multiple_files() -> Result<()> {
@@ -2602,14 +4605,90 @@ fn minimum_prek_version() {
     context.write_pre_commit_config(indoc::indoc! {r"
         minimum_prek_version: 10.0.0
         repos:
-          - repo: local
+          - repo: local
+            hooks:
+              - id: directory
+                name: directory
+                language: system
+                entry: echo
+                verbose: true
+    "});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(
+            r"current version `\d+\.\d+\.\d+(?:-[0-9A-Za-z]+(?:\.[0-9A-Za-z]+)*)?`",
+            "current version `[CURRENT_VERSION]`",
+        )])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters, context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `.pre-commit-config.yaml`
+      caused by: error: line 1 column 23: Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
+     --> <input>:1:23
+      |
+    1 | minimum_prek_version: 10.0.0
+      |                       ^ Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
+    2 | repos:
+    3 |   - repo: local
+      |
+    ");
+}
+
+/// A hook repository can require a prek version for all of its hooks.
+#[test]
+fn manifest_minimum_prek_version() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook_repo = context.home_dir().child("minimum-version-hook-repo");
+    hook_repo.create_dir_all()?;
+
+    git_cmd(&hook_repo)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+
+    hook_repo
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        minimum_prek_version: 10.0.0
+        hooks:
+          - id: echo
+            name: echo
+            entry: echo ok
+            language: system
+    "})?;
+
+    git_cmd(&hook_repo).arg("add").arg(".").assert().success();
+    git_cmd(&hook_repo)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&hook_repo)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo}
+            rev: v1.0.0
             hooks:
-              - id: directory
-                name: directory
-                language: system
-                entry: echo
-                verbose: true
-    "});
+              - id: echo
+    ", repo = hook_repo.display()});
     context.git_add(".");
 
     let filters = context
@@ -2619,24 +4698,159 @@ fn minimum_prek_version() {
             r"current version `\d+\.\d+\.\d+(?:-[0-9A-Za-z]+(?:\.[0-9A-Za-z]+)*)?`",
             "current version `[CURRENT_VERSION]`",
         )])
+        .chain([(r"repos/[0-9a-f]{16}", "repos/[HASH]")])
         .collect::<Vec<_>>();
 
-    cmd_snapshot!(filters, context.run(), @"
+    cmd_snapshot!(filters, context.run(), @r"
     success: false
     exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
-    error: Failed to parse `.pre-commit-config.yaml`
+    error: Failed to init hooks
+      caused by: Failed to read manifest of `[HOME]/minimum-version-hook-repo`
+      caused by: Failed to parse `[HOME]/repos/[HASH]/.pre-commit-hooks.yaml`
       caused by: error: line 1 column 23: Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
      --> <input>:1:23
       |
     1 | minimum_prek_version: 10.0.0
       |                       ^ Required minimum prek version `10.0.0` is greater than current version `[CURRENT_VERSION]`; Please consider updating prek
-    2 | repos:
-    3 |   - repo: local
+    2 | hooks:
+    3 |   - id: echo
       |
     ");
+
+    Ok(())
+}
+
+/// Create a hook repo with three commits, the last one tagged `v1.0.0`.
+fn create_hook_repo_with_history(context: &TestContext) -> Result<ChildPath> {
+    let hook_repo = context.home_dir().child("history-hook-repo");
+    hook_repo.create_dir_all()?;
+
+    git_cmd(&hook_repo)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+    // Allow partial clones from this repo.
+    git_cmd(&hook_repo)
+        .arg("config")
+        .arg("uploadpack.allowFilter")
+        .arg("true")
+        .assert()
+        .success();
+
+    for n in 1..=3 {
+        hook_repo
+            .child(PRE_COMMIT_HOOKS_YAML)
+            .write_str(&indoc::formatdoc! {r"
+            - id: echo
+              name: echo
+              entry: echo {n}
+              language: system
+        "})?;
+        git_cmd(&hook_repo).arg("add").arg(".").assert().success();
+        git_cmd(&hook_repo)
+            .arg("commit")
+            .arg("-m")
+            .arg(format!("Commit {n}"))
+            .assert()
+            .success();
+    }
+    git_cmd(&hook_repo)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+
+    Ok(hook_repo)
+}
+
+/// Run `prek run` against `hook_repo` at `rev` and return the path of the clone in the store.
+fn clone_hook_repo(
+    context: &TestContext,
+    hook_repo: &Path,
+    rev: &str,
+    depth: Option<&str>,
+) -> Result<PathBuf> {
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {repo}
+            rev: {rev}
+            hooks:
+              - id: echo
+    ", repo = hook_repo.display()});
+    context.git_add(".");
+
+    let mut cmd = context.run();
+    if let Some(depth) = depth {
+        cmd.env(EnvVars::PREK_CLONE_DEPTH, depth);
+    }
+    cmd.assert().success();
+
+    let clone = std::fs::read_dir(context.home_dir().child("repos"))?
+        .next()
+        .expect("repo was cloned")?
+        .path();
+    assert!(clone.join(PRE_COMMIT_HOOKS_YAML).is_file());
+    Ok(clone)
+}
+
+fn count_commits(repo: &Path) -> String {
+    let output = git_cmd(repo)
+        .arg("rev-list")
+        .arg("--count")
+        .arg("HEAD")
+        .output()
+        .expect("git rev-list");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Remote repos are cloned shallowly, `PREK_CLONE_DEPTH` sets the depth and `0` clones everything.
+#[test]
+fn clone_depth() -> Result<()> {
+    for (depth, commits) in [(None, "1"), (Some("2"), "2"), (Some("0"), "3")] {
+        let context = TestContext::new();
+        context.init_project();
+        let hook_repo = create_hook_repo_with_history(&context)?;
+
+        let clone = clone_hook_repo(&context, &hook_repo, "v1.0.0", depth)?;
+        assert_eq!(count_commits(&clone), commits, "PREK_CLONE_DEPTH={depth:?}");
+    }
+
+    Ok(())
+}
+
+/// A rev that can't be fetched shallowly, like an abbreviated commit, falls back to a partial clone.
+#[test]
+fn clone_abbreviated_rev_uses_partial_clone() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    let hook_repo = create_hook_repo_with_history(&context)?;
+
+    let output = git_cmd(&hook_repo)
+        .arg("rev-parse")
+        .arg("--short")
+        .arg("HEAD~1")
+        .output()?;
+    let rev = String::from_utf8(output.stdout)?.trim().to_string();
+
+    let clone = clone_hook_repo(&context, &hook_repo, &rev, None)?;
+    assert_eq!(count_commits(&clone), "2");
+    assert_eq!(
+        fs_err::read_to_string(clone.join(PRE_COMMIT_HOOKS_YAML))?,
+        "- id: echo\n  name: echo\n  entry: echo 2\n  language: system\n"
+    );
+
+    let promisor = git_cmd(&clone)
+        .arg("config")
+        .arg("remote.origin.promisor")
+        .output()?;
+    assert_eq!(String::from_utf8(promisor.stdout)?.trim(), "true");
+
+    Ok(())
 }
 
 /// Run hooks that would echo color.
@@ -2970,12 +5184,16 @@ fn selectors_completion() -> Result<()> {
     ----- stdout -----
     install	Install prek Git shims into Git's effective hooks directory
     prepare-hooks	Prepare environments for all hooks used in the config file
+    warmup	Prepare environments for all hooks and verify that they are healthy
+    doctor	Check the health of installed hook environments
     run	Run hooks
     list	List hooks configured in the current workspace
     uninstall	Uninstall prek Git shims
     validate-config	Validate configuration files (prek.toml or .pre-commit-config.yaml)
     validate-manifest	Validate `.pre-commit-hooks.yaml` files
+    migrate-config	Rewrite pre-commit-era syntax in configuration files
     sample-config	Produce a sample configuration file (prek.toml or .pre-commit-config.yaml)
+    config-schema	Print the JSON schema of the configuration file
     auto-update	Auto-update the `rev` field of repositories in the config file to the latest version
     cache	Manage the prek cache
     try-repo	Try the pre-commit hooks in the current repo
@@ -2993,16 +5211,39 @@ fn selectors_completion() -> Result<()> {
     --from-ref	The original ref in a `<from_ref>...<to_ref>` diff expression. Files changed in this diff will be run through the hooks
     --to-ref	The destination ref in a `from_ref...to_ref` diff expression. Defaults to `HEAD` if `from_ref` is specified
     --last-commit	Run hooks against the last commit. Equivalent to `--from-ref HEAD~1 --to-ref HEAD`
+    --since	Run hooks on files changed since the merge-base of `HEAD` and the given ref
+    --include-deleted	Also pass files deleted in the ref range to hooks
+    --include-untracked	Also pass untracked files that are not ignored by `.gitignore` to hooks
     --show-diff-on-failure	When hooks fail, run `git diff` directly afterward
+    --show-diff	Print a diff of the changes builtin fixer hooks would make, instead of modifying files
     --fail-fast	Stop running hooks after the first failure
+    --keep-going	Run every hook even after failures, and print a summary of the hook outcomes at the end
     --dry-run	Do not run the hooks, but print the hooks that would have been run
+    --explain	Do not run the hooks, but explain which files the hook with this id would receive
+    --commit-msg-file	Run the `commit-msg` hooks against a commit message file, the way git does when committing
     --stage	The stage during which the hook is fired
     --group	Run hooks belonging to the specified group
     --no-group	Do not run hooks belonging to the specified group
+    --files-from-stdin	Read the files to run hooks on from stdin, separated by NUL characters
+    --relativize-paths	Replace the store path and git root in hook output with `<store>` and `<root>`
+    --fail-on-warnings	Exit with a non-zero status if prek emitted any warnings during the run
+    --output-format	The output format
+    --notify	Send a desktop notification when the run completes
+    --retries	Re-run a failing hook up to `N` times before reporting it as failed
+    --timeout	Kill a hook and report it as failed when it runs for longer than `SECONDS`
+    --language-concurrency	Run at most `N` hook processes of `LANGUAGE` at the same time, e.g. `python=2`
+    --parallel-projects	Start each workspace project as soon as the projects nested inside it have finished
+    --report-junit	Write a `JUnit` XML report of the hook results to the given path
+    --modified-files	Write the files that hooks modified during the run to the given path, one per line
+    --timings	Print how long each hook took after the run, slowest first
+    --timings-file	Write the timings report as JSON to the given path, apart from the run output
+    --no-cache	Run `cacheable` hooks on every matched file, ignoring the results of previous runs
+    --allow-missing-config	Exit successfully without running anything if no configuration file is found
     --config	Path to alternate config file
     --cd	Change to directory before running
     --color	Whether to use color in output
     --refresh	Refresh all cached data
+    --offline	Disable network access
     --help	Display the concise help for this command
     --no-progress	Hide all progress outputs
     --quiet	Use quiet output
@@ -3179,7 +5420,7 @@ fn dry_run() {
     context.git_add(".");
 
     // Run with `--dry-run`
-    cmd_snapshot!(context.filters(), context.run().arg("--dry-run").arg("-v"), @r"
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run").arg("-v"), @"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -3187,13 +5428,75 @@ fn dry_run() {
     - hook id: fail
     - duration: [TIME]
 
-      `fail` would be run on 1 files:
+      `fail` would be run on 1 file:
       - .pre-commit-config.yaml
 
     ----- stderr -----
     ");
 }
 
+/// `--explain` lists every candidate file of a hook with the reason it was selected or not.
+#[test]
+fn explain() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: python
+                name: python
+                entry: fail
+                language: fail
+                types_or: [python]
+                exclude: ^vendor/
+              - id: manual
+                name: manual
+                entry: fail
+                language: fail
+                stages: [manual]
+    "});
+    let cwd = context.work_dir();
+    cwd.child("main.py").write_str("print(1)\n")?;
+    cwd.child("types.pyi").write_str("x: int\n")?;
+    cwd.child("vendor/lib.py").write_str("print(2)\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--explain").arg("python"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hook `python` in project `.`:
+      excluded .pre-commit-config.yaml: no tag from `types_or` [python] (file tags: file, non-executable, text, yaml)
+      included main.py
+      excluded types.pyi: no tag from `types_or` [python] (file tags: file, non-executable, pyi, text)
+      excluded vendor/lib.py: matched by `exclude`
+      1 of 4 files selected
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--explain").arg("manual"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hook `manual` in project `.` is skipped: not configured for stage `pre-commit` (stages: manual)
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--explain").arg("missing"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No hook with id `missing` found after filtering with the given selectors
+    ");
+
+    Ok(())
+}
+
 /// Supports reading `pre-commit-config.yml` as well.
 #[test]
 fn alternate_config_file() -> Result<()> {
@@ -3218,12 +5521,11 @@ fn alternate_config_file() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hello, world!
     local-python-hook........................................................Passed
     - hook id: local-python-hook
     - duration: [TIME]
 
-      Hello, world!
-
     ----- stderr -----
     ");
 
@@ -3245,12 +5547,11 @@ fn alternate_config_file() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hello, world!
     local-python-hook........................................................Passed
     - hook id: local-python-hook
     - duration: [TIME]
 
-      Hello, world!
-
     ----- stderr -----
     warning: Multiple configuration files found (`.pre-commit-config.yaml`, `.pre-commit-config.yml`); using `[TEMP_DIR]/.pre-commit-config.yaml`
     ");
@@ -3276,12 +5577,11 @@ fn alternate_config_file() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hello, world!
     local-python-hook........................................................Passed
     - hook id: local-python-hook
     - duration: [TIME]
 
-      Hello, world!
-
     ----- stderr -----
     warning: Multiple configuration files found (`prek.toml`, `.pre-commit-config.yaml`, `.pre-commit-config.yml`); using `[TEMP_DIR]/prek.toml`
     ");
@@ -3316,14 +5616,77 @@ fn prek_toml() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
+      Hello, world!
     local-python-hook........................................................Passed
     - hook id: local-python-hook
     - duration: [TIME]
 
-      Hello, world!
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `--modified-files` lists the files hooks rewrote, relative to the git root.
+#[test]
+fn modified_files_output() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: append
+                name: append
+                language: system
+                entry: python3 -c "import sys; [open(f, 'a').write('fixed\n') for f in sys.argv[1:]]"
+                files: ^src/
+              - id: check
+                name: check
+                language: system
+                entry: python3 -c "exit(0)"
+    "#});
+    let cwd = context.work_dir();
+    cwd.child("src/a.txt").write_str("a\n")?;
+    cwd.child("src/b.txt").write_str("b\n")?;
+    cwd.child("other.txt").write_str("other\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--modified-files").arg("modified.txt"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    append...................................................................Failed
+    - hook id: append
+    - files were modified by this hook
+    check....................................................................Passed
+
+    ----- stderr -----
+    ");
+    assert_eq!(
+        fs_err::read_to_string(cwd.child("modified.txt"))?,
+        "src/a.txt\nsrc/b.txt\n"
+    );
+
+    // Unstaged changes that no hook touched are not listed.
+    context.git_add(".");
+    cwd.child("other.txt").write_str("changed\n")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--modified-files").arg("modified.txt"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    append...................................................................Failed
+    - hook id: append
+    - files were modified by this hook
+    check....................................................................Passed
 
     ----- stderr -----
     ");
+    assert_eq!(
+        fs_err::read_to_string(cwd.child("modified.txt"))?,
+        "src/a.txt\nsrc/b.txt\n"
+    );
 
     Ok(())
 }
@@ -4004,3 +6367,161 @@ fn pass_filenames_2_limits_batch_size() -> Result<()> {
 
     Ok(())
 }
+
+/// `--offline` uses repositories already in the store, and fails fast on repositories that
+/// would need to be cloned.
+#[test]
+fn run_offline() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo = context.home_dir().child("hook-repo");
+    repo.create_dir_all()?;
+    git_cmd(&repo).arg("init").assert().success();
+    repo.child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+            - id: hello
+              name: hello
+              entry: echo hello
+              language: system
+              pass_filenames: false
+        "})?;
+    git_cmd(&repo).arg("add").arg(".").assert().success();
+    let commit = |message: &str| -> Result<String> {
+        git_cmd(&repo)
+            .args(["commit", "--allow-empty", "-m", message])
+            .assert()
+            .success();
+        let output = git_cmd(&repo).args(["rev-parse", "HEAD"]).output()?;
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    };
+    let first = commit("Initial commit")?;
+    let second = commit("Second commit")?;
+
+    let repo_path = repo.path().to_string_lossy().replace('\\', "/");
+    let config = |rev: &str| {
+        format!("repos:\n  - repo: {repo_path}\n    rev: {rev}\n    hooks:\n      - id: hello\n")
+    };
+    context.write_pre_commit_config(&config(&first));
+    context.git_add(".");
+
+    // Populate the store.
+    context.run().assert().success();
+
+    cmd_snapshot!(context.filters(), context.run().arg("--offline"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hello....................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    context.write_pre_commit_config(&config(&second));
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_OFFLINE, "1"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: offline: `[HOME]/hook-repo` is not cached
+    ");
+
+    cmd_snapshot!(context.filters(), context.auto_update().arg("--offline"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: offline: `prek auto-update` needs network access to fetch new revisions
+    ");
+
+    Ok(())
+}
+
+/// Hooks see prek's color choice through `FORCE_COLOR`/`CLICOLOR_FORCE` or `NO_COLOR`.
+#[test]
+fn color_env_passed_to_hooks() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: color-env
+                name: color-env
+                language: system
+                entry: python3 -c "import os; open('color.log', 'w').write(' '.join(f'{k}={os.environ.get(k)}' for k in ('FORCE_COLOR', 'CLICOLOR_FORCE', 'NO_COLOR')))"
+                pass_filenames: false
+                always_run: true
+    "#});
+    context.git_add(".");
+
+    let color_env = |args: &[&str], no_color: Option<&str>| -> Result<String> {
+        let mut cmd = context.run();
+        cmd.args(args)
+            .env_remove(EnvVars::NO_COLOR)
+            .env_remove(EnvVars::FORCE_COLOR)
+            .env_remove(EnvVars::CLICOLOR_FORCE);
+        if let Some(no_color) = no_color {
+            cmd.env(EnvVars::NO_COLOR, no_color);
+        }
+        cmd.assert().success();
+        Ok(fs_err::read_to_string(
+            context.work_dir().join("color.log"),
+        )?)
+    };
+
+    assert_eq!(
+        color_env(&["--color=always"], None)?,
+        "FORCE_COLOR=1 CLICOLOR_FORCE=1 NO_COLOR=None"
+    );
+    assert_eq!(
+        color_env(&["--color=never"], None)?,
+        "FORCE_COLOR=None CLICOLOR_FORCE=None NO_COLOR=1"
+    );
+    // Output is not a terminal, so `auto` disables color.
+    assert_eq!(
+        color_env(&[], None)?,
+        "FORCE_COLOR=None CLICOLOR_FORCE=None NO_COLOR=1"
+    );
+    // A `NO_COLOR` set by the user is respected even with `--color=always`.
+    assert_eq!(
+        color_env(&["--color=always"], Some("yes"))?,
+        "FORCE_COLOR=None CLICOLOR_FORCE=None NO_COLOR=yes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn container_image_rejected_for_unsupported_language() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: check-todo
+                name: check-todo
+                language: pygrep
+                entry: TODO
+                container_image: alpine
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `check-todo`
+      caused by: Hook specified `container_image` but the language `pygrep` does not support running in a container, only `system` and `script` do
+    ");
+}
@@ -228,15 +228,21 @@ fn dry_run_skips_all_hooks() -> Result<()> {
     cwd.child("file.txt").write_str("content")?;
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run().arg("--dry-run"), @r#"
+    cmd_snapshot!(context.filters(), context.run().arg("--dry-run"), @"
     success: true
     exit_code: 0
     ----- stdout -----
     formatter...............................................................Dry Run
+    - hook id: formatter
+
+      `formatter` would be run on 1 file
     linter..................................................................Dry Run
+    - hook id: linter
+
+      `linter` would be run on 1 file
 
     ----- stderr -----
-    "#);
+    ");
 
     assert_eq!(context.read("file.txt"), "content");
 
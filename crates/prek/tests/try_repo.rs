@@ -335,6 +335,115 @@ fn try_repo_uncommitted_changes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn try_repo_untracked_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_path = create_hook_repo(&context, "try-repo-untracked")?;
+    git_cmd(&repo_path)
+        .arg("rm")
+        .arg("--cached")
+        .arg("--quiet")
+        .arg(PRE_COMMIT_HOOKS_YAML)
+        .assert()
+        .success();
+    git_cmd(&repo_path)
+        .arg("commit")
+        .arg("-m")
+        .arg("Untrack manifest")
+        .assert()
+        .success();
+
+    // The manifest is only in the working tree, not yet added to git.
+    ChildPath::new(&repo_path)
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        - id: untracked-hook
+          name: Untracked Hook
+          entry: echo untracked
+          language: system
+        "})?;
+
+    context.work_dir().child("test.txt").write_str("test")?;
+    context.git_add(".");
+
+    let mut filters = context.filters();
+    filters.extend([
+        (r"try-repo-[^/\\]+", "[REPO]"),
+        (r"[a-f0-9]{40}", "[COMMIT_SHA]"),
+        ("'", "\""),
+    ]);
+
+    cmd_snapshot!(filters, context.try_repo().arg(&repo_path), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Using generated `prek.toml`:
+    [[repos]]
+    repo = "[HOME]/scratch/[REPO]/shadow-repo"
+    rev = "[COMMIT_SHA]"
+    hooks = [
+      { id = "untracked-hook" },
+    ]
+
+    Untracked Hook...........................................................Passed
+
+    ----- stderr -----
+    warning: Creating temporary repo with uncommitted changes...
+    "#);
+
+    Ok(())
+}
+
+#[test]
+fn try_repo_without_commits() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/try-repo-no-commits");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir).arg("init").assert().success();
+    repo_dir
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        - id: new-hook
+          name: New Hook
+          entry: echo new
+          language: system
+        "})?;
+
+    context.work_dir().child("test.txt").write_str("test")?;
+    context.git_add(".");
+
+    let mut filters = context.filters();
+    filters.extend([
+        (r"try-repo-[^/\\]+", "[REPO]"),
+        (r"[a-f0-9]{40}", "[COMMIT_SHA]"),
+        ("'", "\""),
+    ]);
+
+    cmd_snapshot!(filters, context.try_repo().arg(&*repo_dir), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Using generated `prek.toml`:
+    [[repos]]
+    repo = "[HOME]/scratch/[REPO]/shadow-repo"
+    rev = "[COMMIT_SHA]"
+    hooks = [
+      { id = "new-hook" },
+    ]
+
+    New Hook.................................................................Passed
+
+    ----- stderr -----
+    warning: Creating temporary repo from the working tree...
+    "#);
+
+    Ok(())
+}
+
 #[test]
 fn try_repo_relative_path() -> Result<()> {
     let context = TestContext::new();
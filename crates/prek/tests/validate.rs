@@ -184,6 +184,47 @@ fn validate_manifest() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn manifest_duplicate_and_reserved_ids() -> anyhow::Result<()> {
+    let context = TestContext::new();
+
+    context
+        .work_dir()
+        .child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+            - id: lint
+              name: lint
+              entry: lint
+              language: system
+            - id: identity
+              name: identity
+              entry: identity
+              language: system
+            - id: lint
+              name: lint again
+              entry: lint --again
+              language: system
+              priority: 10
+            - id: trailing-whitespace
+              name: trailing whitespace
+              entry: trailing-whitespace
+              language: system
+        "})?;
+
+    cmd_snapshot!(context.filters(), context.validate_manifest().arg(".pre-commit-hooks.yaml"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook id `identity` in `.pre-commit-hooks.yaml` (line 5) is reserved for the meta hook of the same name
+    error: Duplicate hook id `lint` in `.pre-commit-hooks.yaml` (line 9)
+    error: Hook `lint` in `.pre-commit-hooks.yaml` (line 9) sets `priority`, which is only allowed in config files
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn unexpected_keys_warning() {
     let context = TestContext::new();
@@ -247,3 +288,167 @@ fn unexpected_keys_warning() {
     success: All configs are valid
     ");
 }
+
+#[test]
+fn strict_rejects_unexpected_keys() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: test-hook
+                name: Test Hook
+                entry: echo test
+                language: system
+                exlude: ^vendor/
+        minimum_pre_commit_version: 1.0.0
+        ci:
+          autofix_prs: false
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg("--strict").arg(PRE_COMMIT_CONFIG_YAML), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unexpected keys in `.pre-commit-config.yaml`: `repos[0].hooks[0].exlude`
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: test-hook
+                name: Test Hook
+                entry: echo test
+                language: system
+        minimum_pre_commit_version: 1.0.0
+        ci:
+          autofix_prs: false
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg("--strict").arg(PRE_COMMIT_CONFIG_YAML), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: All configs are valid
+    ");
+}
+
+#[test]
+fn backtracking_regex_warning() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        files: ^(?!vendor/)
+        repos:
+          - repo: local
+            hooks:
+              - id: test-hook
+                name: Test Hook
+                entry: echo test
+                language: system
+                files: ^(\w+/?)+(?<!/)\.py$
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: The `files` regex of hook `test-hook` in `.pre-commit-config.yaml` backtracks excessively on some paths and may hang `prek run`, consider simplifying it: `^(/w+/?)+(?<!/)/.py$`
+    success: All configs are valid
+    ");
+}
+
+#[test]
+fn stage_warnings() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        default_stages: [commit, push]
+        repos:
+          - repo: local
+            hooks:
+              - id: legacy-stages
+                name: Legacy Stages
+                entry: echo test
+                language: system
+                stages: [merge-commit, commit]
+              - id: post-commit-files
+                name: Post Commit Files
+                entry: echo test
+                language: system
+                pass_filenames: true
+                stages: [post-commit, post-merge]
+              - id: post-commit-no-files
+                name: Post Commit No Files
+                entry: echo test
+                language: system
+                pass_filenames: false
+                always_run: true
+                stages: [post-commit]
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Deprecated stage names in `.pre-commit-config.yaml`: `commit` (use `pre-commit`), `push` (use `pre-push`), `merge-commit` (use `pre-merge-commit`)
+    warning: Hook `post-commit-files` in `.pre-commit-config.yaml` sets `pass_filenames` but only runs at stages without file input (post-commit, post-merge), so it will never receive files
+    success: All configs are valid
+    ");
+}
+
+#[test]
+fn merge_conflict_markers_in_config() {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+        <<<<<<< HEAD
+              - id: test-hook
+                name: Test Hook
+        =======
+              - id: other-hook
+                name: Other Hook
+        >>>>>>> feature
+                entry: echo test
+                language: system
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `.pre-commit-config.yaml` contains an unresolved merge conflict marker on line 4, resolve the conflict and try again
+    ");
+}
+
+// The published schema may be checked out with CRLF line endings on Windows.
+#[cfg(all(unix, feature = "schemars"))]
+#[test]
+fn config_schema_matches_published_schema() -> anyhow::Result<()> {
+    let context = TestContext::new();
+
+    let output = context.command().arg("config-schema").output()?;
+    assert!(output.status.success());
+
+    let published = fs_err::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../prek.schema.json"),
+    )?;
+    assert_eq!(String::from_utf8(output.stdout)?, published);
+
+    Ok(())
+}
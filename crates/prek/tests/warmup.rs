@@ -0,0 +1,54 @@
+use assert_fs::fixture::PathChild;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// `prek warmup` creates hook environments and health-checks each of them.
+#[test]
+fn warmup_creates_and_checks_environments() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: perl-hook
+                name: Perl Hook
+                language: perl
+                entry: perl -e 'print "perl\n"'
+              - id: perl-hook-shared
+                name: Perl Hook Shared
+                language: perl
+                entry: perl -e 'print "shared\n"'
+              - id: system-hook
+                name: System Hook
+                language: system
+                entry: echo system
+        "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.warmup(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: Prepared 1 healthy environment for 3 hooks
+    ");
+
+    // Both Perl hooks share one environment.
+    assert_eq!(context.home_dir().child("hooks").read_dir()?.count(), 1);
+
+    // A second run reuses the existing environment and checks it again.
+    cmd_snapshot!(context.filters(), context.warmup(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: Prepared 1 healthy environment for 3 hooks
+    ");
+
+    Ok(())
+}
@@ -171,12 +171,13 @@ fn basic_discovery() -> Result<()> {
     - duration: [TIME]
 
       [TEMP_DIR]/project3
-      ['.prekignore', '.pre-commit-config.yaml', 'project5/.pre-commit-config.yaml']
+      ['.prekignore', '.pre-commit-config.yaml']
 
     ----- stderr -----
     ");
 
-    // Ignoring everything under project3, but when runs from project3, it’s still getting picked up.
+    // Ignoring everything under project3: the project itself is still discovered when run from
+    // project3, but none of its files reach the hook.
     context
         .work_dir()
         .child("project3/.prekignore")
@@ -186,12 +187,7 @@ fn basic_discovery() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    Show CWD.................................................................Passed
-    - hook id: show-cwd
-    - duration: [TIME]
-
-      [TEMP_DIR]/project3
-      ['.prekignore', '.pre-commit-config.yaml', 'project5/.pre-commit-config.yaml']
+    Show CWD.............................................(no files to check)Skipped
 
     ----- stderr -----
     ");
@@ -329,6 +325,134 @@ fn fail_fast_stops_after_current_project_level() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parallel_projects_runs_unrelated_projects_across_depths() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config("repos: []");
+
+    let config = indoc! {r#"
+    repos:
+      - repo: local
+        hooks:
+        - id: slow-hook
+          name: Slow Hook
+          language: system
+          entry: python3 -c "import os, time; from pathlib import Path; name = Path.cwd().name; log = Path(os.environ['EVENTS_LOG']); log.open('a').write('start ' + name + '\n'); time.sleep(0.5); log.open('a').write('end ' + name + '\n')"
+          always_run: true
+          pass_filenames: false
+    "#};
+
+    for project in ["a", "a/nested", "b"] {
+        let project_dir = context.work_dir().child(project);
+        project_dir.create_dir_all()?;
+        project_dir
+            .child(".pre-commit-config.yaml")
+            .write_str(config)?;
+    }
+    context.git_add(".");
+
+    let mut run = context.run();
+    run.arg("--all-files")
+        .arg("--parallel-projects")
+        .env(EnvVars::PREK_MAX_CONCURRENCY, "3")
+        .env("EVENTS_LOG", context.work_dir().join("events.log"));
+    cmd_snapshot!(context.filters(), run, @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ a/nested
+      Slow Hook..............................................................Passed
+    ✓ a
+      Slow Hook..............................................................Passed
+    ✓ b
+      Slow Hook..............................................................Passed
+
+    ----- stderr -----
+    "#);
+
+    let events = context.read("events.log");
+    let start_nested = events.find("start nested").expect("a/nested should start");
+    let end_nested = events.find("end nested").expect("a/nested should end");
+    let start_a = events.find("start a").expect("a should start");
+    let start_b = events.find("start b").expect("b should start");
+    let end_b = events.find("end b").expect("b should end");
+
+    // The parent project waits for its nested project.
+    assert!(end_nested < start_a);
+    // An unrelated project at a different depth runs alongside the nested project.
+    assert!(start_b < end_nested);
+    assert!(start_nested < end_b);
+
+    Ok(())
+}
+
+/// An `exclusive` hook does not overlap with hooks of projects running concurrently.
+#[test]
+fn exclusive_hook_runs_alone_across_projects() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config("repos: []");
+
+    let config = |exclusive: bool| {
+        format!(
+            indoc! {r#"
+            repos:
+              - repo: local
+                hooks:
+                - id: slow-hook
+                  name: Slow Hook
+                  language: system
+                  entry: python3 -c "import os, time; from pathlib import Path; name = Path.cwd().name; log = Path(os.environ['EVENTS_LOG']); log.open('a').write('start ' + name + '\n'); time.sleep(0.5); log.open('a').write('end ' + name + '\n')"
+                  always_run: true
+                  pass_filenames: false
+                  exclusive: {}
+            "#},
+            exclusive
+        )
+    };
+
+    for (project, exclusive) in [("a", true), ("b", false), ("c", false)] {
+        let project_dir = context.work_dir().child(project);
+        project_dir.create_dir_all()?;
+        project_dir
+            .child(".pre-commit-config.yaml")
+            .write_str(&config(exclusive))?;
+    }
+    context.git_add(".");
+
+    let mut run = context.run();
+    run.arg("--all-files")
+        .env(EnvVars::PREK_MAX_CONCURRENCY, "3")
+        .env("EVENTS_LOG", context.work_dir().join("events.log"));
+    cmd_snapshot!(context.filters(), run, @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ a
+      Slow Hook..............................................................Passed
+    ✓ b
+      Slow Hook..............................................................Passed
+    ✓ c
+      Slow Hook..............................................................Passed
+
+    ----- stderr -----
+    ");
+
+    let events = context.read("events.log");
+    let lines = events.lines().collect::<Vec<_>>();
+    let start_a = lines
+        .iter()
+        .position(|line| *line == "start a")
+        .expect("a should start");
+    // Nothing starts or ends while the exclusive hook runs.
+    assert_eq!(lines[start_a + 1], "end a", "{events}");
+
+    Ok(())
+}
+
 #[test]
 fn config_not_staged() -> Result<()> {
     let context = TestContext::new();
@@ -1031,6 +1155,70 @@ fn gitignore_respected() -> Result<()> {
     Ok(())
 }
 
+/// Files under directories listed in `.prekignore` are not passed to any hook.
+#[test]
+fn prekignore_excludes_files_from_hooks() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc! {r"
+    repos:
+      - repo: local
+        hooks:
+        - id: show-files
+          name: Show Files
+          language: system
+          entry: python3 -c 'import sys; print(sorted(sys.argv[1:]))'
+          verbose: true
+    "});
+    context
+        .work_dir()
+        .child("src/main.py")
+        .write_str("print('hi')\n")?;
+    context
+        .work_dir()
+        .child("vendor/lib.py")
+        .write_str("print('vendored')\n")?;
+    context
+        .work_dir()
+        .child("vendor/nested/util.py")
+        .write_str("pass\n")?;
+    context
+        .work_dir()
+        .child(".prekignore")
+        .write_str("vendor/\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['.pre-commit-config.yaml', '.prekignore', 'src/main.py']
+
+    ----- stderr -----
+    ");
+
+    // Explicitly passed files are filtered too.
+    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("vendor/lib.py").arg("src/main.py"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['src/main.py']
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn nested_project_exclude_is_relative() -> Result<()> {
     let context = TestContext::new();